@@ -0,0 +1,66 @@
+//! Build script.
+//!
+//! Generates a minimal linker script for the `nostd_smoke` test when building
+//! for `thumbv6m-none-eabi`. Memory layout matches QEMU's `microbit` machine
+//! (nRF51: 256K flash, 16K RAM). Other targets are left untouched.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+const SMOKE_TARGET: &str = "thumbv6m-none-eabi";
+
+const LINKER_SCRIPT: &str = r#"
+MEMORY
+{
+  FLASH : ORIGIN = 0x00000000, LENGTH = 256K
+  RAM   : ORIGIN = 0x20000000, LENGTH = 16K
+}
+
+ENTRY(reset);
+EXTERN(RESET_VECTOR);
+
+SECTIONS
+{
+  .vector_table ORIGIN(FLASH) :
+  {
+    LONG(ORIGIN(RAM) + LENGTH(RAM));
+    KEEP(*(.vector_table.reset));
+  } > FLASH
+
+  .text : { *(.text .text.*); } > FLASH
+  .rodata : ALIGN(4) { *(.rodata .rodata.*); . = ALIGN(4); } > FLASH
+
+  .data : ALIGN(4)
+  {
+    __sdata = .;
+    *(.data .data.*);
+    . = ALIGN(4);
+    __edata = .;
+  } > RAM AT > FLASH
+  __sidata = LOADADDR(.data);
+
+  .bss (NOLOAD) : ALIGN(4)
+  {
+    __sbss = .;
+    *(.bss .bss.*);
+    . = ALIGN(4);
+    __ebss = .;
+  } > RAM
+
+  /DISCARD/ : { *(.ARM.exidx .ARM.exidx.*); }
+}
+"#;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    if env::var("TARGET").as_deref() != Ok(SMOKE_TARGET) {
+        return;
+    }
+
+    let out = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    fs::write(out.join("nostd_smoke.x"), LINKER_SCRIPT).expect("write linker script");
+    println!("cargo:rustc-link-search={}", out.display());
+    println!("cargo:rustc-link-arg-tests=-Tnostd_smoke.x");
+}
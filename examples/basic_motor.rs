@@ -3,7 +3,7 @@
 //! Demonstrates creating a stepper motor from configuration and executing
 //! a simple trajectory with asymmetric acceleration/deceleration.
 //!
-//! This example uses embedded-hal-mock for testing without real hardware.
+//! This example uses the crate's `testing` doubles instead of real hardware.
 //!
 //! ## Key Concepts Demonstrated:
 //! - Manual motor configuration via builder
@@ -14,59 +14,25 @@
 use stepper_motion::{
     config::units::{Degrees, DegreesPerSec, DegreesPerSecSquared, Microsteps},
     motor::StepperMotorBuilder,
-    motion::MotionProfile,
+    motion::MotionProfileBuilder,
+    testing::{InstantDelay, RecordingPin, StepCounter, VirtualClock},
 };
 
-/// Mock delay provider for demonstration.
-struct MockDelay;
-
-impl embedded_hal::delay::DelayNs for MockDelay {
-    fn delay_ns(&mut self, ns: u32) {
-        // In real code, this would use hardware timer
-        std::thread::sleep(std::time::Duration::from_nanos(ns as u64));
-    }
-}
-
-/// Mock output pin for demonstration.
-struct MockPin {
-    state: bool,
-}
-
-impl MockPin {
-    fn new() -> Self {
-        Self { state: false }
-    }
-}
-
-impl embedded_hal::digital::OutputPin for MockPin {
-    fn set_high(&mut self) -> Result<(), Self::Error> {
-        self.state = true;
-        Ok(())
-    }
-
-    fn set_low(&mut self) -> Result<(), Self::Error> {
-        self.state = false;
-        Ok(())
-    }
-}
-
-impl embedded_hal::digital::ErrorType for MockPin {
-    type Error = core::convert::Infallible;
-}
-
 fn main() {
     println!("=== Basic Motor Control Example ===\n");
 
-    // Create mock hardware
-    let step_pin = MockPin::new();
-    let dir_pin = MockPin::new();
-    let delay = MockDelay;
+    // Create mock hardware: pulses are counted and delays advance a
+    // simulated clock instead of sleeping
+    let clock = VirtualClock::new();
+    let step_pin = StepCounter::new();
+    let dir_pin = RecordingPin::new(&clock);
+    let delay = InstantDelay::new(&clock);
 
     // Build motor from manual configuration
     let motor = StepperMotorBuilder::new()
         .name("demo_motor")
-        .step_pin(step_pin)
-        .dir_pin(dir_pin)
+        .step_pin(step_pin.clone())
+        .dir_pin(dir_pin.clone())
         .delay(delay)
         .steps_per_revolution(200)
         .microsteps(Microsteps::SIXTEENTH)
@@ -119,12 +85,13 @@ fn main() {
     // ========================================================================
     
     // Demonstrate motion profile calculation
-    let profile = MotionProfile::asymmetric_trapezoidal(
-        3200,  // steps (1 full revolution at 16x microstepping)
-        3200.0, // max velocity (steps/sec)
-        6400.0, // acceleration (steps/sec²)
-        3200.0, // deceleration (steps/sec²) - slower decel for smooth stop
-    );
+    let profile = MotionProfileBuilder::new()
+        .steps(3200) // 1 full revolution at 16x microstepping
+        .max_velocity_steps_per_sec(3200.0)
+        .acceleration(6400.0)
+        .deceleration(3200.0) // slower decel for smooth stop
+        .build() // starts from rest by default
+        .expect("valid motion profile");
 
     println!("\n=== Motion Profile ===");
     println!("Total steps: {}", profile.total_steps);
@@ -192,6 +159,18 @@ velocity_percent = 100
         }
     }
 
+    // ========================================================================
+    // Execution on the test doubles
+    // ========================================================================
+    println!("\n=== Move Execution ===");
+    let motor = motor
+        .move_to_blocking(Degrees(90.0))
+        .map_err(|(_, e)| e)
+        .expect("move to 90° should succeed");
+    println!("Position: {}", motor.position_degrees_display());
+    println!("STEP pulses: {}", step_pin.pulses());
+    println!("DIR level: {}", if dir_pin.level() == Some(true) { "high (CW)" } else { "low" });
+    println!("Simulated time: {:.3} s", clock.now_ns() as f64 / 1e9);
+
     println!("\n=== Example Complete ===");
-    println!("In production code, call motor.move_to(Degrees(90.0)) to execute motion.");
 }
@@ -4,65 +4,23 @@
 //! - Load motor and trajectory configuration from TOML
 //! - Use the TrajectoryRegistry for named lookups
 //! - Calculate motion profiles from configuration
+//! - Execute named trajectories on the crate's hardware-free test doubles
 //!
 //! Run with: `cargo run --example config_driven --features std`
 
+// Matches the library: `Error` carries heapless strings for no_std
+#![allow(clippy::result_large_err)]
+
 use stepper_motion::{
+    config::units::{DegreesPerSec, DegreesPerSecSquared},
     config::{MechanicalConstraints, SystemConfig},
     error::{ConfigError, Error, Result},
-    motion::MotionProfile,
+    motion::MotionProfileBuilder,
+    motor::StepperMotorBuilder,
+    testing::{InstantDelay, RecordingPin, StepCounter, VirtualClock},
     trajectory::TrajectoryRegistry,
 };
 
-/// Mock STEP pin for demonstration.
-/// (Kept for future example expansion showing actual motor control.)
-#[allow(dead_code)]
-struct MockStepPin;
-
-impl embedded_hal::digital::ErrorType for MockStepPin {
-    type Error = core::convert::Infallible;
-}
-
-impl embedded_hal::digital::OutputPin for MockStepPin {
-    fn set_low(&mut self) -> core::result::Result<(), Self::Error> {
-        Ok(())
-    }
-
-    fn set_high(&mut self) -> core::result::Result<(), Self::Error> {
-        Ok(())
-    }
-}
-
-/// Mock DIR pin for demonstration.
-/// (Kept for future example expansion showing actual motor control.)
-#[allow(dead_code)]
-struct MockDirPin;
-
-impl embedded_hal::digital::ErrorType for MockDirPin {
-    type Error = core::convert::Infallible;
-}
-
-impl embedded_hal::digital::OutputPin for MockDirPin {
-    fn set_low(&mut self) -> core::result::Result<(), Self::Error> {
-        Ok(())
-    }
-
-    fn set_high(&mut self) -> core::result::Result<(), Self::Error> {
-        Ok(())
-    }
-}
-
-/// Mock delay for demonstration.
-/// (Kept for future example expansion showing actual motor control.)
-#[allow(dead_code)]
-struct MockDelay;
-
-impl embedded_hal::delay::DelayNs for MockDelay {
-    fn delay_ns(&mut self, _ns: u32) {
-        // In real code, this would actually delay
-    }
-}
-
 fn main() -> Result<()> {
     println!("=== Configuration-Driven Trajectory Example ===\n");
 
@@ -146,7 +104,7 @@ velocity_percent = 75
         "  Max acceleration: {} °/s²",
         motor_config.max_acceleration.value()
     );
-    println!("  Total steps/rev: {}", motor_config.total_steps_per_revolution());
+    println!("  Total steps/rev: {}", motor_config.total_steps_per_revolution_f32());
     println!("  Steps/degree: {:.2}", motor_config.steps_per_degree());
     println!();
 
@@ -190,25 +148,25 @@ velocity_percent = 75
     for name in trajectory_names {
         if let Some(traj) = registry.get(name) {
             // Calculate steps for this move (from position 0)
-            let target_steps = constraints.degrees_to_steps(traj.target_degrees.value()).abs() as u32;
-
-            // Get motion parameters
-            let velocity_steps = constraints.velocity_to_steps(
-                traj.effective_velocity(&constraints),
-            );
-            let accel_steps = constraints.acceleration_to_steps(
-                traj.effective_acceleration(&constraints),
-            );
-            let decel_steps = constraints.acceleration_to_steps(
-                traj.effective_deceleration(&constraints),
-            );
-
-            let profile = MotionProfile::asymmetric_trapezoidal(
-                target_steps as i64,
-                velocity_steps,
-                accel_steps,
-                decel_steps,
-            );
+            let target_steps = constraints.degrees_to_steps(traj.target_degrees.value()).unsigned_abs() as u32;
+
+            // Motion parameters in degrees, converted by the builder
+            let profile = MotionProfileBuilder::new()
+                .steps(target_steps as i64)
+                .max_velocity_degrees(
+                    DegreesPerSec(traj.effective_velocity(&constraints)),
+                    &constraints,
+                )
+                .acceleration_degrees(
+                    DegreesPerSecSquared(traj.effective_acceleration(&constraints)),
+                    &constraints,
+                )
+                .deceleration_degrees(
+                    DegreesPerSecSquared(traj.effective_deceleration(&constraints)),
+                    &constraints,
+                )
+                .entry_velocity(constraints.initial_velocity_steps_per_sec)
+                .build()?;
 
             println!("Trajectory: {}", name);
             println!(
@@ -257,6 +215,31 @@ velocity_percent = 75
     }
     println!();
 
+    // Execute trajectories on test doubles instead of real pins
+    println!("Execution (test doubles):");
+    let clock = VirtualClock::new();
+    let step = StepCounter::new();
+    let dir = RecordingPin::new(&clock);
+    let mut motor = StepperMotorBuilder::new()
+        .from_config(&config, motor_name)?
+        .step_pin(step.clone())
+        .dir_pin(dir.clone())
+        .delay(InstantDelay::new(&clock))
+        .build()?;
+
+    for name in ["right_90", "left_90", "home"] {
+        motor = motor.execute(name, &registry).map_err(|(_, e)| e)?;
+        println!(
+            "  {} → {} (DIR {}, {} pulses so far, {:.3}s simulated)",
+            name,
+            motor.position_degrees_display(),
+            if dir.level() == Some(true) { "high" } else { "low" },
+            step.pulses(),
+            clock.now_ns() as f64 / 1e9
+        );
+    }
+    println!();
+
     // Demonstrate looking up a non-existent trajectory
     let missing_name = "nonexistent";
     match registry.get(missing_name) {
@@ -0,0 +1,10 @@
+//! Example: Export the configuration JSON Schema.
+//!
+//! Prints the schema for a whole configuration file, for editors that
+//! validate TOML against a JSON Schema:
+//!
+//! Run with: `cargo run --example export_schema --features schema > stepper-motion-schema.json`
+
+fn main() {
+    println!("{}", stepper_motion::config::schema::system_config_schema());
+}
@@ -7,10 +7,14 @@
 //!
 //! Run with: `cargo run --example multi_motor --features std`
 
+// Matches the library: `Error` carries heapless strings for no_std
+#![allow(clippy::result_large_err)]
+
 use stepper_motion::{
+    config::units::{DegreesPerSec, DegreesPerSecSquared},
     config::{MechanicalConstraints, SystemConfig},
     error::{ConfigError, Error, Result},
-    motion::MotionProfile,
+    motion::MotionProfileBuilder,
     trajectory::TrajectoryRegistry,
 };
 
@@ -139,10 +143,10 @@ velocity_percent = 100
                 motor_config.steps_per_revolution,
                 motor_config.microsteps.value(),
                 motor_config.gear_ratio,
-                motor_config.total_steps_per_revolution()
+                motor_config.total_steps_per_revolution_f32()
             );
             println!("  Resolution: {:.4}° per step ({:.2} steps/degree)",
-                360.0 / motor_config.total_steps_per_revolution() as f32,
+                360.0 / motor_config.total_steps_per_revolution_f32(),
                 motor_config.steps_per_degree()
             );
             println!("  Max velocity: {}°/s ({:.0} steps/s)",
@@ -184,17 +188,18 @@ velocity_percent = 100
         for traj_name in registry.names() {
             if let Some(traj) = registry.get(traj_name) {
                 if traj.motor.as_str() == motor_name {
-                    let target_steps = constraints.degrees_to_steps(traj.target_degrees.value()).abs() as u32;
-                    let velocity = traj.effective_velocity(&constraints);
-                    let accel = traj.effective_acceleration(&constraints);
-                    let decel = traj.effective_deceleration(&constraints);
-
-                    let profile = MotionProfile::asymmetric_trapezoidal(
-                        target_steps as i64,
-                        constraints.velocity_to_steps(velocity),
-                        constraints.acceleration_to_steps(accel),
-                        constraints.acceleration_to_steps(decel),
-                    );
+                    let target_steps = constraints.degrees_to_steps(traj.target_degrees.value()).unsigned_abs() as u32;
+                    let velocity = DegreesPerSec(traj.effective_velocity(&constraints));
+                    let accel = DegreesPerSecSquared(traj.effective_acceleration(&constraints));
+                    let decel = DegreesPerSecSquared(traj.effective_deceleration(&constraints));
+
+                    let profile = MotionProfileBuilder::new()
+                        .steps(target_steps as i64)
+                        .max_velocity_degrees(velocity, &constraints)
+                        .acceleration_degrees(accel, &constraints)
+                        .deceleration_degrees(decel, &constraints)
+                        .entry_velocity(constraints.initial_velocity_steps_per_sec)
+                        .build()?;
 
                     let profile_type = if traj.is_asymmetric() { "A" } else { "S" };
 
@@ -239,14 +244,19 @@ velocity_percent = 100
         if let Some(traj) = registry.get(traj_name) {
             if let Some(motor_config) = config.motor(&traj.motor) {
                 let constraints = MechanicalConstraints::from_config(motor_config);
-                let target_steps = constraints.degrees_to_steps(traj.target_degrees.value()).abs() as u32;
-
-                let profile = MotionProfile::asymmetric_trapezoidal(
-                    target_steps as i64,
-                    constraints.velocity_to_steps(traj.effective_velocity(&constraints)),
-                    constraints.acceleration_to_steps(traj.effective_acceleration(&constraints)),
-                    constraints.acceleration_to_steps(traj.effective_deceleration(&constraints)),
-                );
+                let target_steps = constraints.degrees_to_steps(traj.target_degrees.value()).unsigned_abs() as u32;
+
+                let velocity = DegreesPerSec(traj.effective_velocity(&constraints));
+                let accel = DegreesPerSecSquared(traj.effective_acceleration(&constraints));
+                let decel = DegreesPerSecSquared(traj.effective_deceleration(&constraints));
+
+                let profile = MotionProfileBuilder::new()
+                    .steps(target_steps as i64)
+                    .max_velocity_degrees(velocity, &constraints)
+                    .acceleration_degrees(accel, &constraints)
+                    .deceleration_degrees(decel, &constraints)
+                    .entry_velocity(constraints.initial_velocity_steps_per_sec)
+                    .build()?;
 
                 let duration = profile.estimated_duration_secs();
                 total_duration = total_duration.max(duration);
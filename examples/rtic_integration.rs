@@ -0,0 +1,257 @@
+//! RTIC v2 integration example.
+//!
+//! Shows how to share a `StepperMotor` between an interrupt-driven step task
+//! and a low-priority command task, the way an RTIC v2 application does:
+//!
+//! ```rust,ignore
+//! #[rtic::app(device = stm32f4xx_hal::pac, dispatchers = [USART1])]
+//! mod app {
+//!     #[shared]
+//!     struct Shared {
+//!         motor: MotorSlot,
+//!     }
+//!
+//!     #[local]
+//!     struct Local {}
+//!
+//!     // Hardware timer interrupt: one step per tick
+//!     #[task(binds = TIM2, shared = [motor], priority = 2)]
+//!     fn step_isr(mut cx: step_isr::Context) {
+//!         let period_ns = cx.shared.motor.lock(|slot| slot.step());
+//!         // Reload the timer with `period_ns`, or stop it when `None`
+//!     }
+//!
+//!     // Software task: start moves from low priority
+//!     #[task(shared = [motor], priority = 1)]
+//!     async fn command_task(mut cx: command_task::Context, target: Degrees) {
+//!         let started = cx.shared.motor.lock(|slot| slot.start(target));
+//!         // Start the timer if `started` is `Ok`
+//!     }
+//! }
+//! ```
+//!
+//! RTIC's `lock` is a critical section around the shared resource. Outside
+//! RTIC the same pattern uses a `critical_section::Mutex`, which is what this
+//! example does so it runs on the host:
+//!
+//! - `MOTOR` is the shared resource, a [`MotorSlot`] behind a `Mutex`
+//! - [`step_isr`] is the timer interrupt handler calling `motor.step()`
+//! - [`command_task`] calls `motor.move_to()` from low priority
+//! - [`MotorSlot`] hands the motor over between its `Idle` and `Moving`
+//!   states, since each transition consumes the motor
+//!
+//! `StepperMotor` is `Send` whenever its pins and delay are, so it can live
+//! in a shared resource as is.
+//!
+//! `step()` waits for the next step interval through the motor's `DelayNs`.
+//! In an interrupt handler that would block the whole priority level, so
+//! [`TimerDelay`] only busy-waits for the short STEP pulse and records longer
+//! delays as the next timer period instead.
+//!
+//! Run on the host with: `cargo run --example rtic_integration`
+//!
+//! Check for a Cortex-M target with (the application supplies a
+//! critical-section implementation, e.g. cortex-m's
+//! `critical-section-single-core` feature):
+//!
+//! ```text
+//! cargo check --example rtic_integration --target thumbv7em-none-eabihf \
+//!     --no-default-features --features libm,shared-pin
+//! ```
+
+#![cfg_attr(target_os = "none", no_std, no_main)]
+// The tasks are only called by the (omitted) RTIC runtime on bare metal
+#![cfg_attr(target_os = "none", allow(dead_code))]
+// Matches the library: `Error` carries heapless strings for no_std
+#![allow(clippy::result_large_err)]
+
+use core::cell::RefCell;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use critical_section::Mutex;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{ErrorType, OutputPin};
+use stepper_motion::motor::StepperMotorBuilder;
+use stepper_motion::state::{Idle, Moving};
+use stepper_motion::{Degrees, DegreesPerSec, DegreesPerSecSquared, Error, Microsteps};
+use stepper_motion::{MotorResultExt, StepperMotor};
+
+/// Delays up to this long are busy-waited inside the interrupt handler.
+const BUSY_WAIT_MAX_NS: u32 = 10_000;
+
+/// Next timer period in nanoseconds, set by [`TimerDelay`].
+static NEXT_PERIOD_NS: AtomicU32 = AtomicU32::new(0);
+
+/// GPIO output; use your HAL's pin type on real hardware.
+struct BoardPin;
+
+impl ErrorType for BoardPin {
+    type Error = core::convert::Infallible;
+}
+
+impl OutputPin for BoardPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Delay that hands step intervals to the hardware timer.
+struct TimerDelay;
+
+impl DelayNs for TimerDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        if ns <= BUSY_WAIT_MAX_NS {
+            // STEP pulse width: spin with a cycle-counting delay here
+            return;
+        }
+        NEXT_PERIOD_NS.store(ns, Ordering::Relaxed);
+    }
+}
+
+type IdleMotor = StepperMotor<BoardPin, BoardPin, TimerDelay, Idle>;
+type MovingMotor = StepperMotor<BoardPin, BoardPin, TimerDelay, Moving>;
+
+/// The motor in whichever state it is in, for use as a shared resource.
+enum MotorSlot {
+    Idle(IdleMotor),
+    Moving(MovingMotor),
+    /// Only while a transition is in progress.
+    Empty,
+}
+
+/// Why a command was not started.
+#[derive(Debug)]
+enum CommandError {
+    /// A move is already running.
+    Busy,
+    /// The motor rejected the move.
+    Motor(Error),
+}
+
+impl MotorSlot {
+    /// Start a move to `target` if the motor is idle.
+    fn start(&mut self, target: Degrees) -> Result<(), CommandError> {
+        match core::mem::replace(self, MotorSlot::Empty) {
+            MotorSlot::Idle(motor) => match motor.move_to(target).map_motor(MotorSlot::Idle) {
+                Ok(moving) => {
+                    *self = MotorSlot::Moving(moving);
+                    Ok(())
+                }
+                Err((idle, e)) => {
+                    *self = idle;
+                    Err(CommandError::Motor(e))
+                }
+            },
+            other => {
+                *self = other;
+                Err(CommandError::Busy)
+            }
+        }
+    }
+
+    /// Take one step, returning the period until the next one, or `None`
+    /// once the motor is idle again.
+    fn step(&mut self) -> Option<u32> {
+        let MotorSlot::Moving(motor) = self else {
+            return None;
+        };
+        if let Ok(false) = motor.step() {
+            return Some(NEXT_PERIOD_NS.load(Ordering::Relaxed));
+        }
+        // Complete, or a pin error: stop where the motor is
+        if let MotorSlot::Moving(motor) = core::mem::replace(self, MotorSlot::Empty) {
+            *self = MotorSlot::Idle(motor.finish());
+        }
+        None
+    }
+
+    fn position(&self) -> Option<Degrees> {
+        match self {
+            MotorSlot::Idle(motor) => Some(motor.position_degrees()),
+            MotorSlot::Moving(motor) => Some(motor.position_degrees()),
+            MotorSlot::Empty => None,
+        }
+    }
+}
+
+/// The shared resource.
+static MOTOR: Mutex<RefCell<MotorSlot>> = Mutex::new(RefCell::new(MotorSlot::Empty));
+
+/// Build the motor and place it in the shared resource (RTIC's `init`).
+fn init() {
+    let motor = StepperMotorBuilder::new()
+        .name("x_axis")
+        .step_pin(BoardPin)
+        .dir_pin(BoardPin)
+        .delay(TimerDelay)
+        .steps_per_revolution(200)
+        .microsteps(Microsteps::SIXTEENTH)
+        .max_velocity(DegreesPerSec(360.0))
+        .max_acceleration(DegreesPerSecSquared(720.0))
+        .build()
+        .expect("valid motor configuration");
+    critical_section::with(|cs| *MOTOR.borrow_ref_mut(cs) = MotorSlot::Idle(motor));
+}
+
+/// Timer interrupt handler (high priority).
+///
+/// Returns the period to reload the timer with, or `None` to stop it.
+fn step_isr() -> Option<u32> {
+    critical_section::with(|cs| MOTOR.borrow_ref_mut(cs).step())
+}
+
+/// Command task (low priority).
+fn command_task(target: Degrees) -> Result<(), CommandError> {
+    critical_section::with(|cs| MOTOR.borrow_ref_mut(cs).start(target))
+}
+
+#[cfg(not(target_os = "none"))]
+fn main() {
+    println!("=== RTIC Integration Example ===\n");
+    init();
+
+    command_task(Degrees(90.0)).expect("motor is idle");
+    println!("Started move to 90°");
+
+    // Simulate the hardware timer: call the handler on every expiry
+    let mut steps = 0u32;
+    let mut elapsed_ns = 0u64;
+    while let Some(period_ns) = step_isr() {
+        steps += 1;
+        elapsed_ns += period_ns as u64;
+
+        if steps == 100 {
+            // The command task runs between interrupts, but the move is busy
+            let result = command_task(Degrees(0.0));
+            println!("Command while moving: {:?}", result);
+        }
+    }
+    // The last step has no period after it
+    let position = critical_section::with(|cs| MOTOR.borrow_ref(cs).position());
+    println!(
+        "Finished after {} steps in {:.3} s at {:?}",
+        steps + 1,
+        elapsed_ns as f64 * 1e-9,
+        position
+    );
+
+    command_task(Degrees(0.0)).expect("motor is idle again");
+    while step_isr().is_some() {}
+    let position = critical_section::with(|cs| MOTOR.borrow_ref(cs).position());
+    println!("Returned to {:?}", position);
+
+    // Errors from the motor come back with the motor still idle
+    if let Err(CommandError::Motor(e)) = command_task(Degrees(0.0)) {
+        println!("Move to the current position rejected: {}", e);
+    }
+}
+
+#[cfg(target_os = "none")]
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
@@ -0,0 +1,584 @@
+//! Semantic comparison of two configurations, for reviewing changes.
+//!
+//! [`diff`] reports which motors, trajectories and waypoint sequences were
+//! added, removed or changed, and for changed ones which fields differ by
+//! their TOML key. Float fields compare within a tolerance, so values that
+//! only differ by parsing or rounding noise don't show up as changes. With
+//! `std`, each field change also carries the old and new values as text.
+
+use core::fmt;
+
+use heapless::{String, Vec};
+
+use super::limits::SoftLimits;
+use super::motor::{HomingConfig, MotorConfig};
+use super::system::SystemConfig;
+use super::thermal::ThermalConfig;
+use super::trajectory::{FinalApproach, TrajectoryConfig, TrajectoryKind, WaypointTrajectory};
+use super::units::{Degrees, DegreesPerSec, DegreesPerSecSquared};
+
+/// Tolerance [`diff`] compares float fields with, in the fields' own units.
+pub const DIFF_TOLERANCE: f32 = 1e-4;
+
+/// Most field changes listed per entry; further ones are left out.
+pub const MAX_FIELD_CHANGES: usize = 16;
+
+/// One field that differs between two versions of an entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    /// TOML key of the field (`limits.max_degrees` for nested tables).
+    pub field: &'static str,
+    /// Old value, as `Debug` prints it (std only).
+    #[cfg(feature = "std")]
+    pub old: std::string::String,
+    /// New value, as `Debug` prints it (std only).
+    #[cfg(feature = "std")]
+    pub new: std::string::String,
+}
+
+impl FieldChange {
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))]
+    fn new(field: &'static str, old: &dyn fmt::Debug, new: &dyn fmt::Debug) -> Self {
+        Self {
+            field,
+            #[cfg(feature = "std")]
+            old: std::format!("{:?}", old),
+            #[cfg(feature = "std")]
+            new: std::format!("{:?}", new),
+        }
+    }
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.field)?;
+        #[cfg(feature = "std")]
+        write!(f, " ({} -> {})", self.old, self.new)?;
+        Ok(())
+    }
+}
+
+/// Changed fields of one entry.
+pub type FieldChanges = Vec<FieldChange, MAX_FIELD_CHANGES>;
+
+/// An entry present in both configurations with different settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryChange {
+    /// Name of the motor, trajectory or sequence.
+    pub name: String<32>,
+    /// The fields that differ, in declaration order.
+    pub fields: FieldChanges,
+}
+
+impl EntryChange {
+    /// Check whether `field` (a TOML key) is among the changes.
+    pub fn changed(&self, field: &str) -> bool {
+        self.fields.iter().any(|change| change.field == field)
+    }
+}
+
+/// Differences between two configurations, from [`diff`].
+///
+/// Names are listed in the order of the configuration they come from. The
+/// report is sized for full configurations, so keep it off small stacks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigDiff {
+    /// Motors only in the new configuration.
+    pub added_motors: Vec<String<32>, 8>,
+    /// Motors only in the old configuration.
+    pub removed_motors: Vec<String<32>, 8>,
+    /// Motors in both with different settings.
+    pub changed_motors: Vec<EntryChange, 8>,
+    /// Trajectories only in the new configuration.
+    pub added_trajectories: Vec<String<32>, 64>,
+    /// Trajectories only in the old configuration.
+    pub removed_trajectories: Vec<String<32>, 64>,
+    /// Trajectories in both with different settings.
+    pub changed_trajectories: Vec<EntryChange, 64>,
+    /// Waypoint sequences only in the new configuration.
+    pub added_sequences: Vec<String<32>, 16>,
+    /// Waypoint sequences only in the old configuration.
+    pub removed_sequences: Vec<String<32>, 16>,
+    /// Waypoint sequences in both with different settings.
+    pub changed_sequences: Vec<EntryChange, 16>,
+}
+
+impl ConfigDiff {
+    /// Check whether the configurations are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.added_motors.is_empty()
+            && self.removed_motors.is_empty()
+            && self.changed_motors.is_empty()
+            && self.added_trajectories.is_empty()
+            && self.removed_trajectories.is_empty()
+            && self.changed_trajectories.is_empty()
+            && self.added_sequences.is_empty()
+            && self.removed_sequences.is_empty()
+            && self.changed_sequences.is_empty()
+    }
+}
+
+/// One line per difference: `+ motor x`, `- trajectory home`, or
+/// `~ sequence scan: dwell_ms, velocity_percent`.
+impl fmt::Display for ConfigDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kinds = [
+            ("motor", &self.added_motors[..], &self.removed_motors[..], &self.changed_motors[..]),
+            (
+                "trajectory",
+                &self.added_trajectories[..],
+                &self.removed_trajectories[..],
+                &self.changed_trajectories[..],
+            ),
+            (
+                "sequence",
+                &self.added_sequences[..],
+                &self.removed_sequences[..],
+                &self.changed_sequences[..],
+            ),
+        ];
+        for (kind, added, removed, changed) in kinds {
+            for name in added {
+                writeln!(f, "+ {} {}", kind, name)?;
+            }
+            for name in removed {
+                writeln!(f, "- {} {}", kind, name)?;
+            }
+            for entry in changed {
+                write!(f, "~ {} {}:", kind, entry.name)?;
+                for (i, change) in entry.fields.iter().enumerate() {
+                    write!(f, "{}{}", if i == 0 { " " } else { ", " }, change)?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compare two configurations with the default [`DIFF_TOLERANCE`].
+pub fn diff(old: &SystemConfig, new: &SystemConfig) -> ConfigDiff {
+    diff_with_tolerance(old, new, DIFF_TOLERANCE)
+}
+
+/// Compare two configurations, treating floats within `tolerance` as equal.
+pub fn diff_with_tolerance(old: &SystemConfig, new: &SystemConfig, tolerance: f32) -> ConfigDiff {
+    let mut report = ConfigDiff::default();
+    diff_maps(
+        old.motors.iter(),
+        |name| new.motor(name),
+        new.motors.iter(),
+        |name| old.motor(name),
+        |a, b| motor_changes(a, b, tolerance),
+        (&mut report.added_motors, &mut report.removed_motors, &mut report.changed_motors),
+    );
+    diff_maps(
+        old.trajectories.iter(),
+        |name| new.trajectory(name),
+        new.trajectories.iter(),
+        |name| old.trajectory(name),
+        |a, b| trajectory_changes(a, b, tolerance),
+        (
+            &mut report.added_trajectories,
+            &mut report.removed_trajectories,
+            &mut report.changed_trajectories,
+        ),
+    );
+    diff_maps(
+        old.sequences.iter(),
+        |name| new.sequence(name),
+        new.sequences.iter(),
+        |name| old.sequence(name),
+        |a, b| sequence_changes(a, b, tolerance),
+        (&mut report.added_sequences, &mut report.removed_sequences, &mut report.changed_sequences),
+    );
+    report
+}
+
+/// Fill the added, removed and changed lists of one kind of entry.
+fn diff_maps<'a, T: 'a, const N: usize>(
+    old: impl Iterator<Item = (&'a String<32>, &'a T)>,
+    in_new: impl Fn(&str) -> Option<&'a T>,
+    new: impl Iterator<Item = (&'a String<32>, &'a T)>,
+    in_old: impl Fn(&str) -> Option<&'a T>,
+    changes: impl Fn(&T, &T) -> FieldChanges,
+    (added, removed, changed): (
+        &mut Vec<String<32>, N>,
+        &mut Vec<String<32>, N>,
+        &mut Vec<EntryChange, N>,
+    ),
+) {
+    for (name, before) in old {
+        match in_new(name) {
+            None => {
+                let _ = removed.push(name.clone());
+            }
+            Some(after) => {
+                let fields = changes(before, after);
+                if !fields.is_empty() {
+                    let _ = changed.push(EntryChange { name: name.clone(), fields });
+                }
+            }
+        }
+    }
+    for (name, _) in new.filter(|(name, _)| in_old(name).is_none()) {
+        let _ = added.push(name.clone());
+    }
+}
+
+impl MotorConfig {
+    /// Compare with `other`, treating floats within `tolerance` as equal.
+    pub fn approx_eq(&self, other: &MotorConfig, tolerance: f32) -> bool {
+        motor_changes(self, other, tolerance).is_empty()
+    }
+}
+
+impl TrajectoryConfig {
+    /// Compare with `other`, treating floats within `tolerance` as equal.
+    pub fn approx_eq(&self, other: &TrajectoryConfig, tolerance: f32) -> bool {
+        trajectory_changes(self, other, tolerance).is_empty()
+    }
+}
+
+impl WaypointTrajectory {
+    /// Compare with `other`, treating floats within `tolerance` as equal.
+    pub fn approx_eq(&self, other: &WaypointTrajectory, tolerance: f32) -> bool {
+        sequence_changes(self, other, tolerance).is_empty()
+    }
+}
+
+impl SoftLimits {
+    /// Compare with `other`, treating floats within `tolerance` as equal.
+    pub fn approx_eq(&self, other: &SoftLimits, tolerance: f32) -> bool {
+        limits_changes(self, other, Changes::new(tolerance)).fields.is_empty()
+    }
+}
+
+fn motor_changes(a: &MotorConfig, b: &MotorConfig, tolerance: f32) -> FieldChanges {
+    let mut c = Changes::new(tolerance);
+    c.field("name", &a.name, &b.name)
+        .field("steps_per_revolution", &a.steps_per_revolution, &b.steps_per_revolution)
+        .field("microsteps", &a.microsteps.value(), &b.microsteps.value())
+        .field("gear_ratio", &a.gear_ratio, &b.gear_ratio)
+        .field("gear_ratio_numerator", &a.gear_ratio_num, &b.gear_ratio_num)
+        .field("gear_ratio_denominator", &a.gear_ratio_den, &b.gear_ratio_den)
+        .field("max_velocity_deg_per_sec", &a.max_velocity, &b.max_velocity)
+        .field("max_acceleration_deg_per_sec2", &a.max_acceleration, &b.max_acceleration)
+        .field("invert_direction", &a.invert_direction, &b.invert_direction)
+        .field("output_inverted", &a.output_inverted, &b.output_inverted);
+    c = match (&a.limits, &b.limits) {
+        (Some(a), Some(b)) => limits_changes(a, b, c),
+        (a, b) => {
+            c.field("limits", a, b);
+            c
+        }
+    };
+    c.field("backlash_compensation_deg", &a.backlash_compensation, &b.backlash_compensation)
+        .field(
+            "backlash_compensation_steps",
+            &a.backlash_compensation_steps,
+            &b.backlash_compensation_steps,
+        )
+        .field("step_pulse_width_ns", &a.step_pulse_ns, &b.step_pulse_ns)
+        .field("reversal_dwell_ms", &a.reversal_dwell_ms, &b.reversal_dwell_ms)
+        .field("delay_overhead_ns", &a.delay_overhead_ns, &b.delay_overhead_ns)
+        .field("clamp_on_startup", &a.clamp_on_startup, &b.clamp_on_startup)
+        .field("winding_resistance_ohm", &a.winding_resistance_ohm, &b.winding_resistance_ohm)
+        .field("peak_current_ma", &a.peak_current_ma, &b.peak_current_ma)
+        .field("max_step_rate_hz", &a.max_step_rate_hz, &b.max_step_rate_hz)
+        .field("park_trajectory", &a.park_trajectory, &b.park_trajectory)
+        .field("thermal", &a.thermal, &b.thermal)
+        .field(
+            "approach_direction",
+            &a.backlash_approach_direction,
+            &b.backlash_approach_direction,
+        )
+        .field("gravity_assist_factor", &a.gravity_assist_factor, &b.gravity_assist_factor)
+        .field("gravity_direction", &a.gravity_direction, &b.gravity_direction)
+        .field("initial_velocity_deg_per_sec", &a.initial_velocity, &b.initial_velocity)
+        .field("minimum_move_steps", &a.minimum_move_steps, &b.minimum_move_steps)
+        .field("soft_start", &a.soft_start, &b.soft_start)
+        .field("rated_torque_nm", &a.rated_torque_nm, &b.rated_torque_nm)
+        .field("torque_speed_curve", &a.torque_speed_curve, &b.torque_speed_curve)
+        .field("homing", &a.homing, &b.homing);
+    c.fields
+}
+
+/// Add the changed keys of two soft limit tables to `c`.
+fn limits_changes(a: &SoftLimits, b: &SoftLimits, mut c: Changes) -> Changes {
+    c.field("limits.min_degrees", &a.min, &b.min)
+        .field("limits.max_degrees", &a.max, &b.max)
+        .field("limits.max_degrees_cw", &a.max_cw, &b.max_cw)
+        .field("limits.max_degrees_ccw", &a.max_ccw, &b.max_ccw)
+        .field("limits.policy", &a.policy, &b.policy)
+        .field("limits.margin_deg", &a.margin, &b.margin)
+        .field(
+            "limits.margin_velocity_percent",
+            &a.margin_velocity_percent,
+            &b.margin_velocity_percent,
+        )
+        .field(
+            "limits.clamp_approach_velocity_percent",
+            &a.clamp_approach_velocity_percent,
+            &b.clamp_approach_velocity_percent,
+        )
+        .field("limits.clamp_slow_zone_deg", &a.clamp_slow_zone, &b.clamp_slow_zone);
+    c
+}
+
+fn trajectory_changes(a: &TrajectoryConfig, b: &TrajectoryConfig, tolerance: f32) -> FieldChanges {
+    let mut c = Changes::new(tolerance);
+    c.field("motor", &a.motor, &b.motor)
+        .field("target_degrees", &a.target_degrees, &b.target_degrees)
+        .field("target_turns", &a.target_turns, &b.target_turns)
+        .field("velocity_percent", &a.velocity_percent, &b.velocity_percent)
+        .field("acceleration_percent", &a.acceleration_percent, &b.acceleration_percent)
+        .field("acceleration_deg_per_sec2", &a.acceleration, &b.acceleration)
+        .field("deceleration_deg_per_sec2", &a.deceleration, &b.deceleration)
+        .field("dwell_ms", &a.dwell_ms, &b.dwell_ms)
+        .field("requires_position_deg", &a.requires_position, &b.requires_position)
+        .field("tolerance_deg", &a.tolerance, &b.tolerance)
+        .field("requires_previous", &a.requires_previous, &b.requires_previous)
+        .field("kind", &a.trajectory_kind, &b.trajectory_kind)
+        .field("approach", &a.approach, &b.approach)
+        .field("overshoot_deg", &a.overshoot, &b.overshoot)
+        .field("limit_override", &a.limit_override, &b.limit_override)
+        .field("load_torque_nm", &a.load_torque_nm, &b.load_torque_nm);
+    c.fields
+}
+
+fn sequence_changes(
+    a: &WaypointTrajectory,
+    b: &WaypointTrajectory,
+    tolerance: f32,
+) -> FieldChanges {
+    let mut c = Changes::new(tolerance);
+    c.field("motor", &a.motor, &b.motor)
+        .field("waypoints", &a.waypoints[..], &b.waypoints[..])
+        .field("dwell_ms", &a.dwell_ms, &b.dwell_ms)
+        .field("velocity_percent", &a.velocity_percent, &b.velocity_percent)
+        .field("acceleration_percent", &a.acceleration_percent, &b.acceleration_percent)
+        .field("acceleration_deg_per_sec2", &a.acceleration, &b.acceleration)
+        .field("deceleration_deg_per_sec2", &a.deceleration, &b.deceleration)
+        .field("final_approach", &a.final_approach, &b.final_approach);
+    c.fields
+}
+
+/// Collects the fields that differ.
+struct Changes {
+    fields: FieldChanges,
+    tolerance: f32,
+}
+
+impl Changes {
+    fn new(tolerance: f32) -> Self {
+        Self { fields: Vec::new(), tolerance }
+    }
+
+    fn field<T: Approx + fmt::Debug + ?Sized>(
+        &mut self,
+        field: &'static str,
+        old: &T,
+        new: &T,
+    ) -> &mut Self {
+        if !old.approx(new, self.tolerance) {
+            let _ = self.fields.push(FieldChange::new(field, &old, &new));
+        }
+        self
+    }
+}
+
+/// Equality with floats compared within a tolerance.
+trait Approx {
+    fn approx(&self, other: &Self, tolerance: f32) -> bool;
+}
+
+impl Approx for f32 {
+    fn approx(&self, other: &Self, tolerance: f32) -> bool {
+        self == other || (self - other).abs() <= tolerance || (self.is_nan() && other.is_nan())
+    }
+}
+
+/// Types without floats compare exactly.
+macro_rules! exact {
+    ($($ty:ty),*) => {
+        $(impl Approx for $ty {
+            fn approx(&self, other: &Self, _tolerance: f32) -> bool {
+                self == other
+            }
+        })*
+    };
+}
+
+exact!(
+    u8,
+    u16,
+    u32,
+    i32,
+    i64,
+    bool,
+    String<32>,
+    crate::motion::Direction,
+    super::limits::LimitPolicy,
+    super::motor::HomingDirection,
+    super::soft_start::SoftStartConfig,
+    super::trajectory::Approach
+);
+
+/// Unit newtypes compare their value.
+macro_rules! unit {
+    ($($ty:ty),*) => {
+        $(impl Approx for $ty {
+            fn approx(&self, other: &Self, tolerance: f32) -> bool {
+                self.0.approx(&other.0, tolerance)
+            }
+        })*
+    };
+}
+
+unit!(Degrees, DegreesPerSec, DegreesPerSecSquared);
+
+impl<T: Approx> Approx for Option<T> {
+    fn approx(&self, other: &Self, tolerance: f32) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.approx(b, tolerance),
+            (a, b) => a.is_none() && b.is_none(),
+        }
+    }
+}
+
+impl<T: Approx> Approx for [T] {
+    fn approx(&self, other: &Self, tolerance: f32) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.approx(b, tolerance))
+    }
+}
+
+impl<T: Approx, const N: usize> Approx for [T; N] {
+    fn approx(&self, other: &Self, tolerance: f32) -> bool {
+        self[..].approx(&other[..], tolerance)
+    }
+}
+
+impl<A: Approx, B: Approx> Approx for (A, B) {
+    fn approx(&self, other: &Self, tolerance: f32) -> bool {
+        self.0.approx(&other.0, tolerance) && self.1.approx(&other.1, tolerance)
+    }
+}
+
+impl Approx for SoftLimits {
+    fn approx(&self, other: &Self, tolerance: f32) -> bool {
+        self.approx_eq(other, tolerance)
+    }
+}
+
+impl Approx for ThermalConfig {
+    fn approx(&self, other: &Self, tolerance: f32) -> bool {
+        self.time_constant_s.approx(&other.time_constant_s, tolerance)
+            && self.max_duty.approx(&other.max_duty, tolerance)
+            && self.enforce == other.enforce
+    }
+}
+
+impl Approx for HomingConfig {
+    fn approx(&self, other: &Self, tolerance: f32) -> bool {
+        self.direction == other.direction
+            && self.velocity_percent == other.velocity_percent
+            && self.backoff.approx(&other.backoff, tolerance)
+            && self.switch_active_low == other.switch_active_low
+            && self.timeout.approx(&other.timeout, tolerance)
+    }
+}
+
+impl Approx for FinalApproach {
+    fn approx(&self, other: &Self, tolerance: f32) -> bool {
+        self.distance.approx(&other.distance, tolerance)
+            && self.velocity_percent == other.velocity_percent
+    }
+}
+
+impl Approx for TrajectoryKind {
+    fn approx(&self, other: &Self, tolerance: f32) -> bool {
+        match (self, other) {
+            (TrajectoryKind::Standard, TrajectoryKind::Standard) => true,
+            (
+                TrajectoryKind::Homing { direction: a, max_travel_degrees: travel_a },
+                TrajectoryKind::Homing { direction: b, max_travel_degrees: travel_b },
+            ) => a == b && travel_a.approx(travel_b, tolerance),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{LimitPolicy, SystemConfig};
+
+    fn config() -> SystemConfig {
+        toml::from_str(
+            r#"
+[motors.x]
+name = "X"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[motors.x.limits]
+min_degrees = 0.0
+max_degrees = 180.0
+
+[sequences.scan]
+motor = "x"
+waypoints = [10.0, 20.0]
+"#,
+        )
+        .unwrap()
+    }
+
+    fn key(name: &str) -> String<32> {
+        String::try_from(name).unwrap()
+    }
+
+    #[test]
+    fn test_nested_and_sequence_changes() {
+        let old = config();
+        let mut new = config();
+        let x = new.motors.get_mut(&key("x")).unwrap();
+        x.max_velocity = DegreesPerSec(180.0);
+        x.limits.as_mut().unwrap().max = Degrees(170.0);
+        let scan = new.sequences.get_mut(&key("scan")).unwrap();
+        scan.waypoints[1] = Degrees(25.0);
+        let mut sweep = scan.clone();
+        sweep.dwell_ms = 100;
+        new.sequences.insert(key("sweep"), sweep).unwrap();
+
+        let report = diff(&old, &new);
+        let fields: Vec<&str, 4> =
+            report.changed_motors[0].fields.iter().map(|c| c.field).collect();
+        assert_eq!(fields, ["max_velocity_deg_per_sec", "limits.max_degrees"]);
+        assert!(report.changed_sequences[0].changed("waypoints"));
+        assert_eq!(report.added_sequences, ["sweep"]);
+
+        // A limits table added or removed is one change
+        new.motors.get_mut(&key("x")).unwrap().limits = None;
+        assert!(diff(&old, &new).changed_motors[0].changed("limits"));
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let limits = SoftLimits::new(Degrees(0.0), Degrees(90.0), LimitPolicy::Reject);
+        let mut close = limits.clone();
+        close.max = Degrees(90.00001);
+        assert!(limits.approx_eq(&close, DIFF_TOLERANCE));
+        assert_ne!(limits, close);
+        assert!(!limits.approx_eq(&close, 0.0));
+
+        let motor = config().motor("x").unwrap().clone();
+        let mut other = motor.clone();
+        other.limits.as_mut().unwrap().max = Degrees(180.00001);
+        assert!(motor.approx_eq(&other, DIFF_TOLERANCE));
+        other.limits.as_mut().unwrap().policy = LimitPolicy::Clamp;
+        assert!(!motor.approx_eq(&other, DIFF_TOLERANCE));
+    }
+}
@@ -0,0 +1,356 @@
+//! Configuration overrides from environment variables (std only).
+
+use core::fmt::Write as _;
+use core::str::FromStr;
+use std::env;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::error::{ConfigError, Error, Result};
+
+use super::units::{Degrees, DegreesPerSec, DegreesPerSecSquared, Microsteps};
+use super::{MotorConfig, SystemConfig, TrajectoryConfig};
+
+/// Prefix of every override variable.
+pub const ENV_PREFIX: &str = "STEPPER_";
+
+/// Why an override was not applied.
+enum OverrideError {
+    UnknownField,
+    InvalidValue,
+}
+
+impl SystemConfig {
+    /// Apply overrides from `STEPPER_*` environment variables, e.g. for CI
+    /// runs that need different limits without editing the file.
+    ///
+    /// See [`apply_overrides`](Self::apply_overrides) for the naming
+    /// convention.
+    ///
+    /// # Errors
+    ///
+    /// As [`apply_overrides`](Self::apply_overrides).
+    pub fn apply_env_overrides(self) -> Result<Self> {
+        self.apply_env_overrides_with(|variable| env::var(variable).ok())
+    }
+
+    /// Apply overrides from variables read through `lookup` instead of the
+    /// process environment.
+    ///
+    /// `lookup` is asked for the `STEPPER_*` variable of every overridable
+    /// field of every motor and trajectory, and returns its value if set.
+    /// Only those names are read, so a variable with a misspelled field is
+    /// ignored here rather than reported as by
+    /// [`apply_overrides`](Self::apply_overrides).
+    ///
+    /// # Errors
+    ///
+    /// As [`apply_overrides`](Self::apply_overrides).
+    pub fn apply_env_overrides_with<F>(self, mut lookup: F) -> Result<Self>
+    where
+        F: FnMut(&str) -> Option<String>,
+    {
+        let motors = self.motors.keys().map(|key| ("MOTOR", key, MOTOR_FIELDS));
+        let trajectories =
+            self.trajectories.keys().map(|key| ("TRAJECTORY", key, TRAJECTORY_FIELDS));
+        let mut vars = Vec::new();
+        for (kind, key, fields) in motors.chain(trajectories) {
+            for field in fields {
+                let variable = std::format!(
+                    "{ENV_PREFIX}{kind}_{}_{}",
+                    env_name(key),
+                    field.to_ascii_uppercase()
+                );
+                if let Some(value) = lookup(&variable) {
+                    vars.push((variable, value));
+                }
+            }
+        }
+        self.apply_overrides(vars)
+    }
+
+    /// Apply overrides from `(variable, value)` pairs named like environment
+    /// variables.
+    ///
+    /// Variables are named `STEPPER_MOTOR_{NAME}_{FIELD}` or
+    /// `STEPPER_TRAJECTORY_{NAME}_{FIELD}`, with the motor or trajectory key
+    /// and the field name in upper case and any character other than a
+    /// letter or digit as `_`: `STEPPER_MOTOR_PAN_MAX_VELOCITY=90.0` sets
+    /// `max_velocity` of `[motors.pan]`. When several keys match, the
+    /// longest wins.
+    ///
+    /// Motor fields: `max_velocity`, `max_acceleration`, `initial_velocity`,
+    /// `steps_per_revolution`, `microsteps`, `gear_ratio`,
+    /// `invert_direction`, `backlash_compensation`,
+    /// `backlash_compensation_steps`, `step_pulse_ns` and
+    /// `reversal_dwell_ms`. Trajectory fields: `target_degrees`,
+    /// `velocity_percent`, `acceleration_percent`, `acceleration`,
+    /// `deceleration` and `dwell_ms`. Units are those of the TOML keys.
+    ///
+    /// Other variables, and ones naming no motor or trajectory in this
+    /// configuration, are ignored. The configuration is validated again
+    /// afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::ParseError` naming the variable if its field is
+    /// unknown or its value doesn't parse, or a validation error.
+    pub fn apply_overrides<I, K, V>(mut self, vars: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for (variable, value) in vars {
+            let (variable, value) = (variable.as_ref(), value.as_ref());
+            let Some(rest) = variable.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            let applied = if let Some(rest) = rest.strip_prefix("MOTOR_") {
+                let Some((name, field)) = split_name(self.motors.keys(), rest) else {
+                    continue;
+                };
+                let Some(motor) = self.motors.get_mut(&name) else {
+                    continue;
+                };
+                set_motor_field(motor, &field, value)
+            } else if let Some(rest) = rest.strip_prefix("TRAJECTORY_") {
+                let Some((name, field)) = split_name(self.trajectories.keys(), rest) else {
+                    continue;
+                };
+                let Some(trajectory) = self.trajectories.get_mut(&name) else {
+                    continue;
+                };
+                set_trajectory_field(trajectory, &field, value)
+            } else {
+                continue;
+            };
+            if let Err(e) = applied {
+                return Err(override_error(variable, value, e));
+            }
+        }
+
+        super::validation::validate_config(&self)?;
+        Ok(self)
+    }
+}
+
+/// Split `rest` into the longest matching key and the lower-case field name.
+fn split_name<'a>(
+    keys: impl Iterator<Item = &'a heapless::String<32>>,
+    rest: &str,
+) -> Option<(heapless::String<32>, String)> {
+    keys.filter_map(|key| {
+        let field = rest.strip_prefix(env_name(key).as_str())?.strip_prefix('_')?;
+        Some((key.clone(), field.to_ascii_lowercase()))
+    })
+    .max_by_key(|(key, _)| key.len())
+}
+
+/// A key as it appears in variable names.
+fn env_name(key: &str) -> String {
+    key.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect()
+}
+
+fn parse<T: FromStr>(value: &str) -> core::result::Result<T, OverrideError> {
+    value.trim().parse().map_err(|_| OverrideError::InvalidValue)
+}
+
+/// Motor fields [`set_motor_field`] accepts.
+const MOTOR_FIELDS: &[&str] = &[
+    "max_velocity",
+    "max_acceleration",
+    "initial_velocity",
+    "steps_per_revolution",
+    "microsteps",
+    "gear_ratio",
+    "invert_direction",
+    "backlash_compensation",
+    "backlash_compensation_steps",
+    "step_pulse_ns",
+    "reversal_dwell_ms",
+];
+
+/// Trajectory fields [`set_trajectory_field`] accepts.
+const TRAJECTORY_FIELDS: &[&str] = &[
+    "target_degrees",
+    "velocity_percent",
+    "acceleration_percent",
+    "acceleration",
+    "deceleration",
+    "dwell_ms",
+];
+
+fn set_motor_field(
+    motor: &mut MotorConfig,
+    field: &str,
+    value: &str,
+) -> core::result::Result<(), OverrideError> {
+    match field {
+        "max_velocity" => motor.max_velocity = DegreesPerSec(parse(value)?),
+        "max_acceleration" => motor.max_acceleration = DegreesPerSecSquared(parse(value)?),
+        "initial_velocity" => motor.initial_velocity = Some(DegreesPerSec(parse(value)?)),
+        "steps_per_revolution" => motor.steps_per_revolution = parse(value)?,
+        "microsteps" => {
+            motor.microsteps =
+                Microsteps::new(parse(value)?).map_err(|_| OverrideError::InvalidValue)?;
+        }
+        "gear_ratio" => motor.gear_ratio = parse(value)?,
+        "invert_direction" => motor.invert_direction = parse(value)?,
+        "backlash_compensation" => motor.backlash_compensation = Some(Degrees(parse(value)?)),
+        "backlash_compensation_steps" => motor.backlash_compensation_steps = Some(parse(value)?),
+        "step_pulse_ns" => motor.step_pulse_ns = parse(value)?,
+        "reversal_dwell_ms" => motor.reversal_dwell_ms = parse(value)?,
+        _ => return Err(OverrideError::UnknownField),
+    }
+    Ok(())
+}
+
+fn set_trajectory_field(
+    trajectory: &mut TrajectoryConfig,
+    field: &str,
+    value: &str,
+) -> core::result::Result<(), OverrideError> {
+    match field {
+        "target_degrees" => trajectory.target_degrees = Degrees(parse(value)?),
+        "velocity_percent" => trajectory.velocity_percent = parse(value)?,
+        "acceleration_percent" => trajectory.acceleration_percent = parse(value)?,
+        "acceleration" => {
+            trajectory.acceleration = Some(DegreesPerSecSquared(parse(value)?));
+        }
+        "deceleration" => {
+            trajectory.deceleration = Some(DegreesPerSecSquared(parse(value)?));
+        }
+        "dwell_ms" => trajectory.dwell_ms = Some(parse(value)?),
+        _ => return Err(OverrideError::UnknownField),
+    }
+    Ok(())
+}
+
+fn override_error(variable: &str, value: &str, error: OverrideError) -> Error {
+    let mut text = String::new();
+    let _ = match error {
+        OverrideError::UnknownField => write!(text, "{}: unknown field", variable),
+        OverrideError::InvalidValue => write!(text, "{}: invalid value '{}'", variable, value),
+    };
+    let mut msg = heapless::String::new();
+    for c in text.chars() {
+        if msg.push(c).is_err() {
+            break;
+        }
+    }
+    Error::Config(ConfigError::ParseError(msg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::loader::parse_config;
+
+    fn config() -> SystemConfig {
+        parse_config(
+            r#"
+[motors.pan]
+name = "Pan"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[motors.pan_tilt]
+name = "Pan-Tilt"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 180.0
+max_acceleration_deg_per_sec2 = 360.0
+
+[trajectories.home]
+motor = "pan"
+target_degrees = 0.0
+
+[trajectories.look-up]
+motor = "pan_tilt"
+target_degrees = 45.0
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_apply_overrides() {
+        let config = config()
+            .apply_overrides([
+                ("STEPPER_MOTOR_PAN_MAX_VELOCITY", "90.0"),
+                ("STEPPER_MOTOR_PAN_TILT_MAX_VELOCITY", "45"),
+                ("STEPPER_MOTOR_PAN_TILT_MICROSTEPS", "32"),
+                ("STEPPER_TRAJECTORY_HOME_VELOCITY_PERCENT", "25"),
+                ("STEPPER_TRAJECTORY_LOOK_UP_DWELL_MS", "150"),
+                // Not for this configuration
+                ("STEPPER_MOTOR_ROLL_MAX_VELOCITY", "1.0"),
+                ("PATH", "/usr/bin"),
+            ])
+            .unwrap();
+
+        let pan = config.motor("pan").unwrap();
+        assert_eq!(pan.max_velocity, DegreesPerSec(90.0));
+        let pan_tilt = config.motor("pan_tilt").unwrap();
+        assert_eq!(pan_tilt.max_velocity, DegreesPerSec(45.0));
+        assert_eq!(pan_tilt.microsteps, Microsteps::THIRTY_SECOND);
+        assert_eq!(config.trajectory("home").unwrap().velocity_percent, 25);
+        assert_eq!(config.trajectory("look-up").unwrap().dwell_ms, Some(150));
+    }
+
+    #[test]
+    fn test_apply_overrides_errors() {
+        let error = |variable: &str, value: &str| {
+            let Err(Error::Config(ConfigError::ParseError(msg))) =
+                config().apply_overrides([(variable, value)])
+            else {
+                panic!("{variable}={value} should fail to parse");
+            };
+            msg
+        };
+        assert_eq!(
+            error("STEPPER_MOTOR_PAN_MAX_VELOCITY", "fast").as_str(),
+            "STEPPER_MOTOR_PAN_MAX_VELOCITY: invalid value 'fast'"
+        );
+        assert_eq!(
+            error("STEPPER_MOTOR_PAN_MAX_SPEED", "90").as_str(),
+            "STEPPER_MOTOR_PAN_MAX_SPEED: unknown field"
+        );
+        error("STEPPER_MOTOR_PAN_MICROSTEPS", "3");
+        error("STEPPER_TRAJECTORY_HOME_VELOCITY_PERCENT", "300");
+
+        // Parsed but invalid values fail validation
+        assert_eq!(
+            config()
+                .apply_overrides([("STEPPER_TRAJECTORY_HOME_VELOCITY_PERCENT", "0")])
+                .unwrap_err(),
+            Error::Config(ConfigError::InvalidVelocityPercent(0))
+        );
+    }
+
+    #[test]
+    fn test_apply_env_overrides() {
+        let mut asked = Vec::new();
+        let config = config()
+            .apply_env_overrides_with(|variable| {
+                asked.push(String::from(variable));
+                match variable {
+                    "STEPPER_MOTOR_PAN_MAX_ACCELERATION" => Some("1440.0".into()),
+                    "STEPPER_TRAJECTORY_LOOK_UP_DWELL_MS" => Some("150".into()),
+                    _ => None,
+                }
+            })
+            .unwrap();
+        let pan = config.motor("pan").unwrap();
+        assert_eq!(pan.max_acceleration, DegreesPerSecSquared(1440.0));
+        assert_eq!(config.trajectory("look-up").unwrap().dwell_ms, Some(150));
+
+        // One variable per field of each motor and trajectory
+        assert_eq!(asked.len(), 2 * MOTOR_FIELDS.len() + 2 * TRAJECTORY_FIELDS.len());
+        assert!(asked.iter().any(|v| v == "STEPPER_MOTOR_PAN_TILT_MICROSTEPS"));
+    }
+}
@@ -0,0 +1,78 @@
+//! Motor groups: sets of motors that must share configuration values.
+
+use heapless::{String, Vec};
+use serde::Deserialize;
+
+use super::motor::MotorConfig;
+
+/// A motor configuration field that a group can enforce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum GroupField {
+    /// Base steps per revolution.
+    StepsPerRevolution,
+    /// Microstep divisor.
+    Microsteps,
+    /// Gear ratio.
+    GearRatio,
+    /// Maximum velocity.
+    MaxVelocity,
+    /// Maximum acceleration.
+    MaxAcceleration,
+    /// STEP pulse width.
+    StepPulseWidthNs,
+}
+
+impl GroupField {
+    /// Name of the field as written in configuration.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GroupField::StepsPerRevolution => "steps_per_revolution",
+            GroupField::Microsteps => "microsteps",
+            GroupField::GearRatio => "gear_ratio",
+            GroupField::MaxVelocity => "max_velocity",
+            GroupField::MaxAcceleration => "max_acceleration",
+            GroupField::StepPulseWidthNs => "step_pulse_width_ns",
+        }
+    }
+
+    /// Read this field from a motor configuration.
+    pub fn value(self, motor: &MotorConfig) -> f32 {
+        match self {
+            GroupField::StepsPerRevolution => motor.steps_per_revolution as f32,
+            GroupField::Microsteps => motor.microsteps.value() as f32,
+            GroupField::GearRatio => motor.effective_gear_ratio() as f32,
+            GroupField::MaxVelocity => motor.max_velocity.0,
+            GroupField::MaxAcceleration => motor.max_acceleration.0,
+            GroupField::StepPulseWidthNs => motor.step_pulse_ns as f32,
+        }
+    }
+}
+
+/// A named group of motors (`[groups.<name>]`).
+///
+/// `validate_config` checks that every field in `enforce` has the same value
+/// on all member motors, e.g. for mechanically coupled gantry axes.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GroupConfig {
+    /// Member motor names (must match motors in config).
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "std::vec::Vec<std::string::String>", length(max = 8))
+    )]
+    pub motors: Vec<String<32>, 8>,
+
+    /// Fields that must be identical across all members.
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(with = "std::vec::Vec<GroupField>", length(max = 8)))]
+    pub enforce: Vec<GroupField, 8>,
+}
+
+impl GroupConfig {
+    /// Check if a motor is a member of this group.
+    pub fn contains(&self, motor: &str) -> bool {
+        self.motors.iter().any(|m| m.as_str() == motor)
+    }
+}
@@ -3,9 +3,11 @@
 use serde::Deserialize;
 
 use super::units::Degrees;
+use crate::motion::Direction;
 
 /// Policy for handling limit violations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum LimitPolicy {
     /// Reject moves that would exceed limits.
@@ -13,10 +15,48 @@ pub enum LimitPolicy {
     Reject,
     /// Clamp target to nearest limit.
     Clamp,
+    /// Reduce the target modulo the span into `[min, max)`, for continuous
+    /// axes indexed over a fixed window (e.g. a 0-360° rotary table).
+    ///
+    /// A target exactly on `max` wraps to `min`.
+    Wrap,
+}
+
+impl LimitPolicy {
+    /// Name of the policy as written in configuration.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LimitPolicy::Reject => "reject",
+            LimitPolicy::Clamp => "clamp",
+            LimitPolicy::Wrap => "wrap",
+        }
+    }
+}
+
+/// Which soft limit a position is measured against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitSide {
+    /// The lower limit in output degrees.
+    Min,
+    /// The upper limit in output degrees.
+    Max,
+}
+
+/// A target outside the soft limits (see
+/// [`StepperMotor::would_exceed_limits`](crate::motor::StepperMotor::would_exceed_limits)).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LimitViolation {
+    /// The limit that would be crossed.
+    pub which: LimitSide,
+    /// How far beyond the limit the target lies (positive).
+    pub by: Degrees,
 }
 
 /// Soft limits in degrees (from configuration).
-#[derive(Debug, Clone, Deserialize)]
+///
+/// `==` compares floats exactly; `approx_eq` allows for rounding.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SoftLimits {
     /// Minimum allowed position in degrees.
     #[serde(rename = "min_degrees")]
@@ -26,20 +66,123 @@ pub struct SoftLimits {
     #[serde(rename = "max_degrees")]
     pub max: Degrees,
 
+    /// Maximum for moves towards higher positions, in degrees (optional,
+    /// defaults to `max`).
+    ///
+    /// Lets an axis travel further in one direction than it may come back
+    /// from, e.g. past a cable wrap that only unwinds one way.
+    #[serde(default, rename = "max_degrees_cw")]
+    pub max_cw: Option<Degrees>,
+
+    /// Maximum for moves towards lower positions, in degrees (optional,
+    /// defaults to `max`).
+    #[serde(default, rename = "max_degrees_ccw")]
+    pub max_ccw: Option<Degrees>,
+
     /// What to do when limit is exceeded.
     #[serde(default)]
     pub policy: LimitPolicy,
+
+    /// Slowdown zone inside each limit in degrees (optional).
+    ///
+    /// Moves ending within the zone cruise no faster than the motor can stop
+    /// from within the margin at its maximum acceleration.
+    #[serde(default, rename = "margin_deg")]
+    pub margin: Option<Degrees>,
+
+    /// Further cap on cruise velocity inside the margin, as a percentage of
+    /// the motor's max (1-100, optional).
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(range(min = 1, max = 100)))]
+    pub margin_velocity_percent: Option<u8>,
+
+    /// Cruise velocity over the final `clamp_slow_zone_deg` of a move whose
+    /// target was clamped, as a percentage of the motor's max (1-100,
+    /// optional).
+    ///
+    /// Keeps a host repeatedly sending out-of-range targets from driving the
+    /// axis into the limit at full speed. Needs `clamp_slow_zone_deg` to take
+    /// effect.
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(range(min = 1, max = 100)))]
+    pub clamp_approach_velocity_percent: Option<u8>,
+
+    /// Travel before the limit covered at `clamp_approach_velocity_percent`
+    /// when a target is clamped, in degrees (optional).
+    #[serde(default, rename = "clamp_slow_zone_deg")]
+    pub clamp_slow_zone: Option<Degrees>,
 }
 
 impl SoftLimits {
     /// Create new soft limits.
     pub fn new(min: Degrees, max: Degrees, policy: LimitPolicy) -> Self {
-        Self { min, max, policy }
+        Self {
+            min,
+            max,
+            max_cw: None,
+            max_ccw: None,
+            policy,
+            margin: None,
+            margin_velocity_percent: None,
+            clamp_approach_velocity_percent: None,
+            clamp_slow_zone: None,
+        }
     }
 
-    /// Check if limits are valid (min < max).
+    /// Set a slowdown zone of `margin` inside each limit.
+    pub fn with_margin(mut self, margin: Degrees) -> Self {
+        self.margin = Some(margin);
+        self
+    }
+
+    /// Cover the final `zone` of clamped moves at `velocity_percent` of the
+    /// motor's max velocity.
+    pub fn with_clamp_approach(mut self, zone: Degrees, velocity_percent: u8) -> Self {
+        self.clamp_slow_zone = Some(zone);
+        self.clamp_approach_velocity_percent = Some(velocity_percent);
+        self
+    }
+
+    /// Use different maxima for moves towards higher (`cw`) and lower
+    /// (`ccw`) positions.
+    pub fn with_directional_max(mut self, cw: Degrees, ccw: Degrees) -> Self {
+        self.max_cw = Some(cw);
+        self.max_ccw = Some(ccw);
+        self
+    }
+
+    /// The maximum that applies to a move in `direction`.
+    pub fn max_for(&self, direction: Direction) -> Degrees {
+        match direction {
+            Direction::Clockwise => self.max_cw,
+            Direction::CounterClockwise => self.max_ccw,
+        }
+        .unwrap_or(self.max)
+    }
+
+    /// Check if the clamp slow zone is non-negative and fits within the
+    /// span.
+    pub fn is_clamp_slow_zone_valid(&self) -> bool {
+        self.clamp_slow_zone
+            .map_or(true, |z| z.0 >= 0.0 && z.0 <= self.max.0 - self.min.0)
+    }
+
+    /// Check if the margin fits: non-negative and at most half the span.
+    pub fn is_margin_valid(&self) -> bool {
+        self.margin
+            .map_or(true, |m| m.0 >= 0.0 && m.0 <= (self.max.0 - self.min.0) / 2.0)
+    }
+
+    /// The lowest of the maxima, directional or not.
+    pub fn lowest_max(&self) -> Degrees {
+        let cw = self.max_for(Direction::Clockwise);
+        let ccw = self.max_for(Direction::CounterClockwise);
+        Degrees(self.max.0.min(cw.0).min(ccw.0))
+    }
+
+    /// Check if limits are valid (min below each maximum).
     pub fn is_valid(&self) -> bool {
-        self.min.0 < self.max.0
+        self.min.0 < self.lowest_max().0
     }
 
     /// Check if a position is within limits.
@@ -47,29 +190,63 @@ impl SoftLimits {
         position.0 >= self.min.0 && position.0 <= self.max.0
     }
 
+    /// Check if a move in `direction` may end at `position`.
+    pub fn contains_moving(&self, position: Degrees, direction: Direction) -> bool {
+        position.0 >= self.min.0 && position.0 <= self.max_for(direction).0
+    }
+
+    /// Check if a move in either direction may end at `position`, for when
+    /// the starting point isn't known.
+    pub fn contains_either(&self, position: Degrees) -> bool {
+        self.contains_moving(position, Direction::Clockwise)
+            || self.contains_moving(position, Direction::CounterClockwise)
+    }
+
     /// Apply limit policy to a target position.
     ///
-    /// Returns `Some(position)` if valid or clamped, `None` if rejected.
+    /// Returns `Some(position)` if valid, clamped or wrapped, `None` if
+    /// rejected.
     pub fn apply(&self, target: Degrees) -> Option<Degrees> {
-        if self.contains(target) {
-            Some(target)
-        } else {
-            match self.policy {
-                LimitPolicy::Reject => None,
-                LimitPolicy::Clamp => {
-                    if target.0 < self.min.0 {
-                        Some(self.min)
-                    } else {
-                        Some(self.max)
-                    }
+        match self.policy {
+            LimitPolicy::Wrap => Some(self.wrap(target).0),
+            _ if self.contains(target) => Some(target),
+            LimitPolicy::Reject => None,
+            LimitPolicy::Clamp => {
+                if target.0 < self.min.0 {
+                    Some(self.min)
+                } else {
+                    Some(self.max)
                 }
             }
         }
     }
+
+    /// Reduce a target modulo the span into `[min, max)`, whatever the policy.
+    ///
+    /// Returns the wrapped target and the number of spans removed (negative
+    /// for targets below `min`).
+    pub fn wrap(&self, target: Degrees) -> (Degrees, i32) {
+        let span = self.max.0 - self.min.0;
+        if span.is_nan() || span <= 0.0 {
+            return (target, 0);
+        }
+        let offset = target.0 - self.min.0;
+        let mut spans = (offset / span) as i32;
+        let mut wrapped = offset - spans as f32 * span;
+        if wrapped < 0.0 {
+            spans -= 1;
+            wrapped += span;
+        }
+        if wrapped >= span {
+            spans += 1;
+            wrapped -= span;
+        }
+        (Degrees(self.min.0 + wrapped), spans)
+    }
 }
 
 /// Soft limits converted to steps (for runtime use).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StepLimits {
     /// Minimum position in steps.
     pub min_steps: i64,
@@ -77,42 +254,133 @@ pub struct StepLimits {
     pub max_steps: i64,
     /// Limit policy.
     pub policy: LimitPolicy,
+    /// Slowdown zone inside each limit in steps (0 = none).
+    pub margin_steps: i64,
+    /// Cruise velocity cap inside the margin as a percentage of max.
+    pub margin_velocity_percent: Option<u8>,
+    /// Final travel of a clamped move at the clamp approach velocity, in
+    /// steps (0 = none).
+    pub clamp_slow_zone_steps: i64,
+    /// Velocity over the clamp slow zone as a percentage of max.
+    pub clamp_approach_velocity_percent: Option<u8>,
+    /// `(min, max)` for moves in the positive step direction, if they
+    /// differ from `min_steps` and `max_steps`.
+    pub cw_bounds: Option<(i64, i64)>,
+    /// `(min, max)` for moves in the negative step direction, if they
+    /// differ from `min_steps` and `max_steps`.
+    pub ccw_bounds: Option<(i64, i64)>,
 }
 
 impl StepLimits {
     /// Create step limits from soft limits and steps per degree.
     pub fn from_soft_limits(soft: &SoftLimits, steps_per_degree: f32) -> Self {
+        let min_steps = (soft.min.0 * steps_per_degree) as i64;
+        let bounds = |max: Option<Degrees>| {
+            max.map(|max| (min_steps, (max.0 * steps_per_degree) as i64))
+        };
         Self {
-            min_steps: (soft.min.0 * steps_per_degree) as i64,
+            min_steps,
             max_steps: (soft.max.0 * steps_per_degree) as i64,
             policy: soft.policy,
+            margin_steps: soft.margin.map_or(0, |m| (m.0 * steps_per_degree) as i64),
+            margin_velocity_percent: soft.margin_velocity_percent,
+            clamp_slow_zone_steps: soft
+                .clamp_slow_zone
+                .map_or(0, |z| (z.0 * steps_per_degree) as i64),
+            clamp_approach_velocity_percent: soft.clamp_approach_velocity_percent,
+            cw_bounds: bounds(soft.max_cw),
+            ccw_bounds: bounds(soft.max_ccw),
         }
     }
 
+    /// Mirror the limits around zero, for an inverted output.
+    ///
+    /// Moves towards higher output positions step negatively, so the
+    /// directional bounds swap too.
+    pub(crate) fn mirrored(self) -> Self {
+        let mirror = |bounds: Option<(i64, i64)>| bounds.map(|(min, max)| (-max, -min));
+        Self {
+            min_steps: -self.max_steps,
+            max_steps: -self.min_steps,
+            cw_bounds: mirror(self.ccw_bounds),
+            ccw_bounds: mirror(self.cw_bounds),
+            ..self
+        }
+    }
+
+    /// The `(min, max)` that apply to a move in `direction`.
+    pub fn bounds(&self, direction: Direction) -> (i64, i64) {
+        match direction {
+            Direction::Clockwise => self.cw_bounds,
+            Direction::CounterClockwise => self.ccw_bounds,
+        }
+        .unwrap_or((self.min_steps, self.max_steps))
+    }
+
+    /// Check if a move in `direction` may end at `steps`.
+    pub fn contains_moving(&self, steps: i64, direction: Direction) -> bool {
+        let (min, max) = self.bounds(direction);
+        steps >= min && steps <= max
+    }
+
     /// Check if a position is within limits.
     pub fn contains(&self, steps: i64) -> bool {
         steps >= self.min_steps && steps <= self.max_steps
     }
 
+    /// Check if a position lies in the slowdown zone next to either limit.
+    pub fn in_margin(&self, steps: i64) -> bool {
+        self.margin_steps > 0
+            && (steps < self.min_steps + self.margin_steps
+                || steps > self.max_steps - self.margin_steps)
+    }
+
     /// Apply limit policy to a target position.
     ///
-    /// Returns `Some(steps)` if valid or clamped, `None` if rejected.
+    /// Returns `Some(steps)` if valid, clamped or wrapped, `None` if rejected.
     pub fn apply(&self, target: i64) -> Option<i64> {
-        if self.contains(target) {
-            Some(target)
-        } else {
-            match self.policy {
-                LimitPolicy::Reject => None,
-                LimitPolicy::Clamp => {
-                    if target < self.min_steps {
-                        Some(self.min_steps)
-                    } else {
-                        Some(self.max_steps)
-                    }
+        match self.policy {
+            LimitPolicy::Wrap => Some(self.wrap(target).0),
+            _ if self.contains(target) => Some(target),
+            LimitPolicy::Reject => None,
+            LimitPolicy::Clamp => {
+                if target < self.min_steps {
+                    Some(self.min_steps)
+                } else {
+                    Some(self.max_steps)
                 }
             }
         }
     }
+
+    /// Apply limit policy to the target of a move in `direction`, using the
+    /// bounds for that direction.
+    ///
+    /// The wrap policy wraps into `[min_steps, max_steps)` as
+    /// [`apply`](Self::apply) does.
+    pub fn apply_moving(&self, target: i64, direction: Direction) -> Option<i64> {
+        let (min, max) = self.bounds(direction);
+        match self.policy {
+            LimitPolicy::Wrap => Some(self.wrap(target).0),
+            _ if self.contains_moving(target, direction) => Some(target),
+            LimitPolicy::Reject => None,
+            LimitPolicy::Clamp => Some(if target < min { min } else { max }),
+        }
+    }
+
+    /// Reduce a target modulo the span into `[min_steps, max_steps)`,
+    /// whatever the policy.
+    ///
+    /// Returns the wrapped target and the number of spans removed (negative
+    /// for targets below `min_steps`).
+    pub fn wrap(&self, target: i64) -> (i64, i64) {
+        let span = self.max_steps - self.min_steps;
+        if span <= 0 {
+            return (target, 0);
+        }
+        let offset = target - self.min_steps;
+        (self.min_steps + offset.rem_euclid(span), offset.div_euclid(span))
+    }
 }
 
 #[cfg(test)]
@@ -138,4 +406,75 @@ mod tests {
         assert_eq!(limits.apply(Degrees(360.0)).unwrap().0, 180.0);
         assert_eq!(limits.apply(Degrees(-360.0)).unwrap().0, -180.0);
     }
+
+    #[test]
+    fn test_soft_limits_wrap() {
+        let limits = SoftLimits::new(Degrees(0.0), Degrees(360.0), LimitPolicy::Wrap);
+
+        assert_eq!(limits.wrap(Degrees(90.0)), (Degrees(90.0), 0));
+        assert_eq!(limits.wrap(Degrees(450.0)), (Degrees(90.0), 1));
+        assert_eq!(limits.wrap(Degrees(1170.0)), (Degrees(90.0), 3));
+        assert_eq!(limits.wrap(Degrees(-90.0)), (Degrees(270.0), -1));
+        assert_eq!(limits.wrap(Degrees(-720.0)), (Degrees(0.0), -2));
+        // The window is half-open: max wraps to min
+        assert_eq!(limits.wrap(Degrees(360.0)), (Degrees(0.0), 1));
+        assert_eq!(limits.apply(Degrees(360.0)), Some(Degrees(0.0)));
+        assert_eq!(limits.apply(Degrees(0.0)), Some(Degrees(0.0)));
+
+        // A span other than 360°, offset from zero
+        let limits = SoftLimits::new(Degrees(-60.0), Degrees(60.0), LimitPolicy::Wrap);
+        assert_eq!(limits.apply(Degrees(70.0)), Some(Degrees(-50.0)));
+        assert_eq!(limits.wrap(Degrees(-190.0)), (Degrees(50.0), -2));
+        assert_eq!(limits.wrap(Degrees(60.0)), (Degrees(-60.0), 1));
+    }
+
+    #[test]
+    fn test_step_limits_wrap() {
+        let soft = SoftLimits::new(Degrees(0.0), Degrees(360.0), LimitPolicy::Wrap);
+        let limits = StepLimits::from_soft_limits(&soft, 200.0 / 360.0);
+
+        assert_eq!(limits.apply(50), Some(50));
+        assert_eq!(limits.wrap(250), (50, 1));
+        assert_eq!(limits.wrap(-50), (150, -1));
+        assert_eq!(limits.wrap(-400), (0, -2));
+        assert_eq!(limits.apply(200), Some(0));
+        assert_eq!(limits.wrap(200), (0, 1));
+    }
+
+    #[test]
+    fn test_directional_maxima() {
+        let limits: SoftLimits = toml::from_str(
+            "min_degrees = 0.0\nmax_degrees = 90.0\nmax_degrees_cw = 100.0\n",
+        )
+        .unwrap();
+        assert_eq!(limits.max_for(Direction::Clockwise), Degrees(100.0));
+        assert_eq!(limits.max_for(Direction::CounterClockwise), Degrees(90.0));
+        assert!(limits.contains_moving(Degrees(95.0), Direction::Clockwise));
+        assert!(!limits.contains_moving(Degrees(95.0), Direction::CounterClockwise));
+        assert!(limits.contains_either(Degrees(95.0)));
+
+        let steps = StepLimits::from_soft_limits(&limits, 1.0);
+        assert_eq!(steps.bounds(Direction::Clockwise), (0, 100));
+        assert_eq!(steps.bounds(Direction::CounterClockwise), (0, 90));
+        assert_eq!(steps.apply_moving(95, Direction::CounterClockwise), None);
+
+        // An inverted output steps negatively towards higher positions
+        let mirrored = steps.mirrored();
+        assert_eq!(mirrored.bounds(Direction::CounterClockwise), (-100, 0));
+        assert_eq!(mirrored.bounds(Direction::Clockwise), (-90, 0));
+
+        // Each maximum must lie above the minimum
+        assert!(!limits.clone().with_directional_max(Degrees(100.0), Degrees(0.0)).is_valid());
+        assert_eq!(limits.with_directional_max(Degrees(50.0), Degrees(100.0)).lowest_max().0, 50.0);
+    }
+
+    #[test]
+    fn test_wrap_policy_from_toml() {
+        let limits: SoftLimits = toml::from_str(
+            "min_degrees = 0.0\nmax_degrees = 360.0\npolicy = \"wrap\"\n",
+        )
+        .unwrap();
+        assert_eq!(limits.policy, LimitPolicy::Wrap);
+        assert_eq!(limits.policy.as_str(), "wrap");
+    }
 }
@@ -1,7 +1,10 @@
 //! Configuration loading from files (std only).
 
 use std::fs;
+use std::ops::ControlFlow;
 use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 use crate::error::{ConfigError, Error, Result};
 
@@ -31,14 +34,24 @@ pub fn load_config<P: AsRef<Path>>(path: P) -> Result<SystemConfig> {
 
 /// Parse configuration from a TOML string.
 ///
+/// Presets selected with `preset = "<name>"` are applied before validation:
+/// fields the preset sets are copied into the trajectory, sequence or
+/// multi-motor sequence step unless it sets them itself.
+///
 /// # Errors
 ///
-/// Returns an error if the TOML is invalid or fails validation.
+/// Returns an error if the TOML is invalid, refers to an undefined preset
+/// (`ConfigError::PresetNotFound`) or fails validation.
 pub fn parse_config(content: &str) -> Result<SystemConfig> {
-    let config: SystemConfig = toml::from_str(content).map_err(|e| {
+    let parse_error = |e: toml::de::Error| {
         let msg = heapless::String::try_from(e.message()).unwrap_or_default();
         Error::Config(ConfigError::ParseError(msg))
-    })?;
+    };
+    let mut table: toml::Table = toml::from_str(content).map_err(parse_error)?;
+    apply_presets(&mut table)?;
+    check_names(&table)?;
+    let mut config = SystemConfig::default();
+    config.deserialize_in_place(toml::Value::Table(table)).map_err(parse_error)?;
 
     // Validate the configuration
     super::validation::validate_config(&config)?;
@@ -46,6 +59,137 @@ pub fn parse_config(content: &str) -> Result<SystemConfig> {
     Ok(config)
 }
 
+/// Check trajectory and sequence names before they are parsed into
+/// fixed-capacity strings, so an overlong name reports its length.
+fn check_names(config: &toml::Table) -> Result<()> {
+    let permissive = config
+        .get("permissive_names")
+        .and_then(toml::Value::as_bool)
+        .unwrap_or(false);
+    for section in ["trajectories", "sequences", "msequences"] {
+        if let Some(toml::Value::Table(entries)) = config.get(section) {
+            for name in entries.keys() {
+                super::check_config_name(name, permissive)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Replace `preset` keys with the preset's fields, keeping explicit ones.
+fn apply_presets(config: &mut toml::Table) -> Result<()> {
+    let presets = match config.get("presets") {
+        Some(toml::Value::Table(presets)) => presets.clone(),
+        _ => toml::Table::new(),
+    };
+
+    for section in ["trajectories", "sequences"] {
+        if let Some(toml::Value::Table(entries)) = config.get_mut(section) {
+            for (_, entry) in entries.iter_mut() {
+                apply_preset(entry, &presets)?;
+            }
+        }
+    }
+    if let Some(toml::Value::Table(msequences)) = config.get_mut("msequences") {
+        for (_, msequence) in msequences.iter_mut() {
+            if let Some(toml::Value::Array(steps)) = msequence.get_mut("steps") {
+                for step in steps {
+                    apply_preset(step, &presets)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Apply the preset selected by one trajectory-like table, if any.
+fn apply_preset(target: &mut toml::Value, presets: &toml::Table) -> Result<()> {
+    let Some(target) = target.as_table_mut() else {
+        return Ok(());
+    };
+    let Some(name) = target.remove("preset") else {
+        return Ok(());
+    };
+    let name = name.as_str().unwrap_or_default();
+    let Some(toml::Value::Table(preset)) = presets.get(name) else {
+        return Err(preset_not_found(name, presets.keys()));
+    };
+    for (key, value) in preset {
+        target.entry(key.as_str()).or_insert_with(|| value.clone());
+    }
+    Ok(())
+}
+
+/// Error for an undefined preset, listing the defined ones.
+fn preset_not_found<'a>(name: &str, available: impl Iterator<Item = &'a String>) -> Error {
+    let mut names: heapless::String<128> = heapless::String::new();
+    for (i, preset) in available.enumerate() {
+        if i > 0 {
+            let _ = names.push_str(", ");
+        }
+        let _ = names.push_str(preset);
+    }
+    Error::Config(ConfigError::PresetNotFound {
+        name: heapless::String::try_from(name).unwrap_or_default(),
+        available: names,
+    })
+}
+
+/// How often [`watch_config`] checks the file's modification time.
+pub const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Watch a configuration file and reload it whenever it changes.
+///
+/// Blocks the calling thread. `callback` receives the result of
+/// [`load_config`] once immediately and again each time the file's
+/// modification time changes, and returns [`ControlFlow::Break`] to stop
+/// watching. Polls every [`WATCH_POLL_INTERVAL`]; while the file is missing
+/// (e.g. mid-way through an editor's atomic save) polling just continues.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use std::ops::ControlFlow;
+///
+/// stepper_motion::config::watch_config("motion.toml", |config| {
+///     match config.and_then(|config| system.reload_config(config)) {
+///         Ok(report) if report.needs_rebuild() => rebuild(&report),
+///         Ok(_) => {}
+///         Err(e) => eprintln!("reload failed: {}", e),
+///     }
+///     ControlFlow::Continue(())
+/// });
+/// ```
+pub fn watch_config<P, F>(path: P, callback: F)
+where
+    P: AsRef<Path>,
+    F: FnMut(Result<SystemConfig>) -> ControlFlow<()>,
+{
+    watch(path.as_ref(), WATCH_POLL_INTERVAL, callback)
+}
+
+fn watch<F>(path: &Path, interval: Duration, mut callback: F)
+where
+    F: FnMut(Result<SystemConfig>) -> ControlFlow<()>,
+{
+    let modified = || -> Option<SystemTime> { fs::metadata(path).and_then(|m| m.modified()).ok() };
+
+    let mut last = modified();
+    if callback(load_config(path)).is_break() {
+        return;
+    }
+    loop {
+        thread::sleep(interval);
+        let current = modified();
+        if current.is_some() && current != last {
+            last = current;
+            if callback(load_config(path)).is_break() {
+                return;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -85,6 +229,123 @@ velocity_percent = 50
         assert!(config.trajectory("home").is_some());
     }
 
+    #[test]
+    fn test_parse_name_rules() {
+        use crate::error::NameError;
+
+        let config = |entries: &str| {
+            let mut toml = std::string::String::from(entries);
+            toml.push_str(
+                r#"
+[motors.x_axis]
+name = "X-Axis"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+"#,
+            );
+            parse_config(&toml).map(|config| config.trajectory("pick place").is_some())
+        };
+        let name_error = |result: Result<bool>| match result {
+            Err(Error::Config(ConfigError::InvalidName { error, .. })) => error,
+            other => panic!("expected a name error, got {:?}", other),
+        };
+
+        let valid = "[trajectories.Home_x-2]\nmotor = \"x_axis\"\ntarget_degrees = 0.0\n";
+        assert_eq!(config(valid), Ok(false));
+        let long = "[trajectories.a_name_that_is_33_characters_long]\nmotor = \"x_axis\"\n";
+        assert_eq!(name_error(config(long)), NameError::TooLong { len: 33 });
+        let spaced = "[trajectories.\"pick place\"]\nmotor = \"x_axis\"\ntarget_degrees = 0.0\n";
+        assert_eq!(
+            name_error(config(spaced)),
+            NameError::InvalidCharacter { character: ' ', index: 4 }
+        );
+        let slashed = "[sequences.\"a/b\"]\nmotor = \"x_axis\"\nwaypoints = [1.0]\n";
+        assert_eq!(
+            name_error(config(slashed)),
+            NameError::InvalidCharacter { character: '/', index: 1 }
+        );
+
+        let permissive = std::format!("permissive_names = true\n{}", spaced);
+        assert_eq!(config(&permissive), Ok(true));
+
+        // Trajectories and sequences share one namespace
+        let shared = "[trajectories.scan]\nmotor = \"x_axis\"\ntarget_degrees = 0.0\n\
+                      [sequences.scan]\nmotor = \"x_axis\"\nwaypoints = [1.0]\n";
+        assert!(matches!(
+            config(shared),
+            Err(Error::Config(ConfigError::DuplicateTrajectoryName(ref n))) if n == "scan"
+        ));
+    }
+
+    #[test]
+    fn test_parse_presets() {
+        let toml = r#"
+[motors.x_axis]
+name = "X-Axis"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[presets.gentle]
+velocity_percent = 30
+acceleration_percent = 20
+
+[presets.fast]
+velocity_percent = 100
+acceleration_percent = 50
+
+[trajectories.soft]
+motor = "x_axis"
+target_degrees = 90.0
+preset = "gentle"
+
+[trajectories.override]
+motor = "x_axis"
+target_degrees = 0.0
+preset = "gentle"
+velocity_percent = 80
+
+[sequences.scan]
+motor = "x_axis"
+waypoints = [0.0, 90.0]
+preset = "fast"
+
+[msequences.both]
+steps = [{ motor = "x_axis", target_degrees = 45.0, preset = "gentle" }]
+"#;
+
+        let config = parse_config(toml).unwrap();
+        let soft = config.trajectory("soft").unwrap();
+        assert_eq!((soft.velocity_percent, soft.acceleration_percent), (30, 20));
+        let explicit = config.trajectory("override").unwrap();
+        assert_eq!((explicit.velocity_percent, explicit.acceleration_percent), (80, 20));
+        assert_eq!(config.sequence("scan").unwrap().acceleration_percent, 50);
+        assert_eq!(config.msequence("both").unwrap().steps[0].acceleration_percent, 20);
+
+        // The same resolution for programmatic trajectories
+        let built = crate::trajectory::TrajectoryBuilder::new()
+            .velocity_percent(80)
+            .preset(config.preset("gentle").unwrap())
+            .motor("x_axis")
+            .target(crate::config::Degrees(0.0))
+            .build()
+            .unwrap();
+        assert_eq!(&built, explicit);
+
+        let err = parse_config(&toml.replace("preset = \"fast\"", "preset = \"slow\"")).unwrap_err();
+        assert_eq!(
+            err,
+            Error::Config(ConfigError::PresetNotFound {
+                name: heapless::String::try_from("slow").unwrap(),
+                available: heapless::String::try_from("fast, gentle").unwrap(),
+            })
+        );
+        assert!(err.to_string().contains("available: fast, gentle"));
+    }
+
     #[test]
     fn test_parse_asymmetric_trajectory() {
         let toml = r#"
@@ -107,4 +368,98 @@ deceleration_deg_per_sec2 = 200.0
         let traj = config.trajectory("gentle_stop").unwrap();
         assert!(traj.is_asymmetric());
     }
+
+    #[test]
+    fn test_parse_step_pulse_width() {
+        let toml = r#"
+[motors.x_axis]
+name = "X-Axis"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+step_pulse_width_ns = 1000
+
+[motors.y_axis]
+name = "Y-Axis"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+"#;
+
+        let config = parse_config(toml).unwrap();
+        assert_eq!(config.motor("x_axis").unwrap().step_pulse_ns, 1000);
+        assert_eq!(config.motor("y_axis").unwrap().step_pulse_ns, 2000);
+    }
+
+    #[test]
+    fn test_watch_reloads_on_change() {
+        const MINIMAL: &str = r#"
+[motors.x_axis]
+name = "X-Axis"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+"#;
+
+        let path = std::env::temp_dir().join(format!("stepper-watch-{}.toml", std::process::id()));
+        fs::write(&path, MINIMAL).unwrap();
+
+        let mut loaded = std::vec::Vec::new();
+        watch(&path, Duration::from_millis(10), |config| {
+            let config = config.unwrap();
+            loaded.push(config.motor("x_axis").unwrap().microsteps.value());
+            if loaded.len() == 1 {
+                // Make sure the new modification time differs from the first
+                thread::sleep(Duration::from_millis(50));
+                fs::write(&path, MINIMAL.replace("microsteps = 16", "microsteps = 32")).unwrap();
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        });
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, [16, 32]);
+    }
+
+    #[test]
+    fn test_parse_top_level_tables() {
+        let motor = |key: usize| {
+            std::format!(
+                "[motors.m{key}]\nname = \"M{key}\"\nsteps_per_revolution = 200\nmicrosteps = 1\n\
+                 max_velocity_deg_per_sec = 360.0\nmax_acceleration_deg_per_sec2 = 720.0\n"
+            )
+        };
+        let parse_error = |toml: &str| match parse_config(toml) {
+            Err(Error::Config(ConfigError::ParseError(msg))) => msg,
+            other => panic!("expected a parse error, got {:?}", other),
+        };
+
+        // Unknown top-level keys are ignored
+        let toml = std::format!("comment = \"bench rig\"\n{}", motor(0));
+        assert_eq!(parse_config(&toml).unwrap().motors.len(), 1);
+
+        assert!(parse_error("permissive_names = true\n").contains("missing field `motors`"));
+        let toml: std::string::String = (0..9).map(motor).collect();
+        assert!(parse_error(&toml).contains("at most 8 entries"));
+    }
+
+    #[test]
+    fn test_parse_full_config_on_small_stack() {
+        // The smallest stack std gives a spawned thread by default
+        let parsed = thread::Builder::new()
+            .stack_size(2 * 1024 * 1024)
+            .spawn(|| {
+                let config = parse_config(include_str!("../../motion.toml")).unwrap();
+                let config = config.apply_overrides([("STEPPER_MOTOR_X_AXIS_MICROSTEPS", "32")]);
+                config.map(|config| (config.motors.len(), config.trajectories.len()))
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(parsed, Ok((3, 7)));
+    }
 }
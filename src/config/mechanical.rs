@@ -1,29 +1,55 @@
 //! Mechanical constraints derived from motor configuration.
 
-use super::limits::StepLimits;
+use core::fmt;
+
+use crate::math::{ceilf, log10f, sqrtf};
+
+use super::limits::{LimitSide, LimitViolation, SoftLimits, StepLimits};
 use super::motor::MotorConfig;
-use super::units::{DegreesPerSec, DegreesPerSecSquared};
+use super::units::{round_to_places, Degrees, DegreesPerSec, DegreesPerSecSquared, Steps, Turns};
+use crate::error::{Error, MotionError, Result};
+use crate::motion::{Direction, MotionProfile};
 
 /// Derived mechanical parameters computed from motor configuration.
 ///
 /// These are computed once at initialization and used for all motion planning.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct MechanicalConstraints {
-    /// Total steps per output revolution (steps × microsteps × gear_ratio).
+    /// Total steps per output revolution (steps × microsteps × gear_ratio),
+    /// rounded down.
+    ///
+    /// Approximate for fractional gear ratios and kept for display; position
+    /// conversions use [`steps_per_revolution_exact`](Self::steps_per_revolution_exact).
     pub steps_per_revolution: u32,
 
-    /// Steps per degree of output rotation.
+    /// Untruncated steps per output revolution.
+    pub steps_per_revolution_exact: f64,
+
+    /// Steps per degree of output rotation (magnitude; see `output_inverted`).
     pub steps_per_degree: f32,
 
+    /// Positive steps move the output towards negative degrees.
+    pub output_inverted: bool,
+
     /// Maximum velocity in steps per second.
     pub max_velocity_steps_per_sec: f32,
 
     /// Maximum acceleration in steps per second squared.
     pub max_acceleration_steps_per_sec2: f32,
 
-    /// Minimum step interval in nanoseconds (at max velocity).
+    /// Velocity at the first step of a move in steps per second (0.0 = from rest).
+    pub initial_velocity_steps_per_sec: f32,
+
+    /// Shortest allowed move in steps (0 = no minimum).
+    pub minimum_move_steps: u32,
+
+    /// Minimum step interval in nanoseconds (at max velocity, or at
+    /// `max_step_rate_hz` if that is more restrictive).
     pub min_step_interval_ns: u32,
 
+    /// Driver step rate cap in steps/sec (if configured).
+    pub max_step_rate_hz: Option<u32>,
+
     /// Soft limits in steps (if configured).
     pub limits: Option<StepLimits>,
 
@@ -32,18 +58,28 @@ pub struct MechanicalConstraints {
 
     /// Maximum acceleration in degrees per second squared.
     pub max_acceleration: DegreesPerSecSquared,
+
+    /// Deceleration factor and the direction gravity assists (if configured).
+    pub gravity_assist: Option<(f32, Direction)>,
+
+    /// Rated torque in newton-metres (if configured).
+    pub rated_torque_nm: Option<f32>,
+
+    /// Torque-speed curve as `(velocity_deg_per_sec, torque_fraction)` points
+    /// (if configured).
+    pub torque_speed_curve: Option<[(f32, f32); 8]>,
 }
 
 impl MechanicalConstraints {
     /// Compute mechanical constraints from motor configuration.
     pub fn from_config(config: &MotorConfig) -> Self {
         // Total steps per output shaft revolution
-        let steps_per_revolution = (config.steps_per_revolution as f32
-            * config.microsteps.value() as f32
-            * config.gear_ratio) as u32;
+        let steps_per_revolution = config.whole_steps_per_revolution();
+        let steps_per_revolution_exact = config.total_steps_per_revolution_f64();
 
-        // Steps per degree
-        let steps_per_degree = steps_per_revolution as f32 / 360.0;
+        // Steps per degree (from the untruncated revolution count so that
+        // fractional gear ratios don't accumulate position error)
+        let steps_per_degree = (steps_per_revolution_exact / 360.0) as f32;
 
         // Convert velocity from deg/sec to steps/sec
         let max_velocity_steps_per_sec = config.max_velocity.0 * steps_per_degree;
@@ -51,41 +87,217 @@ impl MechanicalConstraints {
         // Convert acceleration from deg/sec² to steps/sec²
         let max_acceleration_steps_per_sec2 = config.max_acceleration.0 * steps_per_degree;
 
+        let initial_velocity_steps_per_sec =
+            config.initial_velocity.map_or(0.0, |v| v.0 * steps_per_degree);
+
         // Minimum step interval at max velocity (nanoseconds)
-        let min_step_interval_ns = if max_velocity_steps_per_sec > 0.0 {
+        let mut min_step_interval_ns = if max_velocity_steps_per_sec > 0.0 {
             (1_000_000_000.0 / max_velocity_steps_per_sec) as u32
         } else {
             u32::MAX
         };
-
-        // Convert soft limits to step limits
-        let limits = config
-            .limits
-            .as_ref()
-            .map(|l| StepLimits::from_soft_limits(l, steps_per_degree));
+        if let Some(rate) = config.max_step_rate_hz.filter(|&r| r > 0) {
+            min_step_interval_ns = min_step_interval_ns.max(1_000_000_000 / rate);
+        }
 
         Self {
             steps_per_revolution,
+            steps_per_revolution_exact,
             steps_per_degree,
+            output_inverted: config.output_inverted,
             max_velocity_steps_per_sec,
             max_acceleration_steps_per_sec2,
+            initial_velocity_steps_per_sec,
+            minimum_move_steps: config.minimum_move_steps.map_or(0, u32::from),
             min_step_interval_ns,
-            limits,
+            max_step_rate_hz: config.max_step_rate_hz,
+            limits: None,
             max_velocity: config.max_velocity,
             max_acceleration: config.max_acceleration,
+            gravity_assist: config.gravity_assist_factor.zip(config.gravity_direction),
+            rated_torque_nm: config.rated_torque_nm,
+            torque_speed_curve: config.torque_speed_curve,
+        }
+        .with_limits(config.limits.as_ref())
+    }
+
+    /// Replace the soft limits, converting them to steps (`None` removes them).
+    pub fn with_limits(mut self, limits: Option<&SoftLimits>) -> Self {
+        self.limits = limits.map(|l| {
+            let limits = StepLimits::from_soft_limits(l, self.steps_per_degree);
+            if self.output_inverted {
+                limits.mirrored()
+            } else {
+                limits
+            }
+        });
+        self
+    }
+
+    /// Torque available at `velocity_deg_per_sec` in newton-metres, or `None`
+    /// without a rated torque.
+    ///
+    /// Interpolates the torque-speed curve linearly, holding its first and
+    /// last points beyond their velocities; without a curve the rated torque
+    /// applies at every velocity. The sign of the velocity is ignored.
+    pub fn available_torque_at_velocity(&self, velocity_deg_per_sec: f32) -> Option<f32> {
+        let rated = self.rated_torque_nm?;
+        let Some(curve) = &self.torque_speed_curve else {
+            return Some(rated);
+        };
+        let velocity = velocity_deg_per_sec.abs();
+        if velocity <= curve[0].0 {
+            return Some(rated * curve[0].1);
         }
+        // The first point at or above the velocity; the one before it is
+        // strictly below, so the segment has a non-zero width
+        let fraction = curve.windows(2).find(|w| velocity <= w[1].0).map_or(
+            curve[curve.len() - 1].1,
+            |w| {
+                let ((v0, f0), (v1, f1)) = (w[0], w[1]);
+                f0 + (f1 - f0) * (velocity - v0) / (v1 - v0)
+            },
+        );
+        Some(rated * fraction)
     }
 
-    /// Convert degrees to steps.
+    /// Convert a position in degrees to steps.
     #[inline]
+    ///
+    /// Whole turns are converted exactly (see [`turns_to_steps`](Self::turns_to_steps)),
+    /// so multi-revolution targets don't lose steps to float rounding.
     pub fn degrees_to_steps(&self, degrees: f32) -> i64 {
-        (degrees * self.steps_per_degree) as i64
+        let whole = (degrees / 360.0) as i64;
+        self.whole_turns_to_steps(whole, degrees - whole as f32 * 360.0)
+    }
+
+    /// Convert a position in output turns to steps.
+    ///
+    /// Computed as whole revolutions × steps per revolution plus the
+    /// fractional turn, so e.g. 12.125 turns at 3200 steps/rev is exactly
+    /// 38 800 steps.
+    pub fn turns_to_steps(&self, turns: Turns) -> i64 {
+        let (whole, remainder) = turns.split();
+        self.whole_turns_to_steps(whole, remainder.0)
+    }
+
+    /// Convert `whole` output turns plus `remainder` degrees to steps.
+    pub(crate) fn whole_turns_to_steps(&self, whole: i64, remainder: f32) -> i64 {
+        // Integer arithmetic for the whole turns only when a turn is a whole
+        // number of steps; fractional gear ratios fall back to f64
+        let per_turn = self.steps_per_revolution_exact;
+        let steps = if (per_turn - self.steps_per_revolution as f64).abs() < per_turn * 1e-6 {
+            whole * self.steps_per_revolution as i64
+                + (remainder * self.steps_per_degree) as i64
+        } else {
+            (whole as f64 * per_turn + remainder as f64 * per_turn / 360.0) as i64
+        };
+        if self.output_inverted {
+            -steps
+        } else {
+            steps
+        }
     }
 
-    /// Convert steps to degrees.
+    /// Convert a position in steps to degrees.
+    ///
+    /// Computed in f64 from the exact steps per revolution, so positions many
+    /// turns out still convert back to within a step.
     #[inline]
     pub fn steps_to_degrees(&self, steps: i64) -> f32 {
-        steps as f32 / self.steps_per_degree
+        let degrees = (steps as f64 * 360.0 / self.steps_per_revolution_exact) as f32;
+        if self.output_inverted {
+            -degrees
+        } else {
+            degrees
+        }
+    }
+
+    /// Convert a position in steps to degrees rounded to `decimal_places`
+    /// (at most 9), for display.
+    ///
+    /// Hides float noise from the steps-per-degree ratio, e.g. shows 90.0
+    /// rather than 89.99998.
+    pub fn steps_to_degrees_rounded(&self, steps: i64, decimal_places: u8) -> f32 {
+        round_to_places(self.steps_to_degrees(steps), decimal_places)
+    }
+
+    /// Steps per degree, negative when the output is inverted.
+    #[inline]
+    pub fn signed_steps_per_degree(&self) -> f32 {
+        if self.output_inverted {
+            -self.steps_per_degree
+        } else {
+            self.steps_per_degree
+        }
+    }
+
+    /// Soft limits as `(min, max)` output degrees, if configured.
+    pub fn limits_degrees(&self) -> Option<(f32, f32)> {
+        self.limits.as_ref().map(|limits| {
+            let a = self.steps_to_degrees(limits.min_steps);
+            let b = self.steps_to_degrees(limits.max_steps);
+            (a.min(b), a.max(b))
+        })
+    }
+
+    /// Travel left from `position` to the `(min, max)` soft limits in degrees.
+    ///
+    /// Distances are measured in steps and then converted, so a move of
+    /// exactly the returned amount lands on the limit. They are negative when
+    /// the position is already beyond that limit. `None` without limits.
+    pub fn travel_from(&self, position: Steps) -> Option<(Degrees, Degrees)> {
+        self.limits.as_ref().map(|limits| {
+            let below = (position.0 - limits.min_steps) as f32 / self.steps_per_degree;
+            let above = (limits.max_steps - position.0) as f32 / self.steps_per_degree;
+            if self.output_inverted {
+                (Degrees(above), Degrees(below))
+            } else {
+                (Degrees(below), Degrees(above))
+            }
+        })
+    }
+
+    /// Report how far a step target lies outside the soft limits, if at all.
+    ///
+    /// This is the containment test that `move_to` applies; under the
+    /// reject policy exactly these targets are refused.
+    pub fn limit_violation(&self, steps: i64) -> Option<LimitViolation> {
+        let limits = self.limits.as_ref()?;
+        self.violation_of((limits.min_steps, limits.max_steps), steps)
+    }
+
+    /// [`limit_violation`](Self::limit_violation) for a move from
+    /// `from_steps`, measured against the bounds for its direction.
+    pub fn limit_violation_from(&self, from_steps: i64, steps: i64) -> Option<LimitViolation> {
+        let limits = self.limits.as_ref()?;
+        self.violation_of(limits.bounds(Direction::from_steps(steps - from_steps)), steps)
+    }
+
+    fn violation_of(&self, (min, max): (i64, i64), steps: i64) -> Option<LimitViolation> {
+        let (step_side, beyond) = if steps < min {
+            (LimitSide::Min, min - steps)
+        } else if steps > max {
+            (LimitSide::Max, steps - max)
+        } else {
+            return None;
+        };
+        let which = match (step_side, self.output_inverted) {
+            (side, false) => side,
+            (LimitSide::Min, true) => LimitSide::Max,
+            (LimitSide::Max, true) => LimitSide::Min,
+        };
+        Some(LimitViolation { which, by: Degrees(beyond as f32 / self.steps_per_degree) })
+    }
+
+    /// Adjust a profile's deceleration for gravity, if configured.
+    ///
+    /// See [`MotionProfile::with_gravity_compensation`].
+    pub fn apply_gravity(&self, profile: MotionProfile) -> MotionProfile {
+        match self.gravity_assist {
+            Some((factor, direction)) => profile.with_gravity_compensation(factor, direction),
+            None => profile,
+        }
     }
 
     /// Convert deg/sec to steps/sec.
@@ -110,6 +322,48 @@ impl MechanicalConstraints {
         }
     }
 
+    /// Get the number of decimal places meaningful for positions in degrees.
+    ///
+    /// One step is `1 / steps_per_degree` degrees, so digits finer than that
+    /// resolution carry no information: 8.889 steps/° (≈0.11° per step) gives
+    /// 1 decimal place. Capped at 6 (the limit of `f32`).
+    pub fn degrees_precision(&self) -> u32 {
+        if self.steps_per_degree <= 1.0 || !self.steps_per_degree.is_finite() {
+            return 0;
+        }
+        // Resolution 10^-d needs d = ceil(log10(steps_per_degree)) places
+        (ceilf(log10f(self.steps_per_degree)) as u32).min(6)
+    }
+
+    /// Cruise velocity cap in steps/sec for a move ending at `target_steps`.
+    ///
+    /// Returns `Some` only if the target lies in the soft limit margin: the
+    /// cap is the speed from which the motor stops within the margin at max
+    /// acceleration, further limited by `margin_velocity_percent` if set.
+    pub fn margin_velocity_limit(&self, target_steps: i64) -> Option<f32> {
+        let limits = self.limits.as_ref().filter(|l| l.in_margin(target_steps))?;
+        let stopping = 2.0 * self.max_acceleration_steps_per_sec2 * limits.margin_steps as f32;
+        let mut cap = sqrtf(stopping);
+        if let Some(percent) = limits.margin_velocity_percent {
+            cap = cap.min(self.max_velocity_steps_per_sec * percent as f32 / 100.0);
+        }
+        Some(cap.min(self.max_velocity_steps_per_sec))
+    }
+
+    /// Check that a move of `steps` is long enough to run.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotionError::MoveTooShort` for a zero-length move or one
+    /// shorter than `minimum_move_steps`.
+    pub fn check_move_steps(&self, steps: i64) -> Result<()> {
+        let minimum = self.minimum_move_steps.max(1) as i64;
+        if steps.abs() < minimum {
+            return Err(Error::Motion(MotionError::MoveTooShort { steps, minimum }));
+        }
+        Ok(())
+    }
+
     /// Check if a position is within soft limits.
     pub fn check_limits(&self, steps: i64) -> Option<i64> {
         match &self.limits {
@@ -117,6 +371,64 @@ impl MechanicalConstraints {
             None => Some(steps), // No limits = always valid
         }
     }
+
+    /// Check if a move in `direction` may end at `steps`, applying the
+    /// bounds for that direction.
+    pub fn check_limits_moving(&self, steps: i64, direction: Direction) -> Option<i64> {
+        match &self.limits {
+            Some(limits) => limits.apply_moving(steps, direction),
+            None => Some(steps),
+        }
+    }
+}
+
+impl fmt::Display for MechanicalConstraints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Motor: {} steps/rev | {:.2} steps/° | max {}°/s ({:.0} steps/s) | max {}°/s² | limits: ",
+            self.steps_per_revolution,
+            self.steps_per_degree,
+            self.max_velocity.0,
+            self.max_velocity_steps_per_sec,
+            self.max_acceleration.0,
+        )?;
+        match &self.limits {
+            Some(limits) => write!(
+                f,
+                "[{}, {}] steps ({})",
+                limits.min_steps,
+                limits.max_steps,
+                limits.policy.as_str()
+            ),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for MechanicalConstraints {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "Motor: {=u32} steps/rev | {=f32} steps/deg | max {=f32} deg/s ({=f32} steps/s) | max {=f32} deg/s2 | limits: ",
+            self.steps_per_revolution,
+            self.steps_per_degree,
+            self.max_velocity.0,
+            self.max_velocity_steps_per_sec,
+            self.max_acceleration.0,
+        );
+        match &self.limits {
+            Some(limits) => defmt::write!(
+                f,
+                "[{=i64}, {=i64}] steps ({=str})",
+                limits.min_steps,
+                limits.max_steps,
+                limits.policy.as_str()
+            ),
+            None => defmt::write!(f, "None"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -130,11 +442,33 @@ mod tests {
             steps_per_revolution: 200,
             microsteps: Microsteps::SIXTEENTH,
             gear_ratio: 1.0,
+            gear_ratio_num: None,
+            gear_ratio_den: None,
             max_velocity: DegreesPerSec(360.0),
             max_acceleration: DegreesPerSecSquared(720.0),
             invert_direction: false,
+            output_inverted: false,
             limits: None,
             backlash_compensation: None,
+            backlash_compensation_steps: None,
+            step_pulse_ns: 2000,
+            reversal_dwell_ms: 0,
+            delay_overhead_ns: 0,
+            clamp_on_startup: false,
+            winding_resistance_ohm: None,
+            peak_current_ma: None,
+            max_step_rate_hz: None,
+            park_trajectory: None,
+            thermal: None,
+            backlash_approach_direction: None,
+            gravity_assist_factor: None,
+            gravity_direction: None,
+            initial_velocity: None,
+            soft_start: None,
+            minimum_move_steps: None,
+            rated_torque_nm: None,
+            torque_speed_curve: None,
+            homing: None,
         }
     }
 
@@ -156,6 +490,140 @@ mod tests {
         assert!((constraints.steps_per_degree - 8.889).abs() < 0.01);
     }
 
+    #[test]
+    fn test_available_torque_at_velocity() {
+        let mut config = make_test_config();
+        let unrated = MechanicalConstraints::from_config(&config);
+        assert_eq!(unrated.available_torque_at_velocity(0.0), None);
+
+        // Flat without a curve
+        config.rated_torque_nm = Some(0.5);
+        let flat = MechanicalConstraints::from_config(&config);
+        assert_eq!(flat.available_torque_at_velocity(1000.0), Some(0.5));
+
+        // Full torque to 90°/s, falling to 20% at 720°/s, padded to 8 points
+        config.torque_speed_curve = Some([
+            (90.0, 1.0),
+            (360.0, 0.6),
+            (720.0, 0.2),
+            (720.0, 0.2),
+            (720.0, 0.2),
+            (720.0, 0.2),
+            (720.0, 0.2),
+            (720.0, 0.2),
+        ]);
+        let curved = MechanicalConstraints::from_config(&config);
+        let torque = |velocity: f32| curved.available_torque_at_velocity(velocity).unwrap();
+        assert_eq!(torque(0.0), 0.5);
+        assert_eq!(torque(90.0), 0.5);
+        assert!((torque(225.0) - 0.4).abs() < 1e-6);
+        assert!((torque(-225.0) - 0.4).abs() < 1e-6);
+        assert!((torque(360.0) - 0.3).abs() < 1e-6);
+        assert!((torque(540.0) - 0.2).abs() < 1e-6);
+        assert!((torque(2000.0) - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_output_inverted_conversions_and_limits() {
+        use crate::config::units::Degrees;
+        use crate::config::{LimitPolicy, SoftLimits};
+
+        let mut config = make_test_config();
+        config.output_inverted = true;
+        config.limits = Some(SoftLimits::new(Degrees(-10.0), Degrees(90.0), LimitPolicy::Reject));
+        let constraints = MechanicalConstraints::from_config(&config);
+
+        // Magnitudes are unchanged; positions flip sign
+        assert!((constraints.max_velocity_steps_per_sec - 3200.0).abs() < 1.0);
+        assert_eq!(constraints.degrees_to_steps(90.0), -800);
+        assert!((constraints.steps_to_degrees(-800) - 90.0).abs() < 1e-3);
+
+        let limits = constraints.limits.as_ref().unwrap();
+        assert_eq!((limits.min_steps, limits.max_steps), (-800, 88));
+        let (min, max) = constraints.limits_degrees().unwrap();
+        assert!((min + 10.0).abs() < 0.2 && (max - 90.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_travel_and_violation() {
+        use crate::config::{LimitPolicy, SoftLimits};
+
+        let mut config = make_test_config();
+        assert_eq!(MechanicalConstraints::from_config(&config).travel_from(Steps(0)), None);
+
+        config.limits = Some(SoftLimits::new(Degrees(-10.0), Degrees(90.0), LimitPolicy::Reject));
+        let constraints = MechanicalConstraints::from_config(&config);
+        let (below, above) = constraints.travel_from(Steps(400)).unwrap();
+        assert!((below.0 - 488.0 / constraints.steps_per_degree).abs() < 1e-4);
+        assert!((above.0 - 45.0).abs() < 1e-3);
+
+        assert_eq!(constraints.limit_violation(800), None);
+        let violation = constraints.limit_violation(801).unwrap();
+        assert_eq!(violation.which, LimitSide::Max);
+        assert!((violation.by.0 - 1.0 / constraints.steps_per_degree).abs() < 1e-6);
+        assert_eq!(constraints.limit_violation(-89).unwrap().which, LimitSide::Min);
+
+        // Inverted output: sides follow output degrees
+        config.output_inverted = true;
+        let constraints = MechanicalConstraints::from_config(&config);
+        let (below, above) = constraints.travel_from(Steps(-400)).unwrap();
+        assert!((below.0 - 488.0 / constraints.steps_per_degree).abs() < 1e-4);
+        assert!((above.0 - 45.0).abs() < 1e-3);
+        assert_eq!(constraints.limit_violation(-801).unwrap().which, LimitSide::Max);
+    }
+
+    #[test]
+    fn test_multi_turn_steps_are_exact() {
+        use crate::config::units::Turns;
+
+        // 3200 steps/rev: 8.888889 steps/° is not exact in f32
+        let constraints = MechanicalConstraints::from_config(&make_test_config());
+        assert_eq!(constraints.turns_to_steps(Turns(12.125)), 38_800);
+        assert_eq!(constraints.turns_to_steps(Turns(-12.125)), -38_800);
+        assert_eq!(constraints.degrees_to_steps(4365.0), 38_800);
+        assert_eq!(constraints.whole_turns_to_steps(1000, 45.0), 3_200_400);
+
+        let mut config = make_test_config();
+        config.output_inverted = true;
+        let constraints = MechanicalConstraints::from_config(&config);
+        assert_eq!(constraints.turns_to_steps(Turns(12.125)), -38_800);
+    }
+
+    #[test]
+    fn test_fractional_steps_per_revolution() {
+        // 200 × 16 × 5.18 = 16 576; 5.18 itself is not exact in f32
+        let mut config = make_test_config();
+        config.gear_ratio = 5.18;
+        let constraints = MechanicalConstraints::from_config(&config);
+        assert_eq!(constraints.steps_per_revolution, 16_576);
+        assert!((constraints.steps_per_revolution_exact - 16_576.0).abs() < 1e-2);
+
+        // 100 one-turn moves land on 36 000° within a step
+        let mut position = 0;
+        for turn in 1..=100 {
+            position = constraints.degrees_to_steps(turn as f32 * 360.0);
+        }
+        let one_step = 1.0 / constraints.steps_per_degree;
+        assert!((constraints.steps_to_degrees(position) - 36_000.0).abs() <= one_step);
+        assert!((position - 1_657_600).abs() <= 1);
+
+        // A truly fractional revolution (4266.56 steps) doesn't drift either
+        config.gear_ratio = 1.3333;
+        let constraints = MechanicalConstraints::from_config(&config);
+        assert_eq!(constraints.steps_per_revolution, 4266);
+        assert!((constraints.degrees_to_steps(36_000.0) - 426_656).abs() <= 1);
+        assert!((constraints.steps_to_degrees(426_656) - 36_000.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_steps_to_degrees_rounded() {
+        let constraints = MechanicalConstraints::from_config(&make_test_config());
+        assert_eq!(constraints.steps_to_degrees_rounded(800, 3), 90.0);
+        assert_eq!(constraints.steps_to_degrees_rounded(1, 2), 0.11);
+        assert_eq!(constraints.steps_to_degrees_rounded(-1, 2), -0.11);
+        assert_eq!(constraints.steps_to_degrees_rounded(7, 0), 1.0);
+    }
+
     #[test]
     fn test_velocity_conversion() {
         let config = make_test_config();
@@ -164,4 +632,38 @@ mod tests {
         // 360 deg/sec * 8.889 steps/deg = 3200 steps/sec
         assert!((constraints.max_velocity_steps_per_sec - 3200.0).abs() < 1.0);
     }
+
+    #[test]
+    fn test_degrees_precision() {
+        let mut config = make_test_config();
+        // 8.889 steps/°: ~0.11° per step
+        assert_eq!(MechanicalConstraints::from_config(&config).degrees_precision(), 1);
+
+        // 1.8° per step
+        config.microsteps = Microsteps::FULL;
+        assert_eq!(MechanicalConstraints::from_config(&config).degrees_precision(), 0);
+
+        // 100:1 gearbox at 1/16: 888.9 steps/°
+        config.microsteps = Microsteps::SIXTEENTH;
+        config.gear_ratio = 100.0;
+        assert_eq!(MechanicalConstraints::from_config(&config).degrees_precision(), 3);
+    }
+
+    #[test]
+    fn test_display() {
+        use crate::config::limits::{LimitPolicy, SoftLimits};
+        use crate::config::units::Degrees;
+        use std::string::ToString;
+
+        let mut config = make_test_config();
+        let constraints = MechanicalConstraints::from_config(&config);
+        assert_eq!(
+            constraints.to_string(),
+            "Motor: 3200 steps/rev | 8.89 steps/° | max 360°/s (3200 steps/s) | max 720°/s² | limits: None"
+        );
+
+        config.limits = Some(SoftLimits::new(Degrees(-90.0), Degrees(90.0), LimitPolicy::Clamp));
+        let constraints = MechanicalConstraints::from_config(&config);
+        assert!(constraints.to_string().ends_with("limits: [-800, 800] steps (clamp)"));
+    }
 }
@@ -3,25 +3,63 @@
 //! Provides types for loading and validating motor and trajectory configurations
 //! from TOML files (with `std` feature) or pre-parsed data.
 
+mod diff;
+mod group;
 mod limits;
 mod mechanical;
 mod motor;
+mod names;
+mod preset;
+mod soft_start;
 mod system;
+mod thermal;
 mod trajectory;
 pub mod units;
+#[cfg(feature = "schema")]
+pub mod schema;
+#[cfg(feature = "std")]
+mod env;
 #[cfg(feature = "std")]
 mod loader;
+mod simulate;
 mod validation;
 
-pub use limits::{LimitPolicy, SoftLimits, StepLimits};
+pub use diff::{
+    diff, diff_with_tolerance, ConfigDiff, EntryChange, FieldChange, FieldChanges,
+    DIFF_TOLERANCE, MAX_FIELD_CHANGES,
+};
+pub use group::{GroupConfig, GroupField};
+pub use limits::{LimitPolicy, LimitSide, LimitViolation, SoftLimits, StepLimits};
 pub use mechanical::MechanicalConstraints;
-pub use motor::MotorConfig;
+pub use motor::{
+    HomingConfig, HomingDirection, MotorConfig, DEFAULT_STEP_PULSE_NS, MIN_STEP_PULSE_NS,
+};
+pub use names::{is_valid_name, MAX_NAME_LEN};
+pub use preset::PresetConfig;
+pub use simulate::{
+    simulate, SimulationEntries, SimulationEntry, SimulationKind, SimulationReport,
+    StartingPoses, MAX_SIMULATION_ENTRIES, MAX_SIMULATION_WARNINGS,
+};
+pub use soft_start::SoftStartConfig;
 pub use system::SystemConfig;
-pub use trajectory::{TrajectoryConfig, WaypointTrajectory};
+pub use thermal::ThermalConfig;
+pub use trajectory::{
+    Approach, FinalApproach, MultiMotorSequence, TrajectoryConfig, TrajectoryKind,
+    WaypointTrajectory, DEFAULT_PRECONDITION_TOLERANCE_DEG,
+};
 pub use validation::validate_config;
+pub(crate) use names::check_config_name;
+pub(crate) use trajectory::{check_turn_remainder, insert_waypoint, HOMING_VELOCITY_FRACTION};
+pub(crate) use validation::{
+    check_gear_ratio_fraction, check_initial_velocity, validate_step_pulse,
+};
 
 #[cfg(feature = "std")]
-pub use loader::load_config;
+pub use env::ENV_PREFIX;
+#[cfg(feature = "std")]
+pub use loader::{load_config, watch_config, WATCH_POLL_INTERVAL};
 
 // Re-export unit types at config level
-pub use units::{Degrees, DegreesPerSec, DegreesPerSecSquared, Microsteps, Steps};
+pub use units::{
+    Degrees, DegreesPerSec, DegreesPerSecSquared, FormattedDegrees, Microsteps, Steps, Turns,
+};
@@ -1,18 +1,28 @@
 //! Motor configuration from TOML.
 
+use core::fmt;
+
 use heapless::String;
 use serde::Deserialize;
 
 use super::limits::SoftLimits;
+use super::soft_start::SoftStartConfig;
+use super::thermal::ThermalConfig;
 use super::units::{Degrees, DegreesPerSec, DegreesPerSecSquared, Microsteps};
+use crate::motion::Direction;
 
 /// Complete motor configuration from TOML.
-#[derive(Debug, Clone, Deserialize)]
+///
+/// `==` compares floats exactly; `approx_eq` allows for rounding.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct MotorConfig {
     /// Human-readable name (max 32 chars).
+    #[cfg_attr(feature = "schema", schemars(with = "std::string::String", length(max = 32)))]
     pub name: String<32>,
 
     /// Base steps per revolution (typically 200 for 1.8° motors).
+    #[cfg_attr(feature = "schema", schemars(range(min = 1)))]
     pub steps_per_revolution: u16,
 
     /// Microstep setting (1, 2, 4, 8, 16, 32, etc.).
@@ -22,6 +32,18 @@ pub struct MotorConfig {
     #[serde(default = "default_gear_ratio")]
     pub gear_ratio: f32,
 
+    /// Gear ratio numerator, for ratios that are not exact in floating point
+    /// (e.g. 7 for 7:5).
+    ///
+    /// Together with `gear_ratio_den` this replaces `gear_ratio`, and step
+    /// counts per revolution are computed in integer arithmetic.
+    #[serde(default, rename = "gear_ratio_numerator")]
+    pub gear_ratio_num: Option<u32>,
+
+    /// Gear ratio denominator (e.g. 5 for 7:5); see `gear_ratio_num`.
+    #[serde(default, rename = "gear_ratio_denominator")]
+    pub gear_ratio_den: Option<u32>,
+
     /// Maximum angular velocity in degrees per second.
     #[serde(rename = "max_velocity_deg_per_sec")]
     pub max_velocity: DegreesPerSec,
@@ -34,6 +56,20 @@ pub struct MotorConfig {
     #[serde(default)]
     pub invert_direction: bool,
 
+    /// The mechanism reverses the output (e.g. a belt reversal stage), so
+    /// positive steps give negative output degrees.
+    ///
+    /// Independent of `invert_direction`, which only sets the DIR pin level:
+    ///
+    /// | `invert_direction` | `output_inverted` | +degrees move: steps | DIR |
+    /// |--------------------|-------------------|----------------------|------|
+    /// | false              | false             | positive             | high |
+    /// | false              | true              | negative             | low  |
+    /// | true               | false             | positive             | low  |
+    /// | true               | true              | negative             | high |
+    #[serde(default)]
+    pub output_inverted: bool,
+
     /// Optional soft limits.
     #[serde(default)]
     pub limits: Option<SoftLimits>,
@@ -41,22 +77,422 @@ pub struct MotorConfig {
     /// Optional backlash compensation in degrees.
     #[serde(default, rename = "backlash_compensation_deg")]
     pub backlash_compensation: Option<Degrees>,
+
+    /// Optional backlash compensation in (full or micro) steps, for
+    /// backlash measured in steps; exclusive with `backlash_compensation`.
+    #[serde(default)]
+    pub backlash_compensation_steps: Option<i64>,
+
+    /// STEP pulse active (high) duration in nanoseconds.
+    #[serde(default = "default_step_pulse_ns", rename = "step_pulse_width_ns")]
+    pub step_pulse_ns: u32,
+
+    /// Pause at zero velocity before a move that reverses direction
+    /// (milliseconds, 0 = disabled).
+    #[serde(default)]
+    pub reversal_dwell_ms: u32,
+
+    /// Fixed overhead of one `DelayNs` call in nanoseconds, subtracted from
+    /// every step delay (0 = none).
+    #[serde(default)]
+    pub delay_overhead_ns: u32,
+
+    /// Move back inside the soft limits when the motor is built
+    /// (see `StepperMotor::constrain_to_limits`).
+    #[serde(default)]
+    pub clamp_on_startup: bool,
+
+    /// Winding resistance in ohms, for energy estimates.
+    #[serde(default)]
+    pub winding_resistance_ohm: Option<f32>,
+
+    /// Peak winding current in milliamps, for energy estimates.
+    #[serde(default)]
+    pub peak_current_ma: Option<u16>,
+
+    /// Highest step rate the driver handles reliably at this microstep
+    /// setting, in steps/sec (optional).
+    #[serde(default)]
+    pub max_step_rate_hz: Option<u32>,
+
+    /// Trajectory that brings this motor to a safe pose on shutdown
+    /// (optional, must target this motor).
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<std::string::String>"))]
+    pub park_trajectory: Option<String<32>>,
+
+    /// Optional duty-cycle budget (see `StepperMotor::duty_tracker`).
+    #[serde(default)]
+    pub thermal: Option<ThermalConfig>,
+
+    /// Direction every `move_to` should finish in (optional).
+    ///
+    /// Moves in the other direction overshoot the target by the backlash
+    /// compensation and come back, so the gear train is always preloaded the
+    /// same way at rest.
+    #[serde(default, rename = "approach_direction")]
+    pub backlash_approach_direction: Option<Direction>,
+
+    /// Deceleration multiplier for moves that gravity assists (optional).
+    ///
+    /// Moves in `gravity_direction` decelerate at the configured rate times
+    /// this factor, moves against it at the rate divided by it. Needs
+    /// `gravity_direction` to take effect.
+    #[serde(default)]
+    pub gravity_assist_factor: Option<f32>,
+
+    /// Direction in which gravity assists the load (optional).
+    #[serde(default)]
+    pub gravity_direction: Option<Direction>,
+
+    /// Velocity at the first step of the acceleration ramp in degrees per
+    /// second (optional, default from rest).
+    ///
+    /// Starting above zero skips the slow first steps of the ramp, for
+    /// mechanisms that resonate at low step rates. Must not exceed
+    /// `max_velocity`.
+    #[serde(default, rename = "initial_velocity_deg_per_sec")]
+    pub initial_velocity: Option<DegreesPerSec>,
+
+    /// Shortest move the motor accepts, in steps (optional, default no
+    /// minimum).
+    ///
+    /// For drivers that need a minimum number of steps between reversals,
+    /// e.g. to let the winding current settle.
+    #[serde(default)]
+    pub minimum_move_steps: Option<u16>,
+
+    /// Gentler acceleration right after the driver is enabled (optional).
+    ///
+    /// See [`StepperMotor::notify_enabled`](crate::motor::StepperMotor::notify_enabled).
+    #[serde(default)]
+    pub soft_start: Option<SoftStartConfig>,
+
+    /// Rated (low-speed) torque in newton-metres at the output, for load
+    /// checks (optional).
+    #[serde(default)]
+    pub rated_torque_nm: Option<f32>,
+
+    /// Torque-speed curve as `(velocity_deg_per_sec, torque_fraction)`
+    /// points, in ascending velocity order (optional).
+    ///
+    /// The fraction of `rated_torque_nm` available is interpolated linearly
+    /// between points and held beyond the first and last. Curves with fewer
+    /// points repeat the last one. Without a curve the rated torque applies
+    /// at every velocity.
+    #[serde(default)]
+    pub torque_speed_curve: Option<[(f32, f32); 8]>,
+
+    /// Homing parameters (optional).
+    ///
+    /// Parsed and validated so configurations can carry them ahead of a
+    /// homing executor; nothing acts on them yet.
+    #[serde(default)]
+    pub homing: Option<HomingConfig>,
+}
+
+/// Direction in which homing searches for the switch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum HomingDirection {
+    /// Towards positive output degrees.
+    Positive,
+    /// Towards negative output degrees.
+    Negative,
+}
+
+/// Homing parameters for a motor, from `[motors.<name>.homing]`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HomingConfig {
+    /// Direction of the switch search.
+    pub direction: HomingDirection,
+
+    /// Search velocity as a percentage of the motor's max velocity (1-100).
+    #[serde(default = "default_homing_velocity_percent")]
+    #[cfg_attr(feature = "schema", schemars(range(min = 1, max = 100)))]
+    pub velocity_percent: u8,
+
+    /// Distance to back off the switch once it triggers, in degrees.
+    #[serde(rename = "backoff_deg")]
+    pub backoff: Degrees,
+
+    /// The switch reads low when triggered.
+    #[serde(default)]
+    pub switch_active_low: bool,
+
+    /// Travel after which the search gives up, in degrees.
+    #[serde(rename = "timeout_deg")]
+    pub timeout: Degrees,
+}
+
+fn default_homing_velocity_percent() -> u8 {
+    100
+}
+
+impl HomingConfig {
+    /// Check that the velocity is 1-100% and that the backoff is positive
+    /// and shorter than the timeout travel.
+    pub fn is_valid(&self) -> bool {
+        (1..=100).contains(&self.velocity_percent)
+            && self.backoff.0 > 0.0
+            && self.timeout.0.is_finite()
+            && self.backoff.0 < self.timeout.0
+    }
+
+    /// Get the search direction as the sign of output degrees (+1 or -1).
+    pub fn direction_sign(&self) -> i8 {
+        match self.direction {
+            HomingDirection::Positive => 1,
+            HomingDirection::Negative => -1,
+        }
+    }
 }
 
+/// Default STEP pulse width in nanoseconds.
+pub const DEFAULT_STEP_PULSE_NS: u32 = 2000;
+
+/// Shortest STEP pulse accepted by validation, in nanoseconds.
+pub const MIN_STEP_PULSE_NS: u32 = 100;
+
 fn default_gear_ratio() -> f32 {
     1.0
 }
 
+fn default_step_pulse_ns() -> u32 {
+    DEFAULT_STEP_PULSE_NS
+}
+
 impl MotorConfig {
-    /// Calculate total steps per output shaft revolution.
+    /// Get the rational gear ratio as (numerator, denominator), if both are
+    /// configured.
+    pub fn gear_ratio_fraction(&self) -> Option<(u32, u32)> {
+        self.gear_ratio_num.zip(self.gear_ratio_den)
+    }
+
+    /// Get the gear ratio in effect: the rational form if configured,
+    /// otherwise `gear_ratio`.
+    pub fn effective_gear_ratio(&self) -> f64 {
+        match self.gear_ratio_fraction() {
+            Some((num, den)) => num as f64 / den as f64,
+            None => self.gear_ratio as f64,
+        }
+    }
+
+    /// Calculate total steps per output shaft revolution, rounded down.
+    ///
+    /// Non-integer gear ratios give a fractional step count per revolution;
+    /// use [`total_steps_per_revolution_f64`](Self::total_steps_per_revolution_f64)
+    /// for the exact value.
+    #[deprecated(note = "rounds down fractional step counts; use total_steps_per_revolution_f64()")]
     pub fn total_steps_per_revolution(&self) -> u32 {
-        (self.steps_per_revolution as f32 * self.microsteps.value() as f32 * self.gear_ratio)
-            as u32
+        self.whole_steps_per_revolution()
+    }
+
+    /// Whole steps per output shaft revolution, rounded down.
+    ///
+    /// With a rational gear ratio this is `steps × microsteps × num / den`
+    /// in integer arithmetic, so exact ratios never lose a step to rounding.
+    pub(crate) fn whole_steps_per_revolution(&self) -> u32 {
+        match self.gear_ratio_fraction() {
+            Some((num, den)) => {
+                let base = self.steps_per_revolution as u64 * self.microsteps.value() as u64;
+                (base * num as u64)
+                    .checked_div(den as u64)
+                    .map_or(0, |steps| steps.min(u32::MAX as u64) as u32)
+            }
+            None => (self.steps_per_revolution as f32
+                * self.microsteps.value() as f32
+                * self.gear_ratio) as u32,
+        }
     }
 
     /// Calculate steps per degree of output rotation.
+    ///
+    /// Same as [`effective_steps_per_degree`](Self::effective_steps_per_degree).
     pub fn steps_per_degree(&self) -> f32 {
-        self.total_steps_per_revolution() as f32 / 360.0
+        self.effective_steps_per_degree()
+    }
+
+    /// Calculate total steps per output shaft revolution without truncation.
+    ///
+    /// Non-integer gear ratios (e.g. 3.7:1) give a fractional step count per
+    /// revolution, which this keeps.
+    pub fn total_steps_per_revolution_f32(&self) -> f32 {
+        self.total_steps_per_revolution_f64() as f32
+    }
+
+    /// Calculate total steps per output shaft revolution in double precision.
+    ///
+    /// The product is formed in f64, so large step counts keep their
+    /// fractional part; [`MechanicalConstraints`](super::MechanicalConstraints)
+    /// derives its conversions from this value.
+    pub fn total_steps_per_revolution_f64(&self) -> f64 {
+        self.steps_per_revolution as f64
+            * self.microsteps.value() as f64
+            * self.effective_gear_ratio()
+    }
+
+    /// Calculate steps per degree of output rotation at full float precision.
+    pub fn effective_steps_per_degree(&self) -> f32 {
+        (self.total_steps_per_revolution_f64() / 360.0) as f32
+    }
+
+    /// Estimate the energy of a move on this motor in joules.
+    ///
+    /// Returns `None` unless both `winding_resistance_ohm` and
+    /// `peak_current_ma` are configured. See
+    /// [`MotionProfile::energy_estimate`](crate::motion::MotionProfile::energy_estimate).
+    pub fn energy_estimate(&self, profile: &crate::motion::MotionProfile) -> Option<f32> {
+        Some(profile.energy_estimate(self.winding_resistance_ohm?, self.peak_current_ma?))
+    }
+
+    /// Get the backlash compensation in steps: `backlash_compensation_steps`
+    /// if set, otherwise `backlash_compensation` converted (rounded towards
+    /// zero), otherwise 0.
+    pub fn backlash_steps(&self) -> i64 {
+        match (self.backlash_compensation_steps, self.backlash_compensation) {
+            (Some(steps), _) => steps,
+            (None, Some(backlash)) => (backlash.0 * self.effective_steps_per_degree()) as i64,
+            (None, None) => 0,
+        }
+    }
+
+    /// Get the homing parameters, if configured.
+    pub fn homing(&self) -> Option<&HomingConfig> {
+        self.homing.as_ref()
+    }
+
+    /// Get the name of the park trajectory, if configured.
+    pub fn park(&self) -> Option<&str> {
+        self.park_trajectory.as_deref()
+    }
+
+    /// Check whether `other` maps positions to steps the same way.
+    ///
+    /// Compares `steps_per_revolution`, `microsteps` and the gear ratio; a
+    /// change to any of them invalidates a motor's tracked position.
+    pub fn same_resolution(&self, other: &MotorConfig) -> bool {
+        self.resolution_change(other).is_none()
+    }
+
+    /// The TOML key of the first resolution field that differs in `other`,
+    /// if any (see [`same_resolution`](Self::same_resolution)).
+    pub fn resolution_change(&self, other: &MotorConfig) -> Option<&'static str> {
+        if self.steps_per_revolution != other.steps_per_revolution {
+            Some("steps_per_revolution")
+        } else if self.microsteps != other.microsteps {
+            Some("microsteps")
+        } else if self.gear_ratio != other.gear_ratio
+            || self.gear_ratio_fraction() != other.gear_ratio_fraction()
+        {
+            Some("gear_ratio")
+        } else {
+            None
+        }
+    }
+
+    /// Describe the motor in one line for generated documentation, e.g.
+    /// `Pan Axis: 200 step × 16 μstep × 4.0 gear = 12800 steps/rev (35.56 steps/°),
+    /// max 180°/s, max 360°/s², limits: -90° to 90° (reject)`.
+    ///
+    /// Descriptions longer than 256 bytes are truncated.
+    pub fn to_human_readable(&self) -> String<256> {
+        let mut out = String::new();
+        let _ = self.write_human_readable(&mut out, None);
+        out
+    }
+
+    /// Write the [`to_human_readable`](Self::to_human_readable) description,
+    /// adding the configuration `key` after the name when they differ.
+    pub(crate) fn write_human_readable<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        key: Option<&str>,
+    ) -> fmt::Result {
+        write!(w, "{}", self.name)?;
+        if let Some(key) = key.filter(|key| *key != self.name.as_str()) {
+            write!(w, " ({})", key)?;
+        }
+        write!(
+            w,
+            ": {} step × {} μstep × {:?} gear = {} steps/rev ({:.2} steps/°), \
+             max {}°/s, max {}°/s², limits: ",
+            self.steps_per_revolution,
+            self.microsteps.value(),
+            GearRatio(self),
+            self.total_steps_per_revolution_f32(),
+            self.effective_steps_per_degree(),
+            self.max_velocity.0,
+            self.max_acceleration.0,
+        )?;
+        match &self.limits {
+            Some(limits) => write!(
+                w,
+                "{}° to {}° ({})",
+                limits.min.0,
+                limits.max.0,
+                limits.policy.as_str()
+            ),
+            None => write!(w, "none"),
+        }
+    }
+}
+
+/// Formats the gear ratio as `num/den` when rational, else as `gear_ratio`
+/// (`{}` or `{:?}`).
+struct GearRatio<'a>(&'a MotorConfig);
+
+impl fmt::Display for GearRatio<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.gear_ratio_fraction() {
+            Some((num, den)) => write!(f, "{}/{}", num, den),
+            None => write!(f, "{}", self.0.gear_ratio),
+        }
+    }
+}
+
+impl fmt::Debug for GearRatio<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.gear_ratio_fraction() {
+            Some(_) => fmt::Display::fmt(self, f),
+            None => write!(f, "{:?}", self.0.gear_ratio),
+        }
+    }
+}
+
+impl fmt::Display for MotorConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} steps/rev × {} microsteps × {} gear | max {}°/s | max {}°/s²",
+            self.name,
+            self.steps_per_revolution,
+            self.microsteps.value(),
+            GearRatio(self),
+            self.max_velocity.0,
+            self.max_acceleration.0,
+        )?;
+        if self.invert_direction {
+            write!(f, " | inverted")?;
+        }
+        if let Some(backlash) = self.backlash_compensation {
+            write!(f, " | backlash {}°", backlash.0)?;
+        }
+        if let Some(steps) = self.backlash_compensation_steps {
+            write!(f, " | backlash {} steps", steps)?;
+        }
+        match &self.limits {
+            Some(limits) => write!(
+                f,
+                " | limits: [{}°, {}°] ({})",
+                limits.min.0,
+                limits.max.0,
+                limits.policy.as_str()
+            ),
+            None => write!(f, " | limits: None"),
+        }
     }
 }
 
@@ -71,14 +507,252 @@ mod tests {
             steps_per_revolution: 200,
             microsteps: Microsteps::SIXTEENTH,
             gear_ratio: 2.0,
+            gear_ratio_num: None,
+            gear_ratio_den: None,
             max_velocity: DegreesPerSec(360.0),
             max_acceleration: DegreesPerSecSquared(720.0),
             invert_direction: false,
+            output_inverted: false,
             limits: None,
             backlash_compensation: None,
+            backlash_compensation_steps: None,
+            step_pulse_ns: 2000,
+            reversal_dwell_ms: 0,
+            delay_overhead_ns: 0,
+            clamp_on_startup: false,
+            winding_resistance_ohm: None,
+            peak_current_ma: None,
+            max_step_rate_hz: None,
+            park_trajectory: None,
+            thermal: None,
+            backlash_approach_direction: None,
+            gravity_assist_factor: None,
+            gravity_direction: None,
+            initial_velocity: None,
+            soft_start: None,
+            minimum_move_steps: None,
+            rated_torque_nm: None,
+            torque_speed_curve: None,
+            homing: None,
         };
 
         // 200 * 16 * 2.0 = 6400
-        assert_eq!(config.total_steps_per_revolution(), 6400);
+        assert_eq!(config.whole_steps_per_revolution(), 6400);
+    }
+
+    #[test]
+    fn test_display() {
+        use std::string::ToString;
+
+        let mut config = MotorConfig {
+            name: String::try_from("Pan Axis").unwrap(),
+            steps_per_revolution: 200,
+            microsteps: Microsteps::SIXTEENTH,
+            gear_ratio: 4.0,
+            gear_ratio_num: None,
+            gear_ratio_den: None,
+            max_velocity: DegreesPerSec(180.0),
+            max_acceleration: DegreesPerSecSquared(360.0),
+            invert_direction: false,
+            output_inverted: false,
+            limits: None,
+            backlash_compensation: None,
+            backlash_compensation_steps: None,
+            step_pulse_ns: 2000,
+            reversal_dwell_ms: 0,
+            delay_overhead_ns: 0,
+            clamp_on_startup: false,
+            winding_resistance_ohm: None,
+            peak_current_ma: None,
+            max_step_rate_hz: None,
+            park_trajectory: None,
+            thermal: None,
+            backlash_approach_direction: None,
+            gravity_assist_factor: None,
+            gravity_direction: None,
+            initial_velocity: None,
+            soft_start: None,
+            minimum_move_steps: None,
+            rated_torque_nm: None,
+            torque_speed_curve: None,
+            homing: None,
+        };
+        assert_eq!(
+            config.to_string(),
+            "Pan Axis: 200 steps/rev × 16 microsteps × 4 gear | max 180°/s | max 360°/s² | limits: None"
+        );
+
+        config.invert_direction = true;
+        config.backlash_compensation = Some(Degrees(0.5));
+        config.limits = Some(SoftLimits::new(
+            Degrees(-90.0),
+            Degrees(90.0),
+            crate::config::LimitPolicy::Reject,
+        ));
+        assert!(config
+            .to_string()
+            .ends_with("| inverted | backlash 0.5° | limits: [-90°, 90°] (reject)"));
+    }
+
+    #[test]
+    fn test_fractional_gear_ratio() {
+        let config = MotorConfig {
+            name: String::try_from("test").unwrap(),
+            steps_per_revolution: 200,
+            microsteps: Microsteps::SIXTEENTH,
+            gear_ratio: 1.3333,
+            gear_ratio_num: None,
+            gear_ratio_den: None,
+            max_velocity: DegreesPerSec(360.0),
+            max_acceleration: DegreesPerSecSquared(720.0),
+            invert_direction: false,
+            output_inverted: false,
+            limits: None,
+            backlash_compensation: None,
+            backlash_compensation_steps: None,
+            step_pulse_ns: 2000,
+            reversal_dwell_ms: 0,
+            delay_overhead_ns: 0,
+            clamp_on_startup: false,
+            winding_resistance_ohm: None,
+            peak_current_ma: None,
+            max_step_rate_hz: None,
+            park_trajectory: None,
+            thermal: None,
+            backlash_approach_direction: None,
+            gravity_assist_factor: None,
+            gravity_direction: None,
+            initial_velocity: None,
+            soft_start: None,
+            minimum_move_steps: None,
+            rated_torque_nm: None,
+            torque_speed_curve: None,
+            homing: None,
+        };
+
+        // 200 * 16 * 1.3333 = 4266.56
+        assert_eq!(config.whole_steps_per_revolution(), 4266);
+        assert!((config.total_steps_per_revolution_f32() - 4266.56).abs() < 0.01);
+        assert!((config.effective_steps_per_degree() - 4266.56 / 360.0).abs() < 1e-4);
+        assert_eq!(config.steps_per_degree(), config.effective_steps_per_degree());
+        let summary = config.to_human_readable();
+        assert!(summary.contains("= 4266.56 steps/rev (11.85 steps/°)"), "{summary}");
+    }
+
+    #[test]
+    fn test_energy_estimate_needs_electrical_parameters() {
+        let toml = r#"
+name = "Pan"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 180.0
+max_acceleration_deg_per_sec2 = 360.0
+winding_resistance_ohm = 1.5
+"#;
+        let mut config: MotorConfig = toml::from_str(toml).unwrap();
+        let profile = crate::motion::MotionProfile::symmetric_trapezoidal(3000, 1000.0, 1000.0);
+        assert_eq!(config.energy_estimate(&profile), None);
+
+        config.peak_current_ma = Some(2000);
+        assert_eq!(config.energy_estimate(&profile), Some(profile.energy_estimate(1.5, 2000)));
+    }
+
+    #[test]
+    fn test_rational_gear_ratio() {
+        let toml = r#"
+name = "Stage"
+steps_per_revolution = 200
+microsteps = 16
+gear_ratio_numerator = 7
+gear_ratio_denominator = 5
+max_velocity_deg_per_sec = 180.0
+max_acceleration_deg_per_sec2 = 360.0
+"#;
+        let mut config: MotorConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.gear_ratio_fraction(), Some((7, 5)));
+        assert_eq!(config.effective_gear_ratio(), 1.4);
+
+        // 200 * 16 * 7 / 5 = 4480, exactly
+        assert_eq!(config.whole_steps_per_revolution(), 4480);
+        assert_eq!(config.total_steps_per_revolution_f64(), 4480.0);
+        assert!(config.to_string().contains("× 7/5 gear"));
+
+        // The float fallback carries 1.4's rounding error
+        let float = MotorConfig { gear_ratio: 1.4, gear_ratio_num: None, ..config.clone() };
+        assert_ne!(float.total_steps_per_revolution_f64(), 4480.0);
+        assert!(!config.same_resolution(&float));
+
+        // Revolutions accumulate no drift
+        let constraints = crate::config::MechanicalConstraints::from_config(&config);
+        assert_eq!(constraints.degrees_to_steps(360.0 * 1000.0), 4_480_000);
+
+        // Both parts are needed for the rational form
+        config.gear_ratio_den = None;
+        assert_eq!(config.whole_steps_per_revolution(), 3200);
+    }
+
+    #[test]
+    fn test_approach_direction_from_toml() {
+        let toml = r#"
+name = "Stage"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 180.0
+max_acceleration_deg_per_sec2 = 360.0
+backlash_compensation_deg = 0.5
+approach_direction = "counter_clockwise"
+"#;
+        let config: MotorConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.backlash_approach_direction, Some(Direction::CounterClockwise));
+    }
+
+    #[test]
+    fn test_backlash_steps() {
+        let toml = r#"
+name = "Stage"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 180.0
+max_acceleration_deg_per_sec2 = 360.0
+backlash_compensation_steps = 5
+"#;
+        let mut config: MotorConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.backlash_compensation_steps, Some(5));
+        assert_eq!(config.backlash_steps(), 5);
+
+        // 0.5° is 0.28 full steps: rounds to nothing
+        config.backlash_compensation_steps = None;
+        config.backlash_compensation = Some(Degrees(0.5));
+        assert_eq!(config.backlash_steps(), 0);
+        config.backlash_compensation = Some(Degrees(9.0));
+        assert_eq!(config.backlash_steps(), 5);
+    }
+
+    #[test]
+    fn test_to_human_readable() {
+        let config: MotorConfig = toml::from_str(
+            r#"
+name = "Pan Axis"
+steps_per_revolution = 200
+microsteps = 16
+gear_ratio = 4.0
+max_velocity_deg_per_sec = 180.0
+max_acceleration_deg_per_sec2 = 360.0
+
+[limits]
+min_degrees = -90.0
+max_degrees = 90.0
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.to_human_readable().as_str(),
+            "Pan Axis: 200 step × 16 μstep × 4.0 gear = 12800 steps/rev (35.56 steps/°), \
+             max 180°/s, max 360°/s², limits: -90° to 90° (reject)"
+        );
+
+        let mut keyed = std::string::String::new();
+        config.write_human_readable(&mut keyed, Some("pan")).unwrap();
+        assert!(keyed.starts_with("Pan Axis (pan): 200 step"));
     }
 }
@@ -0,0 +1,87 @@
+//! Naming rules for trajectories and sequences.
+//!
+//! Names appear in error messages, logs and host protocols, so by default
+//! they are limited to ASCII letters, digits, `_` and `-`. Setting
+//! `permissive_names = true` at the top of the configuration allows any
+//! characters except control characters. Either way names are 1 to 32 bytes.
+
+use heapless::String;
+
+use crate::error::{ConfigError, Error, NameError};
+
+/// Maximum length of a name in bytes.
+pub const MAX_NAME_LEN: usize = 32;
+
+/// Check a name against the default rules.
+///
+/// # Errors
+///
+/// Returns the first rule the name breaks.
+pub fn is_valid_name(name: &str) -> Result<(), NameError> {
+    check_name(name, false)
+}
+
+/// Check a name, allowing any non-control character when `permissive`.
+fn check_name(name: &str, permissive: bool) -> Result<(), NameError> {
+    if name.is_empty() {
+        return Err(NameError::Empty);
+    }
+    if name.len() > MAX_NAME_LEN {
+        return Err(NameError::TooLong { len: name.len() });
+    }
+    let allowed = |c: char| {
+        if permissive {
+            !c.is_control()
+        } else {
+            c.is_ascii_alphanumeric() || c == '_' || c == '-'
+        }
+    };
+    match name.char_indices().find(|&(_, c)| !allowed(c)) {
+        Some((index, character)) => Err(NameError::InvalidCharacter { character, index }),
+        None => Ok(()),
+    }
+}
+
+/// Check a configured name, reporting `ConfigError::InvalidName`.
+pub(crate) fn check_config_name(name: &str, permissive: bool) -> crate::error::Result<()> {
+    check_name(name, permissive).map_err(|error| {
+        // Keep as much of an overlong name as fits, on a character boundary
+        let mut truncated = String::new();
+        for c in name.chars() {
+            if truncated.push(c).is_err() {
+                break;
+            }
+        }
+        Error::Config(ConfigError::InvalidName { name: truncated, error })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_rules() {
+        assert_eq!(is_valid_name("home_x-2"), Ok(()));
+        assert_eq!(is_valid_name(""), Err(NameError::Empty));
+        assert_eq!(
+            is_valid_name("a_name_that_is_33_characters_long"),
+            Err(NameError::TooLong { len: 33 })
+        );
+        assert_eq!(
+            is_valid_name("pick/place"),
+            Err(NameError::InvalidCharacter { character: '/', index: 4 })
+        );
+        assert_eq!(
+            is_valid_name("pick place"),
+            Err(NameError::InvalidCharacter { character: ' ', index: 4 })
+        );
+
+        // Permissive names still reject control characters
+        assert_eq!(check_name("pick place/é", true), Ok(()));
+        assert_eq!(
+            check_name("tab\there", true),
+            Err(NameError::InvalidCharacter { character: '\t', index: 3 })
+        );
+    }
+}
@@ -0,0 +1,33 @@
+//! Named motion parameter presets shared by trajectories.
+
+use serde::Deserialize;
+
+use super::units::DegreesPerSecSquared;
+
+/// Named set of motion parameters (`[presets.<name>]`).
+///
+/// Trajectories, sequences and multi-motor sequence steps select one with
+/// `preset = "<name>"`; [`parse_config`](super::parse_config) copies every
+/// field the preset sets and the trajectory doesn't, so explicit fields win.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct PresetConfig {
+    /// Velocity as percentage of motor's max (1-200).
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(range(min = 1, max = 200)))]
+    pub velocity_percent: Option<u8>,
+
+    /// Acceleration as percentage of motor's max (1-200).
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(range(min = 1, max = 200)))]
+    pub acceleration_percent: Option<u8>,
+
+    /// Absolute acceleration rate in degrees/sec².
+    #[serde(default, rename = "acceleration_deg_per_sec2")]
+    pub acceleration: Option<DegreesPerSecSquared>,
+
+    /// Absolute deceleration rate in degrees/sec².
+    #[serde(default, rename = "deceleration_deg_per_sec2")]
+    pub deceleration: Option<DegreesPerSecSquared>,
+}
@@ -0,0 +1,99 @@
+//! JSON Schema of the configuration format (`schema` feature).
+//!
+//! Editors with JSON Schema support (e.g. VS Code with Even Better TOML)
+//! use it to complete keys, show field descriptions and flag out-of-range
+//! values while a TOML file is being written. The schemas follow the TOML
+//! layout: keys carry their unit suffixes and names are plain strings.
+//!
+//! Each schema is generated on first use and kept for the life of the
+//! program. `cargo run --example export_schema --features schema` prints
+//! the [`system_config_schema`] for a whole configuration file.
+//!
+//! Cross-field rules (limits ordering, trajectories naming a known motor,
+//! ...) are out of reach of the schema; [`validate_config`](super::validate_config)
+//! still checks those.
+
+use std::string::String;
+use std::sync::OnceLock;
+
+use schemars::JsonSchema;
+
+use super::motor::MotorConfig;
+use super::system::SystemConfig;
+use super::trajectory::TrajectoryConfig;
+
+/// JSON Schema for one `[motors.<name>]` table.
+pub fn motor_config_schema() -> &'static str {
+    static SCHEMA: OnceLock<String> = OnceLock::new();
+    SCHEMA.get_or_init(render::<MotorConfig>)
+}
+
+/// JSON Schema for one `[trajectories.<name>]` table.
+pub fn trajectory_config_schema() -> &'static str {
+    static SCHEMA: OnceLock<String> = OnceLock::new();
+    SCHEMA.get_or_init(render::<TrajectoryConfig>)
+}
+
+/// JSON Schema for a whole configuration file.
+pub fn system_config_schema() -> &'static str {
+    static SCHEMA: OnceLock<String> = OnceLock::new();
+    SCHEMA.get_or_init(render::<SystemConfig>)
+}
+
+fn render<T: JsonSchema>() -> String {
+    let schema = schemars::schema_for!(T);
+    serde_json::to_string_pretty(&schema).expect("schemas always serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn parse(schema: &str) -> Value {
+        serde_json::from_str(schema).unwrap()
+    }
+
+    #[test]
+    fn test_motor_schema() {
+        let schema = parse(motor_config_schema());
+        assert_eq!(schema["title"], "MotorConfig");
+        let properties = &schema["properties"];
+        // TOML keys, not Rust field names
+        assert!(properties.get("max_velocity_deg_per_sec").is_some());
+        assert!(properties.get("max_velocity").is_none());
+        assert_eq!(properties["name"]["type"], "string");
+        assert_eq!(properties["name"]["maxLength"], 32);
+        assert_eq!(
+            properties["steps_per_revolution"]["description"],
+            "Base steps per revolution (typically 200 for 1.8° motors)."
+        );
+
+        let required = schema["required"].as_array().unwrap();
+        assert!(required.contains(&"steps_per_revolution".into()));
+        assert!(!required.contains(&"gear_ratio".into()));
+
+        let microsteps = &schema["definitions"]["Microsteps"];
+        assert_eq!(microsteps["enum"].as_array().unwrap().len(), 9);
+    }
+
+    #[test]
+    fn test_trajectory_and_system_schemas() {
+        let schema = parse(trajectory_config_schema());
+        let properties = &schema["properties"];
+        assert_eq!(properties["velocity_percent"]["minimum"], 1.0);
+        assert_eq!(properties["velocity_percent"]["maximum"], 200.0);
+        assert!(properties.get("kind").is_some());
+        assert!(properties.get("homing_max_travel_deg").is_some());
+        assert_eq!(schema["required"], serde_json::json!(["motor"]));
+
+        let schema = parse(system_config_schema());
+        assert_eq!(schema["required"], serde_json::json!(["motors"]));
+        let motors = &schema["properties"]["motors"];
+        assert_eq!(motors["type"], "object");
+        assert_eq!(motors["additionalProperties"]["$ref"], "#/definitions/MotorConfig");
+        for name in ["MotorConfig", "TrajectoryConfig", "SoftLimits", "LimitPolicy"] {
+            assert!(schema["definitions"].get(name).is_some(), "{name}");
+        }
+    }
+}
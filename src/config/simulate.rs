@@ -0,0 +1,389 @@
+//! Dry-run simulation of a whole configuration.
+//!
+//! [`simulate`] plans every trajectory and waypoint sequence from a starting
+//! pose without touching hardware, so a new configuration can be reviewed (or
+//! diffed in CI) before it is deployed. Under `std` the report is
+//! serde-`Serialize`.
+
+use core::fmt::Write;
+
+use heapless::{FnvIndexMap, String, Vec};
+#[cfg(feature = "std")]
+use serde::Serialize;
+
+use crate::error::{ConfigError, Error, Result};
+use crate::motion::{Direction, MotionProfile};
+
+use super::mechanical::MechanicalConstraints;
+use super::system::SystemConfig;
+use super::trajectory::TrajectoryConfig;
+use super::units::Degrees;
+
+/// Maximum number of entries in a [`SimulationReport`] (all trajectories plus
+/// all sequences of a [`SystemConfig`]).
+pub const MAX_SIMULATION_ENTRIES: usize = 80;
+
+/// Maximum number of warnings kept per [`SimulationEntry`].
+pub const MAX_SIMULATION_WARNINGS: usize = 4;
+
+/// Storage of [`SimulationReport::entries`]: on the heap with `alloc`.
+#[cfg(any(feature = "alloc", feature = "std"))]
+pub type SimulationEntries = alloc::vec::Vec<SimulationEntry>;
+
+/// Storage of [`SimulationReport::entries`]: inline without `alloc`, room for
+/// [`MAX_SIMULATION_ENTRIES`] entries (about 40 KB).
+#[cfg(not(any(feature = "alloc", feature = "std")))]
+pub type SimulationEntries = Vec<SimulationEntry, MAX_SIMULATION_ENTRIES>;
+
+/// Starting position of each motor for [`simulate`].
+///
+/// Motors without an explicit pose start at 0° (home).
+#[derive(Debug, Clone, Default)]
+pub struct StartingPoses {
+    poses: FnvIndexMap<String<32>, Degrees, 8>,
+}
+
+impl StartingPoses {
+    /// Create an empty set of poses (every motor at home).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the starting position of a motor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::MotorNotFound` if the name is longer than 32
+    /// characters or poses are already set for 8 other motors; neither can
+    /// match a configured motor.
+    pub fn set(&mut self, motor: &str, position: Degrees) -> Result<()> {
+        let not_found = || {
+            Error::Config(ConfigError::MotorNotFound(
+                String::try_from(motor).unwrap_or_default(),
+            ))
+        };
+        let key = String::try_from(motor).map_err(|_| not_found())?;
+        self.poses.insert(key, position).map_err(|_| not_found())?;
+        Ok(())
+    }
+
+    /// Get the starting position of a motor.
+    pub fn get(&self, motor: &str) -> Degrees {
+        self.poses
+            .iter()
+            .find(|(k, _)| k.as_str() == motor)
+            .map(|(_, v)| *v)
+            .unwrap_or(Degrees(0.0))
+    }
+}
+
+/// Kind of configuration entry that was simulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+pub enum SimulationKind {
+    /// A `[trajectories.<name>]` entry.
+    Trajectory,
+    /// A `[sequences.<name>]` waypoint sequence.
+    Sequence,
+}
+
+/// Simulated outcome of one trajectory or sequence.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+pub struct SimulationEntry {
+    /// Name of the entry in the configuration.
+    pub name: String<32>,
+    /// Whether this is a trajectory or a sequence.
+    pub kind: SimulationKind,
+    /// Motor the entry runs on.
+    pub motor: String<32>,
+    /// Starting position in degrees.
+    pub start_degrees: f32,
+    /// Final position in degrees.
+    pub end_degrees: f32,
+    /// Direction of the net displacement (`None` if it ends where it started).
+    pub direction: Option<Direction>,
+    /// Steps emitted, summed over all segments.
+    pub total_steps: u32,
+    /// Estimated duration in seconds, including dwells.
+    pub duration_secs: f32,
+    /// Highest velocity reached in degrees/sec.
+    pub peak_velocity_deg_per_sec: f32,
+    /// Acceleration rate in degrees/sec².
+    pub acceleration_deg_per_sec2: f32,
+    /// Deceleration rate in degrees/sec².
+    pub deceleration_deg_per_sec2: f32,
+    /// Feasibility and planning problems, in the order found.
+    pub warnings: Vec<String<96>, MAX_SIMULATION_WARNINGS>,
+}
+
+impl SimulationEntry {
+    /// Check if the entry raised any warning.
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+
+    fn warn(&mut self, error: &Error) {
+        let mut message = String::new();
+        // A message longer than the buffer is kept truncated
+        let _ = write!(message, "{}", error);
+        if !self.warnings.contains(&message) {
+            let _ = self.warnings.push(message);
+        }
+    }
+
+    /// Plan one segment from `*position` and fold it into the totals.
+    fn add_segment(
+        &mut self,
+        segment: &TrajectoryConfig,
+        constraints: &MechanicalConstraints,
+        position: &mut i64,
+    ) {
+        if let Err(e) = segment.check_feasibility(constraints) {
+            self.warn(&e);
+        }
+
+        match segment.plan_profile(constraints, *position) {
+            Ok(profile) => {
+                self.total_steps = self.total_steps.saturating_add(profile.total_steps);
+                self.duration_secs += profile.estimated_duration_secs();
+                let peak = peak_velocity_steps_per_sec(&profile) / constraints.steps_per_degree;
+                self.peak_velocity_deg_per_sec = self.peak_velocity_deg_per_sec.max(peak);
+                *position = segment.target_steps(constraints);
+            }
+            Err(e) => self.warn(&e),
+        }
+
+        if let Some(dwell_ms) = segment.dwell_ms {
+            self.duration_secs += dwell_ms as f32 / 1000.0;
+        }
+    }
+}
+
+/// Result of [`simulate`]: one entry per trajectory, then one per sequence.
+///
+/// With `alloc` the entries live on the heap and the report itself is a few
+/// words; without it they are stored inline, see [`SimulationEntries`].
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+pub struct SimulationReport {
+    /// Simulated entries in configuration order.
+    pub entries: SimulationEntries,
+}
+
+impl SimulationReport {
+    /// Find the entry for a trajectory or sequence by name.
+    pub fn entry(&self, name: &str) -> Option<&SimulationEntry> {
+        self.entries.iter().find(|e| e.name.as_str() == name)
+    }
+
+    /// Iterate over the entries that raised warnings.
+    pub fn with_warnings(&self) -> impl Iterator<Item = &SimulationEntry> {
+        self.entries.iter().filter(|e| e.has_warnings())
+    }
+
+    /// Sum of the estimated durations of all entries in seconds.
+    pub fn total_duration_secs(&self) -> f32 {
+        self.entries.iter().map(|e| e.duration_secs).sum()
+    }
+}
+
+/// Simulate every trajectory and waypoint sequence in `config`.
+///
+/// Each entry is planned independently from its motor's starting pose with
+/// the same profiles the motor would run. Feasibility problems (limits,
+/// velocity or acceleration out of range) and planning failures are recorded
+/// as warnings on the entry rather than failing the whole simulation.
+///
+/// Without `alloc` the report is large (room for one entry per trajectory
+/// and sequence), so keep it off small stacks there.
+///
+/// # Errors
+///
+/// Returns `ConfigError::MotorNotFound` if an entry references a motor that
+/// is not configured.
+pub fn simulate(config: &SystemConfig, poses: &StartingPoses) -> Result<SimulationReport> {
+    let mut report = SimulationReport::default();
+
+    for (name, trajectory) in config.trajectories.iter() {
+        let (constraints, mut entry) = start_entry(
+            config,
+            poses,
+            name,
+            SimulationKind::Trajectory,
+            &trajectory.motor,
+        )?;
+        entry.acceleration_deg_per_sec2 = trajectory.effective_acceleration(&constraints);
+        entry.deceleration_deg_per_sec2 = trajectory.effective_deceleration(&constraints);
+
+        let mut position =
+            constraints.degrees_to_steps(poses.get(&trajectory.motor).0);
+        entry.add_segment(trajectory, &constraints, &mut position);
+        finish_entry(&mut report, entry, &constraints, position);
+    }
+
+    for (name, sequence) in config.sequences.iter() {
+        let (constraints, mut entry) = start_entry(
+            config,
+            poses,
+            name,
+            SimulationKind::Sequence,
+            &sequence.motor,
+        )?;
+        entry.acceleration_deg_per_sec2 = sequence.effective_acceleration(&constraints);
+        entry.deceleration_deg_per_sec2 = sequence.effective_deceleration(&constraints);
+
+        let mut position =
+            constraints.degrees_to_steps(poses.get(&sequence.motor).0);
+        for segment in sequence.segments() {
+            entry.add_segment(&segment, &constraints, &mut position);
+        }
+        finish_entry(&mut report, entry, &constraints, position);
+    }
+
+    Ok(report)
+}
+
+fn start_entry(
+    config: &SystemConfig,
+    poses: &StartingPoses,
+    name: &str,
+    kind: SimulationKind,
+    motor: &str,
+) -> Result<(MechanicalConstraints, SimulationEntry)> {
+    let motor_config = config.motor(motor).ok_or_else(|| {
+        Error::Config(ConfigError::MotorNotFound(
+            String::try_from(motor).unwrap_or_default(),
+        ))
+    })?;
+    let constraints = MechanicalConstraints::from_config(motor_config);
+
+    let entry = SimulationEntry {
+        name: String::try_from(name).unwrap_or_default(),
+        kind,
+        motor: String::try_from(motor).unwrap_or_default(),
+        start_degrees: poses.get(motor).0,
+        end_degrees: poses.get(motor).0,
+        direction: None,
+        total_steps: 0,
+        duration_secs: 0.0,
+        peak_velocity_deg_per_sec: 0.0,
+        acceleration_deg_per_sec2: 0.0,
+        deceleration_deg_per_sec2: 0.0,
+        warnings: Vec::new(),
+    };
+    Ok((constraints, entry))
+}
+
+fn finish_entry(
+    report: &mut SimulationReport,
+    mut entry: SimulationEntry,
+    constraints: &MechanicalConstraints,
+    position: i64,
+) {
+    let start = constraints.degrees_to_steps(entry.start_degrees);
+    entry.end_degrees = constraints.steps_to_degrees(position);
+    entry.direction = (position != start).then(|| Direction::from_steps(position - start));
+    #[cfg(any(feature = "alloc", feature = "std"))]
+    report.entries.push(entry);
+    // A SystemConfig holds at most MAX_SIMULATION_ENTRIES trajectories and sequences
+    #[cfg(not(any(feature = "alloc", feature = "std")))]
+    let _ = report.entries.push(entry);
+}
+
+/// Highest step rate of a profile: the cruise rate, or the rate at the top of
+/// the ramp for a triangle profile.
+fn peak_velocity_steps_per_sec(profile: &MotionProfile) -> f32 {
+    if profile.is_zero() {
+        return 0.0;
+    }
+    let step = if profile.is_triangle() {
+        profile.cruise_start().saturating_sub(1)
+    } else {
+        profile.cruise_start()
+    };
+    1_000_000_000.0 / profile.interval_at(step) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[motors.x]
+name = "X"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[motors.x.limits]
+min_degrees = -90.0
+max_degrees = 180.0
+
+[trajectories.far]
+motor = "x"
+target_degrees = 360.0
+
+[trajectories.short]
+motor = "x"
+target_degrees = 9.0
+dwell_ms = 500
+
+[sequences.there_and_back]
+motor = "x"
+waypoints = [90.0, 0.0]
+dwell_ms = 100
+"#;
+
+    fn config() -> SystemConfig {
+        toml::from_str(CONFIG).unwrap()
+    }
+
+    #[test]
+    fn test_simulate_trajectories_and_sequences() {
+        let report = simulate(&config(), &StartingPoses::new()).unwrap();
+        assert_eq!(report.entries.len(), 3);
+        // Entries are on the heap, not inline in the report
+        assert!(core::mem::size_of::<SimulationReport>() <= 4 * core::mem::size_of::<usize>());
+
+        let far = report.entry("far").unwrap();
+        assert_eq!(far.kind, SimulationKind::Trajectory);
+        assert_eq!(far.warnings.len(), 1);
+        assert!(far.warnings[0].contains("exceeds"), "{}", far.warnings[0]);
+
+        // 9° = 5 steps never reaches cruise; the dwell is counted
+        let short = report.entry("short").unwrap();
+        assert!(!short.has_warnings());
+        assert_eq!(short.total_steps, 5);
+        assert!(short.peak_velocity_deg_per_sec < 360.0);
+        assert!(short.duration_secs > 0.5);
+
+        let sequence = report.entry("there_and_back").unwrap();
+        assert_eq!(sequence.kind, SimulationKind::Sequence);
+        assert_eq!(sequence.total_steps, 100);
+        assert_eq!(sequence.direction, None);
+        assert_eq!(sequence.end_degrees, 0.0);
+        // Triangle segments meet halfway: sqrt(2 * 720°/s² * 45°)
+        let expected = crate::math::sqrtf(720.0 * 90.0);
+        assert!((sequence.peak_velocity_deg_per_sec - expected).abs() / expected < 0.05);
+
+        assert_eq!(report.with_warnings().count(), 1);
+    }
+
+    #[test]
+    fn test_simulate_from_starting_pose() {
+        let mut poses = StartingPoses::new();
+        poses.set("x", Degrees(90.0)).unwrap();
+        let report = simulate(&config(), &poses).unwrap();
+
+        let short = report.entry("short").unwrap();
+        assert_eq!(short.start_degrees, 90.0);
+        assert_eq!(short.total_steps, 45);
+        assert_eq!(short.direction, Some(Direction::CounterClockwise));
+
+        let sequence = report.entry("there_and_back").unwrap();
+        assert_eq!(sequence.total_steps, 50);
+        assert_eq!(sequence.direction, Some(Direction::CounterClockwise));
+    }
+}
@@ -0,0 +1,33 @@
+//! Soft-start configuration for the first move after the driver is enabled.
+
+use serde::Deserialize;
+
+/// Gentler acceleration right after the driver is enabled (from configuration).
+///
+/// Holding torque takes a moment to establish once the driver is enabled, so
+/// moves starting within `duration_ms` of an enable event accelerate at
+/// `accel_percent` of the planned rate. Velocity and deceleration are
+/// unchanged. Without a clock, only the first move after enabling is softened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SoftStartConfig {
+    /// Length of the window after an enable event in milliseconds.
+    pub duration_ms: u32,
+
+    /// Acceleration during the window, as a percentage of the planned rate
+    /// (1-100).
+    #[cfg_attr(feature = "schema", schemars(range(min = 1, max = 100)))]
+    pub accel_percent: u8,
+}
+
+impl SoftStartConfig {
+    /// Create a soft-start window.
+    pub fn new(duration_ms: u32, accel_percent: u8) -> Self {
+        Self { duration_ms, accel_percent }
+    }
+
+    /// Check that the window is non-empty and the percentage is in 1-100.
+    pub fn is_valid(&self) -> bool {
+        self.duration_ms > 0 && (1..=100).contains(&self.accel_percent)
+    }
+}
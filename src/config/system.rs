@@ -1,24 +1,62 @@
 //! System configuration - root configuration structure.
 
+use core::fmt;
+use core::marker::PhantomData;
+
 use heapless::{FnvIndexMap, String};
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, Visitor};
 use serde::Deserialize;
 
+use super::group::GroupConfig;
 use super::motor::MotorConfig;
-use super::trajectory::{TrajectoryConfig, WaypointTrajectory};
+use super::preset::PresetConfig;
+use super::trajectory::{MultiMotorSequence, TrajectoryConfig, WaypointTrajectory};
+
+/// Named entries as the schema sees them.
+#[cfg(feature = "schema")]
+type Map<T> = std::collections::BTreeMap<std::string::String, T>;
 
 /// Root configuration structure from TOML.
-#[derive(Debug, Clone, Deserialize)]
+///
+/// The maps are held by value and add up to over 50 KB, so deserializing
+/// fills one value entry by entry rather than moving whole maps through the
+/// deserializer's stack frames.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SystemConfig {
     /// Named motor configurations.
+    #[cfg_attr(feature = "schema", schemars(with = "Map<MotorConfig>"))]
     pub motors: FnvIndexMap<String<32>, MotorConfig, 8>,
 
     /// Named trajectory configurations.
-    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(default))]
+    #[cfg_attr(feature = "schema", schemars(with = "Map<TrajectoryConfig>"))]
     pub trajectories: FnvIndexMap<String<32>, TrajectoryConfig, 64>,
 
     /// Named waypoint trajectories (sequences).
-    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(default))]
+    #[cfg_attr(feature = "schema", schemars(with = "Map<WaypointTrajectory>"))]
     pub sequences: FnvIndexMap<String<32>, WaypointTrajectory, 16>,
+
+    /// Named multi-motor sequences.
+    #[cfg_attr(feature = "schema", schemars(default))]
+    #[cfg_attr(feature = "schema", schemars(with = "Map<MultiMotorSequence>"))]
+    pub msequences: FnvIndexMap<String<32>, MultiMotorSequence, 8>,
+
+    /// Named motor groups with shared configuration.
+    #[cfg_attr(feature = "schema", schemars(default))]
+    #[cfg_attr(feature = "schema", schemars(with = "Map<GroupConfig>"))]
+    pub groups: FnvIndexMap<String<32>, GroupConfig, 8>,
+
+    /// Named motion parameter presets, already applied by `parse_config`.
+    #[cfg_attr(feature = "schema", schemars(default))]
+    #[cfg_attr(feature = "schema", schemars(with = "Map<PresetConfig>"))]
+    pub presets: FnvIndexMap<String<32>, PresetConfig, 16>,
+
+    /// Allow any non-control characters in trajectory and sequence names
+    /// instead of only letters, digits, `_` and `-`.
+    #[cfg_attr(feature = "schema", schemars(default))]
+    pub permissive_names: bool,
 }
 
 impl SystemConfig {
@@ -46,6 +84,38 @@ impl SystemConfig {
             .map(|(_, v)| v)
     }
 
+    /// Get a multi-motor sequence by name.
+    pub fn msequence(&self, name: &str) -> Option<&MultiMotorSequence> {
+        self.msequences
+            .iter()
+            .find(|(k, _)| k.as_str() == name)
+            .map(|(_, v)| v)
+    }
+
+    /// Get a preset by name.
+    pub fn preset(&self, name: &str) -> Option<&PresetConfig> {
+        self.presets
+            .iter()
+            .find(|(k, _)| k.as_str() == name)
+            .map(|(_, v)| v)
+    }
+
+    /// Get a motor group by name.
+    pub fn group(&self, name: &str) -> Option<&GroupConfig> {
+        self.groups
+            .iter()
+            .find(|(k, _)| k.as_str() == name)
+            .map(|(_, v)| v)
+    }
+
+    /// List the names of all groups a motor belongs to.
+    pub fn groups_for_motor<'a>(&'a self, motor: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.groups
+            .iter()
+            .filter(move |(_, group)| group.contains(motor))
+            .map(|(name, _)| name.as_str())
+    }
+
     /// List all motor names.
     pub fn motor_names(&self) -> impl Iterator<Item = &str> {
         self.motors.keys().map(|s| s.as_str())
@@ -60,6 +130,66 @@ impl SystemConfig {
     pub fn sequence_names(&self) -> impl Iterator<Item = &str> {
         self.sequences.keys().map(|s| s.as_str())
     }
+
+    /// List all multi-motor sequence names.
+    pub fn msequence_names(&self) -> impl Iterator<Item = &str> {
+        self.msequences.keys().map(|s| s.as_str())
+    }
+
+    /// Summarize the whole configuration for logging at startup.
+    ///
+    /// Lists every motor and trajectory with its
+    /// [`MotorConfig::to_human_readable`] / [`TrajectoryConfig::to_human_readable`]
+    /// description, followed by sequences and groups.
+    #[cfg(feature = "std")]
+    pub fn document(&self) -> std::string::String {
+        use core::fmt::Write;
+
+        let mut out = std::string::String::new();
+        let _ = writeln!(out, "Motors ({}):", self.motors.len());
+        for (key, motor) in &self.motors {
+            out.push_str("  ");
+            let _ = motor.write_human_readable(&mut out, Some(key.as_str()));
+            out.push('\n');
+        }
+        if !self.trajectories.is_empty() {
+            let _ = writeln!(out, "Trajectories ({}):", self.trajectories.len());
+            for (name, trajectory) in &self.trajectories {
+                let _ = write!(out, "  {}: ", name);
+                let _ = trajectory.write_human_readable(&mut out);
+                out.push('\n');
+            }
+        }
+        if !self.sequences.is_empty() {
+            let _ = writeln!(out, "Sequences ({}):", self.sequences.len());
+            for (name, sequence) in &self.sequences {
+                let _ = writeln!(
+                    out,
+                    "  {}: {} through {} waypoints",
+                    name,
+                    sequence.motor,
+                    sequence.waypoints.len()
+                );
+            }
+        }
+        if !self.msequences.is_empty() {
+            let _ = writeln!(out, "Multi-motor sequences ({}):", self.msequences.len());
+            for (name, msequence) in &self.msequences {
+                let _ = writeln!(out, "  {}: {} steps", name, msequence.steps.len());
+            }
+        }
+        if !self.groups.is_empty() {
+            let _ = writeln!(out, "Groups ({}):", self.groups.len());
+            for (name, group) in &self.groups {
+                let _ = write!(out, "  {}:", name);
+                for motor in &group.motors {
+                    let _ = write!(out, " {}", motor);
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
 }
 
 impl Default for SystemConfig {
@@ -68,6 +198,162 @@ impl Default for SystemConfig {
             motors: FnvIndexMap::new(),
             trajectories: FnvIndexMap::new(),
             sequences: FnvIndexMap::new(),
+            msequences: FnvIndexMap::new(),
+            groups: FnvIndexMap::new(),
+            presets: FnvIndexMap::new(),
+            permissive_names: false,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SystemConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut config = Self::default();
+        InPlace(&mut config).deserialize(deserializer)?;
+        Ok(config)
+    }
+}
+
+impl SystemConfig {
+    /// Deserialize into `self`, which must be empty, without returning the
+    /// configuration by value through the deserializer.
+    #[cfg(feature = "std")]
+    pub(crate) fn deserialize_in_place<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        InPlace(self).deserialize(deserializer)
+    }
+}
+
+const FIELDS: &[&str] = &[
+    "motors",
+    "trajectories",
+    "sequences",
+    "msequences",
+    "groups",
+    "presets",
+    "permissive_names",
+];
+
+/// Fills a configuration from a table, one entry at a time.
+struct InPlace<'a>(&'a mut SystemConfig);
+
+impl<'de> DeserializeSeed<'de> for InPlace<'_> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("SystemConfig", FIELDS, self)
+    }
+}
+
+impl<'de> Visitor<'de> for InPlace<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("struct SystemConfig")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<(), A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let config = self.0;
+        let mut seen = [false; FIELDS.len()];
+        while let Some(key) = map.next_key::<Field>()? {
+            let Field::Known(index) = key else {
+                map.next_value::<IgnoredAny>()?;
+                continue;
+            };
+            if core::mem::replace(&mut seen[index], true) {
+                return Err(de::Error::duplicate_field(FIELDS[index]));
+            }
+            match index {
+                0 => map.next_value_seed(Entries(&mut config.motors))?,
+                1 => map.next_value_seed(Entries(&mut config.trajectories))?,
+                2 => map.next_value_seed(Entries(&mut config.sequences))?,
+                3 => map.next_value_seed(Entries(&mut config.msequences))?,
+                4 => map.next_value_seed(Entries(&mut config.groups))?,
+                5 => map.next_value_seed(Entries(&mut config.presets))?,
+                _ => config.permissive_names = map.next_value()?,
+            }
+        }
+        if !seen[0] {
+            return Err(de::Error::missing_field("motors"));
+        }
+        Ok(())
+    }
+}
+
+/// Top-level key: the index of a known field, or anything else.
+enum Field {
+    Known(usize),
+    Other,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FieldVisitor;
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("field identifier")
+            }
+
+            fn visit_str<E: de::Error>(self, key: &str) -> Result<Field, E> {
+                Ok(FIELDS
+                    .iter()
+                    .position(|&field| field == key)
+                    .map_or(Field::Other, Field::Known))
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+/// Deserializes a table straight into an existing map, one entry at a time.
+struct Entries<'a, V, const N: usize>(&'a mut FnvIndexMap<String<32>, V, N>);
+
+impl<'de, V: Deserialize<'de>, const N: usize> DeserializeSeed<'de> for Entries<'_, V, N> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, V: Deserialize<'de>, const N: usize> Visitor<'de> for Entries<'_, V, N> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a map with at most {} entries", N)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<(), A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while let Some(key) = map.next_key::<String<32>>()? {
+            let value = map.next_value_seed(PhantomData::<V>)?;
+            if self.0.insert(key, value).is_err() {
+                return Err(de::Error::invalid_length(N + 1, &self));
+            }
         }
+        Ok(())
     }
 }
@@ -0,0 +1,38 @@
+//! Thermal duty budget configuration.
+
+use serde::Deserialize;
+
+/// Duty-cycle budget for a motor and its driver (from configuration).
+///
+/// The duty is the fraction of recent time spent moving, averaged with an
+/// exponential decay of `time_constant_s`. It is bookkeeping to keep back to
+/// back full-current moves from overheating the driver, not a thermal model.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ThermalConfig {
+    /// Time constant of the exponential average in seconds.
+    pub time_constant_s: f32,
+
+    /// Highest allowed duty (0.0 exclusive to 1.0 inclusive).
+    #[cfg_attr(feature = "schema", schemars(range(min = 0.0, max = 1.0)))]
+    pub max_duty: f32,
+
+    /// Refuse to start moves while the duty is above `max_duty`.
+    #[serde(default)]
+    pub enforce: bool,
+}
+
+impl ThermalConfig {
+    /// Create a thermal budget without enforcement.
+    pub fn new(time_constant_s: f32, max_duty: f32) -> Self {
+        Self { time_constant_s, max_duty, enforce: false }
+    }
+
+    /// Check that the time constant is positive and the duty limit is in (0, 1].
+    pub fn is_valid(&self) -> bool {
+        self.time_constant_s.is_finite()
+            && self.time_constant_s > 0.0
+            && self.max_duty > 0.0
+            && self.max_duty <= 1.0
+    }
+}
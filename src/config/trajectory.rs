@@ -1,44 +1,308 @@
 //! Trajectory configuration from TOML.
 
+use core::fmt;
+
 use heapless::{String, Vec};
 use serde::Deserialize;
 
+use super::limits::SoftLimits;
 use super::mechanical::MechanicalConstraints;
-use super::units::{Degrees, DegreesPerSecSquared};
+use super::units::{Degrees, DegreesPerSecSquared, Steps};
+use crate::motion::Direction;
+
+/// Fraction of the maximum velocity homing runs at, before `velocity_percent`.
+///
+/// Shared by [`StepperMotor::start_homing`](crate::motor::StepperMotor::start_homing)
+/// and homing trajectories, so both approach the switch at the same speed.
+pub(crate) const HOMING_VELOCITY_FRACTION: f32 = 0.25;
 
 /// A named trajectory from configuration.
-#[derive(Debug, Clone, Deserialize)]
+///
+/// A homing trajectory is declared with `kind = "homing"` plus
+/// `homing_direction` and `homing_max_travel_deg`, and has no
+/// `target_degrees`.
+///
+/// `==` compares floats exactly; `approx_eq` allows for rounding.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "TrajectoryToml")]
 pub struct TrajectoryConfig {
     /// Target motor name (must match a motor in config).
     pub motor: String<32>,
 
     /// Target position in degrees (absolute from origin).
+    ///
+    /// Always zero for homing trajectories, which end at the new origin.
+    /// With `target_turns`, only the remainder within the last turn.
     pub target_degrees: Degrees,
 
+    /// Whole output turns of the target (optional), for multi-turn moves.
+    ///
+    /// `target_degrees` is then the remainder: less than one turn, with the
+    /// sign of `target_turns`. See [`target_steps`](Self::target_steps).
+    pub target_turns: Option<i32>,
+
     /// Velocity as percentage of motor's max (1-200).
-    #[serde(default = "default_velocity_percent")]
     pub velocity_percent: u8,
 
     /// Acceleration as percentage of motor's max (1-200).
     /// Used when absolute rates are not specified.
-    #[serde(default = "default_acceleration_percent")]
     pub acceleration_percent: u8,
 
     /// Absolute acceleration rate in degrees/sec² (optional).
     /// Overrides acceleration_percent for the acceleration phase.
-    #[serde(default, rename = "acceleration_deg_per_sec2")]
     pub acceleration: Option<DegreesPerSecSquared>,
 
     /// Absolute deceleration rate in degrees/sec² (optional).
     /// If not set, uses acceleration value (symmetric profile).
-    #[serde(default, rename = "deceleration_deg_per_sec2")]
     pub deceleration: Option<DegreesPerSecSquared>,
 
     /// Optional dwell time at target (milliseconds).
-    #[serde(default)]
     pub dwell_ms: Option<u32>,
+
+    /// Position the motor must be at before starting, in degrees (optional).
+    pub requires_position: Option<Degrees>,
+
+    /// Tolerance for `requires_position_deg` in degrees
+    /// (default [`DEFAULT_PRECONDITION_TOLERANCE_DEG`]).
+    pub tolerance: Option<Degrees>,
+
+    /// Trajectory that must be the last one executed on the motor (optional).
+    pub requires_previous: Option<String<32>>,
+
+    /// Whether this is a plain move or a homing run.
+    pub trajectory_kind: TrajectoryKind,
+
+    /// Direction the target must be approached from (default either).
+    pub approach: Approach,
+
+    /// Overshoot past the target before a one-sided approach, in degrees.
+    ///
+    /// Only used when the direct move would arrive from the wrong side.
+    pub overshoot: Degrees,
+
+    /// Soft limits to use instead of the motor's for this trajectory only
+    /// (optional), e.g. for a maintenance move past the operating range.
+    pub limit_override: Option<SoftLimits>,
+
+    /// Load torque in newton-metres the motor must overcome (optional).
+    ///
+    /// Checked against the motor's torque at the trajectory's cruise
+    /// velocity when the motor has a `rated_torque_nm`.
+    pub load_torque_nm: Option<f32>,
 }
 
+/// Side from which a trajectory approaches its target.
+///
+/// Arriving from the same side every time takes up backlash consistently,
+/// improving repeatability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Approach {
+    /// Move straight to the target.
+    #[default]
+    Either,
+    /// Arrive moving in the positive (clockwise) direction.
+    Positive,
+    /// Arrive moving in the negative (counter-clockwise) direction.
+    Negative,
+}
+
+impl From<Direction> for Approach {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Clockwise => Approach::Positive,
+            Direction::CounterClockwise => Approach::Negative,
+        }
+    }
+}
+
+/// What a trajectory does when executed.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TrajectoryKind {
+    /// Move to `target_degrees`.
+    #[default]
+    Standard,
+    /// Move until the home switch triggers, then make that position the origin.
+    Homing {
+        /// Direction of travel towards the switch.
+        direction: Direction,
+        /// Travel after which homing gives up.
+        max_travel_degrees: Degrees,
+    },
+}
+
+/// `kind` key of a trajectory table.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+enum KindTag {
+    #[default]
+    Standard,
+    Homing,
+}
+
+/// TOML layout of [`TrajectoryConfig`], with the kind spread over flat keys.
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct TrajectoryToml {
+    /// Target motor name (must match a motor in config).
+    #[cfg_attr(feature = "schema", schemars(with = "std::string::String", length(max = 32)))]
+    motor: String<32>,
+    /// Target position in degrees; required unless `kind = "homing"` or
+    /// `target_turns` is set.
+    #[serde(default)]
+    target_degrees: Option<Degrees>,
+    /// Whole output turns of the target, for multi-turn moves.
+    #[serde(default)]
+    target_turns: Option<i32>,
+    /// Velocity as percentage of motor's max (1-200).
+    #[serde(default = "default_velocity_percent")]
+    #[cfg_attr(feature = "schema", schemars(range(min = 1, max = 200)))]
+    velocity_percent: u8,
+    /// Acceleration as percentage of motor's max (1-200).
+    #[serde(default = "default_acceleration_percent")]
+    #[cfg_attr(feature = "schema", schemars(range(min = 1, max = 200)))]
+    acceleration_percent: u8,
+    /// Absolute acceleration rate in degrees/sec².
+    #[serde(default, rename = "acceleration_deg_per_sec2")]
+    acceleration: Option<DegreesPerSecSquared>,
+    /// Absolute deceleration rate in degrees/sec² (defaults to acceleration).
+    #[serde(default, rename = "deceleration_deg_per_sec2")]
+    deceleration: Option<DegreesPerSecSquared>,
+    /// Dwell time at target in milliseconds.
+    #[serde(default)]
+    dwell_ms: Option<u32>,
+    /// Position the motor must be at before starting, in degrees.
+    #[serde(default, rename = "requires_position_deg")]
+    requires_position: Option<Degrees>,
+    /// Tolerance for `requires_position_deg` in degrees.
+    #[serde(default, rename = "tolerance_deg")]
+    tolerance: Option<Degrees>,
+    /// Trajectory that must be the last one executed on the motor.
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<std::string::String>"))]
+    requires_previous: Option<String<32>>,
+    /// Plain move or homing run.
+    #[serde(default)]
+    kind: KindTag,
+    /// Direction of travel towards the home switch (`kind = "homing"`).
+    #[serde(default)]
+    homing_direction: Option<Direction>,
+    /// Travel after which homing gives up, in degrees (`kind = "homing"`).
+    #[serde(default, rename = "homing_max_travel_deg")]
+    homing_max_travel: Option<Degrees>,
+    /// Direction the target must be approached from.
+    #[serde(default)]
+    approach: Approach,
+    /// Overshoot past the target before a one-sided approach, in degrees.
+    #[serde(default, rename = "overshoot_deg")]
+    #[cfg_attr(feature = "schema", schemars(range(min = 0)))]
+    overshoot: Degrees,
+    /// Soft limits to use instead of the motor's for this trajectory only.
+    #[serde(default)]
+    limit_override: Option<SoftLimits>,
+    /// Load torque in newton-metres the motor must overcome.
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(range(min = 0)))]
+    load_torque_nm: Option<f32>,
+}
+
+// Described by its TOML layout, which is what configuration files contain
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for TrajectoryConfig {
+    fn schema_name() -> std::string::String {
+        "TrajectoryConfig".into()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        let mut schema = TrajectoryToml::json_schema(gen).into_object();
+        schema.metadata().description = Some("A named trajectory from configuration.".into());
+        schema.into()
+    }
+}
+
+impl TryFrom<TrajectoryToml> for TrajectoryConfig {
+    type Error = &'static str;
+
+    fn try_from(raw: TrajectoryToml) -> core::result::Result<Self, Self::Error> {
+        if raw.overshoot.0.is_nan() || raw.overshoot.0 < 0.0 {
+            return Err("overshoot_deg must not be negative");
+        }
+        if raw.limit_override.as_ref().is_some_and(|limits| !limits.is_valid()) {
+            return Err("limit_override min_degrees must be below each max_degrees");
+        }
+        if raw.load_torque_nm.is_some_and(|torque| !(torque >= 0.0 && torque.is_finite())) {
+            return Err("load_torque_nm must not be negative");
+        }
+        let (target_degrees, trajectory_kind) = match raw.kind {
+            KindTag::Standard => {
+                if raw.homing_direction.is_some() || raw.homing_max_travel.is_some() {
+                    return Err("homing_* keys require kind = \"homing\"");
+                }
+                let target = match raw.target_turns {
+                    Some(turns) => {
+                        let remainder = raw.target_degrees.unwrap_or_default();
+                        check_turn_remainder(turns, remainder)?;
+                        remainder
+                    }
+                    None => raw.target_degrees.ok_or("missing field `target_degrees`")?,
+                };
+                (target, TrajectoryKind::Standard)
+            }
+            KindTag::Homing => {
+                if raw.target_degrees.is_some() || raw.target_turns.is_some() {
+                    return Err("homing trajectories have no target_degrees");
+                }
+                if raw.approach != Approach::Either {
+                    return Err("homing trajectories have no approach direction");
+                }
+                let direction = raw.homing_direction.ok_or("missing field `homing_direction`")?;
+                let max_travel =
+                    raw.homing_max_travel.ok_or("missing field `homing_max_travel_deg`")?;
+                if max_travel.0.is_nan() || max_travel.0 <= 0.0 {
+                    return Err("homing_max_travel_deg must be positive");
+                }
+                let kind = TrajectoryKind::Homing { direction, max_travel_degrees: max_travel };
+                (Degrees(0.0), kind)
+            }
+        };
+
+        Ok(Self {
+            motor: raw.motor,
+            target_degrees,
+            target_turns: raw.target_turns,
+            velocity_percent: raw.velocity_percent,
+            acceleration_percent: raw.acceleration_percent,
+            acceleration: raw.acceleration,
+            deceleration: raw.deceleration,
+            dwell_ms: raw.dwell_ms,
+            requires_position: raw.requires_position,
+            tolerance: raw.tolerance,
+            requires_previous: raw.requires_previous,
+            trajectory_kind,
+            approach: raw.approach,
+            overshoot: raw.overshoot,
+            limit_override: raw.limit_override,
+            load_torque_nm: raw.load_torque_nm,
+        })
+    }
+}
+
+/// Check that `remainder` is less than a turn and has the sign of `turns`.
+pub(crate) fn check_turn_remainder(turns: i32, remainder: Degrees) -> Result<(), &'static str> {
+    let r = remainder.0;
+    let same_sign = r == 0.0 || turns == 0 || (r > 0.0) == (turns > 0);
+    if r.abs() < 360.0 && same_sign {
+        Ok(())
+    } else {
+        Err("with target_turns, target_degrees must be under one turn with the same sign")
+    }
+}
+
+/// Default tolerance for a trajectory's required starting position (degrees).
+pub const DEFAULT_PRECONDITION_TOLERANCE_DEG: f32 = 0.1;
+
 fn default_velocity_percent() -> u8 {
     100
 }
@@ -78,6 +342,214 @@ impl TrajectoryConfig {
             && self.acceleration != self.deceleration
     }
 
+    /// Get the tolerance for the required starting position in degrees.
+    pub fn effective_tolerance(&self) -> f32 {
+        self.tolerance
+            .map(|t| t.0)
+            .unwrap_or(DEFAULT_PRECONDITION_TOLERANCE_DEG)
+    }
+
+    /// Get the target position in degrees, including any whole turns.
+    ///
+    /// For very large multi-turn targets this is only as precise as `f32`;
+    /// use [`target_steps`](Self::target_steps) for the exact step count.
+    pub fn target(&self) -> Degrees {
+        match self.target_turns {
+            Some(turns) => Degrees(turns as f32 * 360.0 + self.target_degrees.0),
+            None => self.target_degrees,
+        }
+    }
+
+    /// Get the target position in steps.
+    ///
+    /// Whole turns are converted as turns × steps per revolution, so the
+    /// count is exact however many revolutions the target is away.
+    pub fn target_steps(&self, constraints: &MechanicalConstraints) -> i64 {
+        match self.target_turns {
+            Some(turns) => constraints.whole_turns_to_steps(turns as i64, self.target_degrees.0),
+            None => constraints.degrees_to_steps(self.target_degrees.0),
+        }
+    }
+
+    /// Check if this trajectory runs until the home switch triggers.
+    pub fn is_homing_trajectory(&self) -> bool {
+        matches!(self.trajectory_kind, TrajectoryKind::Homing { .. })
+    }
+
+    /// Get the intermediate position for a one-sided approach from `from`.
+    ///
+    /// Returns `Some(target ± overshoot)` when the direct move would arrive
+    /// from the wrong side, `None` when the motor can move straight to the
+    /// target (or no approach direction is set).
+    pub fn approach_via(&self, from: Degrees) -> Option<Degrees> {
+        if self.is_homing_trajectory() || self.overshoot.0 <= 0.0 {
+            return None;
+        }
+        let target = self.target().0;
+        match self.approach {
+            Approach::Negative if from.0 < target => Some(Degrees(target + self.overshoot.0)),
+            Approach::Positive if from.0 > target => Some(Degrees(target - self.overshoot.0)),
+            _ => None,
+        }
+    }
+
+    /// Describe the trajectory in one line for generated documentation, e.g.
+    /// `move pan to 45° at 50% velocity, 100% acceleration, dwell 200 ms`.
+    ///
+    /// Descriptions longer than 256 bytes are truncated.
+    pub fn to_human_readable(&self) -> String<256> {
+        let mut out = String::new();
+        let _ = self.write_human_readable(&mut out);
+        out
+    }
+
+    /// Write the [`to_human_readable`](Self::to_human_readable) description.
+    pub(crate) fn write_human_readable<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        match self.trajectory_kind {
+            TrajectoryKind::Standard => {
+                write!(w, "move {} to {}°", self.motor, self.target().0)?
+            }
+            TrajectoryKind::Homing { direction, max_travel_degrees } => write!(
+                w,
+                "home {} {} (max {}°)",
+                self.motor,
+                match direction {
+                    Direction::Clockwise => "clockwise",
+                    Direction::CounterClockwise => "counter-clockwise",
+                },
+                max_travel_degrees.0
+            )?,
+        }
+        write!(
+            w,
+            " at {}% velocity, {}% acceleration",
+            self.velocity_percent, self.acceleration_percent
+        )?;
+        if let Some(accel) = self.acceleration {
+            write!(w, ", accel {}°/s²", accel.0)?;
+        }
+        if let Some(decel) = self.deceleration {
+            write!(w, ", decel {}°/s²", decel.0)?;
+        }
+        let side = match self.approach {
+            Approach::Either => None,
+            Approach::Positive => Some("positive"),
+            Approach::Negative => Some("negative"),
+        };
+        if let Some(side) = side {
+            write!(w, ", approach {} (overshoot {}°)", side, self.overshoot.0)?;
+        }
+        if let Some(position) = self.requires_position {
+            write!(w, ", requires {}° ± {}°", position.0, self.effective_tolerance())?;
+        }
+        if let Some(previous) = &self.requires_previous {
+            write!(w, ", after {}", previous)?;
+        }
+        if let Some(dwell) = self.dwell_ms {
+            write!(w, ", dwell {} ms", dwell)?;
+        }
+        Ok(())
+    }
+
+    /// Check if this trajectory has starting preconditions.
+    pub fn has_preconditions(&self) -> bool {
+        self.requires_position.is_some() || self.requires_previous.is_some()
+    }
+
+    /// Check the starting preconditions against the motor's state.
+    ///
+    /// `previous` is the name of the last trajectory executed on the motor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TrajectoryError::PreconditionFailed` if the previous
+    /// trajectory or the position (within tolerance) doesn't match.
+    pub fn check_preconditions(
+        &self,
+        position: Degrees,
+        previous: Option<&str>,
+    ) -> crate::error::Result<()> {
+        use crate::error::{Error, Precondition, TrajectoryError};
+
+        if let Some(ref required) = self.requires_previous {
+            if previous != Some(required.as_str()) {
+                return Err(Error::Trajectory(TrajectoryError::PreconditionFailed {
+                    expected: Precondition::Previous(Some(required.clone())),
+                    actual: Precondition::Previous(
+                        previous.and_then(|p| String::try_from(p).ok()),
+                    ),
+                }));
+            }
+        }
+
+        if let Some(required) = self.requires_position {
+            if (position.0 - required.0).abs() > self.effective_tolerance() {
+                return Err(Error::Trajectory(TrajectoryError::PreconditionFailed {
+                    expected: Precondition::Position(required.0),
+                    actual: Precondition::Position(position.0),
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Plan the motion profile for this trajectory starting at `from_steps`.
+    ///
+    /// Uses the effective velocity, acceleration and deceleration, so
+    /// asymmetric rates produce asymmetric ramps. A trajectory whose target is
+    /// the current position gives a zero-length profile. Targets inside the
+    /// soft limit margin cruise no faster than
+    /// [`MechanicalConstraints::margin_velocity_limit`].
+    ///
+    /// For a homing trajectory the profile covers the full
+    /// `homing_max_travel_deg` in the homing direction, regardless of
+    /// `from_steps`; execution stops early when the switch triggers. It
+    /// cruises at a quarter of the velocity `velocity_percent` would give a
+    /// standard move, the speed
+    /// [`StepperMotor::start_homing`](crate::motor::StepperMotor::start_homing)
+    /// uses at 100 %.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotionError::Overflow` if the move exceeds `u32::MAX` steps.
+    pub fn plan_profile(
+        &self,
+        constraints: &MechanicalConstraints,
+        from_steps: i64,
+    ) -> crate::error::Result<crate::motion::MotionProfile> {
+        let steps_per_degree = constraints.steps_per_degree;
+        let mut velocity = self.effective_velocity(constraints) * steps_per_degree;
+        let delta = match self.trajectory_kind {
+            TrajectoryKind::Standard => {
+                let target = self.target_steps(constraints);
+                if let Some(cap) = constraints.margin_velocity_limit(target) {
+                    velocity = velocity.min(cap);
+                }
+                target - from_steps
+            }
+            TrajectoryKind::Homing { direction, max_travel_degrees } => {
+                velocity *= HOMING_VELOCITY_FRACTION;
+                direction.sign() * Steps::from_degrees(max_travel_degrees, steps_per_degree).0
+            }
+        };
+        crate::motion::MotionProfile::try_asymmetric_trapezoidal(
+            delta,
+            velocity,
+            self.effective_acceleration(constraints) * steps_per_degree,
+            self.effective_deceleration(constraints) * steps_per_degree,
+            constraints.initial_velocity_steps_per_sec,
+        )
+    }
+
+    /// Chain another trajectory to run after this one.
+    ///
+    /// Returns a [`TrajectorySequence`](crate::trajectory::TrajectorySequence)
+    /// that can be extended with further `then()` calls and executed on a motor.
+    pub fn then(self, next: TrajectoryConfig) -> crate::trajectory::TrajectorySequence {
+        crate::trajectory::TrajectorySequence::new().then(self).then(next)
+    }
+
     /// Check if this trajectory is feasible given the motor constraints.
     ///
     /// Returns `Ok(())` if the trajectory can be executed, or an error describing
@@ -86,15 +558,59 @@ impl TrajectoryConfig {
     /// # Checks performed:
     /// - Velocity percent is valid (1-200)
     /// - Acceleration percent is valid (1-200)
-    /// - Target position is within soft limits (if configured)
+    /// - Target position is within soft limits (the trajectory's
+    ///   `limit_override` if set, else the motor's; not applied to homing
+    ///   trajectories, which run before the position is known). With
+    ///   direction-dependent maxima the target passes if a move in either
+    ///   direction may end there
     /// - Effective velocity doesn't exceed motor max
+    /// - The motor's torque at the effective velocity covers
+    ///   `load_torque_nm` (if both the load and the motor's rated torque are
+    ///   set)
+    /// - Cruise step interval isn't shorter than the motor's minimum
     /// - Effective acceleration doesn't exceed motor max
+    ///
+    /// The move distance is not checked, as the starting position is unknown;
+    /// see [`check_feasibility_from`](Self::check_feasibility_from).
     pub fn check_feasibility(
         &self,
         constraints: &MechanicalConstraints,
+    ) -> crate::error::Result<()> {
+        self.feasibility(constraints, None)
+    }
+
+    /// Check feasibility for a move starting at `from`.
+    ///
+    /// Performs every check of [`check_feasibility`](Self::check_feasibility),
+    /// checking the target against the soft limits for the direction of the
+    /// move from `from`. It also rejects standard trajectories whose distance
+    /// from `from` is less than one step at the motor's resolution, or less
+    /// than its `minimum_move_steps`, which would fail at runtime with
+    /// `MoveTooShort`.
+    pub fn check_feasibility_from(
+        &self,
+        constraints: &MechanicalConstraints,
+        from: Degrees,
+    ) -> crate::error::Result<()> {
+        self.feasibility(constraints, Some(from))
+    }
+
+    fn feasibility(
+        &self,
+        constraints: &MechanicalConstraints,
+        from: Option<Degrees>,
     ) -> crate::error::Result<()> {
         use crate::error::{Error, MotionError};
 
+        let overridden;
+        let constraints = match &self.limit_override {
+            Some(limits) => {
+                overridden = constraints.clone().with_limits(Some(limits));
+                &overridden
+            }
+            None => constraints,
+        };
+
         // Check velocity percent
         if self.velocity_percent == 0 || self.velocity_percent > 200 {
             return Err(Error::Config(crate::error::ConfigError::InvalidVelocityPercent(
@@ -109,14 +625,25 @@ impl TrajectoryConfig {
             )));
         }
 
-        // Check if target is within limits
-        if let Some(ref limits) = constraints.limits {
-            let target_steps = constraints.degrees_to_steps(self.target_degrees.0);
-            if limits.apply(target_steps).is_none() {
+        // Check if target is within limits (homing establishes the position)
+        let limits = constraints.limits.as_ref().filter(|_| !self.is_homing_trajectory());
+        if let Some(limits) = limits {
+            let target_steps = self.target_steps(constraints);
+            // Without a start the move may come from either side
+            let directions = match from {
+                Some(from) => {
+                    let from_steps = constraints.degrees_to_steps(from.0);
+                    [Direction::from_steps(target_steps - from_steps); 2]
+                }
+                None => [Direction::Clockwise, Direction::CounterClockwise],
+            };
+            if directions.iter().all(|&d| limits.apply_moving(target_steps, d).is_none()) {
+                let (a, b) = limits.bounds(directions[0]);
+                let (a, b) = (constraints.steps_to_degrees(a), constraints.steps_to_degrees(b));
                 return Err(Error::Trajectory(crate::error::TrajectoryError::TargetExceedsLimits {
-                    target: self.target_degrees.0,
-                    min: constraints.limits.as_ref().map(|l| l.min_steps as f32 / constraints.steps_per_degree).unwrap_or(f32::MIN),
-                    max: constraints.limits.as_ref().map(|l| l.max_steps as f32 / constraints.steps_per_degree).unwrap_or(f32::MAX),
+                    target: self.target().0,
+                    min: a.min(b),
+                    max: a.max(b),
                 }));
             }
         }
@@ -130,6 +657,26 @@ impl TrajectoryConfig {
             }));
         }
 
+        // The motor must still carry the load at cruise velocity
+        if let Some(required) = self.load_torque_nm {
+            let available = constraints.available_torque_at_velocity(effective_velocity);
+            if let Some(available) = available.filter(|&available| required > available) {
+                return Err(Error::Motion(MotionError::InsufficientTorque { available, required }));
+            }
+        }
+
+        // Cruise interval must not undercut the motor's step interval floor
+        let cruise_steps_per_sec = constraints.velocity_to_steps(effective_velocity);
+        if cruise_steps_per_sec > 0.0
+            && ((1_000_000_000.0 / cruise_steps_per_sec) as u32) < constraints.min_step_interval_ns
+        {
+            let max_steps_per_sec = 1_000_000_000.0 / constraints.min_step_interval_ns as f32;
+            return Err(Error::Motion(MotionError::VelocityExceedsLimit {
+                requested: effective_velocity,
+                max: max_steps_per_sec / constraints.steps_per_degree,
+            }));
+        }
+
         // Check effective acceleration against max
         let effective_accel = self.effective_acceleration(constraints);
         if effective_accel > constraints.max_acceleration.0 * 2.0 {
@@ -147,17 +694,59 @@ impl TrajectoryConfig {
             }));
         }
 
+        // The move must cover the motor's minimum (at least one step) from
+        // a known start
+        if let Some(from) = from.filter(|_| !self.is_homing_trajectory()) {
+            let steps = self.target_steps(constraints) - constraints.degrees_to_steps(from.0);
+            constraints.check_move_steps(steps)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for TrajectoryConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.trajectory_kind {
+            TrajectoryKind::Standard => write!(f, "{} → {}°", self.motor, self.target().0)?,
+            TrajectoryKind::Homing { direction, max_travel_degrees } => write!(
+                f,
+                "{} → home {:?} (max {}°)",
+                self.motor, direction, max_travel_degrees.0
+            )?,
+        }
+        write!(
+            f,
+            " | velocity {}% | acceleration {}%",
+            self.velocity_percent, self.acceleration_percent
+        )?;
+        if let Some(accel) = self.acceleration {
+            write!(f, " | accel {}°/s²", accel.0)?;
+        }
+        if let Some(decel) = self.deceleration {
+            write!(f, " | decel {}°/s²", decel.0)?;
+        }
+        if let Some(dwell) = self.dwell_ms {
+            write!(f, " | dwell {} ms", dwell)?;
+        }
         Ok(())
     }
 }
 
 /// Trajectory with multiple waypoints.
-#[derive(Debug, Clone, Deserialize)]
+///
+/// Motion parameters apply to every segment (the move into each waypoint).
+///
+/// `==` compares floats exactly; `approx_eq` allows for rounding.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct WaypointTrajectory {
     /// Target motor name.
+    #[cfg_attr(feature = "schema", schemars(with = "std::string::String", length(max = 32)))]
     pub motor: String<32>,
 
     /// Ordered list of waypoint positions in degrees (max 32).
+    #[cfg_attr(feature = "schema", schemars(with = "std::vec::Vec<Degrees>", length(max = 32)))]
     pub waypoints: Vec<Degrees, 32>,
 
     /// Dwell time at each waypoint (milliseconds).
@@ -166,14 +755,232 @@ pub struct WaypointTrajectory {
 
     /// Velocity percent for all moves.
     #[serde(default = "default_velocity_percent")]
+    #[cfg_attr(feature = "schema", schemars(range(min = 1, max = 200)))]
+    pub velocity_percent: u8,
+
+    /// Acceleration as percentage of motor's max (1-200).
+    /// Used when absolute rates are not specified.
+    #[serde(default = "default_acceleration_percent")]
+    #[cfg_attr(feature = "schema", schemars(range(min = 1, max = 200)))]
+    pub acceleration_percent: u8,
+
+    /// Absolute acceleration rate in degrees/sec² (optional).
+    #[serde(default, rename = "acceleration_deg_per_sec2")]
+    pub acceleration: Option<DegreesPerSecSquared>,
+
+    /// Absolute deceleration rate in degrees/sec² (optional).
+    /// If not set, uses acceleration value (symmetric profile).
+    #[serde(default, rename = "deceleration_deg_per_sec2")]
+    pub deceleration: Option<DegreesPerSecSquared>,
+
+    /// Slow "creep" over the end of the final segment (optional).
+    #[serde(default)]
+    pub final_approach: Option<FinalApproach>,
+}
+
+/// Fixed slow approach to the last waypoint of a [`WaypointTrajectory`].
+///
+/// ```toml
+/// final_approach = { distance_deg = 5.0, velocity_percent = 10 }
+/// ```
+///
+/// The final segment runs at the sequence velocity, slows to the creep
+/// velocity `distance` before the last waypoint, and covers the rest at the
+/// creep velocity before the usual deceleration to rest.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FinalApproach {
+    /// Distance before the last waypoint covered at the creep velocity.
+    #[serde(rename = "distance_deg")]
+    pub distance: Degrees,
+
+    /// Creep velocity as percentage of the motor's max velocity, at most the
+    /// sequence's `velocity_percent`.
+    #[cfg_attr(feature = "schema", schemars(range(min = 1, max = 200)))]
     pub velocity_percent: u8,
 }
 
+impl FinalApproach {
+    /// Get the creep velocity in degrees/sec.
+    pub fn effective_velocity(&self, constraints: &MechanicalConstraints) -> f32 {
+        constraints.max_velocity.0 * (self.velocity_percent as f32 / 100.0)
+    }
+}
+
+impl WaypointTrajectory {
+    /// Get the trajectory for the segment ending at `target`.
+    ///
+    /// The segment carries this sequence's motion parameters and dwell.
+    pub fn segment(&self, target: Degrees) -> TrajectoryConfig {
+        TrajectoryConfig {
+            motor: self.motor.clone(),
+            target_degrees: target,
+            target_turns: None,
+            velocity_percent: self.velocity_percent,
+            acceleration_percent: self.acceleration_percent,
+            acceleration: self.acceleration,
+            deceleration: self.deceleration,
+            dwell_ms: if self.dwell_ms > 0 { Some(self.dwell_ms) } else { None },
+            requires_position: None,
+            tolerance: None,
+            requires_previous: None,
+            trajectory_kind: TrajectoryKind::Standard,
+            approach: Approach::Either,
+            overshoot: Degrees(0.0),
+            limit_override: None,
+            load_torque_nm: None,
+        }
+    }
+
+    /// Insert `position` at `index`, shifting later waypoints right.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TrajectoryError::TooManyWaypoints` if the sequence is already
+    /// at capacity (32), or `TrajectoryError::WaypointIndexOutOfRange` if
+    /// `index` is past the end of the list.
+    pub fn with_waypoint_inserted(
+        mut self,
+        index: usize,
+        position: Degrees,
+    ) -> crate::error::Result<Self> {
+        insert_waypoint(&mut self.waypoints, index, position)?;
+        Ok(self)
+    }
+
+    /// Get an iterator over the per-waypoint segments in order.
+    pub fn segments(&self) -> impl Iterator<Item = TrajectoryConfig> + '_ {
+        self.waypoints.iter().map(|w| self.segment(*w))
+    }
+
+    /// Get effective acceleration rate for each segment.
+    pub fn effective_acceleration(&self, constraints: &MechanicalConstraints) -> f32 {
+        self.acceleration.map(|a| a.0).unwrap_or_else(|| {
+            constraints.max_acceleration.0 * (self.acceleration_percent as f32 / 100.0)
+        })
+    }
+
+    /// Get effective deceleration rate for each segment.
+    /// Falls back to acceleration if not specified (symmetric profile).
+    pub fn effective_deceleration(&self, constraints: &MechanicalConstraints) -> f32 {
+        self.deceleration
+            .map(|d| d.0)
+            .or_else(|| self.acceleration.map(|a| a.0))
+            .unwrap_or_else(|| {
+                constraints.max_acceleration.0 * (self.acceleration_percent as f32 / 100.0)
+            })
+    }
+
+    /// Get effective velocity for each segment.
+    pub fn effective_velocity(&self, constraints: &MechanicalConstraints) -> f32 {
+        constraints.max_velocity.0 * (self.velocity_percent as f32 / 100.0)
+    }
+
+    /// Check if the segments use asymmetric acceleration.
+    pub fn is_asymmetric(&self) -> bool {
+        self.deceleration.is_some()
+            && self.acceleration.is_some()
+            && self.acceleration != self.deceleration
+    }
+
+    /// Check every segment against the motor constraints.
+    ///
+    /// Applies [`TrajectoryConfig::check_feasibility`] to each waypoint, and
+    /// [`check_final_approach`](Self::check_final_approach).
+    pub fn check_feasibility(
+        &self,
+        constraints: &MechanicalConstraints,
+    ) -> crate::error::Result<()> {
+        for segment in self.segments() {
+            segment.check_feasibility(constraints)?;
+        }
+        self.check_final_approach()
+    }
+
+    /// Check that the [final approach](FinalApproach) fits the sequence.
+    ///
+    /// The creep distance must be positive and, when the sequence has two or
+    /// more waypoints, shorter than the final segment. The creep velocity
+    /// must be between 1% and the sequence's `velocity_percent`. A final
+    /// segment from an unknown start shorter than the creep distance runs
+    /// entirely at the creep velocity.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TrajectoryError::InvalidFinalApproach` otherwise.
+    pub fn check_final_approach(&self) -> crate::error::Result<()> {
+        use crate::error::{Error, TrajectoryError};
+
+        let Some(approach) = self.final_approach else {
+            return Ok(());
+        };
+        let segment = match self.waypoints.len() {
+            len if len >= 2 => Some((self.waypoints[len - 1].0 - self.waypoints[len - 2].0).abs()),
+            _ => None,
+        };
+        let distance = approach.distance.0;
+        let fits = distance > 0.0 && segment.map_or(true, |segment| distance < segment);
+        let percent = approach.velocity_percent;
+        if !fits || percent == 0 || percent > self.velocity_percent {
+            return Err(Error::Trajectory(TrajectoryError::InvalidFinalApproach {
+                distance,
+                velocity_percent: percent,
+            }));
+        }
+        Ok(())
+    }
+}
+
+/// Insert `position` at `index`, checking bounds and capacity.
+pub(crate) fn insert_waypoint(
+    waypoints: &mut Vec<Degrees, 32>,
+    index: usize,
+    position: Degrees,
+) -> crate::error::Result<()> {
+    use crate::error::{Error, TrajectoryError};
+
+    if index > waypoints.len() {
+        return Err(Error::Trajectory(TrajectoryError::WaypointIndexOutOfRange {
+            index,
+            len: waypoints.len(),
+        }));
+    }
+    waypoints
+        .insert(index, position)
+        .map_err(|_| Error::Trajectory(TrajectoryError::TooManyWaypoints))
+}
+
+/// Sequence of moves spanning several motors (`[msequences.<name>]`).
+///
+/// Each step is a full trajectory, so it names its own motor:
+///
+/// ```toml
+/// [msequences.load_part]
+/// steps = [
+///     { motor = "feeder", target_degrees = 30.0 },
+///     { motor = "table", target_degrees = 90.0, dwell_ms = 200 },
+/// ]
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MultiMotorSequence {
+    /// Ordered steps (max [`MAX_SEQUENCE_LENGTH`](crate::trajectory::MAX_SEQUENCE_LENGTH)).
+    #[cfg_attr(
+        feature = "schema",
+        schemars(
+            with = "std::vec::Vec<TrajectoryConfig>",
+            length(max = "crate::trajectory::MAX_SEQUENCE_LENGTH")
+        )
+    )]
+    pub steps: Vec<TrajectoryConfig, { crate::trajectory::MAX_SEQUENCE_LENGTH }>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::units::{DegreesPerSec, Microsteps};
-    use crate::config::MotorConfig;
+    use crate::config::{LimitPolicy, MotorConfig};
+    use crate::error::{Error, MotionError};
 
     fn make_test_constraints() -> MechanicalConstraints {
         let config = MotorConfig {
@@ -181,11 +988,33 @@ mod tests {
             steps_per_revolution: 200,
             microsteps: Microsteps::SIXTEENTH,
             gear_ratio: 1.0,
+            gear_ratio_num: None,
+            gear_ratio_den: None,
             max_velocity: DegreesPerSec(360.0),
             max_acceleration: DegreesPerSecSquared(720.0),
             invert_direction: false,
+            output_inverted: false,
             limits: None,
             backlash_compensation: None,
+            backlash_compensation_steps: None,
+            step_pulse_ns: 2000,
+            reversal_dwell_ms: 0,
+            delay_overhead_ns: 0,
+            clamp_on_startup: false,
+            winding_resistance_ohm: None,
+            peak_current_ma: None,
+            max_step_rate_hz: None,
+            park_trajectory: None,
+            thermal: None,
+            backlash_approach_direction: None,
+            gravity_assist_factor: None,
+            gravity_direction: None,
+            initial_velocity: None,
+            soft_start: None,
+            minimum_move_steps: None,
+            rated_torque_nm: None,
+            torque_speed_curve: None,
+            homing: None,
         };
         MechanicalConstraints::from_config(&config)
     }
@@ -195,11 +1024,20 @@ mod tests {
         let traj = TrajectoryConfig {
             motor: String::try_from("test").unwrap(),
             target_degrees: Degrees(90.0),
+            target_turns: None,
             velocity_percent: 100,
             acceleration_percent: 50,
             acceleration: None,
             deceleration: None,
             dwell_ms: None,
+            requires_position: None,
+            tolerance: None,
+            requires_previous: None,
+            trajectory_kind: TrajectoryKind::Standard,
+            approach: Approach::Either,
+            overshoot: Degrees(0.0),
+            limit_override: None,
+            load_torque_nm: None,
         };
 
         let constraints = make_test_constraints();
@@ -216,11 +1054,20 @@ mod tests {
         let traj = TrajectoryConfig {
             motor: String::try_from("test").unwrap(),
             target_degrees: Degrees(90.0),
+            target_turns: None,
             velocity_percent: 100,
             acceleration_percent: 100,
             acceleration: Some(DegreesPerSecSquared(500.0)),
             deceleration: Some(DegreesPerSecSquared(200.0)),
             dwell_ms: None,
+            requires_position: None,
+            tolerance: None,
+            requires_previous: None,
+            trajectory_kind: TrajectoryKind::Standard,
+            approach: Approach::Either,
+            overshoot: Degrees(0.0),
+            limit_override: None,
+            load_torque_nm: None,
         };
 
         let constraints = make_test_constraints();
@@ -231,4 +1078,332 @@ mod tests {
         assert!((decel - 200.0).abs() < 0.1);
         assert!(traj.is_asymmetric());
     }
+
+    #[test]
+    fn test_display() {
+        use std::string::ToString;
+
+        let mut traj = TrajectoryConfig {
+            motor: String::try_from("pan").unwrap(),
+            target_degrees: Degrees(-45.5),
+            target_turns: None,
+            velocity_percent: 50,
+            acceleration_percent: 100,
+            acceleration: None,
+            deceleration: None,
+            dwell_ms: None,
+            requires_position: None,
+            tolerance: None,
+            requires_previous: None,
+            trajectory_kind: TrajectoryKind::Standard,
+            approach: Approach::Either,
+            overshoot: Degrees(0.0),
+            limit_override: None,
+            load_torque_nm: None,
+        };
+        assert_eq!(traj.to_string(), "pan → -45.5° | velocity 50% | acceleration 100%");
+
+        traj.deceleration = Some(DegreesPerSecSquared(200.0));
+        traj.dwell_ms = Some(250);
+        assert_eq!(
+            traj.to_string(),
+            "pan → -45.5° | velocity 50% | acceleration 100% | decel 200°/s² | dwell 250 ms"
+        );
+    }
+
+    #[test]
+    fn test_waypoint_asymmetric_segments() {
+        let toml = r#"
+motor = "test"
+waypoints = [90.0, 0.0]
+dwell_ms = 250
+acceleration_deg_per_sec2 = 720.0
+deceleration_deg_per_sec2 = 180.0
+"#;
+        let seq: WaypointTrajectory = toml::from_str(toml).unwrap();
+        let constraints = make_test_constraints();
+
+        assert!(seq.is_asymmetric());
+        assert!((seq.effective_acceleration(&constraints) - 720.0).abs() < 0.1);
+        assert!((seq.effective_deceleration(&constraints) - 180.0).abs() < 0.1);
+        assert!(seq.check_feasibility(&constraints).is_ok());
+
+        let segments: std::vec::Vec<TrajectoryConfig> = seq.segments().collect();
+        assert_eq!(segments.len(), 2);
+        assert!(segments.iter().all(|s| s.is_asymmetric() && s.dwell_ms == Some(250)));
+
+        // 90° = 800 steps out, then 800 steps back
+        let out = segments[0].plan_profile(&constraints, 0).unwrap();
+        let back = segments[1].plan_profile(&constraints, 800).unwrap();
+        assert_eq!(out.total_steps, 800);
+        assert_eq!(back.direction, crate::motion::Direction::CounterClockwise);
+        for profile in [out, back] {
+            // Decelerating at a quarter of the acceleration takes 4x the distance
+            assert!(profile.decel_steps > 3 * profile.accel_steps);
+        }
+    }
+
+    #[test]
+    fn test_final_approach_checks() {
+        use crate::error::TrajectoryError;
+
+        let toml = r#"
+motor = "test"
+waypoints = [90.0, 0.0]
+velocity_percent = 50
+final_approach = { distance_deg = 5.0, velocity_percent = 10 }
+"#;
+        let mut seq: WaypointTrajectory = toml::from_str(toml).unwrap();
+        let approach = seq.final_approach.unwrap();
+        assert_eq!(approach.distance, Degrees(5.0));
+        assert!((approach.effective_velocity(&make_test_constraints()) - 36.0).abs() < 0.01);
+        assert!(seq.check_feasibility(&make_test_constraints()).is_ok());
+
+        let invalid = |seq: &WaypointTrajectory| {
+            let approach = seq.final_approach.unwrap();
+            Err(Error::Trajectory(TrajectoryError::InvalidFinalApproach {
+                distance: approach.distance.0,
+                velocity_percent: approach.velocity_percent,
+            }))
+        };
+        // Creep faster than the sequence
+        seq.final_approach = Some(FinalApproach { velocity_percent: 60, ..approach });
+        assert_eq!(seq.check_final_approach(), invalid(&seq));
+        // Longer than the 90° final segment
+        seq.final_approach = Some(FinalApproach { distance: Degrees(90.0), ..approach });
+        assert_eq!(seq.check_final_approach(), invalid(&seq));
+        // Unknown start: only the distance itself is checked
+        seq.waypoints.remove(0);
+        assert!(seq.check_final_approach().is_ok());
+        seq.final_approach = Some(FinalApproach { distance: Degrees(0.0), ..approach });
+        assert_eq!(seq.check_final_approach(), invalid(&seq));
+    }
+
+    #[test]
+    fn test_waypoint_insertion() {
+        use crate::error::{Error, TrajectoryError};
+        use crate::trajectory::{WaypointTrajectoryBuilder, MAX_WAYPOINTS};
+
+        let seq = WaypointTrajectoryBuilder::new()
+            .motor("test")
+            .waypoints(&[Degrees(10.0), Degrees(30.0)])
+            .insert_waypoint_at(1, Degrees(20.0))
+            .unwrap()
+            .insert_waypoint_at(0, Degrees(0.0))
+            .unwrap()
+            .build()
+            .unwrap();
+        let expected = [Degrees(0.0), Degrees(10.0), Degrees(20.0), Degrees(30.0)];
+        assert_eq!(seq.waypoints, expected);
+
+        let appended = seq.clone().with_waypoint_inserted(4, Degrees(40.0)).unwrap();
+        assert_eq!(appended.waypoints.last(), Some(&Degrees(40.0)));
+
+        let err = seq.with_waypoint_inserted(5, Degrees(0.0)).unwrap_err();
+        assert_eq!(
+            err,
+            Error::Trajectory(TrajectoryError::WaypointIndexOutOfRange { index: 5, len: 4 })
+        );
+
+        let full: std::vec::Vec<Degrees> = (0..MAX_WAYPOINTS).map(|i| Degrees(i as f32)).collect();
+        let builder = WaypointTrajectoryBuilder::new().motor("test").waypoints(&full);
+        let err = builder.clone().insert_waypoint_at(0, Degrees(-1.0)).unwrap_err();
+        assert_eq!(err, Error::Trajectory(TrajectoryError::TooManyWaypoints));
+        let seq = builder.build().unwrap();
+        assert!(seq.with_waypoint_inserted(0, Degrees(-1.0)).is_err());
+    }
+
+    #[test]
+    fn test_homing_kind_from_toml() {
+        let traj: TrajectoryConfig = toml::from_str(
+            r#"
+motor = "test"
+kind = "homing"
+homing_direction = "counter_clockwise"
+homing_max_travel_deg = 360.0
+velocity_percent = 25
+"#,
+        )
+        .unwrap();
+        assert!(traj.is_homing_trajectory());
+        assert_eq!(
+            traj.trajectory_kind,
+            TrajectoryKind::Homing {
+                direction: Direction::CounterClockwise,
+                max_travel_degrees: Degrees(360.0),
+            }
+        );
+        assert_eq!(traj.target_degrees, Degrees(0.0));
+
+        // The profile covers the full travel regardless of the start position
+        let constraints = make_test_constraints();
+        let profile = traj.plan_profile(&constraints, 1234).unwrap();
+        assert_eq!(profile.direction, Direction::CounterClockwise);
+        assert_eq!(profile.total_steps, 3200);
+
+        let standard: TrajectoryConfig =
+            toml::from_str("motor = \"test\"\ntarget_degrees = 10.0").unwrap();
+        assert!(!standard.is_homing_trajectory());
+
+        for invalid in [
+            "motor = \"test\"\nkind = \"homing\"\nhoming_max_travel_deg = 360.0",
+            "motor = \"test\"\nkind = \"homing\"\nhoming_direction = \"clockwise\"",
+            "motor = \"test\"\nkind = \"homing\"\nhoming_direction = \"clockwise\"\n\
+             homing_max_travel_deg = 90.0\ntarget_degrees = 10.0",
+            "motor = \"test\"\ntarget_degrees = 10.0\nhoming_direction = \"clockwise\"",
+            "motor = \"test\"",
+        ] {
+            assert!(toml::from_str::<TrajectoryConfig>(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_approach_from_toml() {
+        let traj: TrajectoryConfig = toml::from_str(
+            "motor = \"test\"\ntarget_degrees = 90.0\napproach = \"negative\"\novershoot_deg = 5.0",
+        )
+        .unwrap();
+        assert_eq!(traj.approach, Approach::Negative);
+        assert_eq!(traj.approach_via(Degrees(0.0)), Some(Degrees(95.0)));
+        assert_eq!(traj.approach_via(Degrees(180.0)), None);
+
+        let standard: TrajectoryConfig =
+            toml::from_str("motor = \"test\"\ntarget_degrees = 90.0").unwrap();
+        assert_eq!(standard.approach, Approach::Either);
+        assert_eq!(standard.approach_via(Degrees(0.0)), None);
+
+        let negative = "motor = \"test\"\ntarget_degrees = 90.0\novershoot_deg = -1.0";
+        assert!(toml::from_str::<TrajectoryConfig>(negative).is_err());
+    }
+
+    #[test]
+    fn test_limit_override_from_toml() {
+        let traj: TrajectoryConfig = toml::from_str(
+            "motor = \"test\"\ntarget_degrees = 200.0\n\n\
+             [limit_override]\nmin_degrees = 0.0\nmax_degrees = 270.0",
+        )
+        .unwrap();
+        let limits = traj.limit_override.as_ref().unwrap();
+        assert_eq!((limits.min, limits.max), (Degrees(0.0), Degrees(270.0)));
+
+        // Checked against the override rather than the motor's limits
+        let constraints = make_test_constraints().with_limits(Some(&SoftLimits::new(
+            Degrees(0.0),
+            Degrees(90.0),
+            LimitPolicy::Reject,
+        )));
+        assert!(traj.check_feasibility_from(&constraints, Degrees(0.0)).is_ok());
+        let plain: TrajectoryConfig =
+            toml::from_str("motor = \"test\"\ntarget_degrees = 200.0").unwrap();
+        assert!(plain.check_feasibility_from(&constraints, Degrees(0.0)).is_err());
+
+        let inverted = "motor = \"test\"\ntarget_degrees = 10.0\n\n\
+                        [limit_override]\nmin_degrees = 90.0\nmax_degrees = 0.0";
+        assert!(toml::from_str::<TrajectoryConfig>(inverted).is_err());
+    }
+
+    #[test]
+    fn test_directional_limits_feasibility() {
+        let limits = SoftLimits::new(Degrees(0.0), Degrees(90.0), LimitPolicy::Reject)
+            .with_directional_max(Degrees(100.0), Degrees(90.0));
+        let constraints = make_test_constraints().with_limits(Some(&limits));
+        let traj: TrajectoryConfig =
+            toml::from_str("motor = \"test\"\ntarget_degrees = 95.0").unwrap();
+
+        // Reachable moving CW, but not moving CCW
+        assert!(traj.check_feasibility_from(&constraints, Degrees(10.0)).is_ok());
+        let err = traj.check_feasibility_from(&constraints, Degrees(100.0)).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::Error::Trajectory(crate::error::TrajectoryError::TargetExceedsLimits {
+                max, ..
+            }) if (max - 90.0).abs() < 0.01
+        ));
+        // Without a start either direction will do
+        assert!(traj.check_feasibility(&constraints).is_ok());
+    }
+
+    #[test]
+    fn test_load_torque_check() {
+        let mut constraints = make_test_constraints();
+        let traj: TrajectoryConfig = toml::from_str(
+            "motor = \"test\"\ntarget_degrees = 90.0\nvelocity_percent = 50\nload_torque_nm = 0.3",
+        )
+        .unwrap();
+        assert_eq!(traj.load_torque_nm, Some(0.3));
+        // No rated torque: not checked
+        assert!(traj.check_feasibility(&constraints).is_ok());
+
+        // 0.4 N·m falling to 0.1 N·m at 360°/s: 0.25 N·m at the 180°/s cruise
+        constraints.rated_torque_nm = Some(0.4);
+        let mut curve = [(360.0, 0.25); 8];
+        curve[0] = (0.0, 1.0);
+        constraints.torque_speed_curve = Some(curve);
+        assert_eq!(
+            traj.check_feasibility(&constraints),
+            Err(Error::Motion(MotionError::InsufficientTorque { available: 0.25, required: 0.3 }))
+        );
+        let slower = TrajectoryConfig { velocity_percent: 25, ..traj.clone() };
+        assert!(slower.check_feasibility(&constraints).is_ok());
+
+        let negative = "motor = \"test\"\ntarget_degrees = 90.0\nload_torque_nm = -1.0";
+        assert!(toml::from_str::<TrajectoryConfig>(negative).is_err());
+    }
+
+    #[test]
+    fn test_multi_turn_target() {
+        let constraints = make_test_constraints();
+        let traj: TrajectoryConfig =
+            toml::from_str("motor = \"test\"\ntarget_turns = 12\ntarget_degrees = 45.0").unwrap();
+        assert_eq!(traj.target(), Degrees(4365.0));
+        assert_eq!(traj.target_steps(&constraints), 38_800);
+        assert_eq!(traj.plan_profile(&constraints, 0).unwrap().total_steps, 38_800);
+        assert!(traj.check_feasibility_from(&constraints, Degrees(4365.0)).is_err());
+
+        let mut short = constraints.clone();
+        short.minimum_move_steps = 10;
+        // 1° is 8.9 steps
+        assert_eq!(
+            traj.check_feasibility_from(&short, Degrees(4364.0)),
+            Err(Error::Motion(MotionError::MoveTooShort { steps: 9, minimum: 10 }))
+        );
+        assert!(traj.check_feasibility_from(&short, Degrees(4363.0)).is_ok());
+
+        let whole: TrajectoryConfig =
+            toml::from_str("motor = \"test\"\ntarget_turns = -2").unwrap();
+        assert_eq!(whole.target_steps(&constraints), -6400);
+        let negative: TrajectoryConfig =
+            toml::from_str("motor = \"test\"\ntarget_turns = -2\ntarget_degrees = -45.0").unwrap();
+        assert_eq!(negative.target_steps(&constraints), -6800);
+
+        for bad in [
+            "motor = \"test\"\ntarget_turns = 12\ntarget_degrees = 360.0",
+            "motor = \"test\"\ntarget_turns = -2\ntarget_degrees = 45.0",
+            "motor = \"test\"\nkind = \"homing\"\ntarget_turns = 1\n\
+             homing_direction = \"clockwise\"\nhoming_max_travel_deg = 90.0",
+        ] {
+            assert!(toml::from_str::<TrajectoryConfig>(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_to_human_readable() {
+        let traj: TrajectoryConfig = toml::from_str(
+            "motor = \"pan\"\ntarget_degrees = 45.0\nvelocity_percent = 50\ndwell_ms = 200",
+        )
+        .unwrap();
+        assert_eq!(
+            traj.to_human_readable().as_str(),
+            "move pan to 45° at 50% velocity, 100% acceleration, dwell 200 ms"
+        );
+
+        let home = TrajectoryConfig {
+            trajectory_kind: TrajectoryKind::Homing {
+                direction: Direction::CounterClockwise,
+                max_travel_degrees: Degrees(360.0),
+            },
+            ..traj
+        };
+        assert!(home.to_human_readable().starts_with("home pan counter-clockwise (max 360°)"));
+    }
 }
@@ -3,16 +3,19 @@
 //! Provides type-safe representations of angles, velocities, accelerations,
 //! and motor steps to prevent unit confusion at compile time.
 
-use core::ops::{Add, Mul, Sub};
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Sub};
 
 use serde::Deserialize;
 
 use crate::error::ConfigError;
+use crate::math::roundf;
 
 /// Angular position in degrees.
 ///
 /// Used for configuration and user-facing API. Internally converted to [`Steps`].
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(transparent)]
 pub struct Degrees(pub f32);
 
@@ -40,6 +43,41 @@ impl Degrees {
     pub fn from_radians(radians: f32) -> Self {
         Self(radians.to_degrees())
     }
+
+    /// Get the magnitude, dropping the sign.
+    #[inline]
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// Format with exactly `precision` decimal places, e.g. `"0.9°"`.
+    ///
+    /// Pair with [`MechanicalConstraints::degrees_precision`](super::MechanicalConstraints::degrees_precision)
+    /// to avoid showing digits finer than one step.
+    #[inline]
+    pub fn format_with_precision(self, precision: u32) -> FormattedDegrees {
+        FormattedDegrees {
+            value: self,
+            precision,
+        }
+    }
+}
+
+/// [`Degrees`] formatted with a fixed number of decimal places.
+///
+/// Created by [`Degrees::format_with_precision`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FormattedDegrees {
+    /// The value to format.
+    pub value: Degrees,
+    /// Number of decimal places.
+    pub precision: u32,
+}
+
+impl fmt::Display for FormattedDegrees {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.*}°", self.precision as usize, self.value.0)
+    }
 }
 
 impl Add for Degrees {
@@ -58,8 +96,97 @@ impl Sub for Degrees {
     }
 }
 
+impl Mul<f32> for Degrees {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self(self.0 * rhs)
+    }
+}
+
+impl Div<f32> for Degrees {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        Self(self.0 / rhs)
+    }
+}
+
+impl Neg for Degrees {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+/// Time in seconds to cover the distance at the velocity.
+impl Div<DegreesPerSec> for Degrees {
+    type Output = f32;
+
+    fn div(self, rhs: DegreesPerSec) -> Self::Output {
+        self.0 / rhs.0
+    }
+}
+
+/// Angular position in whole and fractional output turns.
+///
+/// Converts to steps as whole revolutions times the steps per revolution plus
+/// the fraction, so large multi-turn targets don't lose steps to float
+/// rounding (see [`MechanicalConstraints::turns_to_steps`](super::MechanicalConstraints::turns_to_steps)).
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Turns(pub f32);
+
+impl Turns {
+    /// Create a new Turns value.
+    #[inline]
+    pub const fn new(value: f32) -> Self {
+        Self(value)
+    }
+
+    /// Get the raw value.
+    #[inline]
+    pub const fn value(self) -> f32 {
+        self.0
+    }
+
+    /// Split into whole turns (rounded towards zero) and the remaining
+    /// degrees, which carry the sign of the turns.
+    #[inline]
+    pub fn split(self) -> (i64, Degrees) {
+        let whole = self.0 as i64;
+        (whole, Degrees((self.0 - whole as f32) * 360.0))
+    }
+
+    /// Convert to degrees.
+    #[inline]
+    pub fn to_degrees(self) -> Degrees {
+        Degrees(self.0 * 360.0)
+    }
+
+    /// Create from degrees.
+    #[inline]
+    pub fn from_degrees(degrees: Degrees) -> Self {
+        Self(degrees.0 / 360.0)
+    }
+}
+
+impl From<Turns> for Degrees {
+    fn from(turns: Turns) -> Self {
+        turns.to_degrees()
+    }
+}
+
+/// Round `value` to `decimal_places` (at most 9) decimal places.
+pub(crate) fn round_to_places(value: f32, decimal_places: u8) -> f32 {
+    let scale = (0..decimal_places.min(9)).fold(1.0f32, |scale, _| scale * 10.0);
+    roundf(value * scale) / scale
+}
+
 /// Angular velocity in degrees per second.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(transparent)]
 pub struct DegreesPerSec(pub f32);
 
@@ -75,6 +202,12 @@ impl DegreesPerSec {
     pub const fn value(self) -> f32 {
         self.0
     }
+
+    /// Get the magnitude, dropping the sign.
+    #[inline]
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
 }
 
 impl Mul<f32> for DegreesPerSec {
@@ -85,8 +218,25 @@ impl Mul<f32> for DegreesPerSec {
     }
 }
 
+impl Div<f32> for DegreesPerSec {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        Self(self.0 / rhs)
+    }
+}
+
+impl Neg for DegreesPerSec {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
 /// Angular acceleration in degrees per second squared.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(transparent)]
 pub struct DegreesPerSecSquared(pub f32);
 
@@ -102,6 +252,12 @@ impl DegreesPerSecSquared {
     pub const fn value(self) -> f32 {
         self.0
     }
+
+    /// Get the magnitude, dropping the sign.
+    #[inline]
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
 }
 
 impl Mul<f32> for DegreesPerSecSquared {
@@ -112,6 +268,22 @@ impl Mul<f32> for DegreesPerSecSquared {
     }
 }
 
+impl Div<f32> for DegreesPerSecSquared {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        Self(self.0 / rhs)
+    }
+}
+
+impl Neg for DegreesPerSecSquared {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
 /// Motor position in steps (absolute from origin).
 ///
 /// Uses i64 for unlimited range in either direction.
@@ -250,6 +422,29 @@ impl<'de> Deserialize<'de> for Microsteps {
     }
 }
 
+// An integer restricted to the valid divisors
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Microsteps {
+    fn schema_name() -> std::string::String {
+        "Microsteps".into()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        use schemars::schema::{InstanceType, Metadata, SchemaObject};
+
+        SchemaObject {
+            instance_type: Some(InstanceType::Integer.into()),
+            enum_values: Some(Self::VALID_VALUES.iter().map(|&v| v.into()).collect()),
+            metadata: Some(std::boxed::Box::new(Metadata {
+                description: Some("Microstep divisor (a power of 2 up to 256).".into()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
 /// Extension trait for creating unit types from primitives.
 pub trait UnitExt {
     /// Convert to Degrees.
@@ -302,6 +497,40 @@ mod tests {
         assert!((d.to_radians() - core::f32::consts::PI).abs() < 0.0001);
     }
 
+    #[test]
+    fn test_scalar_arithmetic() {
+        assert_eq!(Degrees(90.0) * 0.5, Degrees(45.0));
+        assert_eq!(Degrees(90.0) / 4.0, Degrees(22.5));
+        assert_eq!(-Degrees(30.0), Degrees(-30.0));
+        assert_eq!(Degrees(-30.0).abs(), Degrees(30.0));
+        // Seconds to cover the distance
+        assert_eq!(Degrees(90.0) / DegreesPerSec(180.0), 0.5);
+
+        assert_eq!(DegreesPerSec(180.0) / 2.0, DegreesPerSec(90.0));
+        assert_eq!((-DegreesPerSec(180.0)).abs(), DegreesPerSec(180.0));
+        assert_eq!(DegreesPerSecSquared(720.0) / 4.0, DegreesPerSecSquared(180.0));
+        assert_eq!(-DegreesPerSecSquared(720.0), DegreesPerSecSquared(-720.0));
+        assert_eq!(DegreesPerSecSquared(-1.0).abs(), DegreesPerSecSquared(1.0));
+    }
+
+    #[test]
+    fn test_format_with_precision() {
+        use std::format;
+
+        assert_eq!(format!("{}", Degrees(0.9000001).format_with_precision(1)), "0.9°");
+        assert_eq!(format!("{}", Degrees(-12.3456).format_with_precision(2)), "-12.35°");
+        assert_eq!(format!("{}", Degrees(90.0).format_with_precision(0)), "90°");
+        assert_eq!(format!("{}", Degrees(1.5).format_with_precision(3)), "1.500°");
+    }
+
+    #[test]
+    fn test_turns_conversion() {
+        assert_eq!(Degrees::from(Turns(12.125)), Degrees(4365.0));
+        assert_eq!(Turns::from_degrees(Degrees(-540.0)), Turns(-1.5));
+        assert_eq!(Turns(12.125).split(), (12, Degrees(45.0)));
+        assert_eq!(Turns(-1.5).split(), (-1, Degrees(-180.0)));
+    }
+
     #[test]
     fn test_steps_to_degrees() {
         let steps = Steps::new(3200);
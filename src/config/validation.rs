@@ -2,21 +2,36 @@
 
 use crate::error::{ConfigError, Error, Result, TrajectoryError};
 
-use super::SystemConfig;
+use super::units::DegreesPerSec;
+use super::{SoftLimits, SystemConfig};
 
 /// Validate a system configuration.
 ///
 /// Checks:
+/// - Float fields are finite (not NaN or infinity)
 /// - Motor configurations are valid
 /// - Trajectory references existing motors
+/// - Park trajectories exist and target their motor
+/// - Required previous trajectories exist and end at the required start position
 /// - Velocity/acceleration percentages are in range
-/// - Soft limits are valid (min < max)
+/// - Trajectory rates are feasible for the motor, and trajectories with a
+///   known starting position move at least one step
+/// - Max velocity is within `max_step_rate_hz` (if set)
+/// - Soft limits are valid (min < max, margin at most half the span)
+/// - Initial velocity is positive and at most the max velocity (if set)
+/// - Backlash compensation is given in degrees or in steps, not both
+/// - Homing blocks have a velocity of 1-100% and a backoff below the timeout
+/// - Multi-motor sequence steps reference existing motors and respect limits
+/// - Motor groups reference existing motors that agree on enforced fields
 pub fn validate_config(config: &SystemConfig) -> Result<()> {
     // Validate motors
     for (name, motor) in config.motors.iter() {
         validate_motor(name.as_str(), motor)?;
+        validate_park(name.as_str(), motor, config)?;
     }
 
+    validate_names(config)?;
+
     // Validate trajectories
     for (name, traj) in config.trajectories.iter() {
         validate_trajectory(name.as_str(), traj, config)?;
@@ -27,10 +42,69 @@ pub fn validate_config(config: &SystemConfig) -> Result<()> {
         validate_sequence(name.as_str(), seq, config)?;
     }
 
+    // Validate multi-motor sequences
+    for (name, mseq) in config.msequences.iter() {
+        validate_msequence(name.as_str(), mseq, config)?;
+    }
+
+    // Validate motor groups
+    for (name, group) in config.groups.iter() {
+        validate_group(name.as_str(), group, config)?;
+    }
+
+    Ok(())
+}
+
+/// Check trajectory and sequence names, which share one namespace.
+fn validate_names(config: &SystemConfig) -> Result<()> {
+    let names = || {
+        config
+            .trajectories
+            .keys()
+            .chain(config.sequences.keys())
+            .chain(config.msequences.keys())
+    };
+    for (index, name) in names().enumerate() {
+        super::check_config_name(name.as_str(), config.permissive_names)?;
+        if names().take(index).any(|earlier| earlier == name) {
+            return Err(Error::Config(ConfigError::DuplicateTrajectoryName(name.clone())));
+        }
+    }
+    Ok(())
+}
+
+fn validate_park(name: &str, motor: &super::MotorConfig, config: &SystemConfig) -> Result<()> {
+    if let Some(park) = motor.park_trajectory.as_ref() {
+        let trajectory = config
+            .trajectory(park.as_str())
+            .ok_or_else(|| Error::Config(ConfigError::TrajectoryNotFound(park.clone())))?;
+        if trajectory.motor.as_str() != name {
+            return Err(Error::Config(ConfigError::ParkTrajectoryWrongMotor {
+                motor: heapless::String::try_from(name).unwrap_or_default(),
+                trajectory: park.clone(),
+            }));
+        }
+    }
     Ok(())
 }
 
 fn validate_motor(_name: &str, config: &super::MotorConfig) -> Result<()> {
+    check_finite([
+        ("gear_ratio", Some(config.gear_ratio)),
+        ("max_velocity_deg_per_sec", Some(config.max_velocity.0)),
+        ("max_acceleration_deg_per_sec2", Some(config.max_acceleration.0)),
+        ("backlash_compensation_deg", config.backlash_compensation.map(|b| b.0)),
+        ("initial_velocity_deg_per_sec", config.initial_velocity.map(|v| v.0)),
+    ])?;
+    if let Some(limits) = &config.limits {
+        check_limits_finite(limits)?;
+    }
+
+    // A rational gear ratio needs both parts, neither zero
+    if config.gear_ratio_num.is_some() || config.gear_ratio_den.is_some() {
+        check_gear_ratio_fraction(config.gear_ratio_num, config.gear_ratio_den)?;
+    }
+
     // Gear ratio must be positive
     if config.gear_ratio <= 0.0 {
         return Err(Error::Config(ConfigError::InvalidGearRatio(config.gear_ratio)));
@@ -50,14 +124,173 @@ fn validate_motor(_name: &str, config: &super::MotorConfig) -> Result<()> {
         )));
     }
 
-    // Soft limits: min must be < max
+    let constraints = super::MechanicalConstraints::from_config(config);
+
+    // Max velocity must not need a faster step rate than the driver handles
+    // (whole Hz, so float rounding in steps/degree doesn't trip the check)
+    if let Some(max_hz) = config.max_step_rate_hz {
+        if constraints.max_velocity_steps_per_sec as u32 > max_hz {
+            return Err(Error::Config(ConfigError::StepRateExceedsMax {
+                rate_hz: constraints.max_velocity_steps_per_sec,
+                max_hz,
+            }));
+        }
+    }
+
+    // Step pulse must be long enough for the driver and fit in the step period
+    validate_step_pulse(config.step_pulse_ns, constraints.min_step_interval_ns)?;
+
+    // Soft limits: min must be below max and any directional maxima
     if let Some(ref limits) = config.limits {
         if !limits.is_valid() {
             return Err(Error::Config(ConfigError::InvalidSoftLimits {
                 min: limits.min.0,
-                max: limits.max.0,
+                max: limits.lowest_max().0,
+            }));
+        }
+
+        // Margin must leave room between the two slowdown zones
+        if let Some(margin) = limits.margin.filter(|_| !limits.is_margin_valid()) {
+            return Err(Error::Config(ConfigError::InvalidLimitMargin {
+                margin: margin.0,
+                span: limits.max.0 - limits.min.0,
             }));
         }
+        if let Some(percent) = limits.margin_velocity_percent {
+            if percent == 0 || percent > 100 {
+                return Err(Error::Config(ConfigError::InvalidVelocityPercent(percent)));
+            }
+        }
+
+        if let Some(zone) = limits.clamp_slow_zone.filter(|_| !limits.is_clamp_slow_zone_valid()) {
+            return Err(Error::Config(ConfigError::InvalidClampSlowZone {
+                zone: zone.0,
+                span: limits.max.0 - limits.min.0,
+            }));
+        }
+        if let Some(percent) = limits.clamp_approach_velocity_percent {
+            if percent == 0 || percent > 100 {
+                return Err(Error::Config(ConfigError::InvalidVelocityPercent(percent)));
+            }
+        }
+    }
+
+    if let Some(factor) = config.gravity_assist_factor.filter(|f| !(*f > 0.0 && f.is_finite())) {
+        return Err(Error::Config(ConfigError::InvalidGravityAssistFactor(factor)));
+    }
+
+    if let Some(thermal) = config.thermal.filter(|t| !t.is_valid()) {
+        return Err(Error::Config(ConfigError::InvalidThermalConfig {
+            time_constant_s: thermal.time_constant_s,
+            max_duty: thermal.max_duty,
+        }));
+    }
+
+    if let Some((degrees, steps)) =
+        config.backlash_compensation.zip(config.backlash_compensation_steps)
+    {
+        return Err(Error::Config(ConfigError::ConflictingBacklashConfig {
+            degrees: degrees.0,
+            steps,
+        }));
+    }
+
+    if let Some(homing) = config.homing.filter(|h| !h.is_valid()) {
+        return Err(Error::Config(ConfigError::InvalidHomingConfig {
+            velocity_percent: homing.velocity_percent,
+            backoff: homing.backoff.0,
+            timeout: homing.timeout.0,
+        }));
+    }
+
+    if let Some(velocity) = config.initial_velocity {
+        check_initial_velocity(velocity, config.max_velocity)?;
+    }
+
+    if let Some(soft_start) = config.soft_start.filter(|s| !s.is_valid()) {
+        return Err(Error::Config(ConfigError::InvalidSoftStart {
+            duration_ms: soft_start.duration_ms,
+            accel_percent: soft_start.accel_percent,
+        }));
+    }
+
+    if let Some(torque) = config.rated_torque_nm.filter(|t| !(*t > 0.0 && t.is_finite())) {
+        return Err(Error::Config(ConfigError::InvalidRatedTorque(torque)));
+    }
+
+    if let Some(curve) = &config.torque_speed_curve {
+        check_torque_curve(curve)?;
+    }
+
+    Ok(())
+}
+
+/// Reject NaN and infinite values, naming the first such field.
+pub(crate) fn check_finite<const N: usize>(fields: [(&'static str, Option<f32>); N]) -> Result<()> {
+    for (field, value) in fields {
+        if let Some(value) = value.filter(|v| !v.is_finite()) {
+            return Err(Error::Config(ConfigError::NonFiniteValue { field, value }));
+        }
+    }
+    Ok(())
+}
+
+fn check_limits_finite(limits: &SoftLimits) -> Result<()> {
+    check_finite([
+        ("min_degrees", Some(limits.min.0)),
+        ("max_degrees", Some(limits.max.0)),
+        ("max_degrees_cw", limits.max_cw.map(|m| m.0)),
+        ("max_degrees_ccw", limits.max_ccw.map(|m| m.0)),
+        ("margin_deg", limits.margin.map(|m| m.0)),
+        ("clamp_slow_zone_deg", limits.clamp_slow_zone.map(|z| z.0)),
+    ])
+}
+
+/// Check that torque-speed curve velocities are non-negative and ascending,
+/// and torque fractions within 0-1.
+fn check_torque_curve(curve: &[(f32, f32)]) -> Result<()> {
+    let mut previous = 0.0;
+    for (point, &(velocity, fraction)) in curve.iter().enumerate() {
+        // Negated so NaN is rejected too
+        if !(velocity >= previous && velocity.is_finite() && (0.0..=1.0).contains(&fraction)) {
+            return Err(Error::Config(ConfigError::InvalidTorqueCurve { point }));
+        }
+        previous = velocity;
+    }
+    Ok(())
+}
+
+/// Check that an initial velocity is positive and at most `max`.
+pub(crate) fn check_initial_velocity(velocity: DegreesPerSec, max: DegreesPerSec) -> Result<()> {
+    if velocity.0 > 0.0 && velocity.0 <= max.0 {
+        Ok(())
+    } else {
+        Err(Error::Config(ConfigError::InvalidInitialVelocity { velocity: velocity.0, max: max.0 }))
+    }
+}
+
+/// Check that a rational gear ratio has both parts and neither is zero.
+pub(crate) fn check_gear_ratio_fraction(num: Option<u32>, den: Option<u32>) -> Result<()> {
+    match num.zip(den) {
+        Some((num, den)) if num > 0 && den > 0 => Ok(()),
+        _ => Err(Error::Config(ConfigError::InvalidGearRatioFraction {
+            numerator: num.unwrap_or(0),
+            denominator: den.unwrap_or(0),
+        })),
+    }
+}
+
+/// Validate a STEP pulse width against the minimum step interval.
+pub(crate) fn validate_step_pulse(pulse_ns: u32, min_step_interval_ns: u32) -> Result<()> {
+    if pulse_ns < super::MIN_STEP_PULSE_NS {
+        return Err(Error::Config(ConfigError::InvalidStepPulseWidth(pulse_ns)));
+    }
+
+    if pulse_ns >= min_step_interval_ns {
+        return Err(Error::Config(ConfigError::StepPulseExceedsInterval {
+            pulse_ns,
+            interval_ns: min_step_interval_ns,
+        }));
     }
 
     Ok(())
@@ -68,6 +301,18 @@ fn validate_trajectory(
     traj: &super::TrajectoryConfig,
     config: &SystemConfig,
 ) -> Result<()> {
+    check_finite([
+        ("target_degrees", Some(traj.target_degrees.0)),
+        ("acceleration_deg_per_sec2", traj.acceleration.map(|a| a.0)),
+        ("deceleration_deg_per_sec2", traj.deceleration.map(|d| d.0)),
+        ("requires_position_deg", traj.requires_position.map(|p| p.0)),
+        ("tolerance_deg", traj.tolerance.map(|t| t.0)),
+        ("overshoot_deg", Some(traj.overshoot.0)),
+    ])?;
+    if let Some(limits) = &traj.limit_override {
+        check_limits_finite(limits)?;
+    }
+
     // Motor must exist
     if config.motor(traj.motor.as_str()).is_none() {
         return Err(Error::Trajectory(TrajectoryError::MotorNotFound {
@@ -90,15 +335,17 @@ fn validate_trajectory(
         )));
     }
 
-    // Check target against limits if motor has them
-    if let Some(motor) = config.motor(traj.motor.as_str()) {
-        if let Some(ref limits) = motor.limits {
-            if !limits.contains(traj.target_degrees) {
-                // Note: This is a warning, not an error, if policy is Clamp
-                // For now, we only error on Reject policy
+    // Check target against the trajectory's limits, or the motor's (homing
+    // has no target)
+    let motor = config.motor(traj.motor.as_str()).filter(|_| !traj.is_homing_trajectory());
+    if let Some(motor) = motor {
+        if let Some(limits) = traj.limit_override.as_ref().or(motor.limits.as_ref()) {
+            if !limits.contains_either(traj.target()) {
+                // Clamp and wrap bring the target inside when the move is
+                // planned, so only reject is an error
                 if limits.policy == super::LimitPolicy::Reject {
                     return Err(Error::Trajectory(TrajectoryError::TargetExceedsLimits {
-                        target: traj.target_degrees.0,
+                        target: traj.target().0,
                         min: limits.min.0,
                         max: limits.max.0,
                     }));
@@ -107,6 +354,31 @@ fn validate_trajectory(
         }
     }
 
+    // Required previous trajectory must exist on the same motor and end at
+    // the required starting position
+    if let Some(ref previous_name) = traj.requires_previous {
+        let previous = config
+            .trajectory(previous_name.as_str())
+            .filter(|p| p.motor == traj.motor)
+            .ok_or_else(|| Error::Config(ConfigError::TrajectoryNotFound(previous_name.clone())))?;
+
+        traj.check_preconditions(previous.target(), Some(previous_name.as_str()))?;
+    }
+
+    // Rates and, where the starting position is known from the
+    // preconditions, the move distance
+    if let Some(motor) = config.motor(traj.motor.as_str()) {
+        let constraints = super::MechanicalConstraints::from_config(motor);
+        let start = traj.requires_position.or_else(|| {
+            let previous = traj.requires_previous.as_ref()?;
+            config.trajectory(previous.as_str()).map(|p| p.target())
+        });
+        match start {
+            Some(start) => traj.check_feasibility_from(&constraints, start)?,
+            None => traj.check_feasibility(&constraints)?,
+        }
+    }
+
     Ok(())
 }
 
@@ -128,11 +400,61 @@ fn validate_sequence(
         return Err(Error::Trajectory(TrajectoryError::EmptyWaypoints));
     }
 
-    // Velocity percent must be 1-200
-    if seq.velocity_percent == 0 || seq.velocity_percent > 200 {
-        return Err(Error::Config(ConfigError::InvalidVelocityPercent(
-            seq.velocity_percent,
-        )));
+    // Each segment gets the same percent-range and limit checks as a trajectory
+    for segment in seq.segments() {
+        validate_trajectory(name, &segment, config)?;
+    }
+
+    seq.check_final_approach()?;
+
+    Ok(())
+}
+
+fn validate_msequence(
+    name: &str,
+    mseq: &super::MultiMotorSequence,
+    config: &SystemConfig,
+) -> Result<()> {
+    if mseq.steps.is_empty() {
+        let name = heapless::String::try_from(name).unwrap_or_default();
+        return Err(Error::Trajectory(TrajectoryError::EmptySequence(name)));
+    }
+
+    // Each step is checked like a standalone trajectory
+    for step in mseq.steps.iter() {
+        validate_trajectory(name, step, config)?;
+    }
+
+    Ok(())
+}
+
+fn validate_group(name: &str, group: &super::GroupConfig, config: &SystemConfig) -> Result<()> {
+    let mut reference: Option<(&str, &super::MotorConfig)> = None;
+
+    for member in group.motors.iter() {
+        let motor = config
+            .motor(member.as_str())
+            .ok_or_else(|| Error::Config(ConfigError::MotorNotFound(member.clone())))?;
+
+        let Some((first_name, first)) = reference else {
+            reference = Some((member.as_str(), motor));
+            continue;
+        };
+
+        for field in group.enforce.iter() {
+            let expected = field.value(first);
+            let found = field.value(motor);
+            if expected != found {
+                return Err(Error::Config(ConfigError::GroupMismatch {
+                    group: heapless::String::try_from(name).unwrap_or_default(),
+                    field: field.as_str(),
+                    expected_motor: heapless::String::try_from(first_name).unwrap_or_default(),
+                    expected,
+                    motor: member.clone(),
+                    found,
+                }));
+            }
+        }
     }
 
     Ok(())
@@ -152,11 +474,33 @@ mod tests {
             steps_per_revolution: 200,
             microsteps: Microsteps::SIXTEENTH,
             gear_ratio: -1.0, // Invalid!
+            gear_ratio_num: None,
+            gear_ratio_den: None,
             max_velocity: DegreesPerSec(360.0),
             max_acceleration: DegreesPerSecSquared(720.0),
             invert_direction: false,
+            output_inverted: false,
             limits: None,
             backlash_compensation: None,
+            backlash_compensation_steps: None,
+            step_pulse_ns: 2000,
+            reversal_dwell_ms: 0,
+            delay_overhead_ns: 0,
+            clamp_on_startup: false,
+            winding_resistance_ohm: None,
+            peak_current_ma: None,
+            max_step_rate_hz: None,
+            park_trajectory: None,
+            thermal: None,
+            backlash_approach_direction: None,
+            gravity_assist_factor: None,
+            gravity_direction: None,
+            initial_velocity: None,
+            soft_start: None,
+            minimum_move_steps: None,
+            rated_torque_nm: None,
+            torque_speed_curve: None,
+            homing: None,
         };
 
         let result = validate_motor("test", &config);
@@ -164,5 +508,751 @@ mod tests {
             result,
             Err(Error::Config(ConfigError::InvalidGearRatio(_)))
         ));
+
+        // Rational form: both parts needed, neither zero
+        let mut config = MotorConfig { gear_ratio: 1.0, gear_ratio_num: Some(7), ..config };
+        let fraction = |numerator, denominator| {
+            Err(Error::Config(ConfigError::InvalidGearRatioFraction { numerator, denominator }))
+        };
+        assert_eq!(validate_motor("test", &config), fraction(7, 0));
+        config.gear_ratio_den = Some(0);
+        assert_eq!(validate_motor("test", &config), fraction(7, 0));
+        config.gear_ratio_den = Some(5);
+        assert_eq!(validate_motor("test", &config), Ok(()));
+    }
+
+    #[test]
+    fn test_limit_margin_validation() {
+        use crate::config::MotorConfig;
+
+        let mut config: MotorConfig = toml::from_str(
+            r#"
+name = "test"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[limits]
+min_degrees = -45.0
+max_degrees = 45.0
+margin_deg = 45.0
+"#,
+        )
+        .unwrap();
+        assert!(validate_motor("test", &config).is_ok());
+
+        let limits = config.limits.as_mut().unwrap();
+        limits.margin = Some(crate::config::units::Degrees(45.5));
+        assert_eq!(
+            validate_motor("test", &config),
+            Err(Error::Config(ConfigError::InvalidLimitMargin { margin: 45.5, span: 90.0 }))
+        );
+
+        let limits = config.limits.as_mut().unwrap();
+        limits.margin = Some(crate::config::units::Degrees(5.0));
+        limits.margin_velocity_percent = Some(0);
+        assert!(validate_motor("test", &config).is_err());
+
+        // Directional maxima must lie above the minimum too
+        let limits = config.limits.as_mut().unwrap();
+        limits.margin_velocity_percent = None;
+        limits.max_ccw = Some(crate::config::units::Degrees(-50.0));
+        assert_eq!(
+            validate_motor("test", &config),
+            Err(Error::Config(ConfigError::InvalidSoftLimits { min: -45.0, max: -50.0 }))
+        );
+    }
+
+    #[test]
+    fn test_clamp_approach_validation() {
+        use crate::config::units::Degrees;
+        use crate::config::MotorConfig;
+
+        let mut config: MotorConfig = toml::from_str(
+            r#"
+name = "test"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[limits]
+min_degrees = -45.0
+max_degrees = 45.0
+policy = "clamp"
+clamp_slow_zone_deg = 10.0
+clamp_approach_velocity_percent = 20
+"#,
+        )
+        .unwrap();
+        assert!(validate_motor("test", &config).is_ok());
+
+        let limits = config.limits.as_mut().unwrap();
+        limits.clamp_slow_zone = Some(Degrees(-1.0));
+        assert_eq!(
+            validate_motor("test", &config),
+            Err(Error::Config(ConfigError::InvalidClampSlowZone { zone: -1.0, span: 90.0 }))
+        );
+
+        let limits = config.limits.as_mut().unwrap();
+        limits.clamp_slow_zone = Some(Degrees(90.0));
+        limits.clamp_approach_velocity_percent = Some(101);
+        assert_eq!(
+            validate_motor("test", &config),
+            Err(Error::Config(ConfigError::InvalidVelocityPercent(101)))
+        );
+    }
+
+    #[test]
+    fn test_thermal_validation() {
+        use crate::config::MotorConfig;
+
+        let mut config: MotorConfig = toml::from_str(
+            r#"
+name = "test"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[thermal]
+time_constant_s = 60.0
+max_duty = 0.6
+enforce = true
+"#,
+        )
+        .unwrap();
+        assert!(config.thermal.unwrap().enforce);
+        assert!(validate_motor("test", &config).is_ok());
+
+        config.thermal.as_mut().unwrap().max_duty = 1.5;
+        assert_eq!(
+            validate_motor("test", &config),
+            Err(Error::Config(ConfigError::InvalidThermalConfig {
+                time_constant_s: 60.0,
+                max_duty: 1.5,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_non_finite_values() {
+        let config = |motor: &str, trajectory: &str| {
+            crate::config::loader::parse_config(&std::format!(
+                r#"
+[motors.x]
+name = "X"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+{motor}
+
+[motors.x.limits]
+min_degrees = -90.0
+max_degrees = 90.0
+
+[trajectories.move]
+motor = "x"
+{trajectory}
+"#
+            ))
+        };
+        assert!(config("", "target_degrees = 45.0").is_ok());
+
+        // Not a limit violation: the infinite target is named as such
+        assert_eq!(
+            config("", "target_degrees = inf").unwrap_err(),
+            Error::Config(ConfigError::NonFiniteValue {
+                field: "target_degrees",
+                value: f32::INFINITY,
+            })
+        );
+        assert!(matches!(
+            config("gear_ratio = nan", "target_degrees = 45.0"),
+            Err(Error::Config(ConfigError::NonFiniteValue { field: "gear_ratio", value }))
+                if value.is_nan()
+        ));
+        assert!(matches!(
+            config("backlash_compensation_deg = -inf", "target_degrees = 45.0"),
+            Err(Error::Config(ConfigError::NonFiniteValue {
+                field: "backlash_compensation_deg",
+                ..
+            }))
+        ));
+        assert!(matches!(
+            config("", "target_degrees = 45.0\nacceleration_deg_per_sec2 = nan"),
+            Err(Error::Config(ConfigError::NonFiniteValue {
+                field: "acceleration_deg_per_sec2",
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_conflicting_backlash() {
+        use crate::config::units::Degrees;
+        use crate::config::MotorConfig;
+
+        let mut config: MotorConfig = toml::from_str(
+            r#"
+name = "test"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+backlash_compensation_steps = 3
+"#,
+        )
+        .unwrap();
+        assert!(validate_motor("test", &config).is_ok());
+
+        config.backlash_compensation = Some(Degrees(0.5));
+        assert_eq!(
+            validate_motor("test", &config),
+            Err(Error::Config(ConfigError::ConflictingBacklashConfig {
+                degrees: 0.5,
+                steps: 3,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_homing_validation() {
+        use crate::config::units::Degrees;
+        use crate::config::{HomingDirection, MotorConfig};
+
+        let motor = |homing: &str| {
+            toml::from_str::<MotorConfig>(&std::format!(
+                r#"
+name = "test"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[homing]
+{homing}
+"#
+            ))
+        };
+
+        let mut config = motor(
+            r#"
+direction = "negative"
+velocity_percent = 20
+backoff_deg = 2.0
+switch_active_low = true
+timeout_deg = 400.0
+"#,
+        )
+        .unwrap();
+        let homing = config.homing().unwrap();
+        assert_eq!(homing.direction, HomingDirection::Negative);
+        assert_eq!(homing.direction_sign(), -1);
+        assert_eq!((homing.velocity_percent, homing.backoff.0), (20, 2.0));
+        assert!(homing.switch_active_low);
+        assert!(validate_motor("test", &config).is_ok());
+
+        // Zero backoff
+        config.homing.as_mut().unwrap().backoff = Degrees(0.0);
+        assert_eq!(
+            validate_motor("test", &config),
+            Err(Error::Config(ConfigError::InvalidHomingConfig {
+                velocity_percent: 20,
+                backoff: 0.0,
+                timeout: 400.0,
+            }))
+        );
+
+        // Backoff beyond the timeout travel, velocity out of range
+        let invalid = |homing: &str| {
+            let config = motor(homing).unwrap();
+            matches!(
+                validate_motor("test", &config),
+                Err(Error::Config(ConfigError::InvalidHomingConfig { .. }))
+            )
+        };
+        assert!(invalid("direction = \"positive\"\nbackoff_deg = 5.0\ntimeout_deg = 5.0"));
+        let percent = "backoff_deg = 1.0\ntimeout_deg = 9.0\ndirection = \"positive\"";
+        assert!(invalid(&std::format!("velocity_percent = 0\n{percent}")));
+        assert!(invalid(&std::format!("velocity_percent = 101\n{percent}")));
+        assert!(invalid("direction = \"positive\"\nbackoff_deg = 1.0\ntimeout_deg = -9.0"));
+
+        // Defaults, and only positive/negative directions
+        let defaults = motor("direction = \"positive\"\nbackoff_deg = 1.0\ntimeout_deg = 9.0");
+        let homing = *defaults.unwrap().homing().unwrap();
+        assert_eq!((homing.velocity_percent, homing.switch_active_low), (100, false));
+        let clockwise = "direction = \"clockwise\"\nbackoff_deg = 1.0\ntimeout_deg = 9.0";
+        assert!(motor(clockwise).is_err());
+
+        // The direction and distances have no defaults
+        assert!(motor("").is_err());
+    }
+
+    #[test]
+    fn test_initial_velocity_validation() {
+        use crate::config::units::DegreesPerSec;
+        use crate::config::{MechanicalConstraints, MotorConfig};
+
+        let mut config: MotorConfig = toml::from_str(
+            r#"
+name = "test"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+initial_velocity_deg_per_sec = 36.0
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.initial_velocity, Some(DegreesPerSec(36.0)));
+        assert!(validate_motor("test", &config).is_ok());
+        let constraints = MechanicalConstraints::from_config(&config);
+        assert!((constraints.initial_velocity_steps_per_sec - 20.0).abs() < 1e-3);
+
+        config.initial_velocity = Some(DegreesPerSec(400.0));
+        assert_eq!(
+            validate_motor("test", &config),
+            Err(Error::Config(ConfigError::InvalidInitialVelocity { velocity: 400.0, max: 360.0 }))
+        );
+        config.initial_velocity = Some(DegreesPerSec(0.0));
+        assert!(validate_motor("test", &config).is_err());
+    }
+
+    #[test]
+    fn test_soft_start_validation() {
+        use crate::config::{MotorConfig, SoftStartConfig};
+
+        let mut config: MotorConfig = toml::from_str(
+            r#"
+name = "test"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+soft_start = { duration_ms = 100, accel_percent = 50 }
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.soft_start, Some(SoftStartConfig::new(100, 50)));
+        assert!(validate_motor("test", &config).is_ok());
+
+        config.soft_start = Some(SoftStartConfig::new(100, 0));
+        assert_eq!(
+            validate_motor("test", &config),
+            Err(Error::Config(ConfigError::InvalidSoftStart { duration_ms: 100, accel_percent: 0 }))
+        );
+    }
+
+    #[test]
+    fn test_torque_validation() {
+        use crate::config::MotorConfig;
+
+        let mut config: MotorConfig = toml::from_str(
+            r#"
+name = "feeder"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+rated_torque_nm = 0.45
+torque_speed_curve = [
+    [0.0, 1.0], [120.0, 0.9], [240.0, 0.6], [360.0, 0.4],
+    [360.0, 0.4], [360.0, 0.4], [360.0, 0.4], [360.0, 0.4],
+]
+"#,
+        )
+        .unwrap();
+        assert!(validate_motor("feeder", &config).is_ok());
+
+        let curve = config.torque_speed_curve.as_mut().unwrap();
+        curve[2] = (100.0, 0.6);
+        assert_eq!(
+            validate_motor("feeder", &config),
+            Err(Error::Config(ConfigError::InvalidTorqueCurve { point: 2 }))
+        );
+        let curve = config.torque_speed_curve.as_mut().unwrap();
+        curve[2] = (240.0, 1.5);
+        assert_eq!(
+            validate_motor("feeder", &config),
+            Err(Error::Config(ConfigError::InvalidTorqueCurve { point: 2 }))
+        );
+        config.torque_speed_curve.as_mut().unwrap()[0] = (f32::NAN, 1.0);
+        assert_eq!(
+            validate_motor("feeder", &config),
+            Err(Error::Config(ConfigError::InvalidTorqueCurve { point: 0 }))
+        );
+
+        config.torque_speed_curve = None;
+        config.rated_torque_nm = Some(0.0);
+        assert_eq!(
+            validate_motor("feeder", &config),
+            Err(Error::Config(ConfigError::InvalidRatedTorque(0.0)))
+        );
+    }
+
+    #[test]
+    fn test_gravity_assist_validation() {
+        use crate::config::{MechanicalConstraints, MotorConfig};
+        use crate::motion::Direction;
+
+        let mut config: MotorConfig = toml::from_str(
+            r#"
+name = "lift"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+gravity_assist_factor = 1.5
+gravity_direction = "clockwise"
+"#,
+        )
+        .unwrap();
+        assert!(validate_motor("lift", &config).is_ok());
+        let constraints = MechanicalConstraints::from_config(&config);
+        assert_eq!(constraints.gravity_assist, Some((1.5, Direction::Clockwise)));
+
+        config.gravity_assist_factor = Some(-1.0);
+        assert_eq!(
+            validate_motor("lift", &config),
+            Err(Error::Config(ConfigError::InvalidGravityAssistFactor(-1.0)))
+        );
+    }
+
+    #[test]
+    fn test_max_step_rate() {
+        use crate::config::{MechanicalConstraints, MotorConfig};
+
+        // 360°/s at 16 microsteps = 3200 steps/s
+        let mut config: MotorConfig = toml::from_str(
+            r#"
+name = "test"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+max_step_rate_hz = 3200
+"#,
+        )
+        .unwrap();
+        assert!(validate_motor("test", &config).is_ok());
+
+        config.max_step_rate_hz = Some(3000);
+        let rate_hz = MechanicalConstraints::from_config(&config).max_velocity_steps_per_sec;
+        assert_eq!(
+            validate_motor("test", &config),
+            Err(Error::Config(ConfigError::StepRateExceedsMax { rate_hz, max_hz: 3000 }))
+        );
+
+        // The cap overrides the interval only when more restrictive
+        assert_eq!(MechanicalConstraints::from_config(&config).min_step_interval_ns, 333_333);
+        config.max_step_rate_hz = Some(10_000);
+        let interval = MechanicalConstraints::from_config(&config).min_step_interval_ns;
+        assert!((312_499..=312_500).contains(&interval), "{}", interval);
+    }
+
+    #[test]
+    fn test_step_pulse_width() {
+        use crate::config::units::{DegreesPerSec, DegreesPerSecSquared, Microsteps};
+        use crate::config::MotorConfig;
+
+        // 3200 steps/s at max velocity = 312500 ns interval
+        let mut config = MotorConfig {
+            name: heapless::String::try_from("test").unwrap(),
+            steps_per_revolution: 200,
+            microsteps: Microsteps::SIXTEENTH,
+            gear_ratio: 1.0,
+            gear_ratio_num: None,
+            gear_ratio_den: None,
+            max_velocity: DegreesPerSec(360.0),
+            max_acceleration: DegreesPerSecSquared(720.0),
+            invert_direction: false,
+            output_inverted: false,
+            limits: None,
+            backlash_compensation: None,
+            backlash_compensation_steps: None,
+            step_pulse_ns: 100,
+            reversal_dwell_ms: 0,
+            delay_overhead_ns: 0,
+            clamp_on_startup: false,
+            winding_resistance_ohm: None,
+            peak_current_ma: None,
+            max_step_rate_hz: None,
+            park_trajectory: None,
+            thermal: None,
+            backlash_approach_direction: None,
+            gravity_assist_factor: None,
+            gravity_direction: None,
+            initial_velocity: None,
+            soft_start: None,
+            minimum_move_steps: None,
+            rated_torque_nm: None,
+            torque_speed_curve: None,
+            homing: None,
+        };
+        assert!(validate_motor("test", &config).is_ok());
+
+        config.step_pulse_ns = 99;
+        assert!(matches!(
+            validate_motor("test", &config),
+            Err(Error::Config(ConfigError::InvalidStepPulseWidth(99)))
+        ));
+
+        config.step_pulse_ns = 312_500;
+        assert!(matches!(
+            validate_motor("test", &config),
+            Err(Error::Config(ConfigError::StepPulseExceedsInterval { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_msequence_validation() {
+        let toml = r#"
+[motors.feeder]
+name = "Feeder"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[motors.table]
+name = "Table"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[motors.table.limits]
+min_degrees = 0.0
+max_degrees = 180.0
+
+[msequences.load_part]
+steps = [
+    { motor = "feeder", target_degrees = 30.0 },
+    { motor = "table", target_degrees = 90.0, dwell_ms = 200 },
+]
+"#;
+        let mut config: SystemConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.msequence("load_part").unwrap().steps.len(), 2);
+        assert!(validate_config(&config).is_ok());
+
+        let mseq = config.msequences.values_mut().next().unwrap();
+        mseq.steps[1].target_degrees = crate::config::units::Degrees(270.0);
+        assert!(matches!(
+            validate_config(&config),
+            Err(Error::Trajectory(TrajectoryError::TargetExceedsLimits { .. }))
+        ));
+
+        let mseq = config.msequences.values_mut().next().unwrap();
+        mseq.steps[0].motor = heapless::String::try_from("conveyor").unwrap();
+        assert!(matches!(
+            validate_config(&config),
+            Err(Error::Trajectory(TrajectoryError::MotorNotFound { .. }))
+        ));
+
+        let mseq = config.msequences.values_mut().next().unwrap();
+        mseq.steps.clear();
+        assert_eq!(
+            validate_config(&config),
+            Err(Error::Trajectory(TrajectoryError::EmptySequence("load_part".try_into().unwrap())))
+        );
+    }
+
+    #[test]
+    fn test_park_validation() {
+        let toml = r#"
+[motors.arm]
+name = "Arm"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+park_trajectory = "arm_park"
+
+[motors.wrist]
+name = "Wrist"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[trajectories.arm_park]
+motor = "arm"
+target_degrees = 10.0
+
+[trajectories.wrist_park]
+motor = "wrist"
+target_degrees = 0.0
+"#;
+        let mut config: SystemConfig = toml::from_str(toml).unwrap();
+        assert!(validate_config(&config).is_ok());
+        assert_eq!(config.motor("arm").unwrap().park(), Some("arm_park"));
+        assert_eq!(config.motor("wrist").unwrap().park(), None);
+
+        // Parking on another motor's trajectory
+        let arm = config.motors.values_mut().next().unwrap();
+        arm.park_trajectory = Some(heapless::String::try_from("wrist_park").unwrap());
+        assert!(matches!(
+            validate_config(&config),
+            Err(Error::Config(ConfigError::ParkTrajectoryWrongMotor { ref motor, ref trajectory }))
+                if motor == "arm" && trajectory == "wrist_park"
+        ));
+
+        // Parking on a trajectory that does not exist
+        let arm = config.motors.values_mut().next().unwrap();
+        arm.park_trajectory = Some(heapless::String::try_from("nowhere").unwrap());
+        assert!(matches!(
+            validate_config(&config),
+            Err(Error::Config(ConfigError::TrajectoryNotFound(ref n))) if n == "nowhere"
+        ));
+    }
+
+    #[test]
+    fn test_trajectory_precondition_validation() {
+        use crate::config::units::Degrees;
+        use crate::error::Precondition;
+
+        let toml = r#"
+[motors.arm]
+name = "Arm"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[trajectories.approach]
+motor = "arm"
+target_degrees = 30.0
+
+[trajectories.insert]
+motor = "arm"
+target_degrees = 45.0
+requires_previous = "approach"
+requires_position_deg = 30.05
+"#;
+        let mut config: SystemConfig = toml::from_str(toml).unwrap();
+        assert!(validate_config(&config).is_ok());
+
+        // Previous trajectory ends outside the required start position
+        config.trajectories.values_mut().next().unwrap().target_degrees = Degrees(40.0);
+        assert!(matches!(
+            validate_config(&config),
+            Err(Error::Trajectory(TrajectoryError::PreconditionFailed {
+                expected: Precondition::Position(_),
+                actual: Precondition::Position(actual),
+            })) if actual == 40.0
+        ));
+
+        let insert = config.trajectories.values_mut().nth(1).unwrap();
+        insert.requires_previous = Some(heapless::String::try_from("retract").unwrap());
+        assert!(matches!(
+            validate_config(&config),
+            Err(Error::Config(ConfigError::TrajectoryNotFound(ref n))) if n == "retract"
+        ));
+    }
+
+    #[test]
+    fn test_trajectory_distance_and_rate_validation() {
+        use crate::config::units::Degrees;
+        use crate::error::MotionError;
+
+        let toml = r#"
+[motors.arm]
+name = "Arm"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[trajectories.nudge]
+motor = "arm"
+target_degrees = 30.2
+requires_position_deg = 30.0
+tolerance_deg = 0.01
+"#;
+        let mut config: SystemConfig = toml::from_str(toml).unwrap();
+        assert!(validate_config(&config).is_ok());
+
+        // 0.02° is below one step (0.1125°) from the required start
+        let nudge = config.trajectories.values_mut().next().unwrap();
+        nudge.target_degrees = Degrees(30.02);
+        assert_eq!(
+            validate_config(&config),
+            Err(Error::Motion(MotionError::MoveTooShort { steps: 0, minimum: 1 }))
+        );
+
+        // Cruising above max velocity undercuts the step interval floor
+        let nudge = config.trajectories.values_mut().next().unwrap();
+        nudge.target_degrees = Degrees(90.0);
+        nudge.velocity_percent = 150;
+        assert!(matches!(
+            validate_config(&config),
+            Err(Error::Motion(MotionError::VelocityExceedsLimit { requested, max }))
+                if requested == 540.0 && (max - 360.0).abs() < 0.1
+        ));
+    }
+
+    fn gantry_config(z3_velocity: f32, members: &str) -> SystemConfig {
+        let toml = format!(
+            r#"
+[motors.z1]
+name = "Z1"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[motors.z2]
+name = "Z2"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[motors.z3]
+name = "Z3"
+steps_per_revolution = 200
+microsteps = 16
+max_velocity_deg_per_sec = {}
+max_acceleration_deg_per_sec2 = 720.0
+
+[groups.z_gantry]
+motors = [{}]
+enforce = ["max_velocity", "max_acceleration", "microsteps", "steps_per_revolution"]
+"#,
+            z3_velocity, members
+        );
+        toml::from_str(&toml).unwrap()
+    }
+
+    #[test]
+    fn test_group_validation() {
+        let config = gantry_config(360.0, r#""z1", "z2", "z3""#);
+        assert!(validate_config(&config).is_ok());
+        assert_eq!(config.group("z_gantry").unwrap().enforce.len(), 4);
+        assert_eq!(
+            config.groups_for_motor("z2").collect::<std::vec::Vec<_>>(),
+            ["z_gantry"]
+        );
+        assert_eq!(config.groups_for_motor("x").count(), 0);
+
+        let config = gantry_config(1440.0, r#""z1", "z2", "z3""#);
+        let err = validate_config(&config).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Config(ConfigError::GroupMismatch { field: "max_velocity", .. })
+        ));
+        assert_eq!(
+            std::format!("{}", err),
+            "Configuration error: Group 'z_gantry': max_velocity differs between 'z1' (360) and 'z3' (1440)"
+        );
+
+        let config = gantry_config(360.0, r#""z1", "z4""#);
+        assert!(matches!(
+            validate_config(&config),
+            Err(Error::Config(ConfigError::MotorNotFound(ref m))) if m == "z4"
+        ));
     }
 }
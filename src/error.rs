@@ -1,6 +1,85 @@
 //! Error types for stepper-motion library.
 //!
 //! Provides unified error handling across configuration, motor control, and motion execution.
+//!
+//! ## Error codes
+//!
+//! Every variant has a stable numeric code from [`Error::code`] for reporting
+//! over binary host protocols. The hundreds digit is the [`ErrorCategory`];
+//! codes are never reused or renumbered, new variants get the next free code.
+//!
+//! | Code | Variant |
+//! |------|---------|
+//! | 100 | `ConfigError::ParseError` |
+//! | 101 | `ConfigError::InvalidMicrosteps` |
+//! | 102 | `ConfigError::MotorNotFound` |
+//! | 103 | `ConfigError::TrajectoryNotFound` |
+//! | 104 | `ConfigError::DuplicateMotorName` |
+//! | 105 | `ConfigError::DuplicateTrajectoryName` |
+//! | 106 | `ConfigError::InvalidVelocityPercent` |
+//! | 107 | `ConfigError::InvalidAccelerationPercent` |
+//! | 108 | `ConfigError::InvalidGearRatio` |
+//! | 109 | `ConfigError::InvalidMaxVelocity` |
+//! | 110 | `ConfigError::InvalidMaxAcceleration` |
+//! | 111 | `ConfigError::InvalidSoftLimits` |
+//! | 112 | `ConfigError::InvalidStepPulseWidth` |
+//! | 113 | `ConfigError::StepPulseExceedsInterval` |
+//! | 114 | `ConfigError::StepRateUnachievable` |
+//! | 115 | `ConfigError::GroupMismatch` |
+//! | 116 | `ConfigError::InvalidLimitMargin` |
+//! | 117 | `ConfigError::StepRateExceedsMax` |
+//! | 118 | `ConfigError::InvalidThermalConfig` |
+//! | 119 | `ConfigError::InvalidGravityAssistFactor` |
+//! | 120 | `ConfigError::InvalidSoftStart` |
+//! | 121 | `ConfigError::PresetNotFound` |
+//! | 122 | `ConfigError::InvalidInitialVelocity` |
+//! | 123 | `ConfigError::InvalidName` |
+//! | 124 | `ConfigError::InvalidGearRatioFraction` |
+//! | 125 | `ConfigError::InvalidRatedTorque` |
+//! | 126 | `ConfigError::InvalidTorqueCurve` |
+//! | 127 | `ConfigError::InvalidClampSlowZone` |
+//! | 128 | `ConfigError::InvalidHomingConfig` |
+//! | 129 | `ConfigError::ConflictingBacklashConfig` |
+//! | 130 | `ConfigError::NonFiniteValue` |
+//! | 131 | `ConfigError::UnsafeReload` |
+//! | 132 | `ConfigError::ParkTrajectoryWrongMotor` |
+//! | 199 | `ConfigError::IoError` (std only) |
+//! | 200 | `MotorError::PinError` |
+//! | 201 | `MotorError::InvalidState` |
+//! | 202 | `MotorError::NotInitialized` |
+//! | 203 | `MotorError::LimitExceeded` |
+//! | 204 | `MotorError::NoHomeSwitch` |
+//! | 205 | `MotorError::HomeSwitchNotFound` |
+//! | 206 | `MotorError::NoSoftLimits` |
+//! | 207 | `MotorError::ThermalBudgetExceeded` |
+//! | 208 | `MotorError::InvalidTelemetry` |
+//! | 209 | `MotorError::InvalidSnapshot` |
+//! | 210 | `MotorError::SnapshotTooLarge` |
+//! | 211 | `MotorError::NotRegistered` |
+//! | 212 | `MotorError::StaleConstraints` |
+//! | 300 | `MotionError::VelocityExceedsLimit` |
+//! | 301 | `MotionError::AccelerationExceedsLimit` |
+//! | 302 | `MotionError::MoveTooShort` |
+//! | 303 | `MotionError::Overflow` |
+//! | 304 | `MotionError::SeekOutOfRange` |
+//! | 305 | `MotionError::InvalidRate` |
+//! | 306 | `MotionError::InsufficientTorque` |
+//! | 307 | `MotionError::NonFiniteInput` |
+//! | 400 | `TrajectoryError::MotorNotFound` |
+//! | 401 | `TrajectoryError::TargetExceedsLimits` |
+//! | 402 | `TrajectoryError::EmptyWaypoints` |
+//! | 403 | `TrajectoryError::TooManyWaypoints` |
+//! | 404 | `TrajectoryError::InvalidName` |
+//! | 405 | `TrajectoryError::Empty` |
+//! | 406 | `TrajectoryError::Duplicate` |
+//! | 407 | `TrajectoryError::NameTooLong` |
+//! | 408 | `TrajectoryError::RegistryFull` |
+//! | 409 | `TrajectoryError::NotFound` |
+//! | 410 | `TrajectoryError::PreconditionFailed` |
+//! | 411 | `TrajectoryError::WaypointIndexOutOfRange` |
+//! | 412 | `TrajectoryError::InvalidFinalApproach` |
+//! | 413 | `TrajectoryError::EmptySequence` |
+//! | 414 | `TrajectoryError::SequenceTooLong` |
 
 use core::fmt;
 
@@ -9,6 +88,7 @@ pub type Result<T> = core::result::Result<T, Error>;
 
 /// Unified error type for all stepper-motion operations.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
     /// Configuration parsing or validation error
     Config(ConfigError),
@@ -20,8 +100,44 @@ pub enum Error {
     Trajectory(TrajectoryError),
 }
 
+/// Rule broken by a trajectory or sequence name.
+///
+/// See [`is_valid_name`](crate::config::is_valid_name).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameError {
+    /// Name is empty
+    Empty,
+    /// Name is longer than 32 bytes
+    TooLong {
+        /// Actual length in bytes
+        len: usize,
+    },
+    /// Name contains a character outside the allowed set
+    InvalidCharacter {
+        /// The offending character
+        character: char,
+        /// Its byte offset in the name
+        index: usize,
+    },
+}
+
+impl fmt::Display for NameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NameError::Empty => write!(f, "name is empty"),
+            NameError::TooLong { len } => write!(f, "name is {} bytes long (max 32)", len),
+            NameError::InvalidCharacter { character, index } => write!(
+                f,
+                "character {:?} at byte {} is not allowed (use letters, digits, '_' or '-')",
+                character, index
+            ),
+        }
+    }
+}
+
 /// Configuration-related errors.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum ConfigError {
     /// Failed to parse TOML configuration
     ParseError(heapless::String<128>),
@@ -52,6 +168,149 @@ pub enum ConfigError {
         /// Maximum limit value
         max: f32,
     },
+    /// STEP pulse width below the minimum a driver can register
+    InvalidStepPulseWidth(u32),
+    /// STEP pulse width does not fit inside the step interval at max velocity
+    StepPulseExceedsInterval {
+        /// Configured pulse width in nanoseconds
+        pulse_ns: u32,
+        /// Minimum step interval in nanoseconds
+        interval_ns: u32,
+    },
+    /// Maximum step rate is faster than the step loop can sustain
+    StepRateUnachievable {
+        /// Minimum step interval required by the max velocity, in nanoseconds
+        required_ns: u32,
+        /// Per-step timing budget, in nanoseconds
+        budget_ns: u32,
+    },
+    /// Members of a motor group disagree on an enforced field
+    GroupMismatch {
+        /// Group name
+        group: heapless::String<32>,
+        /// Enforced field that differs
+        field: &'static str,
+        /// First member of the group (reference value)
+        expected_motor: heapless::String<32>,
+        /// Value on the first member
+        expected: f32,
+        /// Member whose value differs
+        motor: heapless::String<32>,
+        /// Value on the differing member
+        found: f32,
+    },
+    /// Soft limit margin is negative or wider than half the limit span
+    InvalidLimitMargin {
+        /// Configured margin in degrees
+        margin: f32,
+        /// Distance between the limits in degrees
+        span: f32,
+    },
+    /// Max velocity needs a faster step rate than `max_step_rate_hz` allows
+    StepRateExceedsMax {
+        /// Step rate at max velocity in steps/sec
+        rate_hz: f32,
+        /// Configured step rate cap in steps/sec
+        max_hz: u32,
+    },
+    /// Thermal budget needs a positive time constant and a duty limit in (0, 1]
+    InvalidThermalConfig {
+        /// Configured time constant in seconds
+        time_constant_s: f32,
+        /// Configured duty limit
+        max_duty: f32,
+    },
+    /// Gravity assist factor must be positive and finite
+    InvalidGravityAssistFactor(f32),
+    /// Soft start needs a non-empty window and an acceleration of 1-100%
+    InvalidSoftStart {
+        /// Configured window in milliseconds
+        duration_ms: u32,
+        /// Configured acceleration percentage
+        accel_percent: u8,
+    },
+    /// A trajectory refers to a preset that isn't defined
+    PresetNotFound {
+        /// Requested preset name
+        name: heapless::String<32>,
+        /// Defined preset names, comma separated (truncated if long)
+        available: heapless::String<128>,
+    },
+    /// Initial velocity must be positive and no faster than max velocity
+    InvalidInitialVelocity {
+        /// Configured initial velocity in degrees/sec
+        velocity: f32,
+        /// Configured max velocity in degrees/sec
+        max: f32,
+    },
+    /// Trajectory or sequence name breaks the naming rules
+    InvalidName {
+        /// The name, truncated to 32 bytes
+        name: heapless::String<32>,
+        /// Rule it breaks
+        error: NameError,
+    },
+    /// Rational gear ratio with a missing or zero part (missing parts are
+    /// reported as 0)
+    InvalidGearRatioFraction {
+        /// Configured numerator
+        numerator: u32,
+        /// Configured denominator
+        denominator: u32,
+    },
+    /// Rated torque must be positive and finite
+    InvalidRatedTorque(f32),
+    /// Torque-speed curve point with a negative velocity, a velocity below
+    /// the previous point's, or a torque fraction outside 0-1
+    InvalidTorqueCurve {
+        /// Index of the first invalid point
+        point: usize,
+    },
+    /// Clamp slow zone is negative or wider than the limit span
+    InvalidClampSlowZone {
+        /// Configured zone in degrees
+        zone: f32,
+        /// Limit span in degrees
+        span: f32,
+    },
+    /// Homing needs a velocity of 1-100% and a positive timeout travel with
+    /// a positive backoff shorter than it
+    InvalidHomingConfig {
+        /// Configured velocity percentage
+        velocity_percent: u8,
+        /// Configured backoff in degrees
+        backoff: f32,
+        /// Configured timeout travel in degrees
+        timeout: f32,
+    },
+    /// Backlash compensation given both in degrees and in steps
+    ConflictingBacklashConfig {
+        /// Configured backlash in degrees
+        degrees: f32,
+        /// Configured backlash in steps
+        steps: i64,
+    },
+    /// Float field set to NaN or infinity
+    NonFiniteValue {
+        /// TOML key of the field
+        field: &'static str,
+        /// Configured value
+        value: f32,
+    },
+    /// Live reload would change a motor's step resolution
+    UnsafeReload {
+        /// Motor being reloaded
+        motor: heapless::String<32>,
+        /// TOML key of the changed field
+        field: &'static str,
+    },
+    /// Park trajectory exists but moves a different motor
+    ParkTrajectoryWrongMotor {
+        /// Motor whose `park_trajectory` names the trajectory
+        motor: heapless::String<32>,
+        /// Park trajectory name
+        trajectory: heapless::String<32>,
+    },
     /// File I/O error (std only)
     #[cfg(feature = "std")]
     IoError(heapless::String<128>),
@@ -59,6 +318,7 @@ pub enum ConfigError {
 
 /// Motor operation errors.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum MotorError {
     /// Pin operation failed
     PinError,
@@ -73,10 +333,50 @@ pub enum MotorError {
         /// Limit that was exceeded (min or max)
         limit: i64,
     },
+    /// Homing requested but no home switch is configured
+    NoHomeSwitch,
+    /// Home switch did not trigger within the maximum homing travel
+    HomeSwitchNotFound {
+        /// Steps travelled before giving up
+        travel_steps: u32,
+    },
+    /// Operation needs soft limits but none are configured
+    NoSoftLimits,
+    /// Move refused because the motor's duty is above its thermal budget
+    ThermalBudgetExceeded {
+        /// Current duty (0.0 to 1.0)
+        duty: f32,
+        /// Configured duty limit
+        max_duty: f32,
+    },
+    /// Telemetry frame has an out-of-range field
+    InvalidTelemetry {
+        /// Byte offset of the field in the frame
+        offset: u8,
+        /// Value found there
+        value: u8,
+    },
+    /// Saved snapshot fails its CRC or has an unknown layout
+    InvalidSnapshot,
+    /// Snapshot does not fit its fixed-size encoding
+    SnapshotTooLarge {
+        /// Bytes the names and positions would need
+        bytes: u32,
+    },
+    /// Trajectory or sequence uses a configured motor that is not registered
+    NotRegistered {
+        /// Trajectory or sequence name
+        trajectory: heapless::String<32>,
+        /// Motor name
+        motor: heapless::String<32>,
+    },
+    /// Registered constraints no longer match the motor's configuration
+    StaleConstraints(heapless::String<32>),
 }
 
 /// Motion profile and execution errors.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum MotionError {
     /// Requested velocity exceeds motor's maximum
     VelocityExceedsLimit {
@@ -101,10 +401,41 @@ pub enum MotionError {
     },
     /// Motion profile computation overflow
     Overflow,
+    /// Seek target past the end of the profile
+    SeekOutOfRange {
+        /// Requested step
+        step: u32,
+        /// Total steps in the profile
+        total: u32,
+    },
+    /// Profile velocity or rate below its minimum
+    InvalidRate {
+        /// Builder parameter name
+        parameter: &'static str,
+        /// Requested value
+        value: f32,
+        /// Smallest accepted value
+        minimum: f32,
+    },
+    /// Load torque exceeds the motor's torque at the trajectory's velocity
+    InsufficientTorque {
+        /// Torque available at cruise velocity in N·m
+        available: f32,
+        /// Load torque in N·m
+        required: f32,
+    },
+    /// Profile input is NaN or infinite
+    NonFiniteInput {
+        /// Constructor parameter name
+        parameter: &'static str,
+        /// Given value
+        value: f32,
+    },
 }
 
 /// Trajectory-related errors.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum TrajectoryError {
     /// Trajectory references non-existent motor
     MotorNotFound {
@@ -130,6 +461,213 @@ pub enum TrajectoryError {
     InvalidName(heapless::String<64>),
     /// Empty trajectory (no waypoints or target)
     Empty,
+    /// A trajectory with this name is already registered
+    Duplicate(heapless::String<32>),
+    /// Trajectory name longer than the registry's 32-byte key
+    NameTooLong(usize),
+    /// Registry has no free slots
+    RegistryFull,
+    /// No trajectory registered under this name
+    NotFound(heapless::String<32>),
+    /// The motor is not in the state a trajectory requires before starting
+    PreconditionFailed {
+        /// Required state
+        expected: Precondition,
+        /// Actual state
+        actual: Precondition,
+    },
+    /// Waypoint insertion index past the end of the list
+    WaypointIndexOutOfRange {
+        /// Requested index
+        index: usize,
+        /// Number of waypoints
+        len: usize,
+    },
+    /// Final approach longer than the final segment or with a creep velocity
+    /// the sequence cannot use
+    InvalidFinalApproach {
+        /// Creep distance in degrees
+        distance: f32,
+        /// Creep velocity percent
+        velocity_percent: u8,
+    },
+    /// Multi-motor sequence without any steps
+    EmptySequence(heapless::String<32>),
+    /// More trajectories chained than a sequence holds (the maximum)
+    SequenceTooLong(usize),
+}
+
+/// A trajectory starting condition, as reported by
+/// [`TrajectoryError::PreconditionFailed`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Precondition {
+    /// Motor position in degrees
+    Position(f32),
+    /// Last trajectory executed on the motor (`None` if none, or if a move
+    /// ran outside `execute()` since)
+    Previous(Option<heapless::String<32>>),
+}
+
+impl fmt::Display for Precondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Precondition::Position(degrees) => write!(f, "position {}°", degrees),
+            Precondition::Previous(Some(name)) => write!(f, "after '{}'", name),
+            Precondition::Previous(None) => write!(f, "no previous trajectory"),
+        }
+    }
+}
+
+/// Error category, encoded as the hundreds digit of [`Error::code`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ErrorCategory {
+    /// [`ConfigError`]
+    Config = 1,
+    /// [`MotorError`]
+    Motor = 2,
+    /// [`MotionError`]
+    Motion = 3,
+    /// [`TrajectoryError`]
+    Trajectory = 4,
+}
+
+impl ErrorCategory {
+    /// Decode the category of an error code (host side).
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code / 100 {
+            1 => Some(ErrorCategory::Config),
+            2 => Some(ErrorCategory::Motor),
+            3 => Some(ErrorCategory::Motion),
+            4 => Some(ErrorCategory::Trajectory),
+            _ => None,
+        }
+    }
+}
+
+impl Error {
+    /// Stable numeric code for this error (see the [module docs](self)).
+    pub fn code(&self) -> u16 {
+        match self {
+            Error::Config(e) => e.code(),
+            Error::Motor(e) => e.code(),
+            Error::Motion(e) => e.code(),
+            Error::Trajectory(e) => e.code(),
+        }
+    }
+
+    /// Category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Error::Config(_) => ErrorCategory::Config,
+            Error::Motor(_) => ErrorCategory::Motor,
+            Error::Motion(_) => ErrorCategory::Motion,
+            Error::Trajectory(_) => ErrorCategory::Trajectory,
+        }
+    }
+}
+
+impl ConfigError {
+    /// Stable numeric code (100-199).
+    pub fn code(&self) -> u16 {
+        match self {
+            ConfigError::ParseError(_) => 100,
+            ConfigError::InvalidMicrosteps(_) => 101,
+            ConfigError::MotorNotFound(_) => 102,
+            ConfigError::TrajectoryNotFound(_) => 103,
+            ConfigError::DuplicateMotorName(_) => 104,
+            ConfigError::DuplicateTrajectoryName(_) => 105,
+            ConfigError::InvalidVelocityPercent(_) => 106,
+            ConfigError::InvalidAccelerationPercent(_) => 107,
+            ConfigError::InvalidGearRatio(_) => 108,
+            ConfigError::InvalidMaxVelocity(_) => 109,
+            ConfigError::InvalidMaxAcceleration(_) => 110,
+            ConfigError::InvalidSoftLimits { .. } => 111,
+            ConfigError::InvalidStepPulseWidth(_) => 112,
+            ConfigError::StepPulseExceedsInterval { .. } => 113,
+            ConfigError::StepRateUnachievable { .. } => 114,
+            ConfigError::GroupMismatch { .. } => 115,
+            ConfigError::InvalidLimitMargin { .. } => 116,
+            ConfigError::StepRateExceedsMax { .. } => 117,
+            ConfigError::InvalidThermalConfig { .. } => 118,
+            ConfigError::InvalidGravityAssistFactor(_) => 119,
+            ConfigError::InvalidSoftStart { .. } => 120,
+            ConfigError::PresetNotFound { .. } => 121,
+            ConfigError::InvalidInitialVelocity { .. } => 122,
+            ConfigError::InvalidName { .. } => 123,
+            ConfigError::InvalidGearRatioFraction { .. } => 124,
+            ConfigError::InvalidRatedTorque(_) => 125,
+            ConfigError::InvalidTorqueCurve { .. } => 126,
+            ConfigError::InvalidClampSlowZone { .. } => 127,
+            ConfigError::InvalidHomingConfig { .. } => 128,
+            ConfigError::ConflictingBacklashConfig { .. } => 129,
+            ConfigError::NonFiniteValue { .. } => 130,
+            ConfigError::UnsafeReload { .. } => 131,
+            ConfigError::ParkTrajectoryWrongMotor { .. } => 132,
+            #[cfg(feature = "std")]
+            ConfigError::IoError(_) => 199,
+        }
+    }
+}
+
+impl MotorError {
+    /// Stable numeric code (200-299).
+    pub fn code(&self) -> u16 {
+        match self {
+            MotorError::PinError => 200,
+            MotorError::InvalidState(_) => 201,
+            MotorError::NotInitialized => 202,
+            MotorError::LimitExceeded { .. } => 203,
+            MotorError::NoHomeSwitch => 204,
+            MotorError::HomeSwitchNotFound { .. } => 205,
+            MotorError::NoSoftLimits => 206,
+            MotorError::ThermalBudgetExceeded { .. } => 207,
+            MotorError::InvalidTelemetry { .. } => 208,
+            MotorError::InvalidSnapshot => 209,
+            MotorError::SnapshotTooLarge { .. } => 210,
+            MotorError::NotRegistered { .. } => 211,
+            MotorError::StaleConstraints(_) => 212,
+        }
+    }
+}
+
+impl MotionError {
+    /// Stable numeric code (300-399).
+    pub fn code(&self) -> u16 {
+        match self {
+            MotionError::VelocityExceedsLimit { .. } => 300,
+            MotionError::AccelerationExceedsLimit { .. } => 301,
+            MotionError::MoveTooShort { .. } => 302,
+            MotionError::Overflow => 303,
+            MotionError::SeekOutOfRange { .. } => 304,
+            MotionError::InvalidRate { .. } => 305,
+            MotionError::InsufficientTorque { .. } => 306,
+            MotionError::NonFiniteInput { .. } => 307,
+        }
+    }
+}
+
+impl TrajectoryError {
+    /// Stable numeric code (400-499).
+    pub fn code(&self) -> u16 {
+        match self {
+            TrajectoryError::MotorNotFound { .. } => 400,
+            TrajectoryError::TargetExceedsLimits { .. } => 401,
+            TrajectoryError::EmptyWaypoints => 402,
+            TrajectoryError::TooManyWaypoints => 403,
+            TrajectoryError::InvalidName(_) => 404,
+            TrajectoryError::Empty => 405,
+            TrajectoryError::Duplicate(_) => 406,
+            TrajectoryError::NameTooLong(_) => 407,
+            TrajectoryError::RegistryFull => 408,
+            TrajectoryError::NotFound(_) => 409,
+            TrajectoryError::PreconditionFailed { .. } => 410,
+            TrajectoryError::WaypointIndexOutOfRange { .. } => 411,
+            TrajectoryError::InvalidFinalApproach { .. } => 412,
+            TrajectoryError::EmptySequence(_) => 413,
+            TrajectoryError::SequenceTooLong(_) => 414,
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -162,6 +700,98 @@ impl fmt::Display for ConfigError {
             ConfigError::InvalidSoftLimits { min, max } => {
                 write!(f, "Invalid soft limits: min ({}) must be < max ({})", min, max)
             }
+            ConfigError::InvalidStepPulseWidth(v) => {
+                write!(f, "Invalid step pulse width: {} ns. Must be >= 100 ns", v)
+            }
+            ConfigError::StepPulseExceedsInterval { pulse_ns, interval_ns } => write!(
+                f,
+                "Step pulse width ({} ns) must be shorter than the minimum step interval ({} ns)",
+                pulse_ns, interval_ns
+            ),
+            ConfigError::StepRateUnachievable { required_ns, budget_ns } => write!(
+                f,
+                "Step interval of {} ns at max velocity is below the timing budget of {} ns",
+                required_ns, budget_ns
+            ),
+            ConfigError::GroupMismatch { group, field, expected_motor, expected, motor, found } => write!(
+                f,
+                "Group '{}': {} differs between '{}' ({}) and '{}' ({})",
+                group, field, expected_motor, expected, motor, found
+            ),
+            ConfigError::InvalidLimitMargin { margin, span } => write!(
+                f,
+                "Limit margin {}° must be between 0 and half the limit span ({}°)",
+                margin, span
+            ),
+            ConfigError::StepRateExceedsMax { rate_hz, max_hz } => write!(
+                f,
+                "Step rate {} Hz at max velocity exceeds max_step_rate_hz {}",
+                rate_hz, max_hz
+            ),
+            ConfigError::InvalidThermalConfig { time_constant_s, max_duty } => write!(
+                f,
+                "Invalid thermal budget: time constant {} s, max duty {}",
+                time_constant_s, max_duty
+            ),
+            ConfigError::InvalidGravityAssistFactor(factor) => {
+                write!(f, "Invalid gravity assist factor: {} (must be positive)", factor)
+            }
+            ConfigError::InvalidSoftStart { duration_ms, accel_percent } => write!(
+                f,
+                "Invalid soft start: {} ms at {}% acceleration",
+                duration_ms, accel_percent
+            ),
+            ConfigError::PresetNotFound { name, available } => {
+                write!(f, "Preset '{}' not found (available: {})", name, available)
+            }
+            ConfigError::InvalidInitialVelocity { velocity, max } => write!(
+                f,
+                "Invalid initial velocity {}°/s (must be positive and at most {}°/s)",
+                velocity, max
+            ),
+            ConfigError::InvalidName { name, error } => {
+                write!(f, "Invalid name '{}': {}", name, error)
+            }
+            ConfigError::InvalidGearRatioFraction { numerator, denominator } => write!(
+                f,
+                "Invalid gear ratio {}/{}: numerator and denominator must both be set and > 0",
+                numerator, denominator
+            ),
+            ConfigError::InvalidRatedTorque(torque) => {
+                write!(f, "Invalid rated torque: {} N·m (must be positive)", torque)
+            }
+            ConfigError::InvalidTorqueCurve { point } => {
+                write!(f, "Invalid torque-speed curve at point {}", point)
+            }
+            ConfigError::InvalidClampSlowZone { zone, span } => write!(
+                f,
+                "Clamp slow zone {}° must be between 0 and the limit span ({}°)",
+                zone, span
+            ),
+            ConfigError::InvalidHomingConfig { velocity_percent, backoff, timeout } => write!(
+                f,
+                "Invalid homing config: velocity {}% must be 1-100, backoff {}° must be \
+                 positive and below the timeout travel ({}°)",
+                velocity_percent, backoff, timeout
+            ),
+            ConfigError::ConflictingBacklashConfig { degrees, steps } => write!(
+                f,
+                "Backlash compensation given as both {}° and {} steps; set only one",
+                degrees, steps
+            ),
+            ConfigError::NonFiniteValue { field, value } => {
+                write!(f, "{} must be a finite number, got {}", field, value)
+            }
+            ConfigError::UnsafeReload { motor, field } => write!(
+                f,
+                "Cannot reload motor '{}' live: {} changed, which invalidates its position",
+                motor, field
+            ),
+            ConfigError::ParkTrajectoryWrongMotor { motor, trajectory } => write!(
+                f,
+                "Park trajectory '{}' of motor '{}' moves a different motor",
+                trajectory, motor
+            ),
             #[cfg(feature = "std")]
             ConfigError::IoError(msg) => write!(f, "I/O error: {}", msg),
         }
@@ -177,6 +807,29 @@ impl fmt::Display for MotorError {
             MotorError::LimitExceeded { position, limit } => {
                 write!(f, "Position {} exceeds limit {}", position, limit)
             }
+            MotorError::NoHomeSwitch => write!(f, "No home switch configured"),
+            MotorError::HomeSwitchNotFound { travel_steps } => {
+                write!(f, "Home switch not found within {} steps", travel_steps)
+            }
+            MotorError::NoSoftLimits => write!(f, "No soft limits configured"),
+            MotorError::ThermalBudgetExceeded { duty, max_duty } => {
+                write!(f, "Duty {:.2} exceeds thermal budget {:.2}", duty, max_duty)
+            }
+            MotorError::InvalidTelemetry { offset, value } => {
+                write!(f, "Invalid telemetry byte {} at offset {}", value, offset)
+            }
+            MotorError::InvalidSnapshot => write!(f, "Snapshot is corrupted or unsupported"),
+            MotorError::SnapshotTooLarge { bytes } => {
+                write!(f, "Snapshot needs {} bytes, more than its encoding holds", bytes)
+            }
+            MotorError::NotRegistered { trajectory, motor } => {
+                write!(f, "'{}' uses motor '{}', which is not registered", trajectory, motor)
+            }
+            MotorError::StaleConstraints(motor) => write!(
+                f,
+                "Motor '{}' was registered with constraints that no longer match its config",
+                motor
+            ),
         }
     }
 }
@@ -194,6 +847,20 @@ impl fmt::Display for MotionError {
                 write!(f, "Move of {} steps too short, minimum is {}", steps, minimum)
             }
             MotionError::Overflow => write!(f, "Motion profile computation overflow"),
+            MotionError::SeekOutOfRange { step, total } => {
+                write!(f, "Cannot seek to step {} of a {}-step profile", step, total)
+            }
+            MotionError::InvalidRate { parameter, value, minimum } => {
+                write!(f, "{} of {} is below the minimum of {}", parameter, value, minimum)
+            }
+            MotionError::InsufficientTorque { available, required } => write!(
+                f,
+                "Load torque {} N·m exceeds the {} N·m available at cruise velocity",
+                required, available
+            ),
+            MotionError::NonFiniteInput { parameter, value } => {
+                write!(f, "{} must be a finite number, got {}", parameter, value)
+            }
         }
     }
 }
@@ -215,10 +882,114 @@ impl fmt::Display for TrajectoryError {
                 write!(f, "Invalid trajectory name or configuration: {}", name)
             }
             TrajectoryError::Empty => write!(f, "Trajectory is empty (no waypoints or target)"),
+            TrajectoryError::Duplicate(name) => {
+                write!(f, "Trajectory '{}' is already registered", name)
+            }
+            TrajectoryError::NameTooLong(len) => {
+                write!(f, "Trajectory name is {} bytes long (max 32)", len)
+            }
+            TrajectoryError::RegistryFull => write!(f, "Trajectory registry is full"),
+            TrajectoryError::NotFound(name) => write!(f, "Trajectory '{}' not found", name),
+            TrajectoryError::PreconditionFailed { expected, actual } => {
+                write!(f, "Trajectory precondition failed: expected {}, found {}", expected, actual)
+            }
+            TrajectoryError::WaypointIndexOutOfRange { index, len } => {
+                write!(f, "Waypoint index {} out of range (have {} waypoints)", index, len)
+            }
+            TrajectoryError::InvalidFinalApproach { distance, velocity_percent } => write!(
+                f,
+                "Final approach of {}° at {}% does not fit the sequence",
+                distance, velocity_percent
+            ),
+            TrajectoryError::EmptySequence(name) => {
+                write!(f, "Multi-motor sequence '{}' has no steps", name)
+            }
+            TrajectoryError::SequenceTooLong(max) => {
+                write!(f, "Too many trajectories in sequence (max {})", max)
+            }
+        }
+    }
+}
+
+/// Maximum number of errors kept in a [`ValidationReport`].
+pub const MAX_REPORTED_ERRORS: usize = 8;
+
+/// Every problem found by a validation pass, instead of just the first.
+///
+/// Errors beyond [`MAX_REPORTED_ERRORS`] are counted but not stored.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ValidationReport {
+    errors: heapless::Vec<Error, MAX_REPORTED_ERRORS>,
+    omitted: usize,
+}
+
+impl ValidationReport {
+    /// Create an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a problem.
+    pub fn push(&mut self, error: Error) {
+        if self.errors.push(error).is_err() {
+            self.omitted += 1;
+        }
+    }
+
+    /// Check if no problems were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Get the total number of problems, including omitted ones.
+    pub fn len(&self) -> usize {
+        self.errors.len() + self.omitted
+    }
+
+    /// Get the number of problems that did not fit in the report.
+    pub fn omitted(&self) -> usize {
+        self.omitted
+    }
+
+    /// Get the stored errors in the order they were found.
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// Get the first problem found.
+    pub fn first(&self) -> Option<&Error> {
+        self.errors.first()
+    }
+
+    /// Consume the report, returning the first problem found.
+    pub fn into_first(self) -> Option<Error> {
+        self.errors.into_iter().next()
+    }
+
+    /// `Ok(())` if the report is empty, otherwise `Err(self)`.
+    pub fn into_result(self) -> core::result::Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
         }
     }
 }
 
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} problem(s)", self.len())?;
+        for (i, error) in self.errors.iter().enumerate() {
+            let sep = if i == 0 { ": " } else { "; " };
+            write!(f, "{}{}", sep, error)?;
+        }
+        if self.omitted > 0 {
+            write!(f, "; and {} more", self.omitted)?;
+        }
+        Ok(())
+    }
+}
+
 // Conversion impls
 impl From<ConfigError> for Error {
     fn from(e: ConfigError) -> Self {
@@ -244,17 +1015,208 @@ impl From<TrajectoryError> for Error {
     }
 }
 
-#[cfg(feature = "std")]
-impl std::error::Error for Error {}
+// `core::error::Error` needs Rust 1.81, newer than the MSRV, so it is opt-in.
+// `std::error::Error` is the same trait re-exported, so only one is implemented.
+#[cfg(feature = "core-error")]
+use core::error::Error as StdError;
+#[cfg(all(feature = "std", not(feature = "core-error")))]
+use std::error::Error as StdError;
 
-#[cfg(feature = "std")]
-impl std::error::Error for ConfigError {}
+#[cfg(any(feature = "std", feature = "core-error"))]
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::Config(e) => Some(e),
+            Error::Motor(e) => Some(e),
+            Error::Motion(e) => Some(e),
+            Error::Trajectory(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "core-error"))]
+impl StdError for ConfigError {}
+
+#[cfg(any(feature = "std", feature = "core-error"))]
+impl StdError for MotorError {}
 
-#[cfg(feature = "std")]
-impl std::error::Error for MotorError {}
+#[cfg(any(feature = "std", feature = "core-error"))]
+impl StdError for MotionError {}
 
-#[cfg(feature = "std")]
-impl std::error::Error for MotionError {}
+#[cfg(any(feature = "std", feature = "core-error"))]
+impl StdError for TrajectoryError {}
+
+#[cfg(any(feature = "std", feature = "core-error"))]
+impl StdError for ValidationReport {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.first().map(|e| e as &(dyn StdError + 'static))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::String;
+
+    /// Codes are part of the host protocol: this table must only ever grow.
+    #[test]
+    fn test_error_codes_are_stable() {
+        let name = || String::try_from("x").unwrap();
+        let expected: &[(Error, u16)] = &[
+            (ConfigError::ParseError(String::new()).into(), 100),
+            (ConfigError::InvalidMicrosteps(3).into(), 101),
+            (ConfigError::MotorNotFound(name()).into(), 102),
+            (ConfigError::TrajectoryNotFound(name()).into(), 103),
+            (ConfigError::DuplicateMotorName(name()).into(), 104),
+            (ConfigError::DuplicateTrajectoryName(name()).into(), 105),
+            (ConfigError::InvalidVelocityPercent(0).into(), 106),
+            (ConfigError::InvalidAccelerationPercent(0).into(), 107),
+            (ConfigError::InvalidGearRatio(0.0).into(), 108),
+            (ConfigError::InvalidMaxVelocity(0.0).into(), 109),
+            (ConfigError::InvalidMaxAcceleration(0.0).into(), 110),
+            (ConfigError::InvalidSoftLimits { min: 1.0, max: 0.0 }.into(), 111),
+            (ConfigError::InvalidStepPulseWidth(0).into(), 112),
+            (ConfigError::StepPulseExceedsInterval { pulse_ns: 0, interval_ns: 0 }.into(), 113),
+            (ConfigError::StepRateUnachievable { required_ns: 0, budget_ns: 0 }.into(), 114),
+            (
+                ConfigError::GroupMismatch {
+                    group: name(),
+                    field: "microsteps",
+                    expected_motor: name(),
+                    expected: 0.0,
+                    motor: name(),
+                    found: 0.0,
+                }
+                .into(),
+                115,
+            ),
+            (ConfigError::InvalidLimitMargin { margin: 0.0, span: 0.0 }.into(), 116),
+            (ConfigError::StepRateExceedsMax { rate_hz: 0.0, max_hz: 0 }.into(), 117),
+            (
+                ConfigError::InvalidThermalConfig { time_constant_s: 0.0, max_duty: 0.0 }.into(),
+                118,
+            ),
+            (ConfigError::InvalidGravityAssistFactor(0.0).into(), 119),
+            (ConfigError::InvalidSoftStart { duration_ms: 0, accel_percent: 0 }.into(), 120),
+            (
+                ConfigError::PresetNotFound { name: String::new(), available: String::new() }
+                    .into(),
+                121,
+            ),
+            (ConfigError::InvalidInitialVelocity { velocity: 0.0, max: 0.0 }.into(), 122),
+            (ConfigError::InvalidName { name: name(), error: NameError::Empty }.into(), 123),
+            (ConfigError::InvalidGearRatioFraction { numerator: 7, denominator: 0 }.into(), 124),
+            (ConfigError::InvalidRatedTorque(0.0).into(), 125),
+            (ConfigError::InvalidTorqueCurve { point: 0 }.into(), 126),
+            (ConfigError::InvalidClampSlowZone { zone: 0.0, span: 0.0 }.into(), 127),
+            (
+                ConfigError::InvalidHomingConfig { velocity_percent: 0, backoff: 0.0, timeout: 0.0 }
+                    .into(),
+                128,
+            ),
+            (ConfigError::ConflictingBacklashConfig { degrees: 0.0, steps: 0 }.into(), 129),
+            (ConfigError::NonFiniteValue { field: "gear_ratio", value: 0.0 }.into(), 130),
+            (ConfigError::UnsafeReload { motor: name(), field: "microsteps" }.into(), 131),
+            (
+                ConfigError::ParkTrajectoryWrongMotor { motor: name(), trajectory: name() }.into(),
+                132,
+            ),
+            (ConfigError::IoError(String::new()).into(), 199),
+            (MotorError::PinError.into(), 200),
+            (MotorError::InvalidState(name()).into(), 201),
+            (MotorError::NotInitialized.into(), 202),
+            (MotorError::LimitExceeded { position: 0, limit: 0 }.into(), 203),
+            (MotorError::NoHomeSwitch.into(), 204),
+            (MotorError::HomeSwitchNotFound { travel_steps: 0 }.into(), 205),
+            (MotorError::NoSoftLimits.into(), 206),
+            (MotorError::ThermalBudgetExceeded { duty: 0.0, max_duty: 0.0 }.into(), 207),
+            (MotorError::InvalidTelemetry { offset: 0, value: 0 }.into(), 208),
+            (MotorError::InvalidSnapshot.into(), 209),
+            (MotorError::SnapshotTooLarge { bytes: 0 }.into(), 210),
+            (MotorError::NotRegistered { trajectory: name(), motor: name() }.into(), 211),
+            (MotorError::StaleConstraints(name()).into(), 212),
+            (MotionError::VelocityExceedsLimit { requested: 0.0, max: 0.0 }.into(), 300),
+            (MotionError::AccelerationExceedsLimit { requested: 0.0, max: 0.0 }.into(), 301),
+            (MotionError::MoveTooShort { steps: 0, minimum: 0 }.into(), 302),
+            (MotionError::Overflow.into(), 303),
+            (MotionError::SeekOutOfRange { step: 0, total: 0 }.into(), 304),
+            (
+                MotionError::InvalidRate { parameter: "acceleration", value: 0.0, minimum: 1.0 }
+                    .into(),
+                305,
+            ),
+            (MotionError::InsufficientTorque { available: 0.0, required: 0.0 }.into(), 306),
+            (MotionError::NonFiniteInput { parameter: "acceleration", value: 0.0 }.into(), 307),
+            (TrajectoryError::MotorNotFound { trajectory: name(), motor: name() }.into(), 400),
+            (TrajectoryError::TargetExceedsLimits { target: 0.0, min: 0.0, max: 0.0 }.into(), 401),
+            (TrajectoryError::EmptyWaypoints.into(), 402),
+            (TrajectoryError::TooManyWaypoints.into(), 403),
+            (TrajectoryError::InvalidName(String::new()).into(), 404),
+            (TrajectoryError::Empty.into(), 405),
+            (TrajectoryError::Duplicate(name()).into(), 406),
+            (TrajectoryError::NameTooLong(0).into(), 407),
+            (TrajectoryError::RegistryFull.into(), 408),
+            (TrajectoryError::NotFound(name()).into(), 409),
+            (
+                TrajectoryError::PreconditionFailed {
+                    expected: Precondition::Position(0.0),
+                    actual: Precondition::Previous(None),
+                }
+                .into(),
+                410,
+            ),
+            (TrajectoryError::WaypointIndexOutOfRange { index: 0, len: 0 }.into(), 411),
+            (
+                TrajectoryError::InvalidFinalApproach { distance: 0.0, velocity_percent: 0 }.into(),
+                412,
+            ),
+            (TrajectoryError::EmptySequence(name()).into(), 413),
+            (TrajectoryError::SequenceTooLong(16).into(), 414),
+        ];
+
+        let mut seen = std::vec::Vec::new();
+        for (error, code) in expected {
+            assert_eq!(error.code(), *code, "{:?}", error);
+            assert_eq!(error.category() as u8 as u16, code / 100);
+            assert_eq!(ErrorCategory::from_code(*code), Some(error.category()));
+            assert!(!seen.contains(code), "duplicate code {}", code);
+            seen.push(*code);
+        }
+        assert_eq!(ErrorCategory::from_code(999), None);
+    }
 
-#[cfg(feature = "std")]
-impl std::error::Error for TrajectoryError {}
+    #[test]
+    fn test_validation_report_overflow() {
+        let mut report = ValidationReport::new();
+        assert_eq!(report.clone().into_result(), Ok(()));
+
+        for _ in 0..MAX_REPORTED_ERRORS + 2 {
+            report.push(MotorError::PinError.into());
+        }
+        assert_eq!(report.len(), MAX_REPORTED_ERRORS + 2);
+        assert_eq!(report.errors().len(), MAX_REPORTED_ERRORS);
+        assert_eq!(report.omitted(), 2);
+
+        let text = std::format!("{}", report);
+        assert!(text.starts_with("10 problem(s): "));
+        assert!(text.ends_with("; and 2 more"));
+    }
+
+    /// Uses whichever error trait is enabled (`core::error::Error` with the
+    /// `core-error` feature), never `std::error` directly.
+    #[cfg(any(feature = "std", feature = "core-error"))]
+    #[test]
+    fn test_error_source_chain() {
+        fn as_dyn(e: &Error) -> &(dyn StdError + 'static) {
+            e
+        }
+
+        let error = Error::Motor(MotorError::PinError);
+        let source = as_dyn(&error).source().unwrap();
+        assert_eq!(source.downcast_ref::<MotorError>(), Some(&MotorError::PinError));
+        assert!(source.source().is_none());
+
+        let error = Error::Motion(MotionError::Overflow);
+        assert!(as_dyn(&error).source().unwrap().is::<MotionError>());
+    }
+}
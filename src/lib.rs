@@ -31,11 +31,37 @@
 //! motor.execute("home")?;
 //! ```
 //!
+//! ## Environment Overrides
+//!
+//! With `std`, [`SystemConfig::apply_env_overrides`] lets CI pipelines
+//! adjust a loaded configuration without editing the file. Variables are
+//! named `STEPPER_{MOTOR|TRAJECTORY}_{NAME}_{FIELD}`, with the motor or
+//! trajectory key and the snake_case field name in upper case:
+//!
+//! ```text
+//! STEPPER_MOTOR_PAN_MAX_VELOCITY=90.0
+//! STEPPER_TRAJECTORY_HOME_VELOCITY_PERCENT=25
+//! ```
+//!
+//! ```rust,ignore
+//! let config = stepper_motion::load_config("motion.toml")?.apply_env_overrides()?;
+//! ```
+//!
+//! A value that doesn't parse is an error, and the result is validated
+//! again. See [`SystemConfig::apply_overrides`] for the supported fields.
+//!
 //! ## Feature Flags
 //!
 //! - `std` (default): Enables file I/O and TOML parsing
 //! - `alloc`: Enables heap allocation for no_std with allocator
+//! - `libm`: Software float math, required without `std`
 //! - `defmt`: Enables defmt logging for embedded targets
+//! - `core-error`: Implements `core::error::Error` without std (Rust 1.81+)
+//! - `json`: JSON export of motion profiles for host-side tuning
+//! - `shared-pin`: `SharedPin` for DIR lines shared between motors
+//! - `shared-delay`: `SharedDelay` for one delay provider shared between motors
+//! - `schema`: JSON Schema of the configuration format in [`config::schema`]
+//! - `test-utils`: Host-side pin and delay test doubles in [`testing`]
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
@@ -44,7 +70,7 @@
 // Allow large error types - necessary for no_std with heapless strings
 #![allow(clippy::result_large_err)]
 
-#[cfg(feature = "alloc")]
+#[cfg(any(feature = "alloc", feature = "std"))]
 extern crate alloc;
 
 // Core modules
@@ -54,11 +80,19 @@ pub mod motion;
 pub mod motor;
 pub mod trajectory;
 
+mod math;
+
+#[cfg(feature = "test-utils")]
+pub mod testing;
+
+#[cfg(test)]
+mod test_util;
+
 // Re-exports for ergonomic API
 pub use config::{MotorConfig, SystemConfig, TrajectoryConfig, validate_config};
-pub use error::{Error, Result};
+pub use error::{Error, ErrorCategory, Result, ValidationReport};
 pub use motion::{Direction, MotionPhase, MotionProfile};
-pub use motor::{state, MotorSystem, StepperMotor};
+pub use motor::{state, MotorResultExt, MotorSystem, StepperMotor};
 pub use trajectory::TrajectoryRegistry;
 
 // Configuration loading (std only)
@@ -66,4 +100,6 @@ pub use trajectory::TrajectoryRegistry;
 pub use config::load_config;
 
 // Unit types
-pub use config::units::{Degrees, DegreesPerSec, DegreesPerSecSquared, Microsteps, Steps};
+pub use config::units::{
+    Degrees, DegreesPerSec, DegreesPerSecSquared, FormattedDegrees, Microsteps, Steps, Turns,
+};
@@ -0,0 +1,75 @@
+//! Float math used by motion planning.
+//!
+//! With `std`, these forward to the inherent `f32` methods, which compile to
+//! hardware instructions where available. Without it, the `libm` feature
+//! provides software implementations. All rounding and roots in the crate go
+//! through here, so there is a single place to change the arithmetic.
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("stepper-motion needs the `std` or the `libm` feature for float math");
+
+#[cfg(any(feature = "std", feature = "libm"))]
+pub(crate) use imp::{ceilf, expf, log10f, roundf, sqrtf};
+
+#[cfg(feature = "std")]
+mod imp {
+    /// Square root.
+    #[inline]
+    pub(crate) fn sqrtf(x: f32) -> f32 {
+        x.sqrt()
+    }
+
+    /// Smallest integer value not less than `x`.
+    #[inline]
+    pub(crate) fn ceilf(x: f32) -> f32 {
+        x.ceil()
+    }
+
+    /// Nearest integer value, rounding half-way cases away from zero.
+    #[inline]
+    pub(crate) fn roundf(x: f32) -> f32 {
+        x.round()
+    }
+
+    /// Base-10 logarithm.
+    #[inline]
+    pub(crate) fn log10f(x: f32) -> f32 {
+        x.log10()
+    }
+
+    /// Exponential function.
+    #[inline]
+    pub(crate) fn expf(x: f32) -> f32 {
+        x.exp()
+    }
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+mod imp {
+    pub(crate) use libm::{ceilf, expf, log10f, roundf, sqrtf};
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    const SAMPLES: [f32; 8] = [0.0, 1e-6, 0.5, 1.0, 2.0, 35.555557, 12800.0, 3.0e9];
+
+    #[test]
+    fn test_std_matches_libm() {
+        for x in SAMPLES {
+            assert_eq!(sqrtf(x), libm::sqrtf(x), "sqrt({})", x);
+            assert_eq!(ceilf(x), libm::ceilf(x), "ceil({})", x);
+            assert_eq!(ceilf(-x), libm::ceilf(-x), "ceil({})", -x);
+            assert_eq!(roundf(-x), libm::roundf(-x), "round({})", -x);
+            if x > 0.0 {
+                let (a, b) = (log10f(x), libm::log10f(x));
+                assert!((a - b).abs() <= f32::EPSILON * a.abs().max(1.0), "log10({})", x);
+            }
+            if x < 80.0 {
+                let (a, b) = (expf(-x), libm::expf(-x));
+                assert!((a - b).abs() <= f32::EPSILON * a.max(1e-30), "exp({})", -x);
+            }
+        }
+    }
+}
@@ -0,0 +1,302 @@
+//! Builder for motion profiles with named, validated parameters.
+
+use crate::config::units::{Degrees, DegreesPerSec, DegreesPerSecSquared};
+use crate::config::MechanicalConstraints;
+use crate::error::{Error, MotionError, Result};
+
+use super::profile::MotionProfile;
+
+/// Slowest cruise velocity and lowest rates the builder accepts, in steps/sec
+/// and steps/sec².
+const MIN_RATE: f32 = 1.0;
+
+/// Builder for [`MotionProfile`], naming each rate so they can't be
+/// transposed.
+///
+/// Rates are in steps; the `_degrees` setters convert through the motor's
+/// constraints. The deceleration defaults to the acceleration, and the entry
+/// velocity to rest. With [`constraints`](Self::constraints) set, unset
+/// rates default to the motor's maxima and set ones are capped at them.
+///
+/// ```rust
+/// use stepper_motion::motion::MotionProfileBuilder;
+///
+/// let profile = MotionProfileBuilder::new()
+///     .steps(3200)
+///     .max_velocity_steps_per_sec(3200.0)
+///     .acceleration(6400.0)
+///     .deceleration(3200.0)
+///     .build()
+///     .unwrap();
+/// assert_eq!(profile.total_steps, 3200);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MotionProfileBuilder {
+    steps: i64,
+    max_velocity: Option<f32>,
+    acceleration: Option<f32>,
+    deceleration: Option<f32>,
+    entry_velocity: f32,
+    ceilings: Option<(f32, f32)>,
+}
+
+impl MotionProfileBuilder {
+    /// Create a new profile builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the signed move length in steps (positive = CW).
+    pub fn steps(mut self, steps: i64) -> Self {
+        self.steps = steps;
+        self
+    }
+
+    /// Set the signed move length in output degrees.
+    pub fn distance_degrees(
+        mut self,
+        distance: Degrees,
+        constraints: &MechanicalConstraints,
+    ) -> Self {
+        self.steps = constraints.degrees_to_steps(distance.0);
+        self
+    }
+
+    /// Set the cruise velocity in steps/sec.
+    pub fn max_velocity_steps_per_sec(mut self, velocity: f32) -> Self {
+        self.max_velocity = Some(velocity);
+        self
+    }
+
+    /// Set the cruise velocity in degrees/sec.
+    pub fn max_velocity_degrees(
+        self,
+        velocity: DegreesPerSec,
+        constraints: &MechanicalConstraints,
+    ) -> Self {
+        self.max_velocity_steps_per_sec(constraints.velocity_to_steps(velocity.0))
+    }
+
+    /// Set the acceleration in steps/sec².
+    pub fn acceleration(mut self, acceleration: f32) -> Self {
+        self.acceleration = Some(acceleration);
+        self
+    }
+
+    /// Set the acceleration in degrees/sec².
+    pub fn acceleration_degrees(
+        self,
+        acceleration: DegreesPerSecSquared,
+        constraints: &MechanicalConstraints,
+    ) -> Self {
+        self.acceleration(constraints.acceleration_to_steps(acceleration.0))
+    }
+
+    /// Set the deceleration in steps/sec² (default: the acceleration).
+    pub fn deceleration(mut self, deceleration: f32) -> Self {
+        self.deceleration = Some(deceleration);
+        self
+    }
+
+    /// Set the deceleration in degrees/sec².
+    pub fn deceleration_degrees(
+        self,
+        deceleration: DegreesPerSecSquared,
+        constraints: &MechanicalConstraints,
+    ) -> Self {
+        self.deceleration(constraints.acceleration_to_steps(deceleration.0))
+    }
+
+    /// Set the velocity at the first step in steps/sec (default 0, from rest).
+    pub fn entry_velocity(mut self, velocity: f32) -> Self {
+        self.entry_velocity = velocity;
+        self
+    }
+
+    /// Default unset rates to the motor's maxima and cap set ones at them.
+    pub fn constraints(mut self, constraints: &MechanicalConstraints) -> Self {
+        self.ceilings = Some((
+            constraints.max_velocity_steps_per_sec,
+            constraints.max_acceleration_steps_per_sec2,
+        ));
+        self
+    }
+
+    /// Build the profile.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotionError::InvalidRate` for a velocity, acceleration or
+    /// deceleration below 1 (per second, or per second²) or not set without
+    /// constraints, or for a negative entry velocity;
+    /// `MotionError::VelocityExceedsLimit` for an entry velocity above the
+    /// cruise velocity; and `MotionError::Overflow` for moves longer than
+    /// `u32::MAX` steps.
+    pub fn build(self) -> Result<MotionProfile> {
+        let cap = |value: Option<f32>, ceiling: Option<f32>| match (value, ceiling) {
+            (Some(value), Some(ceiling)) => value.min(ceiling),
+            (value, ceiling) => value.or(ceiling).unwrap_or(0.0),
+        };
+        let max_velocity = cap(self.max_velocity, self.ceilings.map(|(v, _)| v));
+        let acceleration = cap(self.acceleration, self.ceilings.map(|(_, a)| a));
+        let deceleration =
+            cap(self.deceleration.or(self.acceleration), self.ceilings.map(|(_, a)| a));
+
+        check_rate("max_velocity_steps_per_sec", max_velocity, MIN_RATE)?;
+        check_rate("acceleration", acceleration, MIN_RATE)?;
+        check_rate("deceleration", deceleration, MIN_RATE)?;
+        check_rate("entry_velocity", self.entry_velocity, 0.0)?;
+        if self.entry_velocity > max_velocity {
+            return Err(Error::Motion(MotionError::VelocityExceedsLimit {
+                requested: self.entry_velocity,
+                max: max_velocity,
+            }));
+        }
+
+        MotionProfile::try_asymmetric_trapezoidal(
+            self.steps,
+            max_velocity,
+            acceleration,
+            deceleration,
+            self.entry_velocity,
+        )
+    }
+}
+
+/// Reject a rate below `minimum` (or NaN).
+fn check_rate(parameter: &'static str, value: f32, minimum: f32) -> Result<()> {
+    if value.is_nan() || value < minimum {
+        return Err(Error::Motion(MotionError::InvalidRate { parameter, value, minimum }));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MotorConfig;
+
+    fn constraints() -> MechanicalConstraints {
+        // 200 steps/rev: 2000 steps/s, 20000 steps/s²
+        let config: MotorConfig = toml::from_str(
+            r#"
+name = "x_axis"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 3600.0
+max_acceleration_deg_per_sec2 = 36000.0
+"#,
+        )
+        .unwrap();
+        MechanicalConstraints::from_config(&config)
+    }
+
+    fn invalid(parameter: &'static str, value: f32, minimum: f32) -> Error {
+        Error::Motion(MotionError::InvalidRate { parameter, value, minimum })
+    }
+
+    #[test]
+    fn test_matches_positional_constructor() {
+        let profile = MotionProfileBuilder::new()
+            .steps(-1000)
+            .max_velocity_steps_per_sec(2000.0)
+            .acceleration(8000.0)
+            .deceleration(4000.0)
+            .entry_velocity(500.0)
+            .build()
+            .unwrap();
+        let expected =
+            MotionProfile::asymmetric_trapezoidal(-1000, 2000.0, 8000.0, 4000.0, 500.0);
+        assert_eq!(profile.direction, expected.direction);
+        assert_eq!(
+            (profile.accel_steps, profile.cruise_steps, profile.decel_steps),
+            (expected.accel_steps, expected.cruise_steps, expected.decel_steps)
+        );
+        assert_eq!(profile.initial_interval_ns, expected.initial_interval_ns);
+
+        // Deceleration follows the acceleration
+        let symmetric = MotionProfileBuilder::new()
+            .steps(1000)
+            .max_velocity_steps_per_sec(2000.0)
+            .acceleration(8000.0)
+            .build()
+            .unwrap();
+        assert_eq!(symmetric.decel_rate, 8000.0);
+    }
+
+    #[test]
+    fn test_constraints_apply_ceilings() {
+        let constraints = constraints();
+        let near = |value: f32, expected: f32| (value - expected).abs() < expected * 1e-4;
+
+        // Defaults to the motor maxima
+        let profile = MotionProfileBuilder::new()
+            .distance_degrees(Degrees(360.0), &constraints)
+            .constraints(&constraints)
+            .build()
+            .unwrap();
+        assert_eq!(profile.total_steps, 200);
+        assert!(profile.cruise_interval_ns.abs_diff(500_000) < 10);
+        assert!(near(profile.accel_rate, 20000.0) && near(profile.decel_rate, 20000.0));
+
+        // Capped at them, in any order
+        let profile = MotionProfileBuilder::new()
+            .constraints(&constraints)
+            .steps(200)
+            .max_velocity_degrees(DegreesPerSec(7200.0), &constraints)
+            .acceleration(1e6)
+            .deceleration_degrees(DegreesPerSecSquared(18000.0), &constraints)
+            .build()
+            .unwrap();
+        assert!(profile.cruise_interval_ns.abs_diff(500_000) < 10);
+        assert!(near(profile.accel_rate, 20000.0) && near(profile.decel_rate, 10000.0));
+
+        // Degree setters without ceilings
+        let profile = MotionProfileBuilder::new()
+            .distance_degrees(Degrees(-36.0), &constraints)
+            .max_velocity_degrees(DegreesPerSec(360.0), &constraints)
+            .acceleration_degrees(DegreesPerSecSquared(720.0), &constraints)
+            .build()
+            .unwrap();
+        assert_eq!(profile.total_steps, 20);
+        assert!(near(profile.accel_rate, 400.0));
+        assert!(profile.cruise_interval_ns.abs_diff(5_000_000) < 100);
+    }
+
+    #[test]
+    fn test_rejects_invalid_rates() {
+        let builder = MotionProfileBuilder::new()
+            .steps(1000)
+            .max_velocity_steps_per_sec(2000.0)
+            .acceleration(8000.0);
+        let error = |builder: &MotionProfileBuilder| builder.clone().build().unwrap_err();
+
+        // Zero acceleration, e.g. from arguments swapped with the velocity
+        let zero_accel = error(&builder.clone().acceleration(0.0));
+        assert_eq!(zero_accel, invalid("acceleration", 0.0, 1.0));
+        assert_eq!(zero_accel.code(), 305);
+        let negative_decel = error(&builder.clone().deceleration(-1.0));
+        assert_eq!(negative_decel, invalid("deceleration", -1.0, 1.0));
+
+        let slow = error(&builder.clone().max_velocity_steps_per_sec(0.5));
+        assert_eq!(slow, invalid("max_velocity_steps_per_sec", 0.5, 1.0));
+        assert_eq!(
+            std::format!("{}", slow),
+            "Motion error: max_velocity_steps_per_sec of 0.5 is below the minimum of 1"
+        );
+        let nan = error(&builder.clone().max_velocity_steps_per_sec(f32::NAN));
+        assert!(matches!(nan, Error::Motion(MotionError::InvalidRate { .. })));
+
+        // Rates are required without constraints
+        let unset = error(&MotionProfileBuilder::new().steps(10));
+        assert_eq!(unset, invalid("max_velocity_steps_per_sec", 0.0, 1.0));
+
+        let backwards = error(&builder.clone().entry_velocity(-1.0));
+        assert_eq!(backwards, invalid("entry_velocity", -1.0, 0.0));
+        assert_eq!(
+            error(&builder.clone().entry_velocity(3000.0)),
+            Error::Motion(MotionError::VelocityExceedsLimit { requested: 3000.0, max: 2000.0 })
+        );
+        assert_eq!(error(&builder.steps(i64::MAX)), Error::Motion(MotionError::Overflow));
+    }
+}
@@ -0,0 +1,60 @@
+//! Delays longer than one `DelayNs` call can express.
+//!
+//! `DelayNs` takes a `u32` argument, so a single `delay_ns` call tops out
+//! around 4.29 s, and some HALs cap their argument lower still (or panic
+//! past it). [`delay_chunked`] splits a long wait into calls no longer than
+//! a given cap.
+
+use embedded_hal::delay::DelayNs;
+
+/// Wait `total_ns` nanoseconds as a series of `delay_ns` calls, none longer
+/// than `max_chunk_ns`.
+///
+/// Every chunk is full length except the last, which takes the remainder, so
+/// the calls always add up to exactly `total_ns`. A zero total makes no call;
+/// a zero cap is treated as 1 ns.
+pub fn delay_chunked<D: DelayNs + ?Sized>(delay: &mut D, total_ns: u64, max_chunk_ns: u32) {
+    let max_chunk_ns = max_chunk_ns.max(1);
+    let mut remaining = total_ns;
+    while remaining > 0 {
+        let chunk = remaining.min(max_chunk_ns as u64) as u32;
+        delay.delay_ns(chunk);
+        remaining -= chunk as u64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{Event, EventLog, EventDelay};
+
+    fn chunks(total_ns: u64, max_chunk_ns: u32) -> std::vec::Vec<u32> {
+        let log = EventLog::default();
+        delay_chunked(&mut EventDelay::new(&log), total_ns, max_chunk_ns);
+        let events = log.borrow();
+        events
+            .iter()
+            .map(|event| match event {
+                Event::DelayNs(ns) => *ns,
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_delay_chunked() {
+        // 2.5 s under a 1 s cap: two full chunks and the remainder
+        let second = 1_000_000_000;
+        assert_eq!(chunks(2_500_000_000, second), [second, second, 500_000_000]);
+        assert_eq!(chunks(3_000, 1_000), [1_000; 3]);
+        assert_eq!(chunks(999, 1_000), [999]);
+        assert!(chunks(0, 1_000).is_empty());
+        assert_eq!(chunks(3, 0), [1; 3]);
+
+        // Beyond the u32 range even without a cap
+        let total = 10_000_000_000;
+        let split = chunks(total, u32::MAX);
+        assert_eq!(split, [u32::MAX, u32::MAX, (total - 2 * u32::MAX as u64) as u32]);
+        assert_eq!(split.iter().map(|&ns| ns as u64).sum::<u64>(), total);
+    }
+}
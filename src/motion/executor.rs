@@ -1,7 +1,24 @@
 //! Motion execution - step pulse generation.
 
+use crate::error::MotionError;
+
 use super::profile::{MotionPhase, MotionProfile};
 
+/// Point-in-time view of a move in progress.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressSnapshot {
+    /// Steps executed so far.
+    pub step: u32,
+    /// Total steps in the move.
+    pub total: u32,
+    /// Current phase of motion.
+    pub phase: MotionPhase,
+    /// Estimated time to completion in seconds.
+    pub eta_secs: f32,
+    /// Current commanded velocity in steps/sec.
+    pub velocity: f32,
+}
+
 /// Runtime state during motion execution.
 #[derive(Debug, Clone)]
 pub struct MotionExecutor {
@@ -21,17 +38,9 @@ pub struct MotionExecutor {
 impl MotionExecutor {
     /// Create a new executor for a motion profile.
     pub fn new(profile: MotionProfile) -> Self {
-        let phase = if profile.is_zero() {
-            MotionPhase::Complete
-        } else {
-            MotionPhase::Accelerating
-        };
-
-        let interval = if profile.is_zero() {
-            u32::MAX
-        } else {
-            profile.initial_interval_ns
-        };
+        // Profiles split by `stop_from` start directly in deceleration
+        let phase = profile.phase_at(0);
+        let interval = profile.interval_at(0);
 
         Self {
             profile,
@@ -44,7 +53,7 @@ impl MotionExecutor {
     /// Check if motion is complete.
     #[inline]
     pub fn is_complete(&self) -> bool {
-        self.phase == MotionPhase::Complete
+        self.phase.is_terminal()
     }
 
     /// Get the current step number.
@@ -71,6 +80,34 @@ impl MotionExecutor {
         self.phase
     }
 
+    /// Get the steps left until the next phase boundary (0 once complete).
+    ///
+    /// With [`phase_steps_elapsed`](Self::phase_steps_elapsed) this lets a
+    /// coordinator see, for example, how soon each axis starts decelerating.
+    #[inline]
+    pub fn phase_remaining_steps(&self) -> u32 {
+        self.phase_end().saturating_sub(self.current_step)
+    }
+
+    /// Get the steps completed in the current phase (0 once complete).
+    #[inline]
+    pub fn phase_steps_elapsed(&self) -> u32 {
+        if self.is_complete() {
+            return 0;
+        }
+        self.current_step
+            .saturating_sub(self.phase_end() - self.profile.steps_in_phase(self.phase))
+    }
+
+    /// Step at which the current phase ends.
+    fn phase_end(&self) -> u32 {
+        match self.phase {
+            MotionPhase::Accelerating => self.profile.cruise_start(),
+            MotionPhase::Cruising => self.profile.decel_start(),
+            MotionPhase::Decelerating | MotionPhase::Complete => self.profile.total_steps,
+        }
+    }
+
     /// Get the current step interval in nanoseconds.
     #[inline]
     pub fn current_interval_ns(&self) -> u32 {
@@ -109,16 +146,125 @@ impl MotionExecutor {
     /// Reset the executor to the beginning.
     pub fn reset(&mut self) {
         self.current_step = 0;
-        self.phase = if self.profile.is_zero() {
-            MotionPhase::Complete
+        self.phase = self.profile.phase_at(0);
+        self.current_interval_ns = self.profile.interval_at(0);
+    }
+
+    /// Jump to an arbitrary step of the profile.
+    ///
+    /// Phase and interval are recomputed as if the executor had advanced to
+    /// `step`, so the move continues at the velocity the profile has there.
+    /// This assumes the motor is physically already at that step (e.g. a
+    /// position persisted before power loss); if it may be at rest instead,
+    /// continue with [`split_remaining`](Self::split_remaining).
+    /// Seeking to `total_steps` completes the move.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotionError::SeekOutOfRange` if `step` is past the end of the
+    /// profile.
+    pub fn seek(&mut self, step: u32) -> Result<(), MotionError> {
+        let total = self.profile.total_steps;
+        if step > total {
+            return Err(MotionError::SeekOutOfRange { step, total });
+        }
+
+        self.current_step = step;
+        if step == total {
+            self.phase = MotionPhase::Complete;
+            self.current_interval_ns = u32::MAX;
         } else {
-            MotionPhase::Accelerating
-        };
-        self.current_interval_ns = if self.profile.is_zero() {
-            u32::MAX
+            self.phase = self.profile.phase_at(step);
+            self.current_interval_ns = self.profile.interval_at(step);
+        }
+        Ok(())
+    }
+
+    /// Get a rest-to-rest profile covering only the steps left from the
+    /// current step.
+    ///
+    /// Unlike [`seek`](Self::seek) this does not assume the motor is moving:
+    /// the remainder starts from rest with the original rates and cruise
+    /// velocity, which is the safe way to resume after an interruption. A
+    /// profile without ramps (e.g. [`MotionProfile::constant_velocity`])
+    /// resumes at its constant interval.
+    pub fn split_remaining(&self) -> MotionProfile {
+        self.profile.remainder_from(self.current_step)
+    }
+
+    /// Split the move at the current step into a stop ramp and the remainder.
+    ///
+    /// The first profile decelerates from the current velocity to rest; the
+    /// second covers the steps left after stopping, re-planned from rest with
+    /// the original rates. Together they reach the original target. A profile
+    /// with a zero acceleration or deceleration rate (e.g.
+    /// [`MotionProfile::constant_velocity`]) stops on the spot and keeps its
+    /// cruise interval for the remainder.
+    pub fn split(&self) -> (MotionProfile, MotionProfile) {
+        let stop = self.profile.stop_from(self.current_step);
+        let remainder = self
+            .profile
+            .remainder_from(self.current_step + stop.total_steps);
+        (stop, remainder)
+    }
+
+    /// Number of steps a controlled stop from the current step would take
+    /// (see [`MotionProfile::stop_steps_from`]).
+    #[inline]
+    pub fn stop_distance_steps(&self) -> u32 {
+        self.profile.stop_steps_from(self.current_step)
+    }
+
+    /// Estimated time to completion in seconds.
+    #[inline]
+    pub fn eta_secs(&self) -> f32 {
+        self.profile.remaining_duration_from(self.current_step)
+    }
+
+    /// Get progress by elapsed time rather than steps (0.0 to 1.0).
+    ///
+    /// The acceleration phase covers few steps but a large share of the time,
+    /// so this tracks wall-clock progress more closely than [`progress`](Self::progress).
+    pub fn elapsed_ratio(&self) -> f32 {
+        let total = self.profile.estimated_duration_secs();
+        if total <= 0.0 {
+            1.0
         } else {
-            self.profile.initial_interval_ns
-        };
+            (1.0 - self.eta_secs() / total).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Current commanded velocity in steps/sec.
+    #[inline]
+    pub fn velocity(&self) -> f32 {
+        if self.is_complete() || self.current_interval_ns == 0 {
+            0.0
+        } else {
+            1_000_000_000.0 / self.current_interval_ns as f32
+        }
+    }
+
+    /// Capture the current progress.
+    pub fn snapshot(&self) -> ProgressSnapshot {
+        ProgressSnapshot {
+            step: self.current_step,
+            total: self.profile.total_steps,
+            phase: self.phase,
+            eta_secs: self.eta_secs(),
+            velocity: self.velocity(),
+        }
+    }
+
+    /// Capture the current progress once every `every` steps.
+    ///
+    /// Returns `None` between reporting points, so it can be polled after each
+    /// step to throttle UI or log updates. The final step is always reported.
+    pub fn snapshot_every(&self, every: u32) -> Option<ProgressSnapshot> {
+        if self.is_complete() || self.current_step % every.max(1) == 0 {
+            Some(self.snapshot())
+        } else {
+            None
+        }
     }
 
     /// Get progress as a percentage (0.0 to 1.0).
@@ -130,6 +276,39 @@ impl MotionExecutor {
             self.current_step as f32 / self.profile.total_steps as f32
         }
     }
+
+    /// Get progress eased for display (0.0 to 1.0).
+    ///
+    /// Each phase keeps its share of [`progress`](Self::progress), but the
+    /// acceleration share is eased in and the deceleration share eased out,
+    /// so a progress bar starts and settles smoothly instead of jumping with
+    /// the step count. Display only: step timing is unaffected.
+    pub fn visual_progress(&self) -> f32 {
+        let total = self.profile.total_steps;
+        if self.current_step >= total {
+            return 1.0;
+        }
+        let (cruise_start, decel_start) = (self.profile.cruise_start(), self.profile.decel_start());
+        let (start, end, ease): (u32, u32, fn(f32) -> f32) = if self.current_step < cruise_start {
+            (0, cruise_start, ease_in)
+        } else if self.current_step < decel_start {
+            (cruise_start, decel_start, |t| t)
+        } else {
+            (decel_start, total, ease_out)
+        };
+        let t = (self.current_step - start) as f32 / (end - start) as f32;
+        (start as f32 + (end - start) as f32 * ease(t)) / total as f32
+    }
+}
+
+/// Quadratic ease-in over `t` in 0-1.
+fn ease_in(t: f32) -> f32 {
+    t * t
+}
+
+/// Quadratic ease-out over `t` in 0-1.
+fn ease_out(t: f32) -> f32 {
+    1.0 - (1.0 - t) * (1.0 - t)
 }
 
 #[cfg(test)]
@@ -160,6 +339,48 @@ mod tests {
         assert_eq!(executor.steps_remaining(), 0);
     }
 
+    #[test]
+    fn test_eta_decreases_monotonically() {
+        let profile = MotionProfile::asymmetric_trapezoidal(500, 1000.0, 2000.0, 800.0, 0.0);
+        let mut executor = MotionExecutor::new(profile);
+
+        assert_eq!(executor.eta_secs(), executor.profile().estimated_duration_secs());
+        assert_eq!(executor.elapsed_ratio(), 0.0);
+
+        let mut last_eta = executor.eta_secs();
+        let mut last_ratio = executor.elapsed_ratio();
+        while executor.advance() {
+            assert!(executor.eta_secs() <= last_eta);
+            assert!(executor.elapsed_ratio() >= last_ratio);
+            last_eta = executor.eta_secs();
+            last_ratio = executor.elapsed_ratio();
+        }
+
+        assert_eq!(executor.eta_secs(), 0.0);
+        assert_eq!(executor.elapsed_ratio(), 1.0);
+    }
+
+    #[test]
+    fn test_snapshot_decimation() {
+        let profile = MotionProfile::symmetric_trapezoidal(25, 1000.0, 2000.0);
+        let mut executor = MotionExecutor::new(profile);
+
+        let mut reported = 0;
+        loop {
+            if let Some(snapshot) = executor.snapshot_every(10) {
+                assert_eq!(snapshot.total, 25);
+                reported += 1;
+            }
+            if !executor.advance() {
+                break;
+            }
+        }
+        assert!(executor.snapshot_every(10).is_some());
+
+        // Steps 0, 10 and 20
+        assert_eq!(reported, 3);
+    }
+
     #[test]
     fn test_phase_transitions() {
         let profile = MotionProfile::symmetric_trapezoidal(100, 1000.0, 2000.0);
@@ -181,4 +402,131 @@ mod tests {
         assert!(saw_accel);
         assert!(saw_decel);
     }
+
+    #[test]
+    fn test_phase_step_counts() {
+        let profile = MotionProfile::symmetric_trapezoidal(1000, 1000.0, 2000.0);
+        let (accel, cruise, decel) = (profile.accel_steps, profile.cruise_steps, profile.decel_steps);
+        let mut executor = MotionExecutor::new(profile);
+
+        assert_eq!(executor.phase_steps_elapsed(), 0);
+        assert_eq!(executor.phase_remaining_steps(), accel);
+
+        executor.seek(accel + 10).unwrap();
+        assert_eq!(executor.phase(), MotionPhase::Cruising);
+        assert_eq!(executor.phase_steps_elapsed(), 10);
+        assert_eq!(executor.phase_remaining_steps(), cruise - 10);
+
+        // Elapsed + remaining always spans the phase
+        while !executor.is_complete() {
+            let phase = executor.phase();
+            let span = executor.phase_steps_elapsed() + executor.phase_remaining_steps();
+            assert_eq!(span, executor.profile().steps_in_phase(phase));
+            executor.advance();
+        }
+        assert_eq!(executor.phase_remaining_steps(), 0);
+        assert_eq!(executor.phase_steps_elapsed(), 0);
+        assert!(decel > 0);
+    }
+
+    #[test]
+    fn test_visual_progress() {
+        let profile = MotionProfile::asymmetric_trapezoidal(1000, 1000.0, 4000.0, 1000.0, 0.0);
+        let (cruise_start, decel_start) = (profile.cruise_start(), profile.decel_start());
+        assert!(cruise_start > 0 && decel_start > cruise_start && decel_start < 1000);
+        let mut executor = MotionExecutor::new(profile);
+        assert_eq!(executor.visual_progress(), 0.0);
+
+        let mut previous = 0.0;
+        while !executor.is_complete() {
+            let (step, visual) = (executor.current_step(), executor.visual_progress());
+            assert!(visual >= previous, "step {step}: {visual} < {previous}");
+            // Phase boundaries match the linear progress
+            if step == cruise_start || step == decel_start {
+                assert!((visual - executor.progress()).abs() < 1e-6);
+            }
+            // Behind it while accelerating, linear cruising, ahead decelerating
+            if step > 0 && step < cruise_start {
+                assert!(visual < executor.progress());
+            } else if (cruise_start..decel_start).contains(&step) {
+                assert!((visual - executor.progress()).abs() < 1e-6);
+            } else if step > decel_start {
+                assert!(visual > executor.progress());
+            }
+            previous = visual;
+            executor.advance();
+        }
+        assert_eq!(executor.visual_progress(), 1.0);
+        assert_eq!(MotionExecutor::new(MotionProfile::zero()).visual_progress(), 1.0);
+    }
+
+    #[test]
+    fn test_seek_mid_cruise() {
+        let profile = MotionProfile::symmetric_trapezoidal(1000, 1000.0, 2000.0);
+        let cruise_interval = profile.cruise_interval_ns;
+        let mut executor = MotionExecutor::new(profile);
+
+        executor.seek(500).unwrap();
+        assert_eq!(executor.current_step(), 500);
+        assert_eq!(executor.phase(), MotionPhase::Cruising);
+        assert_eq!(executor.current_interval_ns(), cruise_interval);
+        assert_eq!(executor.steps_remaining(), 500);
+
+        // Stepping on from the seek point matches a full run
+        let mut reference = MotionExecutor::new(executor.profile().clone());
+        while reference.current_step() < 900 {
+            reference.advance();
+        }
+        executor.seek(899).unwrap();
+        executor.advance();
+        assert_eq!(executor.phase(), reference.phase());
+        assert_eq!(executor.current_interval_ns(), reference.current_interval_ns());
+
+        executor.seek(1000).unwrap();
+        assert!(executor.is_complete());
+        assert_eq!(
+            executor.seek(1001),
+            Err(MotionError::SeekOutOfRange { step: 1001, total: 1000 })
+        );
+    }
+
+    #[test]
+    fn test_split_remaining() {
+        let profile = MotionProfile::symmetric_trapezoidal(-1000, 1000.0, 2000.0);
+        let mut executor = MotionExecutor::new(profile.clone());
+        executor.seek(500).unwrap();
+
+        let remaining = executor.split_remaining();
+        assert_eq!(remaining.total_steps, 500);
+        assert_eq!(remaining.direction, profile.direction);
+        // Starts from rest, not at cruise
+        assert_eq!(remaining.phase_at(0), MotionPhase::Accelerating);
+        assert_eq!(remaining.initial_interval_ns, profile.initial_interval_ns);
+        assert_eq!(remaining.cruise_interval_ns, profile.cruise_interval_ns);
+
+        // A constant-velocity move resumes with every step it has left
+        let profile = MotionProfile::constant_velocity(-8, 500.0);
+        let mut executor = MotionExecutor::new(profile.clone());
+        executor.seek(3).unwrap();
+        let remaining = executor.split_remaining();
+        assert_eq!(remaining.total_steps, 5);
+        assert_eq!(remaining.direction, profile.direction);
+        assert_eq!(remaining.phase_at(0), MotionPhase::Cruising);
+        assert_eq!(remaining.interval_at(4), profile.cruise_interval_ns);
+    }
+
+    #[test]
+    fn test_split_zero_rate_profile() {
+        let profile = MotionProfile::constant_velocity(40, 500.0);
+        let mut executor = MotionExecutor::new(profile.clone());
+        executor.seek(15).unwrap();
+
+        // Stops on the spot; every step left is kept at the same interval
+        let (stop, remainder) = executor.split();
+        assert!(stop.is_zero());
+        assert_eq!(remainder.total_steps, 25);
+        assert_eq!(remainder.direction, profile.direction);
+        assert_eq!(remainder.cruise_interval_ns, profile.cruise_interval_ns);
+        assert_eq!(15 + stop.total_steps + remainder.total_steps, profile.total_steps);
+    }
 }
@@ -0,0 +1,379 @@
+//! Velocity/time sampling of motion profiles, for plotting and tuning.
+//!
+//! [`MotionProfile::rows`] walks the planned step timing without allocating,
+//! and [`MotionProfile::render_intervals_csv`] writes it into a byte buffer
+//! on targets without a display. On the host, profiles can be resampled on a
+//! uniform time grid, exported as CSV (`std`) or JSON (`json` feature) for
+//! plotting, or drawn as text with [`MotionProfile::render_ascii`].
+
+use core::fmt::Write as _;
+
+#[cfg(any(feature = "alloc", feature = "std"))]
+use alloc::vec::Vec;
+
+use serde::Serialize;
+
+use super::profile::{MotionPhase, MotionProfile};
+
+/// Timing of one planned step.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ProfileRow {
+    /// Step index (0-based).
+    pub step: u32,
+    /// Time in seconds at the end of this step's interval.
+    pub time_s: f64,
+    /// Interval before the next step in nanoseconds.
+    pub interval_ns: u32,
+    /// Step rate during this interval in steps/sec.
+    pub velocity_steps_s: f32,
+    /// Phase this step belongs to.
+    pub phase: MotionPhase,
+}
+
+/// A profile sample on a uniform time grid.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ProfileSample {
+    /// Sample time in seconds from the start of the move.
+    pub time_s: f32,
+    /// Distance travelled in steps (fractional within a step interval).
+    pub position_steps: f32,
+    /// Step rate in steps/sec (0 once complete).
+    pub velocity_steps_s: f32,
+    /// Phase at this time.
+    pub phase: MotionPhase,
+}
+
+impl MotionProfile {
+    /// Iterate over the planned timing of every step.
+    pub fn rows(&self) -> impl Iterator<Item = ProfileRow> + '_ {
+        let mut time_s = 0.0;
+        (0..self.total_steps).map(move |step| {
+            let interval_ns = self.interval_at(step);
+            time_s += interval_ns as f64 * 1e-9;
+            ProfileRow {
+                step,
+                time_s,
+                interval_ns,
+                velocity_steps_s: 1_000_000_000.0 / interval_ns as f32,
+                phase: self.phase_at(step),
+            }
+        })
+    }
+
+    /// Resample the profile on a uniform time grid of `dt_s` seconds.
+    ///
+    /// The last sample is taken at the end of the move with zero velocity.
+    /// Returns an empty `Vec` if `dt_s` is not positive.
+    #[cfg(any(feature = "alloc", feature = "std"))]
+    pub fn to_samples(&self, dt_s: f32) -> Vec<ProfileSample> {
+        let mut samples = Vec::new();
+        if dt_s.is_nan() || dt_s <= 0.0 {
+            return samples;
+        }
+
+        let mut rows = self.rows();
+        let mut current = rows.next();
+        let mut step_start = 0.0;
+        let mut k = 0u32;
+        loop {
+            let t = k as f64 * dt_s as f64;
+            while let Some(row) = current.filter(|row| t >= row.time_s) {
+                step_start = row.time_s;
+                current = rows.next();
+            }
+
+            let Some(row) = current else {
+                samples.push(ProfileSample {
+                    time_s: step_start as f32,
+                    position_steps: self.total_steps as f32,
+                    velocity_steps_s: 0.0,
+                    phase: MotionPhase::Complete,
+                });
+                return samples;
+            };
+
+            let fraction = (t - step_start) / (row.time_s - step_start);
+            samples.push(ProfileSample {
+                time_s: t as f32,
+                position_steps: row.step as f32 + fraction as f32,
+                velocity_steps_s: row.velocity_steps_s,
+                phase: row.phase,
+            });
+            k += 1;
+        }
+    }
+
+    /// Write the per-step timing as CSV with a header row.
+    ///
+    /// Columns: `step,time_s,interval_ns,velocity_steps_s,phase`.
+    #[cfg(feature = "std")]
+    pub fn export_csv<W: std::io::Write>(&self, mut w: W) -> std::io::Result<()> {
+        writeln!(w, "step,time_s,interval_ns,velocity_steps_s,phase")?;
+        for row in self.rows() {
+            writeln!(
+                w,
+                "{},{:.9},{},{:.3},{}",
+                row.step,
+                row.time_s,
+                row.interval_ns,
+                row.velocity_steps_s,
+                row.phase.as_str()
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Write `step,interval_ns` CSV rows, after a header row, into `buf`.
+    ///
+    /// Only whole rows are written: when `buf` fills up the output stops at
+    /// the last row that fits. Returns the number of bytes written.
+    pub fn render_intervals_csv(&self, buf: &mut [u8]) -> usize {
+        let mut written = copy_line(buf, "step,interval_ns\n");
+        if written == 0 {
+            return 0;
+        }
+        for step in 0..self.total_steps {
+            let mut line = heapless::String::<24>::new();
+            let _ = writeln!(line, "{},{}", step, self.interval_at(step));
+            let n = copy_line(&mut buf[written..], &line);
+            if n == 0 {
+                break;
+            }
+            written += n;
+        }
+        written
+    }
+
+    /// Draw the velocity along the move as text into `buf`.
+    ///
+    /// The plot is `height` lines of `width` cells, each line ending in
+    /// `\n`, with distance across and velocity up scaled to the peak: `/`
+    /// while accelerating, `\` while decelerating, `‾` at a constant velocity
+    /// and `_` at rest. Each column shows the first step of its share of the
+    /// move, read with [`interval_at`](Self::interval_at), so drawing time
+    /// depends on the plot size and not on the length of the move. `‾` takes
+    /// 3 bytes in UTF-8, so a full plot needs up to `height × (3 × width + 1)`
+    /// bytes; output stops at the last whole character that fits. Returns the
+    /// number of bytes written.
+    ///
+    /// ```text
+    ///     /‾‾‾‾‾\\\\\
+    ///   //           \\\\
+    ///  /                 \
+    /// /
+    /// ```
+    pub fn render_ascii(&self, buf: &mut [u8], width: usize, height: usize) -> usize {
+        if width == 0 || height == 0 {
+            return 0;
+        }
+
+        // Velocity and phase at the first step of each column
+        let sample = |column: usize| {
+            let step = column as u64 * self.total_steps as u64 / width as u64;
+            match self.phase_at(step as u32) {
+                MotionPhase::Complete => (0.0, MotionPhase::Complete),
+                phase => (1_000_000_000.0 / self.interval_at(step as u32) as f32, phase),
+            }
+        };
+        let peak = (0..width).map(|column| sample(column).0).fold(0.0, f32::max);
+        let top = (height - 1) as f32;
+        let level = |velocity: f32| {
+            if peak > 0.0 {
+                crate::math::roundf(velocity / peak * top) as usize
+            } else {
+                0
+            }
+        };
+
+        let mut written = 0;
+        for line in (0..height).rev() {
+            let mut previous = 0;
+            for column in 0..=width {
+                let c = if column == width {
+                    '\n'
+                } else {
+                    let (velocity, phase) = sample(column);
+                    let current = level(velocity);
+                    let c = if current > previous && (previous + 1..=current).contains(&line) {
+                        '/'
+                    } else if current < previous && (current + 1..=previous).contains(&line) {
+                        '\\'
+                    } else if current == previous && current == line {
+                        match phase {
+                            MotionPhase::Accelerating => '/',
+                            MotionPhase::Decelerating => '\\',
+                            _ if line == 0 => '_',
+                            _ => '‾',
+                        }
+                    } else {
+                        ' '
+                    };
+                    previous = current;
+                    c
+                };
+                let mut encoded = [0u8; 4];
+                let n = copy_line(&mut buf[written..], c.encode_utf8(&mut encoded));
+                if n == 0 {
+                    return written;
+                }
+                written += n;
+            }
+        }
+        written
+    }
+
+    /// Write the per-step timing as a JSON array of [`ProfileRow`] objects.
+    #[cfg(feature = "json")]
+    pub fn export_json<W: std::io::Write>(&self, w: W) -> serde_json::Result<()> {
+        use serde::Serializer;
+
+        let mut serializer = serde_json::Serializer::new(w);
+        serializer.collect_seq(self.rows())
+    }
+}
+
+/// Copy `text` to the start of `buf` if it fits whole, returning its length,
+/// or 0 if it does not fit.
+fn copy_line(buf: &mut [u8], text: &str) -> usize {
+    let bytes = text.as_bytes();
+    match buf.get_mut(..bytes.len()) {
+        Some(out) => {
+            out.copy_from_slice(bytes);
+            bytes.len()
+        }
+        None => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_VELOCITY: f32 = 2000.0;
+
+    fn profile() -> MotionProfile {
+        MotionProfile::asymmetric_trapezoidal(1000, MAX_VELOCITY, 8000.0, 4000.0, 0.0)
+    }
+
+    #[test]
+    fn test_csv_final_time_matches_estimate() {
+        let profile = profile();
+        let mut out = std::vec::Vec::new();
+        profile.export_csv(&mut out).unwrap();
+        let csv = std::string::String::from_utf8(out).unwrap();
+
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("step,time_s,interval_ns,velocity_steps_s,phase"));
+        let rows: std::vec::Vec<&str> = lines.collect();
+        assert_eq!(rows.len(), 1000);
+
+        let last: std::vec::Vec<&str> = rows[999].split(',').collect();
+        assert_eq!(last[0], "999");
+        assert_eq!(last[4], "Decelerating");
+        let total: f32 = last[1].parse().unwrap();
+        let expected = profile.estimated_duration_secs();
+        assert!((total - expected).abs() / expected < 0.01, "{} vs {}", total, expected);
+    }
+
+    #[test]
+    fn test_samples_respect_max_velocity() {
+        let profile = profile();
+        let samples = profile.to_samples(0.001);
+
+        let first = samples.first().unwrap();
+        assert_eq!(first.time_s, 0.0);
+        assert_eq!(first.phase, MotionPhase::Accelerating);
+
+        let last = samples.last().unwrap();
+        assert_eq!(last.phase, MotionPhase::Complete);
+        assert_eq!(last.position_steps, 1000.0);
+        let expected = profile.estimated_duration_secs();
+        assert!((last.time_s - expected).abs() / expected < 0.01);
+
+        for pair in samples.windows(2) {
+            assert!(pair[1].time_s >= pair[0].time_s);
+            assert!(pair[1].position_steps >= pair[0].position_steps);
+        }
+        // Cruise interval is rounded to whole nanoseconds
+        assert!(samples.iter().all(|s| s.velocity_steps_s <= MAX_VELOCITY * 1.001));
+        assert!(samples.iter().any(|s| s.phase == MotionPhase::Cruising));
+    }
+
+    #[test]
+    fn test_samples_degenerate_input() {
+        assert!(profile().to_samples(0.0).is_empty());
+        assert!(profile().to_samples(f32::NAN).is_empty());
+
+        let samples = MotionProfile::zero().to_samples(0.01);
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].phase, MotionPhase::Complete);
+    }
+
+    #[test]
+    fn test_render_intervals_csv() {
+        let constant = MotionProfile::constant_velocity(3, 1000.0);
+        let mut buf = [0u8; 64];
+        let n = constant.render_intervals_csv(&mut buf);
+        let csv = core::str::from_utf8(&buf[..n]).unwrap();
+        assert_eq!(csv, "step,interval_ns\n0,1000000\n1,1000000\n2,1000000\n");
+
+        // Rows that don't fit whole are left out
+        let n = constant.render_intervals_csv(&mut buf[..40]);
+        assert_eq!(&buf[..n], b"step,interval_ns\n0,1000000\n1,1000000\n");
+        assert_eq!(constant.render_intervals_csv(&mut buf[..10]), 0);
+
+        let mut big = std::vec![0u8; 32 * 1024];
+        let n = profile().render_intervals_csv(&mut big);
+        let csv = core::str::from_utf8(&big[..n]).unwrap();
+        assert_eq!(csv.lines().count(), 1001);
+        let first = std::format!("0,{}", profile().interval_at(0));
+        assert_eq!(csv.lines().nth(1), Some(first.as_str()));
+    }
+
+    #[test]
+    fn test_render_ascii() {
+        let mut buf = [0u8; 1024];
+        let n = profile().render_ascii(&mut buf, 20, 4);
+        let plot = core::str::from_utf8(&buf[..n]).unwrap();
+        let lines: std::vec::Vec<&str> = plot.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|line| line.chars().count() == 20));
+        // Ramps up to a cruise at the top, and back down
+        assert!(lines[0].contains("/‾") && lines[0].contains("‾\\"));
+        assert!(lines[3].starts_with('/'));
+        assert!(!plot.contains('_'));
+
+        // Constant velocity sits flat along the top
+        let n = MotionProfile::constant_velocity(100, 1000.0).render_ascii(&mut buf, 8, 2);
+        assert_eq!(core::str::from_utf8(&buf[..n]).unwrap(), "/‾‾‾‾‾‾‾\n        \n");
+
+        // At rest: a flat baseline
+        let n = MotionProfile::zero().render_ascii(&mut buf, 4, 2);
+        assert_eq!(&buf[..n], b"    \n____\n");
+
+        // Truncated to whole characters
+        let n = profile().render_ascii(&mut buf[..30], 20, 4);
+        assert!(core::str::from_utf8(&buf[..n]).is_ok());
+        assert!(n <= 30);
+        assert_eq!(profile().render_ascii(&mut buf, 0, 4), 0);
+
+        // Sampled per column, so long moves cost no more to draw
+        let long = MotionProfile::constant_velocity(u32::MAX as i64, 1000.0);
+        let n = long.render_ascii(&mut buf, 8, 2);
+        assert_eq!(core::str::from_utf8(&buf[..n]).unwrap(), "/‾‾‾‾‾‾‾\n        \n");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_export() {
+        let profile = MotionProfile::symmetric_trapezoidal(10, 1000.0, 2000.0);
+        let mut out = std::vec::Vec::new();
+        profile.export_json(&mut out).unwrap();
+
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let rows = value.as_array().unwrap();
+        assert_eq!(rows.len(), 10);
+        assert_eq!(rows[0]["step"], 0);
+        assert_eq!(rows[0]["phase"], "Accelerating");
+    }
+}
@@ -2,8 +2,14 @@
 //!
 //! Provides motion profile calculation and step execution.
 
+mod builder;
+mod delay;
 mod executor;
+mod export;
 mod profile;
 
-pub use executor::MotionExecutor;
-pub use profile::{Direction, MotionPhase, MotionProfile};
+pub use builder::MotionProfileBuilder;
+pub use delay::delay_chunked;
+pub use executor::{MotionExecutor, ProgressSnapshot};
+pub use export::{ProfileRow, ProfileSample};
+pub use profile::{Direction, MotionPhase, MotionProfile, MIN_TRAPEZOIDAL_STEPS};
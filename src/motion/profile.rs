@@ -3,10 +3,20 @@
 //! Provides asymmetric trapezoidal motion profiles with independent
 //! acceleration and deceleration rates.
 
-use libm::sqrtf;
+use crate::math::{ceilf, sqrtf};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, MotionError, Result};
+
+/// Longest move, in steps, that [`MotionProfile::optimal`] runs at constant
+/// velocity instead of with acceleration ramps.
+pub const MIN_TRAPEZOIDAL_STEPS: u32 = 10;
 
 /// Direction of motor motion.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all(deserialize = "snake_case"))]
 pub enum Direction {
     /// Clockwise (positive step count).
     Clockwise,
@@ -36,7 +46,7 @@ impl Direction {
 }
 
 /// Current phase of motion execution.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum MotionPhase {
     /// Accelerating from rest toward cruise velocity.
     Accelerating,
@@ -48,6 +58,49 @@ pub enum MotionPhase {
     Complete,
 }
 
+impl MotionPhase {
+    /// Check if motion has finished (`Complete`).
+    #[inline]
+    pub fn is_terminal(self) -> bool {
+        matches!(self, MotionPhase::Complete)
+    }
+
+    /// Check if velocity is changing (`Accelerating` or `Decelerating`).
+    #[inline]
+    pub fn is_changing_velocity(self) -> bool {
+        matches!(self, MotionPhase::Accelerating | MotionPhase::Decelerating)
+    }
+
+    /// Check if velocity is constant (`Cruising`).
+    #[inline]
+    pub fn is_constant_velocity(self) -> bool {
+        matches!(self, MotionPhase::Cruising)
+    }
+
+    /// Fraction of the peak winding current drawn during this phase, for
+    /// [`MotionProfile::energy_estimate`].
+    ///
+    /// Ramps need full torque; at cruise the driver only has to overcome
+    /// friction, approximated as 70% of peak current.
+    pub fn duty_cycle(self) -> f32 {
+        match self {
+            MotionPhase::Accelerating | MotionPhase::Decelerating => 1.0,
+            MotionPhase::Cruising => 0.7,
+            MotionPhase::Complete => 0.0,
+        }
+    }
+
+    /// Get the phase name for display.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MotionPhase::Accelerating => "Accelerating",
+            MotionPhase::Cruising => "Cruising",
+            MotionPhase::Decelerating => "Decelerating",
+            MotionPhase::Complete => "Complete",
+        }
+    }
+}
+
 /// Computed motion profile for a move (asymmetric trapezoidal).
 #[derive(Debug, Clone)]
 pub struct MotionProfile {
@@ -69,9 +122,17 @@ pub struct MotionProfile {
     /// Initial step interval (nanoseconds) - at start of acceleration.
     pub initial_interval_ns: u32,
 
+    /// Steps of the from-rest acceleration ramp already covered when the
+    /// profile starts (0 = starts from rest).
+    pub accel_offset_steps: u32,
+
     /// Cruise step interval (nanoseconds) - at max velocity.
     pub cruise_interval_ns: u32,
 
+    /// Steps of the deceleration ramp to rest still left when the profile
+    /// ends (0 = ends at rest).
+    pub decel_offset_steps: u32,
+
     /// Acceleration rate in steps/sec².
     pub accel_rate: f32,
 
@@ -82,68 +143,179 @@ pub struct MotionProfile {
 impl MotionProfile {
     /// Create an asymmetric trapezoidal motion profile.
     ///
+    /// Moves longer than `u32::MAX` steps cannot be represented and produce a
+    /// zero-length profile; use [`try_asymmetric_trapezoidal`] to detect that case.
+    ///
     /// # Arguments
     ///
     /// * `total_steps` - Signed step count (positive = CW, negative = CCW)
     /// * `max_velocity` - Maximum velocity in steps/sec
     /// * `acceleration` - Acceleration rate in steps/sec²
     /// * `deceleration` - Deceleration rate in steps/sec²
+    /// * `initial_velocity` - Velocity at the first step in steps/sec
+    ///   (0.0 = from rest, capped at `max_velocity`)
+    ///
+    /// [`try_asymmetric_trapezoidal`]: Self::try_asymmetric_trapezoidal
     pub fn asymmetric_trapezoidal(
         total_steps: i64,
         max_velocity: f32,
         acceleration: f32,
         deceleration: f32,
+        initial_velocity: f32,
     ) -> Self {
+        Self::try_asymmetric_trapezoidal(
+            total_steps,
+            max_velocity,
+            acceleration,
+            deceleration,
+            initial_velocity,
+        )
+        .unwrap_or_else(|_| Self::zero())
+    }
+
+    /// Create an asymmetric trapezoidal motion profile, rejecting moves that
+    /// don't fit the profile's step counter.
+    ///
+    /// A positive `initial_velocity` starts the acceleration ramp where a ramp
+    /// from rest would have reached it, so fewer steps are needed to reach
+    /// cruise. If the move is too short to stop from `initial_velocity`, it
+    /// decelerates over every step.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotionError::Overflow` if `|total_steps|` exceeds `u32::MAX`,
+    /// and `MotionError::NonFiniteInput` for a NaN or infinite velocity or
+    /// rate.
+    pub fn try_asymmetric_trapezoidal(
+        total_steps: i64,
+        max_velocity: f32,
+        acceleration: f32,
+        deceleration: f32,
+        initial_velocity: f32,
+    ) -> Result<Self> {
+        Self::try_between_velocities(
+            total_steps,
+            max_velocity,
+            acceleration,
+            deceleration,
+            initial_velocity,
+            0.0,
+        )
+    }
+
+    /// Create an asymmetric trapezoidal profile that ends at
+    /// `final_velocity` (steps/sec) instead of at rest.
+    ///
+    /// The deceleration ramp stops where a ramp to rest would still have
+    /// `final_velocity² / 2d` steps to go, so a following profile starting at
+    /// `final_velocity` continues without a velocity jump. Both boundary
+    /// velocities are capped at `max_velocity`. A move too short to slow from
+    /// `initial_velocity` to `final_velocity` decelerates over every step.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotionError::Overflow` if `|total_steps|` exceeds `u32::MAX`,
+    /// and `MotionError::NonFiniteInput` for a NaN or infinite velocity or
+    /// rate.
+    pub fn try_between_velocities(
+        total_steps: i64,
+        max_velocity: f32,
+        acceleration: f32,
+        deceleration: f32,
+        initial_velocity: f32,
+        final_velocity: f32,
+    ) -> Result<Self> {
+        // NaN would pass the non-positive check below and poison the timing
+        let inputs = [
+            ("max_velocity", max_velocity),
+            ("acceleration", acceleration),
+            ("deceleration", deceleration),
+            ("initial_velocity", initial_velocity),
+            ("final_velocity", final_velocity),
+        ];
+        if let Some(&(parameter, value)) = inputs.iter().find(|(_, v)| !v.is_finite()) {
+            return Err(Error::Motion(MotionError::NonFiniteInput { parameter, value }));
+        }
+
         let direction = Direction::from_steps(total_steps);
-        let steps = total_steps.unsigned_abs() as u32;
+        let steps = u32::try_from(total_steps.unsigned_abs())
+            .map_err(|_| Error::Motion(MotionError::Overflow))?;
 
         if steps == 0 || max_velocity <= 0.0 || acceleration <= 0.0 || deceleration <= 0.0 {
-            return Self::zero();
+            return Ok(Self::zero());
         }
 
-        // Calculate phase lengths for asymmetric profile
-        // Time to reach max velocity: t = v_max / a
-        // Distance during acceleration: d = 0.5 * a * t²
-        let t_accel = max_velocity / acceleration;
-        let t_decel = max_velocity / deceleration;
-
-        let accel_distance = 0.5 * acceleration * t_accel * t_accel;
-        let decel_distance = 0.5 * deceleration * t_decel * t_decel;
-
-        let (accel_steps, cruise_steps, decel_steps) =
-            if accel_distance + decel_distance >= steps as f32 {
-                // Triangle profile: can't reach max velocity
-                // Scale down proportionally based on acceleration rates
-                let ratio = acceleration / (acceleration + deceleration);
-                let accel_steps = (steps as f32 * ratio) as u32;
-                let decel_steps = steps.saturating_sub(accel_steps);
-                (accel_steps, 0u32, decel_steps)
-            } else {
-                // Full trapezoidal profile
-                let accel_steps = accel_distance as u32;
-                let decel_steps = decel_distance as u32;
-                let cruise_steps = steps.saturating_sub(accel_steps + decel_steps);
-                (accel_steps, cruise_steps, decel_steps)
-            };
+        // Phase distances are computed in f64: above ~16.7M steps f32 can no
+        // longer represent every integer step count.
+        let v_max = max_velocity as f64;
+        let v0 = (initial_velocity as f64).clamp(0.0, v_max);
+        let v1 = (final_velocity as f64).clamp(0.0, v_max);
+        let accel = acceleration as f64;
+        let decel = deceleration as f64;
+
+        // Distance covered by a ramp from rest up to v0: v0² / 2a, and
+        // likewise from v1 down to rest
+        let accel_offset_steps = (v0 * v0 / (2.0 * accel)) as u32;
+        let decel_offset_steps = (v1 * v1 / (2.0 * decel)) as u32;
+        let accel_distance = (v_max * v_max - v0 * v0) / (2.0 * accel);
+        let decel_distance = (v_max * v_max - v1 * v1) / (2.0 * decel);
+
+        let (accel_steps, cruise_steps, decel_steps) = if (v0 * v0 - v1 * v1) / (2.0 * decel)
+            >= steps as f64
+        {
+            // Not even room to slow down: decelerate all the way
+            (0, 0, steps)
+        } else if accel_distance + decel_distance >= steps as f64 {
+            // Triangle profile: can't reach max velocity
+            // Peak v satisfies (v² - v0²) / 2a + (v² - v1²) / 2d = steps;
+            // from rest to rest the accel share is d / (a + d)
+            let peak_sq = (2.0 * steps as f64 + v0 * v0 / accel + v1 * v1 / decel)
+                / (1.0 / accel + 1.0 / decel);
+            let accel_steps = (((peak_sq - v0 * v0) / (2.0 * accel)) as u32).min(steps);
+            (accel_steps, 0, steps - accel_steps)
+        } else {
+            // Full trapezoidal profile (both distances are below `steps`)
+            let accel_steps = accel_distance as u32;
+            let decel_steps = decel_distance as u32;
+            (accel_steps, steps.saturating_sub(accel_steps + decel_steps), decel_steps)
+        };
 
         // Calculate step intervals
-        // Initial interval is very long (starting from rest)
-        // We use a practical minimum initial velocity
-        let initial_velocity = sqrtf(2.0 * acceleration);
-        let initial_interval_ns = (1_000_000_000.0 / initial_velocity) as u32;
+        // The first step from rest takes sqrt(2 / a) seconds
+        let initial_interval_ns = interval_from_rest(accel_offset_steps, acceleration);
         let cruise_interval_ns = (1_000_000_000.0 / max_velocity) as u32;
 
-        Self {
+        Ok(Self {
             total_steps: steps,
             direction,
             accel_steps,
             cruise_steps,
             decel_steps,
             initial_interval_ns,
+            accel_offset_steps,
             cruise_interval_ns,
+            decel_offset_steps,
             accel_rate: acceleration,
             decel_rate: deceleration,
+        })
+    }
+
+    /// Create the cheapest suitable profile for a move from rest.
+    ///
+    /// Moves of at most [`MIN_TRAPEZOIDAL_STEPS`] steps barely accelerate
+    /// before they must slow down again, so they run at a constant 25% of
+    /// `max_velocity`. Longer moves get the full asymmetric trapezoidal
+    /// profile.
+    pub fn optimal(
+        total_steps: i64,
+        max_velocity: f32,
+        acceleration: f32,
+        deceleration: f32,
+    ) -> Self {
+        if total_steps.unsigned_abs() <= MIN_TRAPEZOIDAL_STEPS as u64 {
+            return Self::constant_velocity(total_steps, max_velocity * 0.25);
         }
+        Self::asymmetric_trapezoidal(total_steps, max_velocity, acceleration, deceleration, 0.0)
     }
 
     /// Create a symmetric trapezoidal profile (same accel and decel).
@@ -152,7 +324,78 @@ impl MotionProfile {
         max_velocity: f32,
         acceleration: f32,
     ) -> Self {
-        Self::asymmetric_trapezoidal(total_steps, max_velocity, acceleration, acceleration)
+        Self::asymmetric_trapezoidal(total_steps, max_velocity, acceleration, acceleration, 0.0)
+    }
+
+    /// Create a symmetric trapezoidal profile, rejecting moves that don't fit
+    /// the profile's step counter.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotionError::Overflow` if `|total_steps|` exceeds `u32::MAX`,
+    /// and `MotionError::NonFiniteInput` for a NaN or infinite velocity or
+    /// rate.
+    pub fn try_symmetric_trapezoidal(
+        total_steps: i64,
+        max_velocity: f32,
+        acceleration: f32,
+    ) -> Result<Self> {
+        Self::try_asymmetric_trapezoidal(total_steps, max_velocity, acceleration, acceleration, 0.0)
+    }
+
+    /// Create a profile for a motor already moving at `current_velocity`
+    /// (steps/sec) in the direction of `remaining_steps`.
+    ///
+    /// The acceleration ramp picks up where a ramp from rest would have
+    /// reached `current_velocity`, so the first step continues the motion
+    /// without a velocity jump. It is shorter than a ramp from rest, or absent
+    /// when already at `max_velocity`; velocities above `max_velocity` are
+    /// treated as `max_velocity`. If the move is too short to stop from
+    /// `current_velocity`, it decelerates over every remaining step, clipped
+    /// like [`stop_from`](Self::stop_from). A non-positive
+    /// `current_velocity` gives the same profile as
+    /// [`asymmetric_trapezoidal`](Self::asymmetric_trapezoidal).
+    pub fn starting_from_velocity(
+        current_velocity: f32,
+        max_velocity: f32,
+        acceleration: f32,
+        deceleration: f32,
+        remaining_steps: i64,
+    ) -> Self {
+        Self::asymmetric_trapezoidal(
+            remaining_steps,
+            max_velocity,
+            acceleration,
+            deceleration,
+            current_velocity,
+        )
+    }
+
+    /// Create a constant-velocity profile with no acceleration ramps.
+    ///
+    /// The motor starts and stops abruptly at `velocity` (steps/sec), so keep
+    /// it below the motor's start/stop rate. Moves longer than `u32::MAX`
+    /// steps or a non-positive velocity produce a zero-length profile.
+    pub fn constant_velocity(total_steps: i64, velocity: f32) -> Self {
+        let steps = match u32::try_from(total_steps.unsigned_abs()) {
+            Ok(steps) if steps > 0 && velocity > 0.0 => steps,
+            _ => return Self::zero(),
+        };
+        let interval_ns = (1_000_000_000.0 / velocity) as u32;
+
+        Self {
+            total_steps: steps,
+            direction: Direction::from_steps(total_steps),
+            accel_steps: 0,
+            cruise_steps: steps,
+            decel_steps: 0,
+            initial_interval_ns: interval_ns,
+            accel_offset_steps: 0,
+            cruise_interval_ns: interval_ns,
+            decel_offset_steps: 0,
+            accel_rate: 0.0,
+            decel_rate: 0.0,
+        }
     }
 
     /// Create a zero-length profile (no motion).
@@ -164,25 +407,304 @@ impl MotionProfile {
             cruise_steps: 0,
             decel_steps: 0,
             initial_interval_ns: u32::MAX,
+            accel_offset_steps: 0,
             cruise_interval_ns: u32::MAX,
+            decel_offset_steps: 0,
             accel_rate: 0.0,
             decel_rate: 0.0,
         }
     }
 
+    /// Get the profile re-planned for a load that gravity helps to stop.
+    ///
+    /// Moving in `direction`, the deceleration rate is multiplied by
+    /// `gravity_decel_modifier`; moving the other way, it is divided by it.
+    /// Acceleration and cruise velocity are unchanged. Profiles without a
+    /// deceleration ramp and non-positive modifiers are returned as is.
+    pub fn with_gravity_compensation(
+        self,
+        gravity_decel_modifier: f32,
+        direction: Direction,
+    ) -> Self {
+        let m = gravity_decel_modifier;
+        if self.is_zero() || self.decel_rate <= 0.0 || !(m > 0.0 && m.is_finite()) {
+            return self;
+        }
+
+        let decel_rate = if self.direction == direction {
+            self.decel_rate * m
+        } else {
+            self.decel_rate / m
+        };
+        self.replanned(self.accel_rate, decel_rate)
+    }
+
+    /// Get the profile re-planned with the acceleration rate times `scale`.
+    ///
+    /// Cruise velocity, deceleration and start velocity are unchanged.
+    /// Profiles without an acceleration rate and non-positive scales are
+    /// returned as is.
+    pub fn with_acceleration_scale(self, scale: f32) -> Self {
+        if self.is_zero() || self.accel_rate <= 0.0 || !(scale > 0.0 && scale.is_finite()) {
+            return self;
+        }
+        self.replanned(self.accel_rate * scale, self.decel_rate)
+    }
+
+    /// Re-plan with new rates, keeping the steps, cruise velocity and
+    /// boundary velocities.
+    fn replanned(&self, acceleration: f32, deceleration: f32) -> Self {
+        Self::try_between_velocities(
+            self.direction.sign() * self.total_steps as i64,
+            1_000_000_000.0 / self.cruise_interval_ns as f32,
+            acceleration,
+            deceleration,
+            self.start_velocity(),
+            self.end_velocity(),
+        )
+        .unwrap_or_else(|_| Self::zero())
+    }
+
+    /// Velocity in steps/sec the acceleration ramp starts from (0.0 from rest).
+    pub fn start_velocity(&self) -> f32 {
+        sqrtf(2.0 * self.accel_rate * self.accel_offset_steps as f32)
+    }
+
+    /// Velocity in steps/sec the deceleration ramp ends at (0.0 at rest).
+    pub fn end_velocity(&self) -> f32 {
+        sqrtf(2.0 * self.decel_rate * self.decel_offset_steps as f32)
+    }
+
+    /// Get the same profile with its direction reversed.
+    ///
+    /// Step timing depends only on the step index, so the inverted profile
+    /// retraces the original move exactly without recomputation.
+    pub fn invert(&self) -> Self {
+        let direction = match self.direction {
+            Direction::Clockwise => Direction::CounterClockwise,
+            Direction::CounterClockwise => Direction::Clockwise,
+        };
+        Self {
+            direction,
+            ..self.clone()
+        }
+    }
+
+    /// Number of steps a controlled stop from `step` takes, i.e. the length
+    /// of [`stop_from`](Self::stop_from).
+    ///
+    /// Stopping from the velocity of the step just taken, v, takes v² / 2d
+    /// steps at the deceleration rate d, rounded up and clipped to the rest
+    /// of the move. 0 before the first step and once the move is done.
+    pub fn stop_steps_from(&self, step: u32) -> u32 {
+        let remaining = self.total_steps.saturating_sub(step);
+        if step == 0 || remaining == 0 || self.decel_rate <= 0.0 {
+            return 0;
+        }
+        let velocity = 1_000_000_000.0 / self.interval_at(step - 1) as f32;
+        let stop_steps = ceilf(velocity * velocity / (2.0 * self.decel_rate)) as u32;
+        stop_steps.clamp(1, remaining)
+    }
+
+    /// Get a deceleration-only profile that stops the move from `step`.
+    ///
+    /// Starts at the velocity reached after `step` steps and ramps down at the
+    /// deceleration rate, clipped so it never runs past the end of the move.
+    /// A profile without a deceleration rate, such as
+    /// [`constant_velocity`](Self::constant_velocity), stops abruptly: the
+    /// stop is zero-length.
+    pub fn stop_from(&self, step: u32) -> Self {
+        let steps = self.stop_steps_from(step);
+        if steps == 0 {
+            return Self::zero();
+        }
+        let interval_ns = self.interval_at(step - 1);
+
+        Self {
+            total_steps: steps,
+            direction: self.direction,
+            accel_steps: 0,
+            cruise_steps: 0,
+            decel_steps: steps,
+            initial_interval_ns: interval_ns,
+            accel_offset_steps: 0,
+            cruise_interval_ns: interval_ns,
+            decel_offset_steps: 0,
+            accel_rate: self.accel_rate,
+            decel_rate: self.decel_rate,
+        }
+    }
+
+    /// Get a fresh profile for the steps left after `step`.
+    ///
+    /// The remainder starts from rest with the standard acceleration ramp and
+    /// the original cruise velocity and rates. A profile without an
+    /// acceleration or deceleration rate, such as
+    /// [`constant_velocity`](Self::constant_velocity), has no ramp to replan:
+    /// the remainder runs its steps at the original cruise interval.
+    pub fn remainder_from(&self, step: u32) -> Self {
+        let remaining = self.total_steps.saturating_sub(step);
+        if remaining == 0 || self.cruise_interval_ns == u32::MAX {
+            return Self::zero();
+        }
+        if self.accel_rate <= 0.0 || self.decel_rate <= 0.0 {
+            return Self {
+                total_steps: remaining,
+                direction: self.direction,
+                accel_steps: 0,
+                cruise_steps: remaining,
+                decel_steps: 0,
+                initial_interval_ns: self.cruise_interval_ns,
+                accel_offset_steps: 0,
+                cruise_interval_ns: self.cruise_interval_ns,
+                decel_offset_steps: 0,
+                accel_rate: 0.0,
+                decel_rate: 0.0,
+            };
+        }
+
+        let max_velocity = 1_000_000_000.0 / self.cruise_interval_ns as f32;
+        Self::try_between_velocities(
+            self.direction.sign() * remaining as i64,
+            max_velocity,
+            self.accel_rate,
+            self.decel_rate,
+            0.0,
+            self.end_velocity(),
+        )
+        .unwrap_or_else(|_| Self::zero())
+    }
+
     /// Check if this is a zero-length profile.
     #[inline]
     pub fn is_zero(&self) -> bool {
         self.total_steps == 0
     }
 
+    /// Check if the profile never reaches cruise velocity (no cruise phase).
+    #[inline]
+    pub fn is_triangle(&self) -> bool {
+        self.cruise_steps == 0
+    }
+
+    /// Check if the profile has a cruise phase, i.e. is not a triangle.
+    #[inline]
+    pub fn can_cruise(&self) -> bool {
+        !self.is_triangle()
+    }
+
+    /// Get the same move lengthened by `extra_steps` spent cruising.
+    ///
+    /// A trapezoidal profile keeps its cruise velocity and simply cruises
+    /// longer. A triangle gains a cruise phase at the peak velocity it
+    /// reaches, so the ramps are unchanged and only the plateau is new. A
+    /// zero-length profile has no velocity to cruise at and is returned as is.
+    /// The lengthened move is capped at `u32::MAX` steps.
+    pub fn upgrade_to_trapezoidal(&self, extra_steps: u32) -> Self {
+        let extra_steps = extra_steps.min(u32::MAX - self.total_steps);
+        if extra_steps == 0 || self.is_zero() {
+            return self.clone();
+        }
+
+        let mut upgraded = self.clone();
+        if self.is_triangle() {
+            // Slower of the two ramp ends, so neither ramp has to speed up
+            let peak_interval = match (self.accel_steps, self.decel_steps) {
+                (0, _) => self.interval_at(self.decel_start()),
+                (a, 0) => self.interval_at(a - 1),
+                (a, _) => self.interval_at(a - 1).max(self.interval_at(self.decel_start())),
+            };
+            upgraded.cruise_interval_ns = peak_interval;
+        }
+        upgraded.cruise_steps = self.cruise_steps + extra_steps;
+        upgraded.total_steps = upgraded.accel_steps + upgraded.cruise_steps + upgraded.decel_steps;
+        upgraded
+    }
+
+    /// Get the step at which acceleration begins (always 0).
+    #[inline]
+    pub fn accel_start(&self) -> u32 {
+        0
+    }
+
+    /// Get the step at which the cruise phase begins.
+    #[inline]
+    pub fn cruise_start(&self) -> u32 {
+        self.accel_steps
+    }
+
+    /// Get the step at which deceleration begins.
+    #[inline]
+    pub fn decel_start(&self) -> u32 {
+        self.accel_steps + self.cruise_steps
+    }
+
+    /// Get the number of steps in a phase (0 for `Complete`).
+    pub fn steps_in_phase(&self, phase: MotionPhase) -> u32 {
+        match phase {
+            MotionPhase::Accelerating => self.accel_steps,
+            MotionPhase::Cruising => self.cruise_steps,
+            MotionPhase::Decelerating => self.decel_steps,
+            MotionPhase::Complete => 0,
+        }
+    }
+
+    /// Get the time spent in a phase in seconds (0 for `Complete`).
+    pub fn phase_duration_secs(&self, phase: MotionPhase) -> f32 {
+        match phase {
+            MotionPhase::Accelerating if self.accel_rate > 0.0 => {
+                let offset = self.accel_offset_steps as f32;
+                sqrtf(2.0 * (offset + self.accel_steps as f32) / self.accel_rate)
+                    - sqrtf(2.0 * offset / self.accel_rate)
+            }
+            MotionPhase::Cruising if self.cruise_steps > 0 => {
+                self.cruise_steps as f32 * self.cruise_interval_ns as f32 * 1e-9
+            }
+            MotionPhase::Decelerating if self.decel_rate > 0.0 => {
+                let offset = self.decel_offset_steps as f32;
+                sqrtf(2.0 * (offset + self.decel_steps as f32) / self.decel_rate)
+                    - sqrtf(2.0 * offset / self.decel_rate)
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Get the total distance in steps as `u64`, for accumulating very long
+    /// moves.
+    #[inline]
+    pub fn total_distance_steps(&self) -> u64 {
+        self.total_steps as u64
+    }
+
+    /// Estimate the resistive energy dissipated in one winding over the move,
+    /// in joules.
+    ///
+    /// Integrates `I²R` over each phase's duration, with `I` the peak current
+    /// scaled by the phase's [`duty_cycle`](MotionPhase::duty_cycle). Holding
+    /// current after the move is not included. This is a rough figure for
+    /// battery lifetime estimates, not a thermal model.
+    pub fn energy_estimate(&self, winding_resistance_ohm: f32, peak_current_ma: u16) -> f32 {
+        let peak_current_a = peak_current_ma as f32 / 1000.0;
+        [
+            MotionPhase::Accelerating,
+            MotionPhase::Cruising,
+            MotionPhase::Decelerating,
+        ]
+        .iter()
+        .map(|&phase| {
+            let current = peak_current_a * phase.duty_cycle();
+            current * current * winding_resistance_ohm * self.phase_duration_secs(phase)
+        })
+        .sum()
+    }
+
     /// Get the phase at a given step number.
     pub fn phase_at(&self, step: u32) -> MotionPhase {
         if step >= self.total_steps {
             MotionPhase::Complete
-        } else if step < self.accel_steps {
+        } else if step < self.cruise_start() {
             MotionPhase::Accelerating
-        } else if step < self.accel_steps + self.cruise_steps {
+        } else if step < self.decel_start() {
             MotionPhase::Cruising
         } else {
             MotionPhase::Decelerating
@@ -199,57 +721,116 @@ impl MotionProfile {
             MotionPhase::Complete => u32::MAX,
             MotionPhase::Cruising => self.cruise_interval_ns,
             MotionPhase::Accelerating => {
-                // During acceleration: interval decreases
-                // t_n = t_0 * sqrt(n / (n + 1)) approximately
-                // We use a simplified linear interpolation for now
-                let progress = step as f32 / self.accel_steps.max(1) as f32;
-                let interval = self.initial_interval_ns as f32
-                    - (self.initial_interval_ns as f32 - self.cruise_interval_ns as f32) * progress;
-                interval as u32
+                // Constant acceleration, continuing any ramp covered before the start
+                interval_from_rest(step.saturating_add(self.accel_offset_steps), self.accel_rate)
+                    .max(self.cruise_interval_ns)
             }
             MotionPhase::Decelerating => {
-                // During deceleration: interval increases
-                let decel_step = step - self.accel_steps - self.cruise_steps;
-                let progress = decel_step as f32 / self.decel_steps.max(1) as f32;
-                let interval = self.cruise_interval_ns as f32
-                    + (self.initial_interval_ns as f32 - self.cruise_interval_ns as f32) * progress;
-                interval as u32
+                // Mirror image of accelerating: count steps left until rest
+                let steps_left = self.total_steps - step - 1 + self.decel_offset_steps;
+                interval_from_rest(steps_left, self.decel_rate).max(self.cruise_interval_ns)
             }
         }
     }
 
+    /// Get the time in nanoseconds from the first step to step `step`: the
+    /// sum of [`interval_at`](Self::interval_at) over the steps before it.
+    ///
+    /// Exact to the executor's integer intervals, so a follower can compare
+    /// its own elapsed time against a leader's schedule. Steps past the end
+    /// give the time of the last step; cruise steps cost nothing to skip.
+    pub fn elapsed_time_ns_at_step(&self, step: u32) -> u64 {
+        let step = step.min(self.total_steps);
+        let decel_start = self.decel_start();
+        let ramp = |steps: core::ops::Range<u32>| {
+            steps.map(|i| self.interval_at(i) as u64).sum::<u64>()
+        };
+
+        let mut elapsed = ramp(0..step.min(self.accel_steps));
+        if step > self.accel_steps {
+            let cruised = step.min(decel_start) - self.accel_steps;
+            elapsed += cruised as u64 * self.cruise_interval_ns as u64;
+        }
+        if step > decel_start {
+            elapsed += ramp(decel_start..step);
+        }
+        elapsed
+    }
+
+    /// Get [`elapsed_time_ns_at_step`](Self::elapsed_time_ns_at_step) for
+    /// every step, computed in one pass.
+    ///
+    /// Entry `i` is the time of step `i` (so the first is 0). Moves longer
+    /// than `N` steps keep the first `N` entries.
+    pub fn cumulative_times<const N: usize>(&self) -> heapless::Vec<u64, N> {
+        let mut times = heapless::Vec::new();
+        let mut elapsed = 0u64;
+        for step in 0..self.total_steps {
+            if times.push(elapsed).is_err() {
+                break;
+            }
+            elapsed += self.interval_at(step) as u64;
+        }
+        times
+    }
+
     /// Estimate total duration of the motion profile in seconds.
     ///
-    /// This is an approximation based on the trapezoidal profile phases.
+    /// Each phase is timed from its own step count, so triangle profiles that
+    /// never reach cruise velocity are not over-estimated.
     pub fn estimated_duration_secs(&self) -> f32 {
-        if self.total_steps == 0 {
+        self.remaining_duration_from(0)
+    }
+
+    /// Estimate the time in seconds needed to finish the move from `step`.
+    ///
+    /// Computed analytically from the phase boundaries (constant acceleration,
+    /// constant cruise, constant deceleration) without iterating over steps.
+    pub fn remaining_duration_from(&self, step: u32) -> f32 {
+        if step >= self.total_steps {
             return 0.0;
         }
 
-        let cruise_velocity = 1_000_000_000.0 / self.cruise_interval_ns as f32;
+        let decel_start = self.accel_steps + self.cruise_steps;
+        let mut remaining = 0.0;
 
-        // Time for each phase
-        // Acceleration: v = a*t, so t = v/a
-        let accel_time = if self.accel_rate > 0.0 {
-            cruise_velocity / self.accel_rate
-        } else {
-            0.0
-        };
+        // Acceleration: step n of a ramp from rest fires at sqrt(2n / a) seconds
+        if step < self.accel_steps && self.accel_rate > 0.0 {
+            let offset = self.accel_offset_steps as f32;
+            remaining += sqrtf(2.0 * (offset + self.accel_steps as f32) / self.accel_rate)
+                - sqrtf(2.0 * (offset + step as f32) / self.accel_rate);
+        }
 
-        // Cruise: t = distance / velocity
-        let cruise_time = self.cruise_steps as f32 / cruise_velocity;
+        // Cruise at constant velocity
+        if step < decel_start && self.cruise_steps > 0 {
+            let cruise_velocity = 1_000_000_000.0 / self.cruise_interval_ns as f32;
+            let cruise_left = decel_start - step.max(self.accel_steps);
+            remaining += cruise_left as f32 / cruise_velocity;
+        }
 
-        // Deceleration: t = v/d
-        let decel_time = if self.decel_rate > 0.0 {
-            cruise_velocity / self.decel_rate
-        } else {
-            0.0
-        };
+        // Deceleration to rest: stopping within r steps takes sqrt(2r / d) seconds
+        if self.decel_rate > 0.0 {
+            let offset = self.decel_offset_steps as f32;
+            let decel_left = (self.total_steps - step.max(decel_start)) as f32;
+            remaining += sqrtf(2.0 * (offset + decel_left) / self.decel_rate)
+                - sqrtf(2.0 * offset / self.decel_rate);
+        }
 
-        accel_time + cruise_time + decel_time
+        remaining
     }
 }
 
+/// Interval in nanoseconds between step `n` and step `n + 1` of a move
+/// starting from rest at constant `rate` (steps/sec²).
+///
+/// Step `n` fires at `sqrt(2n / rate)`, so the interval is
+/// `sqrt(2 / rate) * (sqrt(n + 1) - sqrt(n))`, rewritten to avoid cancellation
+/// for large `n`.
+fn interval_from_rest(n: u32, rate: f32) -> u32 {
+    let n = n as f32;
+    (1_000_000_000.0 * sqrtf(2.0 / rate) / (sqrtf(n + 1.0) + sqrtf(n))) as u32
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -276,6 +857,7 @@ mod tests {
             1000.0, // steps/sec
             2000.0, // accel steps/sec²
             1000.0, // decel steps/sec² (slower)
+            0.0,    // from rest
         );
 
         assert!(profile.decel_steps > profile.accel_steps);
@@ -292,6 +874,193 @@ mod tests {
 
         // Should be a triangle (no cruise phase)
         assert_eq!(profile.cruise_steps, 0);
+        assert!(profile.is_triangle());
+    }
+
+    #[test]
+    fn test_upgrade_to_trapezoidal() {
+        let triangle = MotionProfile::symmetric_trapezoidal(100, 10000.0, 1000.0);
+        assert!(!triangle.can_cruise());
+        assert_eq!(triangle.upgrade_to_trapezoidal(0).total_steps, triangle.total_steps);
+
+        let upgraded = triangle.upgrade_to_trapezoidal(50);
+        assert!(upgraded.can_cruise());
+        assert_eq!(upgraded.total_steps, 150);
+        assert_eq!(upgraded.cruise_steps, 50);
+        assert_eq!(upgraded.accel_steps, triangle.accel_steps);
+        assert_eq!(upgraded.decel_steps, triangle.decel_steps);
+        // Cruises at the peak the triangle reached, with the ramps untouched
+        let peak = triangle.interval_at(triangle.accel_steps - 1);
+        assert_eq!(upgraded.cruise_interval_ns, peak);
+        for step in 0..triangle.accel_steps {
+            assert_eq!(upgraded.interval_at(step), triangle.interval_at(step));
+        }
+        assert_eq!(upgraded.interval_at(upgraded.cruise_start()), peak);
+
+        let trapezoid = MotionProfile::symmetric_trapezoidal(1000, 1000.0, 2000.0);
+        let longer = trapezoid.upgrade_to_trapezoidal(200);
+        assert_eq!(longer.total_steps, 1200);
+        assert_eq!(longer.cruise_steps, trapezoid.cruise_steps + 200);
+        assert_eq!(longer.cruise_interval_ns, trapezoid.cruise_interval_ns);
+
+        assert!(MotionProfile::zero().upgrade_to_trapezoidal(10).is_zero());
+
+        // Capped at the step counter's range instead of overflowing
+        let longest = trapezoid.upgrade_to_trapezoidal(u32::MAX);
+        assert_eq!(longest.total_steps, u32::MAX);
+        assert_eq!(longest.cruise_steps, u32::MAX - trapezoid.accel_steps - trapezoid.decel_steps);
+        assert_eq!(longest.upgrade_to_trapezoidal(1).total_steps, u32::MAX);
+    }
+
+    #[test]
+    fn test_phase_boundaries() {
+        let profile = MotionProfile::symmetric_trapezoidal(1000, 1000.0, 2000.0);
+        assert!(!profile.is_triangle());
+        assert_eq!(profile.accel_start(), 0);
+        assert_eq!(profile.cruise_start(), profile.accel_steps);
+        assert_eq!(profile.decel_start(), profile.accel_steps + profile.cruise_steps);
+
+        for phase in [
+            MotionPhase::Accelerating,
+            MotionPhase::Cruising,
+            MotionPhase::Decelerating,
+        ] {
+            let count = (0..profile.total_steps).filter(|&s| profile.phase_at(s) == phase).count();
+            assert_eq!(profile.steps_in_phase(phase), count as u32);
+        }
+        assert_eq!(profile.steps_in_phase(MotionPhase::Complete), 0);
+
+        assert_eq!(profile.phase_at(profile.cruise_start() - 1), MotionPhase::Accelerating);
+        assert_eq!(profile.phase_at(profile.cruise_start()), MotionPhase::Cruising);
+        assert_eq!(profile.phase_at(profile.decel_start()), MotionPhase::Decelerating);
+    }
+
+    #[test]
+    fn test_asymmetric_triangle_split() {
+        // Fast accel, slow decel: most of the distance is spent decelerating
+        let profile = MotionProfile::asymmetric_trapezoidal(800, 3200.0, 6400.0, 1600.0, 0.0);
+        assert_eq!(profile.cruise_steps, 0);
+        assert_eq!(profile.accel_steps, 160);
+        assert_eq!(profile.decel_steps, 640);
+    }
+
+    #[test]
+    fn test_overflow_rejected() {
+        let result = MotionProfile::try_symmetric_trapezoidal(5_000_000_000, 1000.0, 2000.0);
+        assert_eq!(result.unwrap_err(), Error::Motion(MotionError::Overflow));
+
+        let result = MotionProfile::try_symmetric_trapezoidal(-5_000_000_000, 1000.0, 2000.0);
+        assert_eq!(result.unwrap_err(), Error::Motion(MotionError::Overflow));
+
+        // The infallible constructor refuses to move rather than truncating
+        let profile = MotionProfile::symmetric_trapezoidal(5_000_000_000, 1000.0, 2000.0);
+        assert!(profile.is_zero());
+    }
+
+    #[test]
+    fn test_non_finite_rejected() {
+        let result = MotionProfile::try_symmetric_trapezoidal(1000, f32::INFINITY, 2000.0);
+        assert_eq!(
+            result.unwrap_err(),
+            Error::Motion(MotionError::NonFiniteInput {
+                parameter: "max_velocity",
+                value: f32::INFINITY,
+            })
+        );
+
+        let result = MotionProfile::try_asymmetric_trapezoidal(1000, 1000.0, 2000.0, 0.0, f32::NAN);
+        assert!(matches!(
+            result,
+            Err(Error::Motion(MotionError::NonFiniteInput { parameter: "initial_velocity", .. }))
+        ));
+
+        let profile = MotionProfile::symmetric_trapezoidal(1000, 1000.0, f32::NAN);
+        assert!(profile.is_zero());
+    }
+
+    #[test]
+    fn test_large_move_phases_sum_exactly() {
+        // Above 2^24 steps f32 can't represent every integer
+        let steps = 16_777_217u32;
+        let profile = MotionProfile::try_asymmetric_trapezoidal(steps as i64, 1e9, 3.0, 7.0, 0.0)
+            .unwrap();
+        assert_eq!(
+            profile.accel_steps + profile.cruise_steps + profile.decel_steps,
+            steps
+        );
+
+        let profile = MotionProfile::try_symmetric_trapezoidal(u32::MAX as i64, 50_000.0, 1000.0)
+            .unwrap();
+        assert_eq!(profile.total_steps, u32::MAX);
+        assert_eq!(
+            profile.accel_steps as u64 + profile.cruise_steps as u64 + profile.decel_steps as u64,
+            u32::MAX as u64
+        );
+    }
+
+    #[test]
+    fn test_remaining_duration_from_start_matches_estimate() {
+        for profile in [
+            MotionProfile::symmetric_trapezoidal(1000, 1000.0, 2000.0),
+            MotionProfile::asymmetric_trapezoidal(1000, 1000.0, 2000.0, 500.0, 0.0),
+            MotionProfile::symmetric_trapezoidal(100, 10000.0, 1000.0),
+        ] {
+            assert_eq!(profile.remaining_duration_from(0), profile.estimated_duration_secs());
+            assert_eq!(profile.remaining_duration_from(profile.total_steps), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_triangle_duration_not_overestimated() {
+        // 100 steps at 1000 steps/s² peaks far below the 10000 steps/s cruise:
+        // 50 steps each way take sqrt(2 * 50 / 1000) ≈ 0.316 s
+        let profile = MotionProfile::symmetric_trapezoidal(100, 10000.0, 1000.0);
+        assert!((profile.estimated_duration_secs() - 0.632).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_invert() {
+        let profile = MotionProfile::asymmetric_trapezoidal(1000, 1000.0, 2000.0, 500.0, 0.0);
+        let inverted = profile.invert();
+
+        assert_eq!(inverted.direction, Direction::CounterClockwise);
+        assert_eq!(inverted.total_steps, profile.total_steps);
+        assert_eq!(inverted.accel_steps, profile.accel_steps);
+        assert_eq!(inverted.decel_steps, profile.decel_steps);
+        assert_eq!(inverted.interval_at(10), profile.interval_at(10));
+        assert_eq!(inverted.invert().direction, Direction::Clockwise);
+    }
+
+    #[test]
+    fn test_phase_predicates() {
+        use MotionPhase::*;
+
+        assert!(Complete.is_terminal());
+        assert!(!Cruising.is_terminal());
+        assert!(Accelerating.is_changing_velocity() && Decelerating.is_changing_velocity());
+        assert!(!Cruising.is_changing_velocity() && !Complete.is_changing_velocity());
+        assert!(Cruising.is_constant_velocity());
+        assert!(!Accelerating.is_constant_velocity());
+        assert_eq!(Decelerating.as_str(), "Decelerating");
+    }
+
+    #[test]
+    fn test_split_covers_remaining_steps() {
+        let profile = MotionProfile::asymmetric_trapezoidal(1000, 1000.0, 2000.0, 500.0, 0.0);
+        for step in [0, 1, 100, 250, 500, 900, 999, 1000] {
+            let stop = profile.stop_from(step);
+            let remainder = profile.remainder_from(step + stop.total_steps);
+            assert_eq!(step + stop.total_steps + remainder.total_steps, 1000, "split at {}", step);
+            assert_eq!(stop.cruise_steps + stop.accel_steps, 0);
+        }
+
+        // Triangle profile: step 400 is already decelerating, so stopping
+        // takes exactly the rest of the move
+        assert_eq!(profile.stop_from(400).total_steps, 600);
+        // Early in the ramp, stopping is quick and the remainder re-accelerates
+        let stop = profile.stop_from(20);
+        assert!(stop.total_steps < 100);
+        assert!(profile.remainder_from(20 + stop.total_steps).accel_steps > 0);
     }
 
     #[test]
@@ -303,4 +1072,276 @@ mod tests {
         assert_eq!(ccw.direction, Direction::CounterClockwise);
         assert_eq!(cw.total_steps, ccw.total_steps);
     }
+
+    #[test]
+    fn test_starting_from_velocity() {
+        // From rest: identical to the plain trapezoid
+        let rest = MotionProfile::asymmetric_trapezoidal(1000, 1000.0, 2000.0, 4000.0, 0.0);
+        let same = MotionProfile::starting_from_velocity(0.0, 1000.0, 2000.0, 4000.0, 1000);
+        assert_eq!((same.accel_steps, same.cruise_steps), (rest.accel_steps, rest.cruise_steps));
+        assert_eq!(same.interval_at(0), rest.interval_at(0));
+
+        // Moving at 500 steps/sec: the ramp resumes 62 steps in (500² / 2·2000)
+        let moving = MotionProfile::starting_from_velocity(500.0, 1000.0, 2000.0, 4000.0, -1000);
+        assert_eq!(moving.direction, Direction::CounterClockwise);
+        assert_eq!(moving.accel_offset_steps, 62);
+        assert_eq!(moving.interval_at(0), rest.interval_at(62));
+        assert_eq!(moving.accel_steps, 187);
+        assert_eq!(moving.decel_steps, rest.decel_steps);
+        assert_eq!(moving.total_steps, 1000);
+        assert!((moving.start_velocity() - 500.0).abs() < 5.0);
+        assert!(moving.estimated_duration_secs() < rest.estimated_duration_secs());
+
+        // Already at cruise speed (or above): no acceleration phase
+        let cruising = MotionProfile::starting_from_velocity(1500.0, 1000.0, 2000.0, 4000.0, 1000);
+        assert_eq!(cruising.accel_steps, 0);
+        assert_eq!(cruising.interval_at(0), cruising.cruise_interval_ns);
+
+        // Triangle from speed: peak² = (2·200 + 500²/2000) / (1/2000 + 1/4000)
+        let short = MotionProfile::starting_from_velocity(500.0, 1000.0, 2000.0, 4000.0, 200);
+        assert!(short.is_triangle());
+        assert_eq!(short.accel_steps + short.decel_steps, 200);
+        assert_eq!(short.accel_steps, 112);
+
+        // Too short to stop: decelerate over every step
+        let clipped = MotionProfile::starting_from_velocity(1000.0, 1000.0, 2000.0, 4000.0, 10);
+        assert_eq!((clipped.accel_steps, clipped.decel_steps), (0, 10));
+    }
+
+    #[test]
+    fn test_optimal() {
+        // Up to 10 steps: constant velocity at 250 steps/sec
+        let short = MotionProfile::optimal(-10, 1000.0, 2000.0, 4000.0);
+        assert_eq!(short.direction, Direction::CounterClockwise);
+        assert_eq!((short.accel_steps, short.cruise_steps, short.decel_steps), (0, 10, 0));
+        assert_eq!(short.cruise_interval_ns, 4_000_000);
+
+        let long = MotionProfile::optimal(11, 1000.0, 2000.0, 4000.0);
+        let trapezoidal = MotionProfile::asymmetric_trapezoidal(11, 1000.0, 2000.0, 4000.0, 0.0);
+        assert_eq!(
+            (long.accel_steps, long.cruise_steps, long.decel_steps),
+            (trapezoidal.accel_steps, trapezoidal.cruise_steps, trapezoidal.decel_steps)
+        );
+        assert!(long.accel_steps > 0);
+        assert!(MotionProfile::optimal(0, 1000.0, 2000.0, 4000.0).is_zero());
+    }
+
+    #[test]
+    fn test_warm_start() {
+        let cold = MotionProfile::asymmetric_trapezoidal(1000, 1000.0, 2000.0, 4000.0, 0.0);
+        let warm = MotionProfile::asymmetric_trapezoidal(1000, 1000.0, 2000.0, 4000.0, 200.0);
+
+        // The first step runs at about 200 steps/sec instead of sqrt(2a)
+        assert!((warm.initial_interval_ns as f32 - 5_000_000.0).abs() < 500_000.0);
+        assert!(warm.initial_interval_ns < cold.initial_interval_ns);
+
+        // Ten fewer steps to cruise (200² / 2·2000), the same stop
+        assert_eq!(warm.accel_steps, cold.accel_steps - 10);
+        assert_eq!(warm.decel_steps, cold.decel_steps);
+        assert_eq!(warm.total_steps, 1000);
+        assert!(warm.estimated_duration_secs() < cold.estimated_duration_secs());
+
+        // Capped at the cruise velocity
+        let capped = MotionProfile::asymmetric_trapezoidal(1000, 1000.0, 2000.0, 4000.0, 5000.0);
+        assert_eq!(capped.accel_steps, 0);
+    }
+
+    #[test]
+    fn test_between_velocities() {
+        let ending = MotionProfile::try_between_velocities(1000, 1000.0, 2000.0, 2000.0, 0.0, 400.0)
+            .unwrap();
+        // Decel stops 400² / 2·2000 = 40 steps short of rest
+        assert_eq!(ending.decel_offset_steps, 40);
+        assert_eq!(ending.decel_steps, 210);
+        assert_eq!(ending.total_steps, 1000);
+        assert!((ending.end_velocity() - 400.0).abs() < 1.0);
+
+        // The next profile picks up at the last interval
+        let next = MotionProfile::try_between_velocities(100, 400.0, 2000.0, 2000.0, 400.0, 0.0)
+            .unwrap();
+        let last = ending.interval_at(999) as f32;
+        assert!((next.initial_interval_ns as f32 - last).abs() / last < 0.02);
+        assert_eq!(next.accel_steps, 0);
+
+        // Re-planning keeps the boundary velocities
+        let assisted = ending.clone().with_gravity_compensation(2.0, Direction::Clockwise);
+        assert!((assisted.end_velocity() - 400.0).abs() < 10.0);
+        assert_eq!(ending.remainder_from(500).decel_offset_steps, 40);
+    }
+
+    #[test]
+    fn test_gravity_compensation() {
+        let profile = MotionProfile::symmetric_trapezoidal(1000, 2000.0, 8000.0);
+
+        let assisted = profile.clone().with_gravity_compensation(2.0, Direction::Clockwise);
+        assert_eq!(assisted.decel_rate, 16000.0);
+        assert_eq!(assisted.accel_rate, 8000.0);
+        assert_eq!(assisted.accel_steps, profile.accel_steps);
+        assert_eq!(assisted.cruise_interval_ns, profile.cruise_interval_ns);
+        assert!(assisted.decel_steps < profile.decel_steps);
+        assert_eq!(assisted.total_steps, 1000);
+
+        let opposed = profile.clone().with_gravity_compensation(2.0, Direction::CounterClockwise);
+        assert_eq!(opposed.decel_rate, 4000.0);
+        assert!(opposed.decel_steps > profile.decel_steps);
+
+        let unchanged = profile.clone().with_gravity_compensation(0.0, Direction::Clockwise);
+        assert_eq!(unchanged.decel_rate, 8000.0);
+        let cruise = MotionProfile::constant_velocity(100, 500.0);
+        assert_eq!(cruise.with_gravity_compensation(2.0, Direction::Clockwise).decel_steps, 0);
+    }
+
+    #[test]
+    fn test_ccw_profile_mirrors_cw() {
+        let cw = MotionProfile::asymmetric_trapezoidal(1000, 1000.0, 2000.0, 500.0, 0.0);
+        let ccw = MotionProfile::asymmetric_trapezoidal(-1000, 1000.0, 2000.0, 500.0, 0.0);
+
+        assert_eq!(ccw.total_steps, 1000);
+        assert_eq!(ccw.direction, Direction::CounterClockwise);
+        assert_eq!(ccw.accel_steps, cw.accel_steps);
+        assert_eq!(ccw.cruise_steps, cw.cruise_steps);
+        assert_eq!(ccw.decel_steps, cw.decel_steps);
+        for step in 0..=1000 {
+            assert_eq!(ccw.phase_at(step), cw.phase_at(step), "phase at {}", step);
+            assert_eq!(ccw.interval_at(step), cw.interval_at(step), "interval at {}", step);
+        }
+    }
+
+    #[test]
+    fn test_extreme_negative_steps() {
+        assert_eq!(Direction::from_steps(i64::MIN), Direction::CounterClockwise);
+        assert_eq!(Direction::from_steps(-1), Direction::CounterClockwise);
+        assert_eq!(Direction::from_steps(0), Direction::Clockwise);
+
+        // |i64::MIN| has no i64 representation; must not panic
+        let result = MotionProfile::try_symmetric_trapezoidal(i64::MIN, 1000.0, 2000.0);
+        assert_eq!(result.unwrap_err(), Error::Motion(MotionError::Overflow));
+        assert!(MotionProfile::symmetric_trapezoidal(i64::MIN, 1000.0, 2000.0).is_zero());
+
+        let profile = MotionProfile::symmetric_trapezoidal(-(u32::MAX as i64), 50_000.0, 1000.0);
+        assert_eq!(profile.total_steps, u32::MAX);
+        assert_eq!(profile.direction, Direction::CounterClockwise);
+    }
+
+    #[test]
+    fn test_constant_velocity() {
+        let profile = MotionProfile::constant_velocity(-(u32::MAX as i64), 500.0);
+        assert_eq!(profile.total_steps, u32::MAX);
+        assert_eq!(profile.direction, Direction::CounterClockwise);
+        assert_eq!(profile.phase_at(0), MotionPhase::Cruising);
+        assert_eq!(profile.interval_at(0), 2_000_000);
+        assert_eq!(profile.interval_at(u32::MAX - 1), 2_000_000);
+        assert!(profile.stop_from(10).is_zero());
+
+        assert!(MotionProfile::constant_velocity(0, 500.0).is_zero());
+        assert!(MotionProfile::constant_velocity(100, 0.0).is_zero());
+        assert!(MotionProfile::constant_velocity(i64::MIN, 500.0).is_zero());
+    }
+
+    /// (steps, velocity steps/s, acceleration steps/s²): trapezoids and triangles.
+    const ANALYTIC_CASES: [(i64, f32, f32); 12] = [
+        (1000, 1000.0, 2000.0),
+        (1000, 2000.0, 8000.0),
+        (3200, 3200.0, 6400.0),
+        (10_000, 5000.0, 20_000.0),
+        (50_000, 10_000.0, 5000.0),
+        (200, 400.0, 1000.0),
+        (500, 800.0, 800.0),
+        (100, 10_000.0, 1000.0),
+        (2000, 50_000.0, 10_000.0),
+        (64, 1000.0, 500.0),
+        (100_000, 20_000.0, 40_000.0),
+        (8, 100.0, 1000.0),
+    ];
+
+    /// Expected duration of a symmetric move from the kinematic equations.
+    fn analytic_duration(steps: f32, velocity: f32, accel: f32) -> f32 {
+        let ramp = velocity * velocity / (2.0 * accel);
+        if 2.0 * ramp <= steps {
+            let t_accel = velocity / accel;
+            let t_cruise = (steps - 2.0 * ramp) / velocity;
+            2.0 * t_accel + t_cruise
+        } else {
+            // Triangle: half the distance accelerating, half decelerating
+            2.0 * sqrtf(steps / accel)
+        }
+    }
+
+    #[test]
+    fn test_interval_sum_matches_analytic_duration() {
+        for (steps, velocity, accel) in ANALYTIC_CASES {
+            let profile = MotionProfile::symmetric_trapezoidal(steps, velocity, accel);
+            let summed: f64 = (0..profile.total_steps)
+                .map(|step| profile.interval_at(step) as f64 * 1e-9)
+                .sum();
+            let expected = analytic_duration(steps as f32, velocity, accel) as f64;
+
+            assert!(
+                (summed - expected).abs() / expected < 0.05,
+                "({}, {}, {}): summed {} s, expected {} s",
+                steps, velocity, accel, summed, expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_elapsed_time_at_step() {
+        let profile = MotionProfile::asymmetric_trapezoidal(1000, 2000.0, 8000.0, 4000.0, 0.0);
+        assert!(profile.cruise_steps > 0);
+        let summed = |step: u32| (0..step).map(|i| profile.interval_at(i) as u64).sum::<u64>();
+
+        for step in [0, 1, profile.cruise_start(), profile.decel_start() + 3, 999, 1000] {
+            assert_eq!(profile.elapsed_time_ns_at_step(step), summed(step), "step {}", step);
+        }
+        assert_eq!(profile.elapsed_time_ns_at_step(5000), summed(1000));
+
+        let times: heapless::Vec<u64, 1000> = profile.cumulative_times();
+        assert_eq!(times.len(), 1000);
+        assert!(times.iter().enumerate().all(|(i, &t)| t == summed(i as u32)));
+
+        // Truncated to the capacity
+        let first: heapless::Vec<u64, 4> = profile.cumulative_times();
+        assert_eq!(first.as_slice(), &times[..4]);
+        assert!(MotionProfile::zero().cumulative_times::<4>().is_empty());
+    }
+
+    #[test]
+    fn test_velocity_continuous_across_phases() {
+        for (steps, velocity, accel) in ANALYTIC_CASES {
+            let profile = MotionProfile::symmetric_trapezoidal(steps, velocity, accel);
+            if profile.accel_steps == 0 || profile.decel_steps == 0 {
+                continue;
+            }
+            let decel_start = profile.accel_steps + profile.cruise_steps;
+            let v_end_accel = 1e9 / profile.interval_at(profile.accel_steps - 1) as f32;
+            let v_start_decel = 1e9 / profile.interval_at(decel_start) as f32;
+
+            assert!(
+                (v_end_accel - v_start_decel).abs() / v_start_decel < 0.05,
+                "({}, {}, {}): {} steps/s at end of accel, {} at start of decel",
+                steps, velocity, accel, v_end_accel, v_start_decel
+            );
+            assert!(v_end_accel <= velocity * 1.001);
+        }
+    }
+
+    #[test]
+    fn test_energy_estimate() {
+        // 1000 steps/s after 500 steps of ramp at 1000 steps/s²: 1 s per ramp
+        let profile = MotionProfile::symmetric_trapezoidal(3000, 1000.0, 1000.0);
+        assert_eq!(profile.total_distance_steps(), 3000);
+        let ramps = profile.phase_duration_secs(MotionPhase::Accelerating)
+            + profile.phase_duration_secs(MotionPhase::Decelerating);
+        let cruise = profile.phase_duration_secs(MotionPhase::Cruising);
+        assert!((ramps - 2.0).abs() < 0.01);
+        assert!((cruise - 2.0).abs() < 0.01);
+        assert!((ramps + cruise - profile.estimated_duration_secs()).abs() < 1e-3);
+
+        // 2 A through 1.5 Ω: 6 W on the ramps, 0.49 * 6 W at cruise
+        let energy = profile.energy_estimate(1.5, 2000);
+        let expected = 6.0 * ramps + 0.49 * 6.0 * cruise;
+        assert!((energy - expected).abs() < 1e-3, "{} vs {}", energy, expected);
+
+        assert_eq!(MotionProfile::zero().energy_estimate(1.5, 2000), 0.0);
+    }
 }
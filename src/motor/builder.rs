@@ -1,21 +1,32 @@
 //! Builder pattern for StepperMotor.
 
 use embedded_hal::delay::DelayNs;
-use embedded_hal::digital::OutputPin;
+use embedded_hal::digital::{InputPin, OutputPin};
 
 use crate::config::units::{DegreesPerSec, DegreesPerSecSquared, Microsteps};
-use crate::config::{MechanicalConstraints, MotorConfig, SystemConfig};
-use crate::error::{ConfigError, Error, Result};
+use crate::config::{
+    check_config_name, check_gear_ratio_fraction, check_initial_velocity, validate_step_pulse,
+    MechanicalConstraints, MotorConfig, SoftStartConfig, SystemConfig, ThermalConfig,
+    DEFAULT_STEP_PULSE_NS,
+};
+use crate::error::{ConfigError, Error, Result, ValidationReport};
+use crate::motion::Direction;
 
-use super::driver::StepperMotor;
+use super::driver::{DirGenerationFn, NoHomeSwitch, StepperMotor};
 use super::state::Idle;
+use super::thermal::DutyTracker;
+use super::timing::{ClockFn, TimingRecorder, DEFAULT_TIMING_BUCKET_NS};
+
+/// Default per-step overhead assumed for the software GPIO + delay path (5 µs).
+pub const DEFAULT_TIMING_BUDGET_NS: u32 = 5_000;
 
 /// Builder for creating StepperMotor instances.
-pub struct StepperMotorBuilder<STEP, DIR, DELAY>
+pub struct StepperMotorBuilder<STEP, DIR, DELAY, HOME = NoHomeSwitch>
 where
     STEP: OutputPin,
     DIR: OutputPin,
     DELAY: DelayNs,
+    HOME: InputPin,
 {
     step_pin: Option<STEP>,
     dir_pin: Option<DIR>,
@@ -24,11 +35,32 @@ where
     steps_per_revolution: Option<u16>,
     microsteps: Option<Microsteps>,
     gear_ratio: f32,
+    gear_ratio_fraction: Option<(u32, u32)>,
     max_velocity: Option<DegreesPerSec>,
     max_acceleration: Option<DegreesPerSecSquared>,
     invert_direction: bool,
+    output_inverted: bool,
     constraints: Option<MechanicalConstraints>,
     backlash_steps: i64,
+    step_pulse_ns: u32,
+    reversal_dwell_ms: u32,
+    delay_overhead_ns: u32,
+    max_delay_chunk_ns: u32,
+    clamp_on_startup: bool,
+    clock: Option<ClockFn>,
+    timing_stats: bool,
+    timing_bucket_ns: u32,
+    timing_budget_ns: u32,
+    home_switch: Option<HOME>,
+    park_trajectory: Option<Result<heapless::String<32>>>,
+    dir_generation: Option<DirGenerationFn<DIR>>,
+    thermal: Option<ThermalConfig>,
+    approach_direction: Option<Direction>,
+    gravity_assist: Option<(f32, Direction)>,
+    initial_velocity: Option<DegreesPerSec>,
+    soft_start: Option<SoftStartConfig>,
+    minimum_move_steps: Option<u16>,
+    conflicting_backlash: Option<(f32, i64)>,
 }
 
 impl<STEP, DIR, DELAY> Default for StepperMotorBuilder<STEP, DIR, DELAY>
@@ -58,14 +90,44 @@ where
             steps_per_revolution: None,
             microsteps: None,
             gear_ratio: 1.0,
+            gear_ratio_fraction: None,
             max_velocity: None,
             max_acceleration: None,
             invert_direction: false,
+            output_inverted: false,
             constraints: None,
             backlash_steps: 0,
+            step_pulse_ns: DEFAULT_STEP_PULSE_NS,
+            reversal_dwell_ms: 0,
+            delay_overhead_ns: 0,
+            max_delay_chunk_ns: u32::MAX,
+            clamp_on_startup: false,
+            clock: None,
+            timing_stats: true,
+            timing_bucket_ns: DEFAULT_TIMING_BUCKET_NS,
+            timing_budget_ns: DEFAULT_TIMING_BUDGET_NS,
+            home_switch: None,
+            park_trajectory: None,
+            dir_generation: None,
+            thermal: None,
+            approach_direction: None,
+            gravity_assist: None,
+            initial_velocity: None,
+            soft_start: None,
+            minimum_move_steps: None,
+            conflicting_backlash: None,
         }
     }
 
+}
+
+impl<STEP, DIR, DELAY, HOME> StepperMotorBuilder<STEP, DIR, DELAY, HOME>
+where
+    STEP: OutputPin,
+    DIR: OutputPin,
+    DELAY: DelayNs,
+    HOME: InputPin,
+{
     /// Set the STEP pin.
     pub fn step_pin(mut self, pin: STEP) -> Self {
         self.step_pin = Some(pin);
@@ -108,6 +170,13 @@ where
         self
     }
 
+    /// Set an exact rational gear ratio `numerator:denominator` (e.g. 7:5),
+    /// used instead of `gear_ratio`.
+    pub fn gear_ratio_fraction(mut self, numerator: u32, denominator: u32) -> Self {
+        self.gear_ratio_fraction = Some((numerator, denominator));
+        self
+    }
+
     /// Set maximum velocity in degrees per second.
     pub fn max_velocity(mut self, velocity: DegreesPerSec) -> Self {
         self.max_velocity = Some(velocity);
@@ -126,6 +195,13 @@ where
         self
     }
 
+    /// Set whether the mechanism reverses the output, so positive steps give
+    /// negative degrees. See [`MotorConfig::output_inverted`].
+    pub fn output_inverted(mut self, inverted: bool) -> Self {
+        self.output_inverted = inverted;
+        self
+    }
+
     /// Set backlash compensation in steps.
     ///
     /// Backlash is applied on direction changes to compensate for mechanical play.
@@ -134,21 +210,217 @@ where
         self
     }
 
+    /// Set the STEP pulse active duration in nanoseconds.
+    ///
+    /// Must be at least 100 ns and shorter than the step interval at max velocity.
+    pub fn step_pulse_ns(mut self, ns: u32) -> Self {
+        self.step_pulse_ns = ns;
+        self
+    }
+
+    /// Set the pause before a move that reverses direction, in milliseconds.
+    ///
+    /// The dwell happens after the DIR pin changes and before any backlash
+    /// compensation or motion pulses. Zero disables it.
+    pub fn reversal_dwell_ms(mut self, ms: u32) -> Self {
+        self.reversal_dwell_ms = ms;
+        self
+    }
+
+    /// Set the fixed overhead of one call into the delay provider, in nanoseconds.
+    ///
+    /// Each step delay is shortened by this amount (floored at zero) so that
+    /// moves run at the planned rate on slow `DelayNs` implementations. Steps
+    /// too short to compensate are counted by
+    /// [`StepperMotor::underrun_steps`].
+    pub fn delay_overhead_ns(mut self, ns: u32) -> Self {
+        self.delay_overhead_ns = ns;
+        self
+    }
+
+    /// Cap the argument of each `delay_ns` call, in nanoseconds.
+    ///
+    /// For HALs that only accept delays up to some limit: longer step
+    /// intervals and dwells are split into several calls of at most `ns`
+    /// (see [`delay_chunked`](crate::motion::delay_chunked)). The default,
+    /// `u32::MAX`, makes one call per delay, with dwells as `delay_ms`.
+    pub fn max_delay_chunk_ns(mut self, ns: u32) -> Self {
+        self.max_delay_chunk_ns = ns;
+        self
+    }
+
+    /// Move back inside the soft limits as part of `build()`.
+    ///
+    /// See [`StepperMotor::constrain_to_limits`]; only has an effect when the
+    /// constraints carry soft limits.
+    pub fn clamp_on_startup(mut self, clamp: bool) -> Self {
+        self.clamp_on_startup = clamp;
+        self
+    }
+
+    /// Set a monotonic nanosecond clock for step timing statistics.
+    ///
+    /// With a clock, every step's actual interval is compared against the
+    /// plan; see [`StepperMotor::take_timing_stats`].
+    pub fn clock(mut self, clock: ClockFn) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Enable or disable step timing statistics (enabled by default when a
+    /// clock is set).
+    pub fn timing_stats(mut self, enabled: bool) -> Self {
+        self.timing_stats = enabled;
+        self
+    }
+
+    /// Set the width of one timing histogram bucket in nanoseconds.
+    pub fn timing_bucket_ns(mut self, ns: u32) -> Self {
+        self.timing_bucket_ns = ns;
+        self
+    }
+
+    /// Set the home switch input used by homing moves and homing trajectories.
+    ///
+    /// The switch counts as triggered while the pin reads high; wrap an
+    /// active-low input so that it reads high when pressed. See
+    /// [`StepperMotor::start_homing`].
+    pub fn home_switch<H: InputPin>(self, switch: H) -> StepperMotorBuilder<STEP, DIR, DELAY, H> {
+        StepperMotorBuilder {
+            step_pin: self.step_pin,
+            dir_pin: self.dir_pin,
+            delay: self.delay,
+            name: self.name,
+            steps_per_revolution: self.steps_per_revolution,
+            microsteps: self.microsteps,
+            gear_ratio: self.gear_ratio,
+            gear_ratio_fraction: self.gear_ratio_fraction,
+            max_velocity: self.max_velocity,
+            max_acceleration: self.max_acceleration,
+            invert_direction: self.invert_direction,
+            output_inverted: self.output_inverted,
+            constraints: self.constraints,
+            backlash_steps: self.backlash_steps,
+            step_pulse_ns: self.step_pulse_ns,
+            reversal_dwell_ms: self.reversal_dwell_ms,
+            delay_overhead_ns: self.delay_overhead_ns,
+            max_delay_chunk_ns: self.max_delay_chunk_ns,
+            clamp_on_startup: self.clamp_on_startup,
+            clock: self.clock,
+            timing_stats: self.timing_stats,
+            timing_bucket_ns: self.timing_bucket_ns,
+            timing_budget_ns: self.timing_budget_ns,
+            home_switch: Some(switch),
+            park_trajectory: self.park_trajectory,
+            dir_generation: self.dir_generation,
+            thermal: self.thermal,
+            approach_direction: self.approach_direction,
+            gravity_assist: self.gravity_assist,
+            initial_velocity: self.initial_velocity,
+            soft_start: self.soft_start,
+            minimum_move_steps: self.minimum_move_steps,
+            conflicting_backlash: self.conflicting_backlash,
+        }
+    }
+
+    /// Set the trajectory run by [`StepperMotor::park`].
+    ///
+    /// An empty or over-long name is reported by [`build`](Self::build).
+    pub fn park_trajectory(mut self, name: &str) -> Self {
+        let name = check_config_name(name, true).map(|()| name.try_into().unwrap_or_default());
+        self.park_trajectory = Some(name);
+        self
+    }
+
+    /// Set a duty-cycle budget; see [`StepperMotor::duty_tracker`].
+    ///
+    /// With a [`clock`](Self::clock), moving and idle times are measured;
+    /// otherwise moves count for their estimated duration.
+    pub fn thermal(mut self, config: ThermalConfig) -> Self {
+        self.thermal = Some(config);
+        self
+    }
+
+    /// Finish every `move_to` travelling in `direction`.
+    ///
+    /// Moves the other way overshoot the target by the backlash compensation
+    /// and approach it again; see [`StepperMotor::move_to`].
+    pub fn approach_direction(mut self, direction: Direction) -> Self {
+        self.approach_direction = Some(direction);
+        self
+    }
+
+    /// Decelerate `factor` times harder moving in `direction`, where gravity
+    /// helps to stop the load, and `factor` times softer the other way.
+    ///
+    /// Applied to [`StepperMotor::move_to`] profiles.
+    pub fn gravity_assist(mut self, factor: f32, direction: Direction) -> Self {
+        self.gravity_assist = Some((factor, direction));
+        self
+    }
+
+    /// Start every move's acceleration ramp at `velocity` instead of from rest.
+    ///
+    /// Applied to [`StepperMotor::move_to`] and trajectory profiles; must be
+    /// positive and no faster than the max velocity.
+    pub fn initial_velocity(mut self, velocity: DegreesPerSec) -> Self {
+        self.initial_velocity = Some(velocity);
+        self
+    }
+
+    /// Soften the acceleration of moves starting soon after the driver is
+    /// enabled; see [`StepperMotor::notify_enabled`].
+    ///
+    /// The built motor counts as just enabled.
+    pub fn soft_start(mut self, config: SoftStartConfig) -> Self {
+        self.soft_start = Some(config);
+        self
+    }
+
+    /// Reject moves shorter than `steps` with `MotionError::MoveTooShort`.
+    pub fn minimum_move_steps(mut self, steps: u16) -> Self {
+        self.minimum_move_steps = Some(steps);
+        self
+    }
+
+    /// Set the per-step timing overhead in nanoseconds.
+    ///
+    /// `build()` rejects configurations whose minimum step interval is shorter
+    /// than this budget, since the step loop could not keep up with the profile.
+    pub fn timing_budget_ns(mut self, ns: u32) -> Self {
+        self.timing_budget_ns = ns;
+        self
+    }
+
     /// Configure from a MotorConfig.
     pub fn from_motor_config(mut self, config: &MotorConfig) -> Self {
         self.name = Some(config.name.clone());
         self.steps_per_revolution = Some(config.steps_per_revolution);
         self.microsteps = Some(config.microsteps);
         self.gear_ratio = config.gear_ratio;
+        self.gear_ratio_fraction = config.gear_ratio_fraction();
         self.max_velocity = Some(config.max_velocity);
         self.max_acceleration = Some(config.max_acceleration);
         self.invert_direction = config.invert_direction;
+        self.output_inverted = config.output_inverted;
+        self.step_pulse_ns = config.step_pulse_ns;
+        self.reversal_dwell_ms = config.reversal_dwell_ms;
+        self.delay_overhead_ns = config.delay_overhead_ns;
+        self.clamp_on_startup = config.clamp_on_startup;
+        self.park_trajectory = config.park_trajectory.clone().map(Ok);
+        self.thermal = config.thermal;
+        self.approach_direction = config.backlash_approach_direction;
+        self.gravity_assist = config.gravity_assist_factor.zip(config.gravity_direction);
+        self.initial_velocity = config.initial_velocity;
+        self.soft_start = config.soft_start;
+        self.minimum_move_steps = config.minimum_move_steps;
         self.constraints = Some(MechanicalConstraints::from_config(config));
-        // Extract backlash compensation if configured (convert degrees to steps)
-        if let Some(backlash_deg) = config.backlash_compensation {
-            let steps_per_degree = config.steps_per_degree();
-            self.backlash_steps = (backlash_deg.0 * steps_per_degree) as i64;
-        }
+        // Backlash in steps if given, else converted from degrees
+        self.backlash_steps = config.backlash_steps();
+        self.conflicting_backlash = config
+            .backlash_compensation
+            .zip(config.backlash_compensation_steps)
+            .map(|(degrees, steps)| (degrees.0, steps));
         self
     }
 
@@ -167,70 +439,201 @@ where
     ///
     /// # Errors
     ///
-    /// Returns an error if required fields are missing.
-    pub fn build(self) -> Result<StepperMotor<STEP, DIR, DELAY, Idle>> {
-        let step_pin = self.step_pin.ok_or_else(|| {
-            Error::Config(ConfigError::ParseError(
-                heapless::String::try_from("step_pin is required").unwrap(),
-            ))
-        })?;
-
-        let dir_pin = self.dir_pin.ok_or_else(|| {
-            Error::Config(ConfigError::ParseError(
-                heapless::String::try_from("dir_pin is required").unwrap(),
-            ))
-        })?;
-
-        let delay = self.delay.ok_or_else(|| {
-            Error::Config(ConfigError::ParseError(
-                heapless::String::try_from("delay is required").unwrap(),
-            ))
-        })?;
+    /// Returns an error if required fields are missing, the STEP pulse width
+    /// is invalid for the configured maximum velocity, or the maximum step rate
+    /// is faster than the timing budget allows. With
+    /// [`clamp_on_startup`](Self::clamp_on_startup), also returns any error
+    /// from moving back inside the soft limits.
+    pub fn build(self) -> Result<StepperMotor<STEP, DIR, DELAY, Idle, HOME>> {
+        self.build_inner(true)
+    }
+
+    /// Build the StepperMotor without checking the step rate against the
+    /// timing budget.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if required fields are missing or the STEP pulse width
+    /// is invalid for the configured maximum velocity.
+    pub fn build_unchecked(self) -> Result<StepperMotor<STEP, DIR, DELAY, Idle, HOME>> {
+        self.build_inner(false)
+    }
+
+    /// Check the builder for every problem that would make `build()` fail.
+    ///
+    /// Unlike `build()`, which stops at the first error, this collects all
+    /// missing required fields plus STEP pulse and timing budget problems, so
+    /// a UI can show them at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationReport`] listing each problem found.
+    pub fn validate(&self) -> core::result::Result<(), ValidationReport> {
+        self.check(true)
+    }
+
+    fn check(&self, check_timing: bool) -> core::result::Result<(), ValidationReport> {
+        let mut report = ValidationReport::new();
+
+        if self.step_pin.is_none() {
+            report.push(required("step_pin"));
+        }
+        if self.dir_pin.is_none() {
+            report.push(required("dir_pin"));
+        }
+        if self.delay.is_none() {
+            report.push(required("delay"));
+        }
+
+        let constraints = match &self.constraints {
+            Some(c) => Some(c.clone()),
+            None => {
+                if self.steps_per_revolution.is_none() {
+                    report.push(required("steps_per_revolution"));
+                }
+                if self.max_velocity.is_none() {
+                    report.push(required("max_velocity"));
+                }
+                if self.max_acceleration.is_none() {
+                    report.push(required("max_acceleration"));
+                }
+                self.constraints_from_fields().ok()
+            }
+        };
+
+        if let Some(constraints) = constraints {
+            if let Err(e) = validate_step_pulse(self.step_pulse_ns, constraints.min_step_interval_ns)
+            {
+                report.push(e);
+            }
+
+            if check_timing && constraints.min_step_interval_ns < self.timing_budget_ns {
+                report.push(Error::Config(ConfigError::StepRateUnachievable {
+                    required_ns: constraints.min_step_interval_ns,
+                    budget_ns: self.timing_budget_ns,
+                }));
+            }
+        }
+
+        if let Some((num, den)) = self.gear_ratio_fraction {
+            if let Err(e) = check_gear_ratio_fraction(Some(num), Some(den)) {
+                report.push(e);
+            }
+        }
+
+        if let Some((factor, _)) = self.gravity_assist.filter(|(f, _)| !(*f > 0.0 && f.is_finite())) {
+            report.push(Error::Config(ConfigError::InvalidGravityAssistFactor(factor)));
+        }
+
+        if let Some(velocity) = self.initial_velocity {
+            let max = self
+                .max_velocity
+                .or(self.constraints.as_ref().map(|c| c.max_velocity));
+            if let Some(Err(e)) = max.map(|max| check_initial_velocity(velocity, max)) {
+                report.push(e);
+            }
+        }
+
+        if let Some(thermal) = self.thermal.filter(|t| !t.is_valid()) {
+            report.push(Error::Config(ConfigError::InvalidThermalConfig {
+                time_constant_s: thermal.time_constant_s,
+                max_duty: thermal.max_duty,
+            }));
+        }
+
+        if let Some(soft_start) = self.soft_start.filter(|s| !s.is_valid()) {
+            report.push(Error::Config(ConfigError::InvalidSoftStart {
+                duration_ms: soft_start.duration_ms,
+                accel_percent: soft_start.accel_percent,
+            }));
+        }
+
+        if let Some((degrees, steps)) = self.conflicting_backlash {
+            report.push(Error::Config(ConfigError::ConflictingBacklashConfig { degrees, steps }));
+        }
+
+        if let Some(Err(e)) = &self.park_trajectory {
+            report.push(e.clone());
+        }
+
+        report.into_result()
+    }
+
+    /// Compute constraints from the individual mechanical fields.
+    fn constraints_from_fields(&self) -> Result<MechanicalConstraints> {
+        let steps = self
+            .steps_per_revolution
+            .ok_or_else(|| required("steps_per_revolution"))?;
+        let max_velocity = self.max_velocity.ok_or_else(|| required("max_velocity"))?;
+        let max_acceleration = self
+            .max_acceleration
+            .ok_or_else(|| required("max_acceleration"))?;
+
+        // Create a temporary MotorConfig to compute constraints
+        let config = MotorConfig {
+            name: heapless::String::new(),
+            steps_per_revolution: steps,
+            microsteps: self.microsteps.unwrap_or(Microsteps::FULL),
+            gear_ratio: self.gear_ratio,
+            gear_ratio_num: self.gear_ratio_fraction.map(|(num, _)| num),
+            gear_ratio_den: self.gear_ratio_fraction.map(|(_, den)| den),
+            max_velocity,
+            max_acceleration,
+            invert_direction: self.invert_direction,
+            output_inverted: self.output_inverted,
+            limits: None,
+            backlash_compensation: None,
+            backlash_compensation_steps: None,
+            step_pulse_ns: self.step_pulse_ns,
+            reversal_dwell_ms: self.reversal_dwell_ms,
+            delay_overhead_ns: self.delay_overhead_ns,
+            clamp_on_startup: false,
+            winding_resistance_ohm: None,
+            peak_current_ma: None,
+            max_step_rate_hz: None,
+            park_trajectory: None,
+            thermal: None,
+            backlash_approach_direction: None,
+            gravity_assist_factor: None,
+            gravity_direction: None,
+            initial_velocity: None,
+            soft_start: None,
+            minimum_move_steps: None,
+            rated_torque_nm: None,
+            torque_speed_curve: None,
+            homing: None,
+        };
+
+        Ok(MechanicalConstraints::from_config(&config))
+    }
+
+    fn build_inner(self, check_timing: bool) -> Result<StepperMotor<STEP, DIR, DELAY, Idle, HOME>> {
+        // Report the first problem, in the same order as `validate()`
+        if let Err(report) = self.check(check_timing) {
+            if let Some(e) = report.into_first() {
+                return Err(e);
+            }
+        }
+
+        let mut constraints = match self.constraints.clone() {
+            Some(c) => c,
+            None => self.constraints_from_fields()?,
+        };
+        constraints.gravity_assist = self.gravity_assist;
+        constraints.initial_velocity_steps_per_sec = self
+            .initial_velocity
+            .map_or(0.0, |v| constraints.velocity_to_steps(v.0));
+        constraints.minimum_move_steps = self.minimum_move_steps.map_or(0, u32::from);
+
+        let step_pin = self.step_pin.ok_or_else(|| required("step_pin"))?;
+        let dir_pin = self.dir_pin.ok_or_else(|| required("dir_pin"))?;
+        let delay = self.delay.ok_or_else(|| required("delay"))?;
 
         let name = self.name.unwrap_or_else(|| {
             heapless::String::try_from("motor").unwrap()
         });
 
-        let constraints = if let Some(c) = self.constraints {
-            c
-        } else {
-            // Build constraints from individual fields
-            let steps = self.steps_per_revolution.ok_or_else(|| {
-                Error::Config(ConfigError::ParseError(
-                    heapless::String::try_from("steps_per_revolution is required").unwrap(),
-                ))
-            })?;
-
-            let microsteps = self.microsteps.unwrap_or(Microsteps::FULL);
-            let max_velocity = self.max_velocity.ok_or_else(|| {
-                Error::Config(ConfigError::ParseError(
-                    heapless::String::try_from("max_velocity is required").unwrap(),
-                ))
-            })?;
-
-            let max_acceleration = self.max_acceleration.ok_or_else(|| {
-                Error::Config(ConfigError::ParseError(
-                    heapless::String::try_from("max_acceleration is required").unwrap(),
-                ))
-            })?;
-
-            // Create a temporary MotorConfig to compute constraints
-            let config = MotorConfig {
-                name: name.clone(),
-                steps_per_revolution: steps,
-                microsteps,
-                gear_ratio: self.gear_ratio,
-                max_velocity,
-                max_acceleration,
-                invert_direction: self.invert_direction,
-                limits: None,
-                backlash_compensation: None,
-            };
-
-            MechanicalConstraints::from_config(&config)
-        };
-
-        Ok(StepperMotor::new(
+        let mut motor = StepperMotor::new(
             step_pin,
             dir_pin,
             delay,
@@ -238,6 +641,202 @@ where
             name,
             self.invert_direction,
             self.backlash_steps,
-        ))
+            self.step_pulse_ns,
+            self.reversal_dwell_ms,
+            self.delay_overhead_ns,
+        );
+        motor.set_timing(TimingRecorder::new(
+            self.clock,
+            self.timing_stats,
+            self.timing_bucket_ns,
+        ));
+        motor.set_home_switch(self.home_switch);
+        motor.set_park_trajectory(self.park_trajectory.and_then(Result::ok));
+        motor.set_dir_generation(self.dir_generation);
+        motor.set_duty_tracker(self.thermal.as_ref().map(DutyTracker::new));
+        motor.set_approach_direction(self.approach_direction);
+        motor.set_soft_start(self.soft_start);
+        motor.set_max_delay_chunk_ns(self.max_delay_chunk_ns);
+
+        if self.clamp_on_startup {
+            motor.constrain_to_limits().map_err(|(_, e)| e)
+        } else {
+            Ok(motor)
+        }
+    }
+}
+
+#[cfg(feature = "shared-pin")]
+impl<'a, STEP, P, DELAY, HOME> StepperMotorBuilder<STEP, super::SharedPin<'a, P>, DELAY, HOME>
+where
+    STEP: OutputPin,
+    P: OutputPin,
+    DELAY: DelayNs,
+    HOME: InputPin,
+{
+    /// Set a DIR pin shared with other motors.
+    ///
+    /// Unlike [`dir_pin`](Self::dir_pin), the motor notices when another
+    /// motor has changed the shared level and rewrites DIR before its next
+    /// move even if its own direction is unchanged.
+    pub fn shared_dir_pin(mut self, pin: super::SharedPin<'a, P>) -> Self {
+        self.dir_pin = Some(pin);
+        self.dir_generation = Some(super::SharedPin::generation);
+        self
+    }
+}
+
+/// Error for a required builder field that was not set.
+fn required(field: &str) -> Error {
+    let mut msg: heapless::String<128> = heapless::String::new();
+    let _ = msg.push_str(field);
+    let _ = msg.push_str(" is required");
+    Error::Config(ConfigError::ParseError(msg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{NoopDelay, NoopPin};
+
+    fn builder() -> StepperMotorBuilder<NoopPin, NoopPin, NoopDelay> {
+        // 3200 steps/s at max velocity = 312500 ns interval
+        StepperMotorBuilder::new()
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .steps_per_revolution(200)
+            .microsteps(Microsteps::SIXTEENTH)
+            .max_velocity(DegreesPerSec(360.0))
+            .max_acceleration(DegreesPerSecSquared(720.0))
+    }
+
+    #[test]
+    fn test_step_pulse_default() {
+        let motor = builder().build().unwrap();
+        assert_eq!(motor.step_pulse_ns(), DEFAULT_STEP_PULSE_NS);
+    }
+
+    #[test]
+    fn test_step_pulse_validation() {
+        assert_eq!(builder().step_pulse_ns(500).build().unwrap().step_pulse_ns(), 500);
+
+        assert!(matches!(
+            builder().step_pulse_ns(50).build(),
+            Err(Error::Config(ConfigError::InvalidStepPulseWidth(50)))
+        ));
+        assert!(matches!(
+            builder().step_pulse_ns(400_000).build(),
+            Err(Error::Config(ConfigError::StepPulseExceedsInterval { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_timing_budget_boundary() {
+        let interval = builder().build().unwrap().constraints().min_step_interval_ns;
+
+        assert!(builder().timing_budget_ns(interval).build().is_ok());
+        assert!(matches!(
+            builder().timing_budget_ns(interval + 1).build(),
+            Err(Error::Config(ConfigError::StepRateUnachievable { required_ns, budget_ns }))
+                if required_ns == interval && budget_ns == interval + 1
+        ));
+        assert!(builder().timing_budget_ns(interval + 1).build_unchecked().is_ok());
+    }
+
+    #[test]
+    fn test_default_timing_budget_rejects_unachievable_rate() {
+        // 7200 °/s at 1/256 microstepping needs a ~195 ns step interval
+        let result = builder()
+            .microsteps(Microsteps::TWO_FIFTY_SIXTH)
+            .max_velocity(DegreesPerSec(7200.0))
+            .step_pulse_ns(100)
+            .build();
+        assert!(matches!(
+            result,
+            Err(Error::Config(ConfigError::StepRateUnachievable {
+                budget_ns: DEFAULT_TIMING_BUDGET_NS,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_backlash_from_motor_config() {
+        let mut config: MotorConfig = toml::from_str(
+            r#"
+name = "x_axis"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+backlash_compensation_steps = 3
+"#,
+        )
+        .unwrap();
+        assert_eq!(builder().from_motor_config(&config).backlash_steps, 3);
+
+        config.backlash_compensation = Some(crate::config::units::Degrees(1.8));
+        assert_eq!(
+            builder().from_motor_config(&config).build().err(),
+            Some(Error::Config(ConfigError::ConflictingBacklashConfig {
+                degrees: 1.8,
+                steps: 3,
+            }))
+        );
+
+        config.backlash_compensation_steps = None;
+        let builder = builder().from_motor_config(&config);
+        assert_eq!(builder.backlash_steps, 1);
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn test_validate_reports_all_missing_fields() {
+        let builder: StepperMotorBuilder<NoopPin, NoopPin, NoopDelay> =
+            StepperMotorBuilder::new().steps_per_revolution(200);
+
+        let report = builder.validate().unwrap_err();
+        let missing: std::vec::Vec<std::string::String> =
+            report.errors().iter().map(|e| std::format!("{}", e)).collect();
+        assert_eq!(report.len(), 5);
+        for field in ["step_pin", "dir_pin", "delay", "max_velocity", "max_acceleration"] {
+            let expected = std::format!("{} is required", field);
+            assert!(missing.iter().any(|m| m.contains(&expected)), "{} not reported", field);
+        }
+
+        // build() still fails on the first of them
+        assert_eq!(builder.build().err(), report.into_first());
+    }
+
+    #[test]
+    fn test_validate_reports_timing_problems_together() {
+        assert!(builder().validate().is_ok());
+
+        let report = builder()
+            .step_pulse_ns(50)
+            .timing_budget_ns(u32::MAX)
+            .validate()
+            .unwrap_err();
+        assert_eq!(report.len(), 2);
+        assert!(matches!(
+            report.errors(),
+            [
+                Error::Config(ConfigError::InvalidStepPulseWidth(50)),
+                Error::Config(ConfigError::StepRateUnachievable { .. }),
+            ]
+        ));
+    }
+    #[test]
+    fn test_park_trajectory_name_checked() {
+        use crate::error::NameError;
+
+        assert!(builder().park_trajectory("arm_park").validate().is_ok());
+        let long = "a_park_trajectory_name_of_39_characters";
+        let err = builder().park_trajectory(long).build().err().unwrap();
+        assert!(matches!(
+            err,
+            Error::Config(ConfigError::InvalidName { error: NameError::TooLong { len: 39 }, .. })
+        ));
     }
 }
@@ -1,19 +1,125 @@
 //! Stepper motor driver.
 //!
 //! Generic over embedded-hal 1.0 pin types with type-state safety.
+//!
+//! # Error handling
+//!
+//! State transitions consume the motor, so a failed one hands it back with
+//! the error as `Err((motor, error))`. [`MotorResultExt`](super::MotorResultExt)
+//! covers the usual ways of handling that:
+//!
+//! - keep the motor and try something else with `retry_with`
+//! - give up on the motor and propagate the error with `discarding_motor()?`
+//! - inspect the error without consuming the result with `error()`
+//! - wrap the motor, e.g. into an enum of states, with `map_motor`
+//! - split the result into options with `into_parts`
 
 use core::marker::PhantomData;
 
 use embedded_hal::delay::DelayNs;
-use embedded_hal::digital::OutputPin;
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin};
 
-use crate::config::units::{Degrees, Steps};
-use crate::config::MechanicalConstraints;
-use crate::error::{Error, MotorError, Result};
-use crate::motion::{Direction, MotionExecutor, MotionPhase, MotionProfile};
+use crate::config::units::{Degrees, FormattedDegrees, Steps};
+use crate::config::{
+    LimitViolation, MechanicalConstraints, SoftStartConfig, HOMING_VELOCITY_FRACTION,
+};
+use crate::error::{ConfigError, Error, MotorError, Result};
+use crate::motion::{
+    delay_chunked, Direction, MotionExecutor, MotionPhase, MotionProfile, ProgressSnapshot,
+};
 
+use super::planner::{MotionPlanner, MovePlan, MoveParams, MoveReport, MAX_PLAN_LEGS};
 use super::position::Position;
-use super::state::{Idle, MotorState, Moving, StateName};
+use super::state::{Armed, Homing, Idle, MotorState, Moving, Paused, StateName};
+use super::telemetry::MotorTelemetry;
+use super::thermal::DutyTracker;
+use super::timing::{TimingRecorder, TimingStats, DEFAULT_TIMING_BUCKET_NS};
+
+/// Fraction of the maximum velocity used by [`StepperMotor::constrain_to_limits`].
+const CONSTRAIN_VELOCITY_FRACTION: f32 = 0.25;
+
+/// Placeholder home switch of a motor built without one.
+///
+/// The default `HOME` parameter of [`StepperMotor`]; it reads as never
+/// triggered, and homing such a motor fails with `MotorError::NoHomeSwitch`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoHomeSwitch;
+
+impl ErrorType for NoHomeSwitch {
+    type Error = core::convert::Infallible;
+}
+
+impl InputPin for NoHomeSwitch {
+    fn is_high(&mut self) -> core::result::Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    fn is_low(&mut self) -> core::result::Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
+
+/// Reads the level-change count of a DIR pin shared with other motors.
+pub(crate) type DirGenerationFn<DIR> = fn(&DIR) -> u32;
+
+/// DIR level tracking for a pin other motors also write.
+struct DirSync<DIR> {
+    read: DirGenerationFn<DIR>,
+    /// Generation right after this motor's last DIR write.
+    seen: Option<u32>,
+}
+
+/// Soft-start settings and the state of the current window.
+#[derive(Clone, Copy)]
+struct SoftStartWindow {
+    config: SoftStartConfig,
+    /// Clock reading at the last enable event.
+    enabled_at_ns: Option<u64>,
+    /// Whether moves may still fall inside the window.
+    armed: bool,
+}
+
+impl SoftStartWindow {
+    /// Acceleration scale for a move starting at `now_ns`, if it is inside
+    /// the window. Without a clock, only the first move is.
+    fn take_scale(&mut self, now_ns: Option<u64>) -> Option<f32> {
+        if !self.armed {
+            return None;
+        }
+        let window_ns = self.config.duration_ms as u64 * 1_000_000;
+        match (self.enabled_at_ns, now_ns) {
+            (Some(at), Some(now)) if now.wrapping_sub(at) < window_ns => {}
+            (Some(_), Some(_)) => {
+                self.armed = false;
+                return None;
+            }
+            _ => self.armed = false,
+        }
+        Some(self.config.accel_percent as f32 / 100.0)
+    }
+}
+
+/// Successful outcome of [`StepperMotor::park`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParkOutcome {
+    /// The park trajectory ran (or the motor was already at its target).
+    Parked,
+    /// No park trajectory is configured; the motor did not move.
+    NotConfigured,
+}
+
+/// Result of a blocking run such as [`StepperMotor::run_to_switch`]: the
+/// motor is returned Idle on both paths.
+type IdleResult<STEP, DIR, DELAY, HOME> = core::result::Result<
+    StepperMotor<STEP, DIR, DELAY, Idle, HOME>,
+    (StepperMotor<STEP, DIR, DELAY, Idle, HOME>, Error),
+>;
+
+/// Result of leaving Idle for `STATE`: on failure the motor stays Idle.
+type StartResult<STEP, DIR, DELAY, STATE, HOME> = core::result::Result<
+    StepperMotor<STEP, DIR, DELAY, STATE, HOME>,
+    (StepperMotor<STEP, DIR, DELAY, Idle, HOME>, Error),
+>;
 
 /// Stepper motor driver with type-state safety.
 ///
@@ -22,11 +128,14 @@ use super::state::{Idle, MotorState, Moving, StateName};
 /// - `DIR`: DIR pin type (must implement `OutputPin`)
 /// - `DELAY`: Delay provider (must implement `DelayNs`)
 /// - `STATE`: Type-state marker (defaults to `Idle`)
-pub struct StepperMotor<STEP, DIR, DELAY, STATE = Idle>
+/// - `HOME`: Home switch input (must implement `InputPin`; defaults to
+///   [`NoHomeSwitch`])
+pub struct StepperMotor<STEP, DIR, DELAY, STATE = Idle, HOME = NoHomeSwitch>
 where
     STEP: OutputPin,
     DIR: OutputPin,
     DELAY: DelayNs,
+    HOME: InputPin,
     STATE: MotorState,
 {
     /// STEP pin (pulse to move one step).
@@ -56,6 +165,55 @@ where
     /// Backlash compensation in steps (applied on direction change).
     backlash_steps: i64,
 
+    /// STEP pulse active duration in nanoseconds.
+    step_pulse_ns: u32,
+
+    /// Pause before a move that reverses direction (milliseconds).
+    reversal_dwell_ms: u32,
+
+    /// Fixed overhead of one delay call, subtracted from each delay (nanoseconds).
+    delay_overhead_ns: u32,
+
+    /// Longest single `delay_ns` call; longer delays are split (nanoseconds).
+    max_delay_chunk_ns: u32,
+
+    /// Steps whose interval was too short to compensate for the delay overhead.
+    underrun_steps: u32,
+
+    /// Name of the last trajectory run by `execute()` (cleared by other moves).
+    last_trajectory: Option<heapless::String<32>>,
+
+    /// Step timing statistics (active when a clock is configured).
+    timing: TimingRecorder,
+
+    /// Home switch used by homing moves, triggered while it reads high.
+    home_switch: Option<HOME>,
+
+    /// Trajectory run by `park()`.
+    park_trajectory: Option<heapless::String<32>>,
+
+    /// Level tracking when the DIR pin is shared with other motors.
+    dir_sync: Option<DirSync<DIR>>,
+
+    /// Duty-cycle bookkeeping (when a thermal budget is configured).
+    duty: Option<DutyTracker>,
+
+    /// Direction every `move_to` finishes in (overshooting when needed).
+    approach_direction: Option<Direction>,
+
+    /// Legs of the current move still to run after the executor's, last
+    /// leg first.
+    pending_legs: heapless::Vec<MotionProfile, { MAX_PLAN_LEGS - 1 }>,
+
+    /// Soft-start window after the driver was last enabled.
+    soft_start: Option<SoftStartWindow>,
+
+    /// Moves started with their target clamped to a soft limit.
+    clamp_events: u32,
+
+    /// Position the last move started from, for `rewind_to_pre_move()`.
+    pre_move_position: Option<Steps>,
+
     /// Motion executor for current move (if any).
     executor: Option<MotionExecutor>,
 
@@ -63,11 +221,12 @@ where
     _state: PhantomData<STATE>,
 }
 
-impl<STEP, DIR, DELAY, STATE> StepperMotor<STEP, DIR, DELAY, STATE>
+impl<STEP, DIR, DELAY, STATE, HOME> StepperMotor<STEP, DIR, DELAY, STATE, HOME>
 where
     STEP: OutputPin,
     DIR: OutputPin,
     DELAY: DelayNs,
+    HOME: InputPin,
     STATE: MotorState + StateName,
 {
     /// Get the motor name.
@@ -88,24 +247,432 @@ where
         self.position.degrees()
     }
 
+    /// Get current position in output revolutions (negative below the
+    /// origin).
+    #[inline]
+    pub fn position_turns(&self) -> f32 {
+        self.position.total_revolutions()
+    }
+
+    /// Get the number of whole output revolutions from the origin, rounded
+    /// towards negative infinity, e.g. for counting spool turns.
+    #[inline]
+    pub fn whole_turns(&self) -> i64 {
+        self.position.whole_turns()
+    }
+
+    /// Get the angle within the current output revolution, in `[0°, 360°)`.
+    ///
+    /// Turns are floored, so −90° is turn −1 at 270°; see
+    /// [`Position`](super::Position#turn-counting).
+    #[inline]
+    pub fn angle_in_turn(&self) -> Degrees {
+        self.position.angle_in_turn()
+    }
+
+    /// Get current position in degrees, formatted to the step resolution.
+    ///
+    /// Only the decimal places given by
+    /// [`MechanicalConstraints::degrees_precision`] are shown.
+    #[inline]
+    pub fn position_degrees_display(&self) -> FormattedDegrees {
+        self.position_degrees()
+            .format_with_precision(self.constraints.degrees_precision())
+    }
+
+    /// Number of moves started with their target clamped to a soft limit,
+    /// since the motor was built or [`reset_clamp_events`](StepperMotor::reset_clamp_events).
+    ///
+    /// A count that keeps rising points at a host sending out-of-range
+    /// targets.
+    #[inline]
+    pub fn clamp_events(&self) -> u32 {
+        self.clamp_events
+    }
+
+    /// Remaining travel from the current position to the `(min, max)` soft
+    /// limits, or `None` without limits.
+    ///
+    /// See [`MechanicalConstraints::travel_from`].
+    #[inline]
+    pub fn travel_to_limits(&self) -> Option<(Degrees, Degrees)> {
+        self.constraints.travel_from(self.position.steps())
+    }
+
+    /// Check a `move_to` target against the soft limits without moving.
+    ///
+    /// The target is converted to steps exactly as `move_to` does, so under
+    /// the reject policy `move_to` fails with `LimitExceeded` if and only if
+    /// this returns `Some`. A target exactly on a limit is allowed. With
+    /// [direction-dependent maxima](crate::config::SoftLimits::max_cw), the one for the
+    /// direction from the current position applies.
+    pub fn would_exceed_limits(&self, target: Degrees) -> Option<LimitViolation> {
+        let target = self.constraints.degrees_to_steps(target.0);
+        self.constraints.limit_violation_from(self.position.steps().0, target)
+    }
+
     /// Get the mechanical constraints.
     #[inline]
     pub fn constraints(&self) -> &MechanicalConstraints {
         &self.constraints
     }
 
+    /// Get the STEP pulse width in nanoseconds.
+    #[inline]
+    pub fn step_pulse_ns(&self) -> u32 {
+        self.step_pulse_ns
+    }
+
     /// Get the current state name.
     #[inline]
     pub fn state_name(&self) -> &'static str {
         STATE::name()
     }
+
+    /// Get the delay provider overhead compensated on each delay (nanoseconds).
+    #[inline]
+    pub fn delay_overhead_ns(&self) -> u32 {
+        self.delay_overhead_ns
+    }
+
+    /// Get the longest single call made to the delay provider (nanoseconds).
+    #[inline]
+    pub fn max_delay_chunk_ns(&self) -> u32 {
+        self.max_delay_chunk_ns
+    }
+
+    /// Get the number of steps whose interval was shorter than the STEP pulse
+    /// plus one delay overhead.
+    ///
+    /// These steps ran slower than planned: a non-zero count means the delay
+    /// provider cannot keep up with the motion profile.
+    #[inline]
+    pub fn underrun_steps(&self) -> u32 {
+        self.underrun_steps
+    }
+
+    /// Get the name of the last trajectory run by `execute()`.
+    ///
+    /// Any other move (including `execute_trajectory`) clears it, since the
+    /// motor may no longer be where that trajectory left it.
+    #[inline]
+    pub fn last_trajectory(&self) -> Option<&str> {
+        self.last_trajectory.as_deref()
+    }
+
+    /// Reset the underrun counter.
+    #[inline]
+    pub fn reset_underrun_steps(&mut self) {
+        self.underrun_steps = 0;
+    }
+
+    /// Get the step timing statistics collected so far.
+    ///
+    /// Returns `None` unless a clock was configured with
+    /// [`StepperMotorBuilder::clock`](super::StepperMotorBuilder::clock) and
+    /// recording was not disabled.
+    #[inline]
+    pub fn timing_stats(&self) -> Option<&TimingStats> {
+        self.timing.stats()
+    }
+
+    /// Take the step timing statistics, starting a fresh set.
+    ///
+    /// Call after a move to get that move's figures. Returns `None` when
+    /// timing statistics are not being collected.
+    pub fn take_timing_stats(&mut self) -> Option<TimingStats> {
+        self.timing.take()
+    }
+
+    /// Install the timing recorder (used by the builder).
+    pub(crate) fn set_timing(&mut self, timing: TimingRecorder) {
+        self.timing = timing;
+    }
+
+    /// Get the name of the trajectory run by [`park`](StepperMotor::park).
+    #[inline]
+    pub fn park_trajectory(&self) -> Option<&str> {
+        self.park_trajectory.as_deref()
+    }
+
+    /// Set the trajectory run by `park()` (used by the builder).
+    pub(crate) fn set_park_trajectory(&mut self, name: Option<heapless::String<32>>) {
+        self.park_trajectory = name;
+    }
+
+    /// Track DIR level changes made by other motors (used by the builder).
+    pub(crate) fn set_dir_generation(&mut self, read: Option<DirGenerationFn<DIR>>) {
+        self.dir_sync = read.map(|read| DirSync { read, seen: None });
+    }
+
+    /// Get the duty-cycle tracker, if a thermal budget is configured.
+    ///
+    /// The duty is updated when moves start and end.
+    #[inline]
+    pub fn duty_tracker(&self) -> Option<&DutyTracker> {
+        self.duty.as_ref()
+    }
+
+    /// Get the duty-cycle tracker mutably, e.g. to record idle time without
+    /// a clock or to restore a persisted tracker.
+    #[inline]
+    pub fn duty_tracker_mut(&mut self) -> Option<&mut DutyTracker> {
+        self.duty.as_mut()
+    }
+
+    /// Get the direction every `move_to` finishes in, if configured.
+    #[inline]
+    pub fn approach_direction(&self) -> Option<Direction> {
+        self.approach_direction
+    }
+
+    /// Set the direction every `move_to` finishes in (used by the builder).
+    pub(crate) fn set_approach_direction(&mut self, direction: Option<Direction>) {
+        self.approach_direction = direction;
+    }
+
+    /// Set the longest single delay call (used by the builder).
+    pub(crate) fn set_max_delay_chunk_ns(&mut self, ns: u32) {
+        self.max_delay_chunk_ns = ns;
+    }
+
+    /// Install the soft-start settings and arm the window (used by the builder).
+    pub(crate) fn set_soft_start(&mut self, config: Option<SoftStartConfig>) {
+        self.soft_start = config.map(|config| SoftStartWindow {
+            config,
+            enabled_at_ns: None,
+            armed: false,
+        });
+        self.arm_soft_start();
+    }
+
+    /// Start the soft-start window, if configured.
+    fn arm_soft_start(&mut self) {
+        let now = self.timing.now();
+        if let Some(window) = self.soft_start.as_mut() {
+            window.enabled_at_ns = now;
+            window.armed = true;
+        }
+    }
+
+    /// Soften the acceleration of `profile` inside the soft-start window.
+    fn soft_start_profile(&mut self, profile: MotionProfile) -> MotionProfile {
+        let now = self.timing.now();
+        match self.soft_start.as_mut().and_then(|window| window.take_scale(now)) {
+            Some(scale) => profile.with_acceleration_scale(scale),
+            None => profile,
+        }
+    }
+
+    /// Install the duty-cycle tracker (used by the builder).
+    pub(crate) fn set_duty_tracker(&mut self, duty: Option<DutyTracker>) {
+        self.duty = duty;
+    }
+
+    /// Update the duty for a move about to start.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotorError::ThermalBudgetExceeded` if the budget is enforced
+    /// and the duty is above it.
+    fn duty_move_started(&mut self) -> Result<()> {
+        let now = self.timing.now();
+        let Some(duty) = self.duty.as_mut() else {
+            return Ok(());
+        };
+        duty.move_started(now);
+        if duty.is_enforced() && duty.is_over_budget() {
+            return Err(Error::Motor(MotorError::ThermalBudgetExceeded {
+                duty: duty.current_duty(),
+                max_duty: duty.max_duty(),
+            }));
+        }
+        Ok(())
+    }
+
+    /// Update the duty for a move that just ended, using `estimated_secs`
+    /// when there is no clock.
+    fn duty_move_ended(&mut self, estimated_secs: f32) {
+        let now = self.timing.now();
+        if let Some(duty) = self.duty.as_mut() {
+            duty.move_ended(now, estimated_secs);
+        }
+    }
+
+    /// Telemetry frame for the current position and the given motion state.
+    fn telemetry(&self, velocity: f32, phase: MotionPhase, progress: f32) -> MotorTelemetry {
+        let mut fault = 0;
+        if self.duty.as_ref().is_some_and(|duty| duty.is_over_budget()) {
+            fault |= MotorTelemetry::FAULT_THERMAL;
+        }
+        if self.underrun_steps > 0 {
+            fault |= MotorTelemetry::FAULT_UNDERRUN;
+        }
+        let position = self.position.steps().value();
+        MotorTelemetry::new(self.name(), position, velocity, phase, progress, fault)
+    }
+
+    /// Estimated time spent on the current executor's steps so far.
+    fn executed_secs(&self) -> f32 {
+        self.executor.as_ref().map_or(0.0, |executor| {
+            let profile = executor.profile();
+            profile.estimated_duration_secs()
+                - profile.remaining_duration_from(executor.current_step())
+        })
+    }
+
+    /// Planner for this motor's constraints and backlash approach.
+    pub fn planner(&self) -> MotionPlanner {
+        MotionPlanner::new(self.constraints.clone())
+            .with_backlash(self.backlash_steps, self.approach_direction)
+    }
+
+    /// Install the home switch (used by the builder).
+    pub(crate) fn set_home_switch(&mut self, switch: Option<HOME>) {
+        self.home_switch = switch;
+    }
+
+    /// Issue the executor's next step pulse and wait out its interval.
+    ///
+    /// Returns `true` if the profile is complete.
+    fn pulse(&mut self) -> Result<bool> {
+        let executor = self.executor.as_ref().ok_or(MotorError::NotInitialized)?;
+        if executor.is_complete() {
+            return Ok(true);
+        }
+        let first = executor.current_step() == 0;
+
+        // Generate step pulse
+        self.timing.edge(first);
+        self.step_pin.set_high().map_err(|_| MotorError::PinError)?;
+
+        // Pulse width (configurable, 2 µs by default)
+        self.delay_compensated_ns(self.step_pulse_ns);
+
+        self.step_pin.set_low().map_err(|_| MotorError::PinError)?;
+
+        let executor = self.executor.as_mut().ok_or(MotorError::NotInitialized)?;
+
+        // Update position
+        let direction = executor.profile().direction;
+        self.position.move_steps(direction.sign());
+
+        // Get delay for next step
+        let interval_ns = executor.current_interval_ns();
+        self.timing.plan(interval_ns);
+
+        // Advance executor
+        let has_more = executor.advance();
+        // A next leg in the same direction carries on without stopping
+        let continues = self.pending_legs.last().is_some_and(|next| next.direction == direction);
+
+        if has_more || continues {
+            // Delay until next step (subtract the pulse as actually issued)
+            let delay_ns = interval_ns.saturating_sub(self.effective_pulse_ns());
+            if delay_ns < self.delay_overhead_ns {
+                self.underrun_steps = self.underrun_steps.saturating_add(1);
+            }
+            self.delay_compensated_ns(delay_ns);
+        }
+
+        Ok(!has_more)
+    }
+
+    /// Delay for `ns` nanoseconds minus the provider overhead.
+    ///
+    /// Zero-length delays are skipped; anything shorter than the overhead
+    /// still makes one call, so the delay is never shorter than requested.
+    fn delay_compensated_ns(&mut self, ns: u32) {
+        if ns > 0 {
+            let ns = ns.saturating_sub(self.delay_overhead_ns);
+            if ns == 0 {
+                self.delay.delay_ns(0);
+            } else {
+                delay_chunked(&mut self.delay, ns as u64, self.max_delay_chunk_ns);
+            }
+        }
+    }
+
+    /// Dwell for `ms` milliseconds.
+    ///
+    /// A single `delay_ms` call unless a chunk cap is set, in which case the
+    /// dwell is split into capped `delay_ns` calls.
+    fn dwell_ms(&mut self, ms: u32) {
+        if self.max_delay_chunk_ns == u32::MAX {
+            self.delay.delay_ms(ms);
+        } else {
+            delay_chunked(&mut self.delay, ms as u64 * 1_000_000, self.max_delay_chunk_ns);
+        }
+    }
+
+    /// Actual STEP pulse width once the delay overhead is included.
+    #[inline]
+    fn effective_pulse_ns(&self) -> u32 {
+        self.step_pulse_ns.max(self.delay_overhead_ns)
+    }
+
+    /// Set direction for `profile`. On a reversal, dwell at zero velocity,
+    /// then take up backlash before the real move starts.
+    fn prepare_direction(&mut self, profile: &MotionProfile) -> core::result::Result<(), ()> {
+        let direction = profile.direction;
+        let reversing = self.current_direction.is_some_and(|d| d != direction);
+        self.set_direction(direction)?;
+
+        if reversing {
+            if self.reversal_dwell_ms > 0 {
+                self.dwell_ms(self.reversal_dwell_ms);
+            }
+            self.take_up_backlash(profile.initial_interval_ns)?;
+        }
+        Ok(())
+    }
+
+    /// Emit backlash compensation pulses without updating the position.
+    fn take_up_backlash(&mut self, interval_ns: u32) -> core::result::Result<(), ()> {
+        for _ in 0..self.backlash_steps.unsigned_abs() {
+            self.step_pin.set_high().map_err(|_| ())?;
+            self.delay_compensated_ns(self.step_pulse_ns);
+            self.step_pin.set_low().map_err(|_| ())?;
+            self.delay_compensated_ns(interval_ns.saturating_sub(self.effective_pulse_ns()));
+        }
+        Ok(())
+    }
+
+    fn set_direction(&mut self, direction: Direction) -> core::result::Result<(), ()> {
+        // A shared DIR pin may have been changed by another motor since our write
+        let level_current = match &self.dir_sync {
+            Some(sync) => sync.seen == Some((sync.read)(&self.dir_pin)),
+            None => true,
+        };
+        if self.current_direction == Some(direction) && level_current {
+            return Ok(());
+        }
+
+        let pin_high = match direction {
+            Direction::Clockwise => !self.invert_direction,
+            Direction::CounterClockwise => self.invert_direction,
+        };
+
+        if pin_high {
+            self.dir_pin.set_high().map_err(|_| ())?;
+        } else {
+            self.dir_pin.set_low().map_err(|_| ())?;
+        }
+
+        if let Some(sync) = self.dir_sync.as_mut() {
+            sync.seen = Some((sync.read)(&self.dir_pin));
+        }
+        self.current_direction = Some(direction);
+        Ok(())
+    }
 }
 
-impl<STEP, DIR, DELAY> StepperMotor<STEP, DIR, DELAY, Idle>
+impl<STEP, DIR, DELAY, HOME> StepperMotor<STEP, DIR, DELAY, Idle, HOME>
 where
     STEP: OutputPin,
     DIR: OutputPin,
     DELAY: DelayNs,
+    HOME: InputPin,
 {
     /// Create a new motor in the Idle state.
     pub(crate) fn new(
@@ -116,49 +683,150 @@ where
         name: heapless::String<32>,
         invert_direction: bool,
         backlash_steps: i64,
+        step_pulse_ns: u32,
+        reversal_dwell_ms: u32,
+        delay_overhead_ns: u32,
     ) -> Self {
         Self {
             step_pin,
             dir_pin,
             delay,
-            position: Position::new(constraints.steps_per_degree),
+            position: Position::from_constraints(&constraints),
             current_direction: None,
             constraints,
             name,
             invert_direction,
             backlash_steps,
+            step_pulse_ns,
+            reversal_dwell_ms,
+            delay_overhead_ns,
+            max_delay_chunk_ns: u32::MAX,
+            underrun_steps: 0,
+            last_trajectory: None,
+            timing: TimingRecorder::new(None, true, DEFAULT_TIMING_BUCKET_NS),
+            home_switch: None,
+            park_trajectory: None,
+            dir_sync: None,
+            duty: None,
+            approach_direction: None,
+            pending_legs: heapless::Vec::new(),
+            soft_start: None,
+            clamp_events: 0,
+            pre_move_position: None,
             executor: None,
             _state: PhantomData,
         }
     }
 
+    /// Record that the driver has just been (re-)enabled.
+    ///
+    /// With a [soft start](super::StepperMotorBuilder::soft_start)
+    /// configured, moves starting within its window accelerate more gently;
+    /// without a clock, only the next move does. The enable pin itself is
+    /// driven by the application.
+    pub fn notify_enabled(&mut self) {
+        self.arm_soft_start();
+    }
+
+    /// Capture a [`MotorTelemetry`] frame: at rest, complete, zero velocity.
+    pub fn telemetry_snapshot(&self) -> MotorTelemetry {
+        self.telemetry(0.0, MotionPhase::Complete, 1.0)
+    }
+
     /// Start a move to an absolute position in degrees.
     ///
     /// Returns a motor in the `Moving` state.
+    ///
+    /// With an [approach direction](super::StepperMotorBuilder::approach_direction)
+    /// configured, a move in the other direction first overshoots the target
+    /// by the backlash compensation, then reverses onto it. Both legs run as
+    /// one move; `is_complete()` turns true at the final target.
+    ///
+    /// Under the clamp limit policy, targets beyond a limit stop at the
+    /// limit; under the wrap policy they are reduced into the window, and
+    /// the plan's `wrapped_spans` says by how many spans.
+    /// [`planner`](Self::planner) computes the same plan without moving.
     pub fn move_to(
-        mut self,
+        self,
+        target: Degrees,
+    ) -> StartResult<STEP, DIR, DELAY, Moving, HOME> {
+        let target_steps = self.constraints.degrees_to_steps(target.0);
+        let plan =
+            self.planner().plan_steps(self.position.steps().0, target_steps, MoveParams::default());
+        match plan {
+            Ok(plan) => self.start_plan(plan),
+            Err(e) => Err((self, e)),
+        }
+    }
+
+    /// Prepare a move to `target` without starting it.
+    ///
+    /// Everything [`move_to`](Self::move_to) does before the first step
+    /// happens here: the move is planned with `params` and checked against
+    /// the limits and thermal budget, the DIR pin is set and the executor is
+    /// built. [`trigger`](StepperMotor::trigger) then starts stepping at
+    /// once, so motors owned by different tasks can start together on a
+    /// shared flag or GPIO edge.
+    ///
+    /// No step pulses are emitted while armed, so the motor holds its
+    /// position with the DIR pin already set. On a reversal, the reversal
+    /// dwell and backlash take-up (which does not change the position) run
+    /// during this call rather than after the trigger.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `move_to`.
+    pub fn arm(
+        self,
         target: Degrees,
-    ) -> core::result::Result<StepperMotor<STEP, DIR, DELAY, Moving>, (Self, Error)> {
-        // Calculate steps to target
-        let target_steps = Steps::from_degrees(target, self.constraints.steps_per_degree);
-        let delta_steps = target_steps.0 - self.position.steps().0;
+        params: MoveParams,
+    ) -> StartResult<STEP, DIR, DELAY, Armed, HOME> {
+        let target_steps = self.constraints.degrees_to_steps(target.0);
+        let plan = self.planner().plan_steps(self.position.steps().0, target_steps, params);
+        match plan {
+            Ok(plan) => self.arm_plan(plan),
+            Err(e) => Err((self, e)),
+        }
+    }
 
-        if delta_steps == 0 {
-            // Already at target, return self unchanged
+    /// Start a move that retraces a previously executed profile in reverse.
+    ///
+    /// The return trip reuses `original_profile` with its direction flipped
+    /// instead of recomputing it, bringing the motor back to where the original
+    /// move started.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile is empty, the return target is outside
+    /// the soft limits, or the DIR pin cannot be set.
+    pub fn return_move(
+        self,
+        original_profile: &MotionProfile,
+    ) -> StartResult<STEP, DIR, DELAY, Moving, HOME> {
+        if original_profile.is_zero() {
             return Err((self, Error::Motion(crate::error::MotionError::MoveTooShort {
                 steps: 0,
                 minimum: 1,
             })));
         }
 
-        // Check limits - extract limit value before potentially moving self
-        let limit_check = self.constraints.limits.as_ref().and_then(|limits| {
-            if limits.apply(target_steps.0).is_none() {
-                Some(if delta_steps > 0 {
-                    limits.max_steps
-                } else {
-                    limits.min_steps
-                })
+        self.start_profile(original_profile.invert())
+    }
+
+    /// Check limits, set direction and transition to `Moving` for a profile
+    /// starting at the current position.
+    fn start_profile(
+        self,
+        profile: MotionProfile,
+    ) -> StartResult<STEP, DIR, DELAY, Moving, HOME> {
+        let delta_steps = profile.direction.sign() * profile.total_steps as i64;
+        let target_steps = Steps(self.position.steps().0 + delta_steps);
+
+        // Check limits - extract limit value before potentially moving self
+        let limit_check = self.constraints.limits.as_ref().and_then(|limits| {
+            if limits.apply_moving(target_steps.0, profile.direction).is_none() {
+                let (min, max) = limits.bounds(profile.direction);
+                Some(if delta_steps > 0 { max } else { min })
             } else {
                 None
             }
@@ -174,23 +842,68 @@ where
             ));
         }
 
-        // Create motion profile
-        let profile = MotionProfile::symmetric_trapezoidal(
-            delta_steps,
-            self.constraints.max_velocity_steps_per_sec,
-            self.constraints.max_acceleration_steps_per_sec2,
-        );
+        self.begin_profile(profile)
+    }
 
-        // Set direction
-        let direction = profile.direction;
-        if self.set_direction(direction).is_err() {
+    /// Transition to `Moving` for a plan starting at the current position,
+    /// keeping the legs after the first for `step()`.
+    ///
+    /// The planner has already applied the soft limits.
+    fn start_plan(
+        self,
+        plan: MovePlan,
+    ) -> StartResult<STEP, DIR, DELAY, Moving, HOME> {
+        self.arm_plan(plan).map(StepperMotor::trigger)
+    }
+
+    /// Transition to `Armed` for a plan starting at the current position.
+    fn arm_plan(
+        self,
+        mut plan: MovePlan,
+    ) -> StartResult<STEP, DIR, DELAY, Armed, HOME> {
+        plan.legs.reverse();
+        let Some(first) = plan.legs.pop() else {
+            return Err((self, Error::Motion(crate::error::MotionError::MoveTooShort {
+                steps: 0,
+                minimum: 1,
+            })));
+        };
+        let mut armed = self.arm_profile(first)?;
+        armed.pending_legs = plan.legs.into_iter().collect();
+        if plan.clamped {
+            armed.clamp_events = armed.clamp_events.saturating_add(1);
+        }
+        Ok(armed)
+    }
+
+    /// Set direction and transition to `Moving` without checking limits.
+    fn begin_profile(
+        self,
+        profile: MotionProfile,
+    ) -> StartResult<STEP, DIR, DELAY, Moving, HOME> {
+        self.arm_profile(profile).map(StepperMotor::trigger)
+    }
+
+    /// Set direction and transition to `Armed` without checking limits.
+    ///
+    /// Every move starts here, so this is where the pre-move position is
+    /// recorded.
+    fn arm_profile(
+        mut self,
+        profile: MotionProfile,
+    ) -> StartResult<STEP, DIR, DELAY, Armed, HOME> {
+        if let Err(e) = self.duty_move_started() {
+            return Err((self, e));
+        }
+        let profile = self.soft_start_profile(profile);
+        if self.prepare_direction(&profile).is_err() {
             return Err((self, Error::Motor(MotorError::PinError)));
         }
 
         // Create executor
         let executor = MotionExecutor::new(profile);
 
-        // Transition to Moving state
+        // Transition to Armed state
         Ok(StepperMotor {
             step_pin: self.step_pin,
             dir_pin: self.dir_pin,
@@ -201,6 +914,23 @@ where
             name: self.name,
             invert_direction: self.invert_direction,
             backlash_steps: self.backlash_steps,
+            step_pulse_ns: self.step_pulse_ns,
+            reversal_dwell_ms: self.reversal_dwell_ms,
+            delay_overhead_ns: self.delay_overhead_ns,
+            max_delay_chunk_ns: self.max_delay_chunk_ns,
+            underrun_steps: self.underrun_steps,
+            // Moved away from where the last trajectory left it
+            last_trajectory: None,
+            timing: self.timing,
+            home_switch: self.home_switch,
+            park_trajectory: self.park_trajectory,
+            dir_sync: self.dir_sync,
+            duty: self.duty,
+            approach_direction: self.approach_direction,
+            pending_legs: heapless::Vec::new(),
+            soft_start: self.soft_start,
+            clamp_events: self.clamp_events,
+            pre_move_position: Some(self.position.steps()),
             executor: Some(executor),
             _state: PhantomData,
         })
@@ -210,19 +940,81 @@ where
     pub fn move_by(
         self,
         delta: Degrees,
-    ) -> core::result::Result<StepperMotor<STEP, DIR, DELAY, Moving>, (Self, Error)> {
+    ) -> StartResult<STEP, DIR, DELAY, Moving, HOME> {
         let target = Degrees(self.position.degrees().0 + delta.0);
         self.move_to(target)
     }
 
     /// Set the current position as the origin (zero).
+    ///
+    /// Like the other position setters, this forgets the pre-move position.
     pub fn set_origin(&mut self) {
         self.position.set_origin();
+        self.pre_move_position = None;
     }
 
     /// Set the current position to a specific value.
     pub fn set_position(&mut self, degrees: Degrees) {
         self.position.set_degrees(degrees);
+        self.pre_move_position = None;
+    }
+
+    /// Set the current position in steps, e.g. from a saved snapshot.
+    pub fn set_position_steps(&mut self, steps: Steps) {
+        self.position.set_steps(steps);
+        self.pre_move_position = None;
+    }
+
+    /// Get the position the last move started from, if it is still known.
+    ///
+    /// Every move records it, whether started with [`move_to`](Self::move_to),
+    /// a trajectory or a soft-limit move; for a sequence it is the start of
+    /// the last waypoint.
+    pub fn pre_move_position(&self) -> Option<Steps> {
+        self.pre_move_position
+    }
+
+    /// Return to where the last move started (blocking); see
+    /// [`pre_move_position`](Self::pre_move_position).
+    ///
+    /// The return trip is planned like any other move, with the usual ramps
+    /// and approach direction, and runs to completion. The stored position
+    /// is used up, so a second call fails until the next move.
+    /// Returns `Ok(self)` unchanged if the motor is already there.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotorError::NotInitialized` if no pre-move position is
+    /// stored (no move yet, or the position was set or homed since), an
+    /// error if the move cannot be planned, or `MotorError::PinError` if a
+    /// pin write fails; the position is then kept for another attempt.
+    pub fn rewind_to_pre_move(mut self) -> core::result::Result<Self, (Self, Error)> {
+        let Some(start) = self.pre_move_position.take() else {
+            return Err((self, Error::Motor(MotorError::NotInitialized)));
+        };
+        let position = self.position.steps();
+        if position == start {
+            return Ok(self);
+        }
+
+        let plan = self.planner().plan_steps(position.0, start.0, MoveParams::default());
+        let result = match plan {
+            Ok(plan) => self.start_plan(plan).and_then(|mut moving| {
+                // The rewind itself is not a move to come back from
+                moving.pre_move_position = None;
+                moving.run_blocking()
+            }),
+            Err(e) => Err((self, e)),
+        };
+        result.map_err(|(mut idle, e)| {
+            idle.pre_move_position = Some(start);
+            (idle, e)
+        })
+    }
+
+    /// Reset the [`clamp_events`](StepperMotor::clamp_events) count to zero.
+    pub fn reset_clamp_events(&mut self) {
+        self.clamp_events = 0;
     }
 
     /// Execute a named trajectory from a registry.
@@ -244,26 +1036,89 @@ where
     /// Returns an error if:
     /// - The trajectory is not found in the registry
     /// - The trajectory's target motor doesn't match this motor's name
+    /// - The trajectory's preconditions (`requires_position_deg`,
+    ///   `requires_previous`) are not met
     /// - The move fails due to limits or hardware errors
+    ///
+    /// A trajectory with a `limit_override` is planned against those limits
+    /// instead of the motor's. The motor's own limits are left unchanged and
+    /// apply again from the next move.
+    ///
+    /// On success the trajectory is remembered as the motor's
+    /// [`last_trajectory`](Self::last_trajectory).
     pub fn execute(
         self,
         trajectory_name: &str,
         registry: &crate::trajectory::TrajectoryRegistry,
     ) -> core::result::Result<Self, (Self, Error)> {
+        self.execute_reported(trajectory_name, registry).map(|(motor, _)| motor)
+    }
+
+    /// [`execute`](Self::execute), also returning a report of the move.
+    ///
+    /// For homing trajectories the report gives the full search travel, and
+    /// the position after the move (the new origin).
+    ///
+    /// # Errors
+    ///
+    /// As [`execute`](Self::execute).
+    pub fn execute_reported(
+        self,
+        trajectory_name: &str,
+        registry: &crate::trajectory::TrajectoryRegistry,
+    ) -> core::result::Result<(Self, MoveReport), (Self, Error)> {
+        let (trajectory, plan) = match self.prepare_trajectory(trajectory_name, registry) {
+            Ok(prepared) => prepared,
+            Err(e) => return Err((self, e)),
+        };
+        let mut report = MoveReport::from_plan(trajectory_name, &plan);
+
+        // Homing trajectories run to the switch; others move to the target
+        let mut motor = if trajectory.is_homing_trajectory() {
+            self.run_homing(trajectory)?
+        } else {
+            self.run_plan(plan)?
+        };
+        motor.last_trajectory = heapless::String::try_from(trajectory_name).ok();
+        report.end_steps = motor.position.steps().0;
+        Ok((motor, report))
+    }
+
+    /// Rehearse a named trajectory: everything
+    /// [`execute_reported`](Self::execute_reported) decides, without moving.
+    ///
+    /// The lookup, precondition and limit checks and the planning are the
+    /// ones a real run makes, but no pin, delay or position is touched and
+    /// dwells are skipped, e.g. to commission a configuration. Homing
+    /// trajectories report their full search travel.
+    ///
+    /// # Errors
+    ///
+    /// As [`execute`](Self::execute), apart from hardware errors.
+    pub fn rehearse(
+        &self,
+        trajectory_name: &str,
+        registry: &crate::trajectory::TrajectoryRegistry,
+    ) -> Result<MoveReport> {
+        let (_, plan) = self.prepare_trajectory(trajectory_name, registry)?;
+        Ok(MoveReport::from_plan(trajectory_name, &plan))
+    }
+
+    /// Look up a trajectory for this motor, check its preconditions and plan
+    /// it from the current position.
+    fn prepare_trajectory<'r>(
+        &self,
+        trajectory_name: &str,
+        registry: &'r crate::trajectory::TrajectoryRegistry,
+    ) -> Result<(&'r crate::config::TrajectoryConfig, MovePlan)> {
         // Look up trajectory
-        let trajectory = match registry.get(trajectory_name) {
-            Some(t) => t,
-            None => {
-                // Build error with available names
-                let mut msg: heapless::String<64> = heapless::String::new();
-                let _ = msg.push_str("trajectory '");
-                let _ = msg.push_str(trajectory_name);
-                let _ = msg.push_str("' not found");
-                return Err((
-                    self,
-                    Error::Trajectory(crate::error::TrajectoryError::InvalidName(msg)),
-                ));
-            }
+        let Some(trajectory) = registry.get(trajectory_name) else {
+            // Build error with available names
+            let mut msg: heapless::String<64> = heapless::String::new();
+            let _ = msg.push_str("trajectory '");
+            let _ = msg.push_str(trajectory_name);
+            let _ = msg.push_str("' not found");
+            return Err(Error::Trajectory(crate::error::TrajectoryError::InvalidName(msg)));
         };
 
         // Verify this trajectory is for this motor
@@ -274,159 +1129,2584 @@ where
             let _ = msg.push_str("' is for motor '");
             let _ = msg.push_str(trajectory.motor.as_str());
             let _ = msg.push_str("'");
-            return Err((
-                self,
-                Error::Trajectory(crate::error::TrajectoryError::InvalidName(msg)),
-            ));
+            return Err(Error::Trajectory(crate::error::TrajectoryError::InvalidName(msg)));
         }
 
-        // Execute the move to the target position
-        let target = trajectory.target_degrees;
-        self.move_to_blocking(target)
+        trajectory.check_preconditions(self.position_degrees(), self.last_trajectory())?;
+        let plan = self.planner().plan_trajectory_steps(self.position.steps().0, trajectory)?;
+        Ok((trajectory, plan))
     }
 
-    /// Move to an absolute position and run to completion (blocking).
+    /// Move to the safe pose of the configured park trajectory (blocking).
     ///
-    /// This is a convenience method that combines `move_to` and `run_to_completion`.
-    pub fn move_to_blocking(
+    /// Runs the trajectory set by `park_trajectory` in the motor
+    /// configuration through [`execute`](Self::execute), so preconditions and
+    /// limits apply as usual. A motor already at the park target counts as
+    /// parked.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from [`execute`](Self::execute).
+    pub fn park(
         self,
-        target: Degrees,
-    ) -> core::result::Result<Self, (Self, Error)> {
-        match self.move_to(target) {
-            Ok(moving) => {
-                match moving.run_to_completion() {
-                    Ok(idle) => Ok(idle),
-                    Err(e) => {
-                        // In practice, step errors are rare and typically unrecoverable
-                        // We can't return the motor in a good state here
-                        panic!("Motor step error during move: {:?}", e);
-                    }
-                }
-            }
+        registry: &crate::trajectory::TrajectoryRegistry,
+    ) -> core::result::Result<(Self, ParkOutcome), (Self, Error)> {
+        let Some(name) = self.park_trajectory.clone() else {
+            return Ok((self, ParkOutcome::NotConfigured));
+        };
+        match self.execute(name.as_str(), registry) {
+            Ok(motor) => Ok((motor, ParkOutcome::Parked)),
+            Err((motor, Error::Motion(crate::error::MotionError::MoveTooShort {
+                steps: 0,
+                ..
+            }))) => Ok((motor, ParkOutcome::Parked)),
             Err(e) => Err(e),
         }
     }
 
-    fn set_direction(&mut self, direction: Direction) -> core::result::Result<(), ()> {
-        if self.current_direction == Some(direction) {
-            return Ok(());
+    /// Execute a trajectory configuration directly (without registry lookup).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the trajectory's target motor doesn't match this
+    /// motor's name, its preconditions are not met, or if the move fails due
+    /// to limits or hardware errors.
+    pub fn execute_trajectory(
+        self,
+        trajectory: &crate::config::TrajectoryConfig,
+    ) -> core::result::Result<Self, (Self, Error)> {
+        if trajectory.motor.as_str() != self.name.as_str() {
+            let mut msg: heapless::String<64> = heapless::String::new();
+            let _ = msg.push_str("trajectory is for motor '");
+            let _ = msg.push_str(trajectory.motor.as_str());
+            let _ = msg.push_str("'");
+            return Err((
+                self,
+                Error::Trajectory(crate::error::TrajectoryError::InvalidName(msg)),
+            ));
         }
 
-        let pin_high = match direction {
-            Direction::Clockwise => !self.invert_direction,
-            Direction::CounterClockwise => self.invert_direction,
-        };
-
-        if pin_high {
-            self.dir_pin.set_high().map_err(|_| ())?;
-        } else {
-            self.dir_pin.set_low().map_err(|_| ())?;
+        if let Err(e) =
+            trajectory.check_preconditions(self.position_degrees(), self.last_trajectory())
+        {
+            return Err((self, e));
         }
 
-        self.current_direction = Some(direction);
-        Ok(())
+        if trajectory.is_homing_trajectory() {
+            return self.run_homing(trajectory);
+        }
+        self.run_approach(trajectory)
     }
-}
 
-impl<STEP, DIR, DELAY> StepperMotor<STEP, DIR, DELAY, Moving>
-where
-    STEP: OutputPin,
-    DIR: OutputPin,
-    DELAY: DelayNs,
-{
-    /// Execute one step pulse.
+    /// Execute a trajectory without any soft-limit checks (blocking).
     ///
-    /// Returns `true` if the move is complete.
-    pub fn step(&mut self) -> Result<bool> {
-        let executor = self.executor.as_mut().ok_or(MotorError::NotInitialized)?;
-
-        if executor.is_complete() {
-            return Ok(true);
+    /// Neither the motor's limits nor the trajectory's `limit_override`
+    /// apply, so the move can leave the mechanism's safe range. Meant for
+    /// testing and maintenance only; the deprecation warning marks each call
+    /// site, and callers acknowledge it with `#[allow(deprecated)]`.
+    ///
+    /// # Errors
+    ///
+    /// As [`execute_trajectory`](Self::execute_trajectory), apart from limit
+    /// errors.
+    #[deprecated(note = "skips soft-limit checks; for testing and maintenance only")]
+    pub fn execute_privileged(
+        self,
+        trajectory: &crate::config::TrajectoryConfig,
+    ) -> core::result::Result<Self, (Self, Error)> {
+        if trajectory.motor.as_str() != self.name.as_str() {
+            let mut msg: heapless::String<64> = heapless::String::new();
+            let _ = msg.push_str("trajectory is for motor '");
+            let _ = msg.push_str(trajectory.motor.as_str());
+            let _ = msg.push_str("'");
+            return Err((
+                self,
+                Error::Trajectory(crate::error::TrajectoryError::InvalidName(msg)),
+            ));
         }
 
-        // Generate step pulse
-        self.step_pin.set_high().map_err(|_| MotorError::PinError)?;
-
-        // Pulse width (typically 1-10 microseconds is sufficient)
-        self.delay.delay_us(2);
-
-        self.step_pin.set_low().map_err(|_| MotorError::PinError)?;
+        if let Err(e) =
+            trajectory.check_preconditions(self.position_degrees(), self.last_trajectory())
+        {
+            return Err((self, e));
+        }
 
-        // Update position
-        let direction = executor.profile().direction;
-        self.position.move_steps(direction.sign());
+        if trajectory.is_homing_trajectory() {
+            return self.run_homing(trajectory);
+        }
+        let plan = self
+            .planner()
+            .with_limits(None)
+            .plan_trajectory_within(self.position.steps().0, trajectory, None);
+        match plan {
+            Ok(plan) => self.run_plan(plan),
+            Err(e) => Err((self, e)),
+        }
+    }
 
-        // Get delay for next step
-        let interval_ns = executor.current_interval_ns();
+    /// Execute a waypoint sequence, blocking until the last waypoint is reached.
+    ///
+    /// Each segment is planned with the sequence's velocity and (possibly
+    /// asymmetric) acceleration/deceleration, and the motor dwells for
+    /// `dwell_ms` after reaching each waypoint. Waypoints equal to the current
+    /// position are skipped without dwelling.
+    /// A [final approach](crate::config::FinalApproach) covers the end of the
+    /// last segment at the creep velocity.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sequence is for another motor, or if a segment
+    /// fails to plan or start (limits, DIR pin). Earlier segments have already
+    /// run.
+    pub fn execute_waypoints(
+        mut self,
+        sequence: &crate::config::WaypointTrajectory,
+    ) -> core::result::Result<Self, (Self, Error)> {
+        if sequence.motor.as_str() != self.name.as_str() {
+            let mut msg: heapless::String<64> = heapless::String::new();
+            let _ = msg.push_str("sequence is for motor '");
+            let _ = msg.push_str(sequence.motor.as_str());
+            let _ = msg.push_str("'");
+            return Err((
+                self,
+                Error::Trajectory(crate::error::TrajectoryError::InvalidName(msg)),
+            ));
+        }
 
-        // Advance executor
-        let has_more = executor.advance();
+        for index in 0..sequence.waypoints.len() {
+            let position = self.position.steps().0;
+            let plan = match self.planner().plan_waypoint(position, sequence, index) {
+                Ok(Some(plan)) => plan,
+                Ok(None) => continue,
+                Err(e) => return Err((self, e)),
+            };
+            let dwell_ms = plan.dwell_ms;
+            self = self.run_plan(plan)?;
 
-        if has_more {
-            // Delay until next step (subtract pulse width)
-            let delay_ns = interval_ns.saturating_sub(2000);
-            if delay_ns > 0 {
-                self.delay.delay_ns(delay_ns);
+            if let Some(dwell_ms) = dwell_ms {
+                self.dwell_ms(dwell_ms);
             }
         }
 
-        Ok(!has_more)
+        Ok(self)
     }
 
-    /// Check if the move is complete.
-    #[inline]
-    pub fn is_complete(&self) -> bool {
-        self.executor
-            .as_ref()
-            .map(|e| e.is_complete())
-            .unwrap_or(true)
+    /// Move to an absolute position and run to completion (blocking).
+    ///
+    /// This is a convenience method that combines `move_to` and `run_to_completion`.
+    /// If a step pulse fails, the motor is returned Idle where it stopped,
+    /// with `MotorError::PinError`.
+    pub fn move_to_blocking(
+        self,
+        target: Degrees,
+    ) -> core::result::Result<Self, (Self, Error)> {
+        self.move_to(target)?.run_blocking()
     }
 
-    /// Get move progress (0.0 to 1.0).
-    #[inline]
-    pub fn progress(&self) -> f32 {
-        self.executor.as_ref().map(|e| e.progress()).unwrap_or(1.0)
-    }
+    /// Move back inside the soft limits if the current position is outside them.
+    ///
+    /// Use this on startup when the position may have been set outside the
+    /// limits (e.g. the axis was moved by hand). The motor moves to the
+    /// nearest limit at a quarter of its maximum velocity and blocks until it
+    /// arrives. Returns `Ok(self)` unchanged if no limits are configured or the
+    /// position is already within them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the move cannot be planned, or
+    /// `MotorError::PinError` if the DIR pin cannot be set or a step pulse
+    /// fails; the motor is then returned where it stopped.
+    pub fn constrain_to_limits(self) -> core::result::Result<Self, (Self, Error)> {
+        let position = self.position.steps().0;
+        let target = match &self.constraints.limits {
+            Some(limits) if position < limits.min_steps => limits.min_steps,
+            Some(limits) if position > limits.max_steps => limits.max_steps,
+            _ => return Ok(self),
+        };
 
-    /// Get current motion phase.
-    #[inline]
-    pub fn phase(&self) -> MotionPhase {
-        self.executor
-            .as_ref()
-            .map(|e| e.phase())
-            .unwrap_or(MotionPhase::Complete)
+        let profile = match MotionProfile::try_symmetric_trapezoidal(
+            target - position,
+            self.constraints.max_velocity_steps_per_sec * CONSTRAIN_VELOCITY_FRACTION,
+            self.constraints.max_acceleration_steps_per_sec2,
+        ) {
+            Ok(profile) => profile,
+            Err(e) => return Err((self, e)),
+        };
+
+        self.start_profile(profile)?.run_blocking()
     }
 
-    /// Complete the move and return to Idle state.
+    /// Drive towards a hard stop at constant velocity, ignoring soft limits.
     ///
-    /// This should be called after `is_complete()` returns true or
-    /// to abandon a move in progress.
-    pub fn finish(self) -> StepperMotor<STEP, DIR, DELAY, Idle> {
-        StepperMotor {
-            step_pin: self.step_pin,
-            dir_pin: self.dir_pin,
-            delay: self.delay,
-            position: self.position,
-            current_direction: self.current_direction,
-            constraints: self.constraints,
-            name: self.name,
-            invert_direction: self.invert_direction,
-            backlash_steps: self.backlash_steps,
-            executor: None,
-            _state: PhantomData,
+    /// Runs at `search_velocity_pct` percent (1-100) of the maximum velocity
+    /// without an acceleration ramp, for up to `u32::MAX` steps. Poll
+    /// [`step`](StepperMotor::step) until an external limit switch fires, then
+    /// call [`abort`](StepperMotor::abort).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::InvalidVelocityPercent` for a percentage outside
+    /// 1-100, or `MotorError::PinError` if the DIR pin cannot be set.
+    pub fn move_to_nearest_limit(
+        self,
+        direction: Direction,
+        search_velocity_pct: u8,
+    ) -> StartResult<STEP, DIR, DELAY, Moving, HOME> {
+        if search_velocity_pct == 0 || search_velocity_pct > 100 {
+            return Err((
+                self,
+                Error::Config(ConfigError::InvalidVelocityPercent(search_velocity_pct)),
+            ));
         }
+
+        let velocity =
+            self.constraints.max_velocity_steps_per_sec * search_velocity_pct as f32 / 100.0;
+        let profile =
+            MotionProfile::constant_velocity(direction.sign() * u32::MAX as i64, velocity);
+        self.begin_profile(profile)
     }
 
-    /// Run the move to completion (blocking).
-    pub fn run_to_completion(mut self) -> Result<StepperMotor<STEP, DIR, DELAY, Idle>> {
-        while !self.is_complete() {
-            self.step()?;
+    /// Move to the soft limit in `direction` (blocking).
+    ///
+    /// Runs at `velocity_pct` percent (1-100) of the maximum velocity with the
+    /// usual acceleration ramps and stops exactly at the limit. Returns
+    /// `Ok(self)` unchanged if the motor is already at or beyond that limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotorError::NoSoftLimits` if no limits are configured,
+    /// `ConfigError::InvalidVelocityPercent` for a percentage outside 1-100,
+    /// an error if the move cannot be planned, or `MotorError::PinError` if
+    /// the DIR pin cannot be set or a step pulse fails.
+    pub fn move_to_step_limit(
+        self,
+        direction: Direction,
+        velocity_pct: u8,
+    ) -> core::result::Result<Self, (Self, Error)> {
+        let limit = match (&self.constraints.limits, direction) {
+            (Some(limits), Direction::Clockwise) => limits.max_steps,
+            (Some(limits), Direction::CounterClockwise) => limits.min_steps,
+            (None, _) => return Err((self, Error::Motor(MotorError::NoSoftLimits))),
+        };
+        if velocity_pct == 0 || velocity_pct > 100 {
+            return Err((self, Error::Config(ConfigError::InvalidVelocityPercent(velocity_pct))));
         }
-        Ok(self.finish())
+
+        let delta = limit - self.position.steps().0;
+        if delta == 0 || Direction::from_steps(delta) != direction {
+            return Ok(self);
+        }
+
+        let profile = match MotionProfile::try_symmetric_trapezoidal(
+            delta,
+            self.constraints.max_velocity_steps_per_sec * velocity_pct as f32 / 100.0,
+            self.constraints.max_acceleration_steps_per_sec2,
+        ) {
+            Ok(profile) => profile,
+            Err(e) => return Err((self, e)),
+        };
+
+        self.start_profile(profile)?.run_blocking()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    // Tests require embedded-hal-mock, which is in dev-dependencies
+    /// Start homing: travel in `direction` until the home switch triggers.
+    ///
+    /// The motor runs at a quarter of its maximum velocity and gives up after
+    /// `max_travel`. Soft limits are not checked, since the position is not
+    /// yet known. Drive the returned motor with
+    /// [`step`](StepperMotor::step) or
+    /// [`run_to_switch`](StepperMotor::run_to_switch).
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotorError::NoHomeSwitch` if no switch was configured with
+    /// [`StepperMotorBuilder::home_switch`](super::StepperMotorBuilder::home_switch),
+    /// or an error if the travel cannot be planned or the DIR pin cannot be set.
+    pub fn start_homing(
+        self,
+        direction: Direction,
+        max_travel: Degrees,
+    ) -> StartResult<STEP, DIR, DELAY, Homing, HOME> {
+        let travel = Steps::from_degrees(max_travel, self.constraints.steps_per_degree);
+        let profile = match MotionProfile::try_symmetric_trapezoidal(
+            direction.sign() * travel.0.abs(),
+            self.constraints.max_velocity_steps_per_sec * HOMING_VELOCITY_FRACTION,
+            self.constraints.max_acceleration_steps_per_sec2,
+        ) {
+            Ok(profile) => profile,
+            Err(e) => return Err((self, e)),
+        };
+
+        self.start_homing_profile(profile)
+    }
+
+    /// Transition to `Homing` with `profile` bounding the travel.
+    fn start_homing_profile(
+        mut self,
+        profile: MotionProfile,
+    ) -> StartResult<STEP, DIR, DELAY, Homing, HOME> {
+        if self.home_switch.is_none() {
+            return Err((self, Error::Motor(MotorError::NoHomeSwitch)));
+        }
+        if profile.is_zero() {
+            return Err((self, Error::Motion(crate::error::MotionError::MoveTooShort {
+                steps: 0,
+                minimum: 1,
+            })));
+        }
+        if let Err(e) = self.duty_move_started() {
+            return Err((self, e));
+        }
+        if self.prepare_direction(&profile).is_err() {
+            return Err((self, Error::Motor(MotorError::PinError)));
+        }
+
+        Ok(StepperMotor {
+            step_pin: self.step_pin,
+            dir_pin: self.dir_pin,
+            delay: self.delay,
+            position: self.position,
+            current_direction: self.current_direction,
+            constraints: self.constraints,
+            name: self.name,
+            invert_direction: self.invert_direction,
+            backlash_steps: self.backlash_steps,
+            step_pulse_ns: self.step_pulse_ns,
+            reversal_dwell_ms: self.reversal_dwell_ms,
+            delay_overhead_ns: self.delay_overhead_ns,
+            max_delay_chunk_ns: self.max_delay_chunk_ns,
+            underrun_steps: self.underrun_steps,
+            last_trajectory: None,
+            timing: self.timing,
+            home_switch: self.home_switch,
+            park_trajectory: self.park_trajectory,
+            dir_sync: self.dir_sync,
+            duty: self.duty,
+            approach_direction: self.approach_direction,
+            pending_legs: heapless::Vec::new(),
+            soft_start: self.soft_start,
+            clamp_events: self.clamp_events,
+            // Homing may move the origin
+            pre_move_position: None,
+            executor: Some(MotionExecutor::new(profile)),
+            _state: PhantomData,
+        })
+    }
+
+    /// Run a homing trajectory to completion (blocking).
+    fn run_homing(
+        self,
+        trajectory: &crate::config::TrajectoryConfig,
+    ) -> core::result::Result<Self, (Self, Error)> {
+        let profile = match trajectory.plan_profile(&self.constraints, self.position.steps().0) {
+            Ok(profile) => profile,
+            Err(e) => return Err((self, e)),
+        };
+        self.start_homing_profile(profile)?.run_to_switch()
+    }
+
+    /// Move to a standard trajectory's target (blocking) at its rates,
+    /// overshooting first when its approach direction requires it.
+    ///
+    /// The whole plan is checked against the soft limits before any motion
+    /// starts.
+    fn run_approach(
+        self,
+        trajectory: &crate::config::TrajectoryConfig,
+    ) -> core::result::Result<Self, (Self, Error)> {
+        let plan = self.planner().plan_trajectory_steps(self.position.steps().0, trajectory);
+        match plan {
+            Ok(plan) => self.run_plan(plan),
+            Err(e) => Err((self, e)),
+        }
+    }
+
+    /// Run a plan to completion (blocking).
+    fn run_plan(self, plan: MovePlan) -> core::result::Result<Self, (Self, Error)> {
+        self.start_plan(plan)?.run_blocking()
+    }
+}
+
+impl<STEP, DIR, DELAY, HOME> StepperMotor<STEP, DIR, DELAY, Armed, HOME>
+where
+    STEP: OutputPin,
+    DIR: OutputPin,
+    DELAY: DelayNs,
+    HOME: InputPin,
+{
+    /// Get the motion profile of the armed move's first leg.
+    #[inline]
+    pub fn profile(&self) -> Option<&MotionProfile> {
+        self.executor.as_ref().map(|e| e.profile())
+    }
+
+    /// Start the armed move; the first step is taken by the next `step()`.
+    pub fn trigger(self) -> StepperMotor<STEP, DIR, DELAY, Moving, HOME> {
+        StepperMotor {
+            step_pin: self.step_pin,
+            dir_pin: self.dir_pin,
+            delay: self.delay,
+            position: self.position,
+            current_direction: self.current_direction,
+            constraints: self.constraints,
+            name: self.name,
+            invert_direction: self.invert_direction,
+            backlash_steps: self.backlash_steps,
+            step_pulse_ns: self.step_pulse_ns,
+            reversal_dwell_ms: self.reversal_dwell_ms,
+            delay_overhead_ns: self.delay_overhead_ns,
+            max_delay_chunk_ns: self.max_delay_chunk_ns,
+            underrun_steps: self.underrun_steps,
+            last_trajectory: self.last_trajectory,
+            timing: self.timing,
+            home_switch: self.home_switch,
+            park_trajectory: self.park_trajectory,
+            dir_sync: self.dir_sync,
+            duty: self.duty,
+            approach_direction: self.approach_direction,
+            pending_legs: self.pending_legs,
+            soft_start: self.soft_start,
+            clamp_events: self.clamp_events,
+            pre_move_position: self.pre_move_position,
+            executor: self.executor,
+            _state: PhantomData,
+        }
+    }
+
+    /// Busy-wait until `ready` returns `true`, then [`trigger`](Self::trigger).
+    ///
+    /// `ready` is polled in a tight loop, so the move starts within one call
+    /// of the condition turning true, e.g. a shared atomic flag or a GPIO
+    /// input read.
+    pub fn trigger_on(
+        self,
+        mut ready: impl FnMut() -> bool,
+    ) -> StepperMotor<STEP, DIR, DELAY, Moving, HOME> {
+        while !ready() {
+            core::hint::spin_loop();
+        }
+        self.trigger()
+    }
+
+    /// Cancel the armed move and return to Idle. The DIR pin keeps its level.
+    pub fn disarm(mut self) -> StepperMotor<STEP, DIR, DELAY, Idle, HOME> {
+        self.duty_move_ended(0.0);
+        StepperMotor {
+            step_pin: self.step_pin,
+            dir_pin: self.dir_pin,
+            delay: self.delay,
+            position: self.position,
+            current_direction: self.current_direction,
+            constraints: self.constraints,
+            name: self.name,
+            invert_direction: self.invert_direction,
+            backlash_steps: self.backlash_steps,
+            step_pulse_ns: self.step_pulse_ns,
+            reversal_dwell_ms: self.reversal_dwell_ms,
+            delay_overhead_ns: self.delay_overhead_ns,
+            max_delay_chunk_ns: self.max_delay_chunk_ns,
+            underrun_steps: self.underrun_steps,
+            last_trajectory: self.last_trajectory,
+            timing: self.timing,
+            home_switch: self.home_switch,
+            park_trajectory: self.park_trajectory,
+            dir_sync: self.dir_sync,
+            duty: self.duty,
+            approach_direction: self.approach_direction,
+            pending_legs: heapless::Vec::new(),
+            soft_start: self.soft_start,
+            clamp_events: self.clamp_events,
+            pre_move_position: self.pre_move_position,
+            executor: None,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<STEP, DIR, DELAY, HOME> StepperMotor<STEP, DIR, DELAY, Moving, HOME>
+where
+    STEP: OutputPin,
+    DIR: OutputPin,
+    DELAY: DelayNs,
+    HOME: InputPin,
+{
+    /// Execute one step pulse.
+    ///
+    /// Returns `true` if the move is complete.
+    pub fn step(&mut self) -> Result<bool> {
+        if !self.pulse()? {
+            return Ok(false);
+        }
+        let Some(profile) = self.pending_legs.pop() else {
+            return Ok(true);
+        };
+
+        // Leg done (e.g. an overshoot): start the next one
+        self.prepare_direction(&profile).map_err(|_| MotorError::PinError)?;
+        self.executor = Some(MotionExecutor::new(profile));
+        Ok(self.is_complete())
+    }
+
+    /// Check if the move is complete.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.pending_legs.is_empty()
+            && self
+                .executor
+                .as_ref()
+                .map(|e| e.is_complete())
+                .unwrap_or(true)
+    }
+
+    /// Get move progress (0.0 to 1.0).
+    #[inline]
+    pub fn progress(&self) -> f32 {
+        self.executor.as_ref().map(|e| e.progress()).unwrap_or(1.0)
+    }
+
+    /// Number of steps [`pause`](Self::pause) would take to bring the motor
+    /// to rest from here, at the current velocity and the profile's
+    /// deceleration rate.
+    ///
+    /// Never more than the steps left in the current leg, since a stop
+    /// late in the final deceleration simply finishes it.
+    pub fn stop_distance_steps(&self) -> u32 {
+        self.executor.as_ref().map_or(0, |e| e.stop_distance_steps())
+    }
+
+    /// Where a controlled stop commanded now would leave the motor.
+    pub fn projected_stop_position(&self) -> Degrees {
+        let direction = self.executor.as_ref().map_or(1, |e| e.profile().direction.sign());
+        let steps = self.position.steps().0 + direction * self.stop_distance_steps() as i64;
+        Steps(steps).to_degrees(self.position.steps_per_degree())
+    }
+
+    /// Check whether a controlled stop commanded now would come to rest
+    /// without passing `limit`.
+    ///
+    /// Stopping exactly on `limit` counts as before it; a `limit` behind
+    /// the motor is never passed.
+    pub fn can_stop_before(&self, limit: Degrees) -> bool {
+        let (here, stop) = (self.position_degrees().0, self.projected_stop_position().0);
+        if stop > here {
+            !(here..stop).contains(&limit.0)
+        } else {
+            !(stop < limit.0 && limit.0 <= here)
+        }
+    }
+
+    /// Get the motion profile being executed.
+    #[inline]
+    pub fn profile(&self) -> Option<&MotionProfile> {
+        self.executor.as_ref().map(|e| e.profile())
+    }
+
+    /// Get move progress eased for display (0.0 to 1.0); see
+    /// [`MotionExecutor::visual_progress`](crate::motion::MotionExecutor::visual_progress).
+    #[inline]
+    pub fn visual_progress(&self) -> f32 {
+        self.executor.as_ref().map(|e| e.visual_progress()).unwrap_or(1.0)
+    }
+
+    /// Get move progress by elapsed time (0.0 to 1.0).
+    #[inline]
+    pub fn elapsed_ratio(&self) -> f32 {
+        self.executor.as_ref().map(|e| e.elapsed_ratio()).unwrap_or(1.0)
+    }
+
+    /// Estimated time to completion in seconds.
+    #[inline]
+    pub fn eta_secs(&self) -> f32 {
+        self.executor.as_ref().map(|e| e.eta_secs()).unwrap_or(0.0)
+    }
+
+    /// Capture the current progress of the move.
+    pub fn progress_snapshot(&self) -> ProgressSnapshot {
+        self.executor
+            .as_ref()
+            .map(|e| e.snapshot())
+            .unwrap_or(ProgressSnapshot {
+                step: 0,
+                total: 0,
+                phase: MotionPhase::Complete,
+                eta_secs: 0.0,
+                velocity: 0.0,
+            })
+    }
+
+    /// Capture a [`MotorTelemetry`] frame with the signed commanded velocity.
+    pub fn telemetry_snapshot(&self) -> MotorTelemetry {
+        let snapshot = self.progress_snapshot();
+        let sign = self.profile().map_or(1, |p| p.direction.sign());
+        self.telemetry(snapshot.velocity * sign as f32, snapshot.phase, self.progress())
+    }
+
+    /// Capture the current progress once every `every` steps.
+    ///
+    /// See [`MotionExecutor::snapshot_every`].
+    pub fn progress_snapshot_every(&self, every: u32) -> Option<ProgressSnapshot> {
+        match self.executor.as_ref() {
+            Some(e) => e.snapshot_every(every),
+            None => Some(self.progress_snapshot()),
+        }
+    }
+
+    /// Get current motion phase.
+    #[inline]
+    pub fn phase(&self) -> MotionPhase {
+        self.executor
+            .as_ref()
+            .map(|e| e.phase())
+            .unwrap_or(MotionPhase::Complete)
+    }
+
+    /// Complete the move and return to Idle state.
+    ///
+    /// This should be called after `is_complete()` returns true or
+    /// to abandon a move in progress.
+    pub fn finish(mut self) -> StepperMotor<STEP, DIR, DELAY, Idle, HOME> {
+        self.duty_move_ended(self.executed_secs());
+        StepperMotor {
+            step_pin: self.step_pin,
+            dir_pin: self.dir_pin,
+            delay: self.delay,
+            position: self.position,
+            current_direction: self.current_direction,
+            constraints: self.constraints,
+            name: self.name,
+            invert_direction: self.invert_direction,
+            backlash_steps: self.backlash_steps,
+            step_pulse_ns: self.step_pulse_ns,
+            reversal_dwell_ms: self.reversal_dwell_ms,
+            delay_overhead_ns: self.delay_overhead_ns,
+            max_delay_chunk_ns: self.max_delay_chunk_ns,
+            underrun_steps: self.underrun_steps,
+            last_trajectory: self.last_trajectory,
+            timing: self.timing,
+            home_switch: self.home_switch,
+            park_trajectory: self.park_trajectory,
+            dir_sync: self.dir_sync,
+            duty: self.duty,
+            approach_direction: self.approach_direction,
+            pending_legs: heapless::Vec::new(),
+            soft_start: self.soft_start,
+            clamp_events: self.clamp_events,
+            pre_move_position: self.pre_move_position,
+            executor: None,
+            _state: PhantomData,
+        }
+    }
+
+    /// Stop immediately, without decelerating, and return to Idle.
+    ///
+    /// Use when an external event such as a limit switch ends the move; the
+    /// position is wherever the last step left it.
+    pub fn abort(self) -> StepperMotor<STEP, DIR, DELAY, Idle, HOME> {
+        self.finish()
+    }
+
+    /// Run the move to completion (blocking).
+    pub fn run_to_completion(mut self) -> Result<StepperMotor<STEP, DIR, DELAY, Idle, HOME>> {
+        while !self.is_complete() {
+            self.step()?;
+        }
+        Ok(self.finish())
+    }
+
+    /// Run the move to completion (blocking), stopping where a failed step
+    /// pulse leaves the motor and returning it with the error.
+    fn run_blocking(mut self) -> IdleResult<STEP, DIR, DELAY, HOME> {
+        while !self.is_complete() {
+            if let Err(e) = self.step() {
+                return Err((self.finish(), e));
+            }
+        }
+        Ok(self.finish())
+    }
+
+    /// Pause the move: decelerate to rest and hold position (blocking).
+    ///
+    /// The motor ramps down at the profile's deceleration rate, so it stops a
+    /// few steps past the current position. The steps still needed to reach
+    /// the original target are kept for [`resume`](StepperMotor::resume).
+    /// Pausing during the final deceleration simply finishes the move. A move
+    /// without a deceleration ramp, such as a short `move_to` or
+    /// [`move_to_nearest_limit`](StepperMotor::move_to_nearest_limit), stops
+    /// on the spot.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotorError::PinError` if a step pulse fails while stopping.
+    pub fn pause(mut self) -> Result<StepperMotor<STEP, DIR, DELAY, Paused, HOME>> {
+        let (stop, remainder) = match self.executor.as_ref() {
+            Some(executor) if !executor.is_complete() => executor.split(),
+            _ => (MotionProfile::zero(), MotionProfile::zero()),
+        };
+
+        let executed = self.executed_secs();
+        self.executor = Some(MotionExecutor::new(stop));
+        // Not `step()`: that would start a pending leg
+        while !self.pulse()? {}
+        self.duty_move_ended(executed + self.executed_secs());
+
+        Ok(StepperMotor {
+            step_pin: self.step_pin,
+            dir_pin: self.dir_pin,
+            delay: self.delay,
+            position: self.position,
+            current_direction: self.current_direction,
+            constraints: self.constraints,
+            name: self.name,
+            invert_direction: self.invert_direction,
+            backlash_steps: self.backlash_steps,
+            step_pulse_ns: self.step_pulse_ns,
+            reversal_dwell_ms: self.reversal_dwell_ms,
+            delay_overhead_ns: self.delay_overhead_ns,
+            max_delay_chunk_ns: self.max_delay_chunk_ns,
+            underrun_steps: self.underrun_steps,
+            last_trajectory: self.last_trajectory,
+            timing: self.timing,
+            home_switch: self.home_switch,
+            park_trajectory: self.park_trajectory,
+            dir_sync: self.dir_sync,
+            duty: self.duty,
+            approach_direction: self.approach_direction,
+            pending_legs: self.pending_legs,
+            soft_start: self.soft_start,
+            clamp_events: self.clamp_events,
+            pre_move_position: self.pre_move_position,
+            executor: Some(MotionExecutor::new(remainder)),
+            _state: PhantomData,
+        })
+    }
+}
+
+impl<STEP, DIR, DELAY, HOME> StepperMotor<STEP, DIR, DELAY, Paused, HOME>
+where
+    STEP: OutputPin,
+    DIR: OutputPin,
+    DELAY: DelayNs,
+    HOME: InputPin,
+{
+    /// Get the number of steps left to reach the paused move's target.
+    #[inline]
+    pub fn remaining_steps(&self) -> u32 {
+        self.executor.as_ref().map(|e| e.total_steps()).unwrap_or(0)
+    }
+
+    /// Resume the paused move, re-accelerating from rest to the original target.
+    ///
+    /// The thermal budget is not enforced here; the paused move was already
+    /// admitted.
+    pub fn resume(mut self) -> StepperMotor<STEP, DIR, DELAY, Moving, HOME> {
+        let now = self.timing.now();
+        if let Some(duty) = self.duty.as_mut() {
+            duty.move_started(now);
+        }
+        StepperMotor {
+            step_pin: self.step_pin,
+            dir_pin: self.dir_pin,
+            delay: self.delay,
+            position: self.position,
+            current_direction: self.current_direction,
+            constraints: self.constraints,
+            name: self.name,
+            invert_direction: self.invert_direction,
+            backlash_steps: self.backlash_steps,
+            step_pulse_ns: self.step_pulse_ns,
+            reversal_dwell_ms: self.reversal_dwell_ms,
+            delay_overhead_ns: self.delay_overhead_ns,
+            max_delay_chunk_ns: self.max_delay_chunk_ns,
+            underrun_steps: self.underrun_steps,
+            last_trajectory: self.last_trajectory,
+            timing: self.timing,
+            home_switch: self.home_switch,
+            park_trajectory: self.park_trajectory,
+            dir_sync: self.dir_sync,
+            duty: self.duty,
+            approach_direction: self.approach_direction,
+            pending_legs: self.pending_legs,
+            soft_start: self.soft_start,
+            clamp_events: self.clamp_events,
+            pre_move_position: self.pre_move_position,
+            executor: self.executor,
+            _state: PhantomData,
+        }
+    }
+
+    /// Abandon the paused move and return to Idle at the current position.
+    pub fn cancel(self) -> StepperMotor<STEP, DIR, DELAY, Idle, HOME> {
+        StepperMotor {
+            step_pin: self.step_pin,
+            dir_pin: self.dir_pin,
+            delay: self.delay,
+            position: self.position,
+            current_direction: self.current_direction,
+            constraints: self.constraints,
+            name: self.name,
+            invert_direction: self.invert_direction,
+            backlash_steps: self.backlash_steps,
+            step_pulse_ns: self.step_pulse_ns,
+            reversal_dwell_ms: self.reversal_dwell_ms,
+            delay_overhead_ns: self.delay_overhead_ns,
+            max_delay_chunk_ns: self.max_delay_chunk_ns,
+            underrun_steps: self.underrun_steps,
+            last_trajectory: self.last_trajectory,
+            timing: self.timing,
+            home_switch: self.home_switch,
+            park_trajectory: self.park_trajectory,
+            dir_sync: self.dir_sync,
+            duty: self.duty,
+            approach_direction: self.approach_direction,
+            pending_legs: heapless::Vec::new(),
+            soft_start: self.soft_start,
+            clamp_events: self.clamp_events,
+            pre_move_position: self.pre_move_position,
+            executor: None,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<STEP, DIR, DELAY, HOME> StepperMotor<STEP, DIR, DELAY, Homing, HOME>
+where
+    STEP: OutputPin,
+    DIR: OutputPin,
+    DELAY: DelayNs,
+    HOME: InputPin,
+{
+    /// Check the home switch, then execute one step towards it.
+    ///
+    /// Returns `true` once the switch has triggered; the current position is
+    /// then the origin.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotorError::HomeSwitchNotFound` once the maximum travel has
+    /// been used up without a trigger, or `MotorError::PinError` if the
+    /// switch cannot be read or a step pulse fails.
+    pub fn step(&mut self) -> Result<bool> {
+        let switch = self.home_switch.as_mut().ok_or(MotorError::NoHomeSwitch)?;
+        if switch.is_high().map_err(|_| MotorError::PinError)? {
+            self.position.set_origin();
+            return Ok(true);
+        }
+
+        let executor = self.executor.as_ref().ok_or(MotorError::NotInitialized)?;
+        if executor.is_complete() {
+            return Err(Error::Motor(MotorError::HomeSwitchNotFound {
+                travel_steps: executor.total_steps(),
+            }));
+        }
+        self.pulse()?;
+        Ok(false)
+    }
+
+    /// Get the number of steps travelled so far.
+    #[inline]
+    pub fn travelled_steps(&self) -> u32 {
+        self.executor.as_ref().map(|e| e.current_step()).unwrap_or(0)
+    }
+
+    /// Stop homing and return to Idle at the current position.
+    ///
+    /// The origin is only changed if the switch triggered during
+    /// [`step`](StepperMotor::step).
+    pub fn finish(mut self) -> StepperMotor<STEP, DIR, DELAY, Idle, HOME> {
+        self.duty_move_ended(self.executed_secs());
+        StepperMotor {
+            step_pin: self.step_pin,
+            dir_pin: self.dir_pin,
+            delay: self.delay,
+            position: self.position,
+            current_direction: self.current_direction,
+            constraints: self.constraints,
+            name: self.name,
+            invert_direction: self.invert_direction,
+            backlash_steps: self.backlash_steps,
+            step_pulse_ns: self.step_pulse_ns,
+            reversal_dwell_ms: self.reversal_dwell_ms,
+            delay_overhead_ns: self.delay_overhead_ns,
+            max_delay_chunk_ns: self.max_delay_chunk_ns,
+            underrun_steps: self.underrun_steps,
+            last_trajectory: self.last_trajectory,
+            timing: self.timing,
+            home_switch: self.home_switch,
+            park_trajectory: self.park_trajectory,
+            dir_sync: self.dir_sync,
+            duty: self.duty,
+            approach_direction: self.approach_direction,
+            pending_legs: heapless::Vec::new(),
+            soft_start: self.soft_start,
+            clamp_events: self.clamp_events,
+            pre_move_position: self.pre_move_position,
+            executor: None,
+            _state: PhantomData,
+        }
+    }
+
+    /// Step until the home switch triggers (blocking).
+    ///
+    /// # Errors
+    ///
+    /// Returns the Idle motor with `MotorError::HomeSwitchNotFound` if the
+    /// maximum travel is used up, or `MotorError::PinError` if the switch
+    /// cannot be read or a step fails.
+    pub fn run_to_switch(
+        mut self,
+    ) -> IdleResult<STEP, DIR, DELAY, HOME> {
+        loop {
+            match self.step() {
+                Ok(true) => return Ok(self.finish()),
+                Ok(false) => {}
+                Err(e) => return Err((self.finish(), e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::units::{DegreesPerSec, DegreesPerSecSquared, Microsteps};
+    use crate::error::MotionError;
+    use crate::motor::StepperMotorBuilder;
+    use crate::test_util::{event_builder, test_motor, Event, EventLog, NoopDelay, NoopPin};
+
+    #[test]
+    fn test_move_to_overflow() {
+        // 200 * 256 * 100 = 5.12M steps/rev: 1000 output revolutions exceed u32
+        let motor = StepperMotorBuilder::new()
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .steps_per_revolution(200)
+            .microsteps(Microsteps::TWO_FIFTY_SIXTH)
+            .gear_ratio(100.0)
+            .max_velocity(DegreesPerSec(3.6))
+            .max_acceleration(DegreesPerSecSquared(7.2))
+            .build()
+            .unwrap();
+
+        let (motor, err) = motor.move_to(Degrees(360_000.0)).err().unwrap();
+        assert_eq!(err, Error::Motion(MotionError::Overflow));
+        assert_eq!(motor.position_steps().0, 0);
+    }
+
+    #[test]
+    fn test_minimum_move_steps() {
+        let motor = StepperMotorBuilder::new()
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .steps_per_revolution(200)
+            .max_velocity(DegreesPerSec(3600.0))
+            .max_acceleration(DegreesPerSecSquared(36000.0))
+            .minimum_move_steps(10)
+            .build()
+            .unwrap();
+
+        // 200 steps/rev: 16.2° is 9 steps
+        let (motor, err) = motor.move_to(Degrees(16.2)).err().unwrap();
+        assert_eq!(err, Error::Motion(MotionError::MoveTooShort { steps: 9, minimum: 10 }));
+        let motor = motor.move_to(Degrees(-18.0)).ok().unwrap().run_to_completion().unwrap();
+        assert_eq!(motor.position_steps().0, -10);
+    }
+
+    #[test]
+    fn test_motor_is_send() {
+        // Needed to hold motors in RTIC resources or `critical_section::Mutex`
+        fn assert_send<T: Send>() {}
+        assert_send::<StepperMotor<NoopPin, NoopPin, NoopDelay, Idle>>();
+        assert_send::<StepperMotor<NoopPin, NoopPin, NoopDelay, Moving>>();
+    }
+
+    #[test]
+    fn test_short_move_at_constant_velocity() {
+        // 5 steps at a quarter of 2000 steps/sec
+        let moving = test_motor().move_to(Degrees(9.0)).ok().unwrap();
+        let profile = moving.profile().unwrap();
+        assert_eq!((profile.accel_steps, profile.cruise_steps), (0, 5));
+        assert!(profile.cruise_interval_ns.abs_diff(2_000_000) < 10);
+        assert_eq!(moving.run_to_completion().unwrap().position_steps().0, 5);
+    }
+
+    #[test]
+    fn test_return_move() {
+        let moving = test_motor().move_to(Degrees(90.0)).ok().unwrap();
+        let profile = moving.profile().unwrap().clone();
+        let motor = moving.run_to_completion().unwrap();
+        assert_eq!(motor.position_steps().0, 50);
+
+        let returning = motor.return_move(&profile).ok().unwrap();
+        assert_eq!(returning.profile().unwrap().direction, Direction::CounterClockwise);
+        let motor = returning.run_to_completion().unwrap();
+        assert_eq!(motor.position_steps().0, 0);
+
+        let (_, err) = motor.return_move(&MotionProfile::zero()).err().unwrap();
+        assert!(matches!(err, Error::Motion(MotionError::MoveTooShort { .. })));
+    }
+
+    /// Collapse an event log into DIR changes, dwells and runs of STEP pulses.
+    fn summarize(log: &[Event]) -> std::vec::Vec<(&'static str, u32)> {
+        let mut out: std::vec::Vec<(&'static str, u32)> = std::vec::Vec::new();
+        for event in log {
+            match event {
+                Event::DirHigh => out.push(("dir_high", 0)),
+                Event::DirLow => out.push(("dir_low", 0)),
+                Event::DelayMs(ms) => out.push(("dwell_ms", *ms)),
+                Event::StepHigh => match out.last_mut() {
+                    Some(("pulses", n)) => *n += 1,
+                    _ => out.push(("pulses", 1)),
+                },
+                _ => {}
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_reversal_dwell_then_backlash_then_move() {
+        let log = EventLog::default();
+        let motor = event_builder(&log)
+            .reversal_dwell_ms(25)
+            .backlash_steps(3)
+            .build()
+            .unwrap();
+
+        // CW 10 steps, CCW 20 steps, CW 5 steps (1.8°/step)
+        let motor = motor.move_to_blocking(Degrees(18.0)).ok().unwrap();
+        let motor = motor.move_to_blocking(Degrees(-18.0)).ok().unwrap();
+        let motor = motor.move_to_blocking(Degrees(-9.0)).ok().unwrap();
+        assert_eq!(motor.position_steps().0, -5);
+
+        assert_eq!(
+            summarize(&log.borrow()),
+            [
+                // First move: no previous direction, so no dwell or backlash
+                ("dir_high", 0),
+                ("pulses", 10),
+                // Reversal: DIR, dwell, 3 backlash pulses, then the move
+                ("dir_low", 0),
+                ("dwell_ms", 25),
+                ("pulses", 3 + 20),
+                ("dir_high", 0),
+                ("dwell_ms", 25),
+                ("pulses", 3 + 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_max_delay_chunk() {
+        // Total waiting time in ns, and the longest single delay_ns call
+        fn run(chunk_ns: u32) -> (u64, u32, bool) {
+            let log = EventLog::default();
+            let motor = event_builder(&log)
+                .reversal_dwell_ms(2)
+                .max_delay_chunk_ns(chunk_ns)
+                .build()
+                .unwrap();
+            assert_eq!(motor.max_delay_chunk_ns(), chunk_ns);
+            let motor = motor.move_to_blocking(Degrees(18.0)).ok().unwrap();
+            motor.move_to_blocking(Degrees(0.0)).ok().unwrap();
+
+            let log = log.borrow();
+            let total = log.iter().fold(0u64, |sum, event| match event {
+                Event::DelayNs(ns) => sum + *ns as u64,
+                Event::DelayMs(ms) => sum + *ms as u64 * 1_000_000,
+                _ => sum,
+            });
+            let longest = log.iter().filter_map(|event| match event {
+                Event::DelayNs(ns) => Some(*ns),
+                _ => None,
+            });
+            let used_ms = log.iter().any(|event| matches!(event, Event::DelayMs(_)));
+            (total, longest.max().unwrap(), used_ms)
+        }
+
+        let (total, longest, used_ms) = run(u32::MAX);
+        assert!(used_ms && longest > 300_000);
+
+        // Step intervals (500 µs at full speed) and the dwell split under the cap
+        let (capped_total, capped_longest, used_ms) = run(300_000);
+        assert_eq!(capped_total, total);
+        assert!(capped_longest <= 300_000);
+        assert!(!used_ms);
+    }
+
+    #[test]
+    fn test_armed_move_matches_direct_move() {
+        let direct = EventLog::default();
+        let motor = event_builder(&direct).build().unwrap();
+        motor.move_to_blocking(Degrees(-18.0)).ok().unwrap();
+
+        let log = EventLog::default();
+        let motor = event_builder(&log).build().unwrap();
+        let armed = motor.arm(Degrees(-18.0), MoveParams::default()).ok().unwrap();
+        assert_eq!(armed.profile().unwrap().total_steps, 10);
+        // Only the DIR pin is set while armed
+        assert_eq!(*log.borrow(), [Event::DirLow]);
+
+        let mut polls = 0;
+        let moving = armed.trigger_on(|| {
+            polls += 1;
+            polls == 3
+        });
+        assert_eq!(log.borrow().len(), 1);
+        let motor = moving.run_to_completion().unwrap();
+        assert_eq!(polls, 3);
+        assert_eq!(motor.position_steps().0, -10);
+        assert_eq!(*log.borrow(), *direct.borrow());
+
+        // Disarming leaves the position alone
+        let motor = motor.arm(Degrees(0.0), MoveParams::default()).ok().unwrap().disarm();
+        assert_eq!(motor.position_steps().0, -10);
+        assert!(!log.borrow()[direct.borrow().len()..].contains(&Event::StepHigh));
+        assert!(motor.arm(Degrees(-18.0), MoveParams::default()).is_err());
+    }
+
+    #[test]
+    fn test_approach_direction_overshoots() {
+        let log = EventLog::default();
+        let motor = event_builder(&log)
+            .backlash_steps(3)
+            .approach_direction(Direction::Clockwise)
+            .build()
+            .unwrap();
+
+        // CW 10 steps: already the preferred direction
+        let motor = motor.move_to_blocking(Degrees(18.0)).ok().unwrap();
+        // CCW to 5 steps: overshoot to 2, then back CW
+        let mut moving = motor.move_to(Degrees(9.0)).ok().unwrap();
+        let mut steps = 0;
+        while !moving.step().unwrap() {
+            steps += 1;
+            assert!(!moving.is_complete());
+        }
+        assert!(moving.is_complete());
+        let motor = moving.finish();
+        assert_eq!(motor.position_steps().0, 5);
+        // 8 CCW and 3 CW profile steps (backlash take-up is not a profile
+        // step); the last step reports completion
+        assert_eq!(steps, 10);
+
+        assert_eq!(
+            summarize(&log.borrow()),
+            [
+                ("dir_high", 0),
+                ("pulses", 10),
+                ("dir_low", 0),
+                ("pulses", 3 + 8),
+                ("dir_high", 0),
+                ("pulses", 3 + 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_approach_direction_limits_and_pause() {
+        let motor = StepperMotorBuilder::new()
+            .from_motor_config(&limited_config())
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .backlash_steps(4)
+            .approach_direction(Direction::Clockwise)
+            .build()
+            .unwrap();
+        let motor = motor.move_to_blocking(Degrees(45.0)).ok().unwrap();
+
+        // Overshooting 4 steps below the 5-step minimum is rejected
+        let (motor, err) = motor.move_to(Degrees(14.4)).err().unwrap();
+        assert_eq!(err, Error::Motor(MotorError::LimitExceeded { position: 4, limit: 5 }));
+
+        // A pause during the overshoot keeps the final approach
+        let motor = motor.move_to_blocking(Degrees(90.0)).ok().unwrap();
+        let mut moving = motor.move_to(Degrees(45.0)).ok().unwrap();
+        moving.step().unwrap();
+        let paused = moving.pause().unwrap();
+        let motor = paused.resume().run_to_completion().unwrap();
+        assert_eq!(motor.position_steps().0, 25);
+    }
+
+    #[test]
+    fn test_travel_to_limits_matches_move_to() {
+        use crate::config::LimitSide;
+
+        let motor = StepperMotorBuilder::new()
+            .from_motor_config(&limited_config())
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .build()
+            .unwrap();
+        let motor = motor.move_to_blocking(Degrees(18.0)).ok().unwrap();
+        let (below, above) = motor.travel_to_limits().unwrap();
+        assert!((below.0 - 9.0).abs() < 1e-4 && (above.0 - 72.0).abs() < 1e-4);
+
+        // Exactly on the limit is allowed
+        assert_eq!(motor.would_exceed_limits(Degrees(90.0)), None);
+        let motor = motor.move_to_blocking(Degrees(90.0)).ok().unwrap();
+        assert_eq!(motor.travel_to_limits().unwrap().1 .0, 0.0);
+
+        // One step beyond is reported, and move_to agrees
+        let violation = motor.would_exceed_limits(Degrees(92.0)).unwrap();
+        assert_eq!(violation.which, LimitSide::Max);
+        assert!((violation.by.0 - 1.8).abs() < 1e-4);
+        let (motor, err) = motor.move_to(Degrees(92.0)).err().unwrap();
+        assert!(matches!(err, Error::Motor(MotorError::LimitExceeded { .. })));
+
+        let violation = motor.would_exceed_limits(Degrees(7.2)).unwrap();
+        assert_eq!(violation.which, LimitSide::Min);
+        assert_eq!(motor.would_exceed_limits(Degrees(9.0)), None);
+        assert!(motor.move_to(Degrees(9.0)).is_ok());
+    }
+
+    #[test]
+    fn test_output_inversion_and_dir_polarity() {
+        // (invert_direction, output_inverted) -> DIR level, step sign for +18°
+        let cases = [
+            ((false, false), Event::DirHigh, 1),
+            ((false, true), Event::DirLow, -1),
+            ((true, false), Event::DirLow, 1),
+            ((true, true), Event::DirHigh, -1),
+        ];
+        for ((invert_direction, output_inverted), dir, sign) in cases {
+            let log = EventLog::default();
+            let motor = event_builder(&log)
+                .invert_direction(invert_direction)
+                .output_inverted(output_inverted)
+                .build()
+                .unwrap();
+
+            let mut moving = motor.move_to(Degrees(18.0)).ok().unwrap();
+            let mut last = 0;
+            loop {
+                let done = moving.step().unwrap();
+                let position = moving.position_steps().0;
+                assert_eq!(position - last, sign, "{:?}", (invert_direction, output_inverted));
+                last = position;
+                if done {
+                    break;
+                }
+            }
+            let motor = moving.finish();
+            assert_eq!(motor.position_steps().0, 10 * sign);
+            assert!((motor.position_degrees().0 - 18.0).abs() < 1e-4);
+            assert_eq!(log.borrow()[0], dir, "{:?}", (invert_direction, output_inverted));
+        }
+    }
+
+    #[test]
+    fn test_gravity_assist_in_move_to() {
+        let motor = event_builder(&EventLog::default())
+            .gravity_assist(1.5, Direction::CounterClockwise)
+            .build()
+            .unwrap();
+        let accel = motor.constraints().max_acceleration_steps_per_sec2;
+
+        let moving = motor.move_to(Degrees(90.0)).ok().unwrap();
+        assert!((moving.profile().unwrap().decel_rate - accel / 1.5).abs() < 1e-2);
+        let motor = moving.run_to_completion().unwrap();
+
+        let moving = motor.move_to(Degrees(0.0)).ok().unwrap();
+        let profile = moving.profile().unwrap();
+        assert!((profile.decel_rate - accel * 1.5).abs() < 1e-2);
+        assert_eq!(profile.accel_rate, accel);
+        assert_eq!(moving.run_to_completion().unwrap().position_steps().0, 0);
+    }
+
+    #[test]
+    fn test_no_dwell_without_reversal() {
+        let log = EventLog::default();
+        let motor = event_builder(&log).reversal_dwell_ms(25).build().unwrap();
+
+        let motor = motor.move_to_blocking(Degrees(9.0)).ok().unwrap();
+        let _motor = motor.move_to_blocking(Degrees(18.0)).ok().unwrap();
+
+        assert!(!log.borrow().contains(&Event::DelayMs(25)));
+    }
+
+    #[test]
+    fn test_eta_while_moving() {
+        let mut moving = test_motor().move_to(Degrees(90.0)).ok().unwrap();
+        let initial = moving.progress_snapshot();
+        assert_eq!(initial.step, 0);
+        assert_eq!(initial.total, 50);
+        assert!(initial.eta_secs > 0.0);
+
+        let mut last_eta = moving.eta_secs();
+        while !moving.step().unwrap() {
+            assert!(moving.eta_secs() <= last_eta);
+            last_eta = moving.eta_secs();
+        }
+        assert_eq!(moving.eta_secs(), 0.0);
+        assert_eq!(moving.elapsed_ratio(), 1.0);
+    }
+
+    #[test]
+    fn test_execute_waypoints() {
+        use crate::trajectory::WaypointTrajectoryBuilder;
+
+        let log = EventLog::default();
+        let motor = event_builder(&log).build().unwrap();
+        let sequence = WaypointTrajectoryBuilder::new()
+            .motor("x_axis")
+            .waypoints(&[Degrees(90.0), Degrees(90.0), Degrees(-18.0)])
+            .asymmetric(DegreesPerSecSquared(3600.0), DegreesPerSecSquared(900.0))
+            .dwell(100)
+            .build()
+            .unwrap();
+
+        let motor = motor.execute_waypoints(&sequence).ok().unwrap();
+        assert_eq!(motor.position_steps().0, -10);
+        assert_eq!(
+            summarize(&log.borrow()),
+            [
+                ("dir_high", 0),
+                ("pulses", 50),
+                ("dwell_ms", 100),
+                // Second waypoint is the current position: skipped
+                ("dir_low", 0),
+                ("pulses", 60),
+                ("dwell_ms", 100),
+            ]
+        );
+
+        let other = WaypointTrajectoryBuilder::new()
+            .motor("y_axis")
+            .waypoint(Degrees(0.0))
+            .build()
+            .unwrap();
+        let (_, err) = motor.execute_waypoints(&other).err().unwrap();
+        assert!(matches!(err, Error::Trajectory(_)));
+    }
+
+    #[test]
+    fn test_final_approach_creeps() {
+        use crate::trajectory::WaypointTrajectoryBuilder;
+
+        let log = EventLog::default();
+        let motor = event_builder(&log).build().unwrap();
+        // Last 18° (10 steps) at 10%: 200 steps/sec, a 5 ms interval
+        let sequence = WaypointTrajectoryBuilder::new()
+            .motor("x_axis")
+            .waypoints(&[Degrees(180.0)])
+            .final_approach(Degrees(18.0), 10)
+            .build()
+            .unwrap();
+
+        let motor = motor.execute_waypoints(&sequence).ok().unwrap();
+        assert_eq!(motor.position_steps().0, 100);
+
+        // Delay after each step but the last, without the 2 µs pulse
+        let log = log.borrow();
+        let delays: std::vec::Vec<u32> = log
+            .windows(2)
+            .filter_map(|pair| match pair {
+                [Event::StepLow, Event::DelayNs(ns)] => Some(*ns + 2000),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(delays.len(), 99);
+        let (fast, creep) = delays.split_at(90);
+        assert!(creep.iter().all(|&ns| ns.abs_diff(5_000_000) < 10), "{:?}", creep);
+        // The junction is reached at about 240 steps/sec, no faster than the
+        // sequence velocity before it
+        assert!(fast.iter().min().unwrap() < &1_000_000);
+        assert!((4_000_000..5_000_000).contains(fast.last().unwrap()));
+        assert!(!log.contains(&Event::DirLow));
+    }
+
+    #[test]
+    fn test_stop_distance() {
+        // 2000 steps/s cruise, 20000 steps/s²: v² / 2d = 100 steps
+        let mut moving = test_motor().move_to(Degrees(1800.0)).ok().unwrap();
+        assert_eq!(moving.stop_distance_steps(), 0);
+        for _ in 0..500 {
+            moving.step().unwrap();
+        }
+        let stop = moving.stop_distance_steps();
+        assert!(stop.abs_diff(100) <= 1, "{stop}");
+
+        let projected = moving.projected_stop_position();
+        assert_eq!(projected, Degrees((500 + stop) as f32 * 1.8));
+        assert!(moving.can_stop_before(projected));
+        assert!(!moving.can_stop_before(Degrees(projected.0 - 1.8)));
+        assert!(moving.can_stop_before(Degrees(0.0)));
+
+        // Pausing stops exactly there
+        let paused = moving.pause().unwrap();
+        assert_eq!(paused.position_degrees(), projected);
+
+        // Late in the deceleration only the last few steps are left
+        let mut moving = paused.resume();
+        while moving.executor.as_ref().unwrap().steps_remaining() > 2 {
+            moving.step().unwrap();
+        }
+        assert!(moving.stop_distance_steps() <= 2);
+        let motor = moving.run_to_completion().unwrap();
+        assert_eq!(motor.position_steps().0, 1000);
+    }
+
+    #[test]
+    fn test_pause_resume_reaches_target() {
+        let log = EventLog::default();
+        let motor = event_builder(&log).build().unwrap();
+
+        // 180° = 100 steps; pause during acceleration, mid-move and while
+        // decelerating
+        let mut moving = motor.move_to(Degrees(180.0)).ok().unwrap();
+        for _ in 0..10 {
+            moving.step().unwrap();
+        }
+        let paused = moving.pause().unwrap();
+        assert_eq!(paused.state_name(), "Paused");
+        let stopped_at = paused.position_steps().0;
+        assert!(stopped_at > 10 && stopped_at < 100);
+        assert_eq!(paused.remaining_steps() as i64, 100 - stopped_at);
+
+        let mut moving = paused.resume();
+        for _ in 0..30 {
+            moving.step().unwrap();
+        }
+        let mut moving = moving.pause().unwrap().resume();
+        while moving.phase() != MotionPhase::Decelerating {
+            moving.step().unwrap();
+        }
+        let motor = moving.pause().unwrap().resume().run_to_completion().unwrap();
+
+        assert_eq!(motor.position_steps().0, 100);
+        let pulses = log.borrow().iter().filter(|e| **e == Event::StepHigh).count();
+        assert_eq!(pulses, 100);
+    }
+
+    #[test]
+    fn test_pause_resume_short_move() {
+        let log = EventLog::default();
+        let motor = event_builder(&log).build().unwrap();
+
+        // 8 steps run at constant velocity, so pausing stops on the spot
+        let steps = 8;
+        assert!(steps <= crate::motion::MIN_TRAPEZOIDAL_STEPS);
+        let mut moving = motor.move_to(Degrees(steps as f32 * 1.8)).ok().unwrap();
+        assert_eq!(moving.executor.as_ref().unwrap().profile().decel_steps, 0);
+        for _ in 0..3 {
+            moving.step().unwrap();
+        }
+        let paused = moving.pause().unwrap();
+        assert_eq!(paused.position_steps().0, 3);
+        assert_eq!(paused.remaining_steps(), steps - 3);
+
+        let motor = paused.resume().run_to_completion().unwrap();
+        assert_eq!(motor.position_steps().0, steps as i64);
+        let pulses = log.borrow().iter().filter(|e| **e == Event::StepHigh).count();
+        assert_eq!(pulses, steps as usize);
+    }
+
+    #[test]
+    fn test_pause_constant_velocity_search() {
+        let motor = test_motor().move_to_nearest_limit(Direction::Clockwise, 10);
+        let mut moving = motor.ok().unwrap();
+        for _ in 0..5 {
+            moving.step().unwrap();
+        }
+
+        // No ramp to stop on: the pause holds where it is and keeps the rest
+        let paused = moving.pause().unwrap();
+        assert_eq!(paused.position_steps().0, 5);
+        assert_eq!(paused.remaining_steps(), u32::MAX - 5);
+        let mut moving = paused.resume();
+        moving.step().unwrap();
+        assert_eq!(moving.position_steps().0, 6);
+    }
+
+    #[test]
+    fn test_cancel_paused_move() {
+        let mut moving = test_motor().move_to(Degrees(180.0)).ok().unwrap();
+        for _ in 0..20 {
+            moving.step().unwrap();
+        }
+        let paused = moving.pause().unwrap();
+        let stopped_at = paused.position_steps();
+
+        let motor = paused.cancel();
+        assert_eq!(motor.position_steps(), stopped_at);
+        assert_eq!(motor.state_name(), "Idle");
+    }
+
+    /// Elapsed time of the recorded delays on a provider with a fixed
+    /// per-call overhead.
+    fn modeled_elapsed_ns(log: &EventLog, overhead_ns: u32) -> u64 {
+        log.borrow()
+            .iter()
+            .filter_map(|e| match e {
+                Event::DelayNs(ns) => Some(*ns as u64 + overhead_ns as u64),
+                _ => None,
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_delay_overhead_compensation() {
+        const OVERHEAD_NS: u32 = 30_000;
+
+        let run = |compensate: bool| {
+            let log = EventLog::default();
+            let builder = event_builder(&log);
+            let builder = if compensate {
+                builder.delay_overhead_ns(OVERHEAD_NS)
+            } else {
+                builder
+            };
+            let moving = builder.build().unwrap().move_to(Degrees(180.0)).ok().unwrap();
+            let planned: u64 = {
+                let profile = moving.profile().unwrap();
+                (0..profile.total_steps - 1).map(|s| profile.interval_at(s) as u64).sum::<u64>()
+                    + OVERHEAD_NS as u64
+            };
+            let motor = moving.run_to_completion().unwrap();
+            (motor, modeled_elapsed_ns(&log, OVERHEAD_NS), planned)
+        };
+
+        // Compensated: modeled time matches the plan
+        let (motor, elapsed, planned) = run(true);
+        assert_eq!(motor.delay_overhead_ns(), OVERHEAD_NS);
+        assert_eq!(motor.underrun_steps(), 0);
+        assert_eq!(motor.position_steps().0, 100);
+        assert!(elapsed.abs_diff(planned) < planned / 1000, "{} vs {}", elapsed, planned);
+
+        // Uncompensated: every delay call adds the overhead
+        let (_motor, elapsed, planned) = run(false);
+        assert!(elapsed > planned + 100 * OVERHEAD_NS as u64);
+    }
+
+    #[test]
+    fn test_delay_underrun_counted() {
+        let log = EventLog::default();
+        // Overhead longer than the 10 ms first interval: no step can be compensated
+        let motor = event_builder(&log).delay_overhead_ns(20_000_000).build().unwrap();
+        let mut motor = motor.move_to(Degrees(90.0)).ok().unwrap().run_to_completion().unwrap();
+
+        // Every step but the last waits for a next step
+        assert_eq!(motor.underrun_steps(), 49);
+        // Pulse delays are floored at zero but still issued, so pulses keep
+        // their width; the pulse alone already exceeds every interval
+        let delays: std::vec::Vec<Event> =
+            log.borrow().iter().copied().filter(|e| matches!(e, Event::DelayNs(_))).collect();
+        assert_eq!(delays.len(), 50);
+        assert!(delays.iter().all(|e| *e == Event::DelayNs(0)));
+        motor.reset_underrun_steps();
+        assert_eq!(motor.underrun_steps(), 0);
+    }
+
+    #[test]
+    fn test_trajectory_preconditions() {
+        use crate::error::{Precondition, TrajectoryError};
+        use crate::trajectory::{TrajectoryBuilder, TrajectoryRegistry};
+
+        let mut registry = TrajectoryRegistry::new();
+        let approach = TrajectoryBuilder::new().motor("x_axis").target(Degrees(90.0));
+        registry.register("approach", approach.build().unwrap()).unwrap();
+        let insert = TrajectoryBuilder::new()
+            .motor("x_axis")
+            .target(Degrees(135.0))
+            .requires_previous("approach")
+            .requires_position(Degrees(90.0), Degrees(0.5));
+        registry.register("insert", insert.build().unwrap()).unwrap();
+
+        // Nothing executed yet
+        let (motor, err) = test_motor().execute("insert", &registry).err().unwrap();
+        assert!(matches!(
+            err,
+            Error::Trajectory(TrajectoryError::PreconditionFailed {
+                actual: Precondition::Previous(None),
+                ..
+            })
+        ));
+
+        let Ok(motor) = motor.execute("approach", &registry) else {
+            panic!("approach should execute");
+        };
+        assert_eq!(motor.last_trajectory(), Some("approach"));
+        let Ok(motor) = motor.execute("insert", &registry) else {
+            panic!("insert should execute after approach");
+        };
+        assert_eq!(motor.last_trajectory(), Some("insert"));
+
+        // A manual move clears the memory, even back to the same position
+        let Ok(motor) = motor.move_to_blocking(Degrees(90.0)) else {
+            panic!("manual move should succeed");
+        };
+        assert_eq!(motor.last_trajectory(), None);
+        assert!(motor.execute("insert", &registry).is_err());
+
+        // A precondition name that doesn't fit is an error, not dropped
+        let long = "a_trajectory_name_well_over_32_bytes";
+        let builder = TrajectoryBuilder::new().motor("x_axis").target(Degrees(0.0));
+        assert_eq!(
+            builder.requires_previous(long).build().unwrap_err(),
+            Error::Trajectory(TrajectoryError::NameTooLong(long.len()))
+        );
+    }
+
+    #[test]
+    fn test_position_precondition() {
+        use crate::error::{Precondition, TrajectoryError};
+        use crate::trajectory::TrajectoryBuilder;
+
+        let from_home = TrajectoryBuilder::new()
+            .motor("x_axis")
+            .target(Degrees(90.0))
+            .requires_position(Degrees(0.0), Degrees(1.0))
+            .build()
+            .unwrap();
+
+        let Ok(motor) = test_motor().execute_trajectory(&from_home) else {
+            panic!("motor starts at home");
+        };
+        let (_motor, err) = motor.execute_trajectory(&from_home).err().unwrap();
+        assert!(matches!(
+            err,
+            Error::Trajectory(TrajectoryError::PreconditionFailed {
+                expected: Precondition::Position(expected),
+                actual: Precondition::Position(actual),
+            }) if expected == 0.0 && (actual - 90.0).abs() < 0.01
+        ));
+    }
+
+    #[test]
+    fn test_position_degrees_display() {
+        // 1.8° per step: whole degrees only
+        let Ok(motor) = test_motor().move_to_blocking(Degrees(90.0)) else {
+            panic!("move should succeed");
+        };
+        assert_eq!(std::format!("{}", motor.position_degrees_display()), "90°");
+
+        // 1/16 microstepping: 8.889 steps/°, one decimal place
+        let motor = StepperMotorBuilder::new()
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .steps_per_revolution(200)
+            .microsteps(Microsteps::SIXTEENTH)
+            .max_velocity(DegreesPerSec(360.0))
+            .max_acceleration(DegreesPerSecSquared(720.0))
+            .build()
+            .unwrap();
+        let Ok(motor) = motor.move_to_blocking(Degrees(0.9)) else {
+            panic!("move should succeed");
+        };
+        // 8 steps = 0.9°
+        assert_eq!(motor.position_steps().0, 8);
+        assert_eq!(std::format!("{}", motor.position_degrees_display()), "0.9°");
+    }
+
+    #[test]
+    fn test_telemetry_snapshot() {
+        let motor = StepperMotorBuilder::new()
+            .from_motor_config(&limited_config())
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .build()
+            .unwrap();
+        let motor = motor.move_to_blocking(Degrees(18.0)).ok().unwrap();
+        let idle = motor.telemetry_snapshot();
+        assert_eq!(idle.name_str(), "x_axis");
+        assert_eq!(({ idle.position_steps }, { idle.velocity_steps_per_sec }), (10, 0));
+        assert_eq!(idle.motion_phase(), Some(MotionPhase::Complete));
+        assert_eq!((idle.progress_pct, idle.fault), (100, 0));
+
+        // Moving towards the lower limit reports a negative velocity
+        let mut moving = motor.move_to(Degrees(9.0)).ok().unwrap();
+        moving.step().unwrap();
+        moving.step().unwrap();
+        let telemetry = moving.telemetry_snapshot();
+        assert_eq!({ telemetry.position_steps }, 8);
+        assert!({ telemetry.velocity_steps_per_sec } < 0);
+        assert_eq!(telemetry.progress_pct, 40);
+        assert_ne!(telemetry.motion_phase(), Some(MotionPhase::Complete));
+        assert_eq!(MotorTelemetry::decode(&telemetry.encode()).unwrap(), telemetry);
+    }
+
+    fn limited_config() -> crate::config::MotorConfig {
+        toml::from_str(
+            r#"
+name = "x_axis"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 3600.0
+max_acceleration_deg_per_sec2 = 36000.0
+
+[limits]
+min_degrees = 9.0
+max_degrees = 90.0
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_directional_limits() {
+        let mut config = limited_config();
+        let limits = config.limits.take().unwrap();
+        config.limits = Some(limits.with_directional_max(Degrees(99.0), Degrees(90.0)));
+        let motor = StepperMotorBuilder::new()
+            .from_motor_config(&config)
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .build()
+            .unwrap();
+
+        // 95.4° (53 steps) is within the CW maximum of 55 steps...
+        let motor = motor.move_to_blocking(Degrees(45.0)).ok().unwrap();
+        assert_eq!(motor.would_exceed_limits(Degrees(95.4)), None);
+        let motor = motor.move_to_blocking(Degrees(95.4)).ok().unwrap();
+        assert_eq!(motor.position_steps().0, 53);
+        let motor = motor.move_to_blocking(Degrees(99.0)).ok().unwrap();
+
+        // ...but beyond the CCW one of 50 steps
+        let violation = motor.would_exceed_limits(Degrees(95.4)).unwrap();
+        assert_eq!(violation.which, crate::config::LimitSide::Max);
+        let (motor, err) = motor.move_to(Degrees(95.4)).err().unwrap();
+        assert_eq!(err, Error::Motor(MotorError::LimitExceeded { position: 53, limit: 50 }));
+        assert_eq!(motor.position_steps().0, 55);
+    }
+
+    #[test]
+    fn test_move_to_wraps_target() {
+        let mut config = limited_config();
+        config.limits = Some(crate::config::SoftLimits::new(
+            Degrees(0.0),
+            Degrees(360.0),
+            crate::config::LimitPolicy::Wrap,
+        ));
+        let mut motor = StepperMotorBuilder::new()
+            .from_motor_config(&config)
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .build()
+            .unwrap();
+
+        // 200 steps per 360° window
+        for (target, spans, expected) in
+            [(450.0, 1, 50), (-90.0, -1, 150), (360.0, 1, 0), (-990.0, -3, 50), (-900.0, -3, 100)]
+        {
+            let from = motor.position_degrees();
+            let plan = motor.planner().plan_absolute(from, Degrees(target), MoveParams::default());
+            assert_eq!(plan.unwrap().wrapped_spans, spans, "{target}");
+            motor = motor.move_to(Degrees(target)).ok().unwrap().run_to_completion().unwrap();
+            assert_eq!(motor.position_steps().0, expected, "{target}");
+        }
+    }
+
+    #[test]
+    fn test_turn_counting() {
+        let mut motor = test_motor();
+        motor = motor.move_to(Degrees(-90.0)).ok().unwrap().run_to_completion().unwrap();
+        assert_eq!((motor.whole_turns(), motor.angle_in_turn()), (-1, Degrees(270.0)));
+        assert_eq!(motor.position_turns(), -0.25);
+
+        motor = motor.move_to(Degrees(810.0)).ok().unwrap().run_to_completion().unwrap();
+        assert_eq!((motor.whole_turns(), motor.angle_in_turn()), (2, Degrees(90.0)));
+        assert_eq!(motor.position_turns(), 2.25);
+    }
+
+    #[test]
+    fn test_clamp_events() {
+        let mut config = limited_config();
+        config.limits.as_mut().unwrap().policy = crate::config::LimitPolicy::Clamp;
+        let mut motor = StepperMotorBuilder::new()
+            .from_motor_config(&config)
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .build()
+            .unwrap();
+        assert_eq!(motor.clamp_events(), 0);
+
+        for (target, events) in [(180.0, 1), (45.0, 1), (-90.0, 2), (270.0, 3)] {
+            motor = motor.move_to(Degrees(target)).ok().unwrap().run_to_completion().unwrap();
+            assert_eq!(motor.clamp_events(), events, "{target}");
+        }
+        assert_eq!(motor.position_steps(), Steps(50));
+
+        // Clamped onto the current position: nothing started, nothing counted
+        let (mut motor, _) = motor.move_to(Degrees(360.0)).err().unwrap();
+        assert_eq!(motor.clamp_events(), 3);
+        motor.reset_clamp_events();
+        assert_eq!(motor.clamp_events(), 0);
+    }
+
+    #[test]
+    fn test_limit_override() {
+        use crate::config::{LimitPolicy, SoftLimits};
+        use crate::trajectory::TrajectoryBuilder;
+
+        let mut motor = StepperMotorBuilder::new()
+            .from_motor_config(&limited_config())
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .build()
+            .unwrap();
+        motor.set_position(Degrees(45.0));
+
+        // Beyond the 90° limit, allowed for this trajectory only
+        let service = TrajectoryBuilder::new()
+            .motor("x_axis")
+            .target(Degrees(162.0))
+            .limit_override(SoftLimits::new(Degrees(0.0), Degrees(180.0), LimitPolicy::Reject))
+            .build()
+            .unwrap();
+        let Ok(motor) = motor.execute_trajectory(&service) else {
+            panic!("override should allow the move");
+        };
+        assert_eq!(motor.position_steps(), Steps(90));
+
+        // The motor's own limits apply again afterwards
+        let (motor, err) = motor.move_to(Degrees(135.0)).err().unwrap();
+        assert_eq!(err, Error::Motor(MotorError::LimitExceeded { position: 75, limit: 50 }));
+
+        // Overrides are checked like any other limits
+        let beyond = TrajectoryBuilder::new()
+            .motor("x_axis")
+            .target(Degrees(270.0))
+            .limit_override(SoftLimits::new(Degrees(0.0), Degrees(180.0), LimitPolicy::Reject))
+            .build()
+            .unwrap();
+        let (motor, err) = motor.execute_trajectory(&beyond).err().unwrap();
+        assert_eq!(err, Error::Motor(MotorError::LimitExceeded { position: 150, limit: 100 }));
+
+        // A privileged move ignores all limits
+        let unchecked = TrajectoryBuilder::new()
+            .motor("x_axis")
+            .target(Degrees(-90.0))
+            .build()
+            .unwrap();
+        #[allow(deprecated)]
+        let Ok(motor) = motor.execute_privileged(&unchecked) else {
+            panic!("privileged move should ignore limits");
+        };
+        assert_eq!(motor.position_steps(), Steps(-50));
+    }
+
+    #[test]
+    fn test_rehearse() {
+        use crate::error::TrajectoryError;
+        use crate::trajectory::{TrajectoryBuilder, TrajectoryRegistry};
+
+        let mut registry = TrajectoryRegistry::new();
+        let approach = TrajectoryBuilder::new()
+            .motor("x_axis")
+            .target(Degrees(90.0))
+            .approach_from(Direction::CounterClockwise, Degrees(9.0))
+            .dwell(250)
+            .build()
+            .unwrap();
+        registry.register("approach", approach).unwrap();
+        let other = TrajectoryBuilder::new().motor("y_axis").target(Degrees(9.0)).build().unwrap();
+        registry.register("other", other).unwrap();
+
+        let log = EventLog::default();
+        let mut motor = event_builder(&log).build().unwrap();
+        motor.set_position(Degrees(18.0));
+        let direction = motor.current_direction;
+
+        // Overshoot to 55 steps, then back onto 50
+        let report = motor.rehearse("approach", &registry).unwrap();
+        assert_eq!(report.trajectory.as_str(), "approach");
+        assert_eq!((report.start_steps, report.end_steps), (10, 50));
+        assert_eq!((report.total_steps, report.legs), (50, 2));
+        assert_eq!(report.dwell_ms, Some(250));
+        assert!(!report.clamped && report.estimated_duration_secs > 0.0);
+
+        // Nothing touched
+        assert!(log.borrow().is_empty());
+        assert_eq!(motor.position_steps(), Steps(10));
+        assert_eq!(motor.current_direction, direction);
+        assert_eq!(motor.last_trajectory(), None);
+
+        // The real run makes the same decisions
+        let Ok((motor, executed)) = motor.execute_reported("approach", &registry) else {
+            panic!("approach should succeed");
+        };
+        assert_eq!(executed, report);
+        assert_eq!(
+            summarize(&log.borrow()),
+            [("dir_high", 0), ("pulses", 45), ("dir_low", 0), ("pulses", 5)]
+        );
+        assert_eq!(motor.last_trajectory(), Some("approach"));
+
+        // Errors are those of a real run
+        let not_found = motor.rehearse("missing", &registry);
+        assert!(matches!(not_found, Err(Error::Trajectory(TrajectoryError::InvalidName(_)))));
+        let (motor, err) = motor.execute("other", &registry).err().unwrap();
+        assert_eq!(motor.rehearse("other", &registry), Err(err));
+        let too_short = Error::Motion(MotionError::MoveTooShort { steps: 0, minimum: 1 });
+        assert_eq!(motor.rehearse("approach", &registry), Err(too_short));
+    }
+
+    #[test]
+    fn test_one_sided_approach() {
+        use crate::trajectory::TrajectoryBuilder;
+
+        // Arrive at 90° moving CCW, overshooting by 9° (5 steps) when needed
+        let trajectory = TrajectoryBuilder::new()
+            .motor("x_axis")
+            .target(Degrees(90.0))
+            .approach_from(Direction::CounterClockwise, Degrees(9.0))
+            .build()
+            .unwrap();
+
+        // From below: overshoot CW, then reverse onto the target
+        let log = EventLog::default();
+        let motor = event_builder(&log).build().unwrap();
+        let Ok(motor) = motor.execute_trajectory(&trajectory) else {
+            panic!("approach should succeed");
+        };
+        assert_eq!(motor.position_steps(), Steps(50));
+        assert_eq!(
+            summarize(&log.borrow()),
+            [("dir_high", 0), ("pulses", 55), ("dir_low", 0), ("pulses", 5)]
+        );
+
+        // From above: already approaching CCW, so no overshoot
+        let log = EventLog::default();
+        let mut motor = event_builder(&log).build().unwrap();
+        motor.set_position(Degrees(180.0));
+        let Ok(motor) = motor.execute_trajectory(&trajectory) else {
+            panic!("direct move should succeed");
+        };
+        assert_eq!(motor.position_steps(), Steps(50));
+        assert_eq!(summarize(&log.borrow()), [("dir_low", 0), ("pulses", 50)]);
+
+        // Overshoot beyond the 90° limit is rejected before moving
+        let trajectory = TrajectoryBuilder::new()
+            .motor("x_axis")
+            .target(Degrees(81.0))
+            .approach_from(Direction::CounterClockwise, Degrees(18.0))
+            .build()
+            .unwrap();
+        let log = EventLog::default();
+        let mut motor = event_builder(&log)
+            .from_motor_config(&limited_config())
+            .build()
+            .unwrap();
+        motor.set_position(Degrees(45.0));
+        let (motor, err) = motor.execute_trajectory(&trajectory).err().unwrap();
+        assert_eq!(err, Error::Motor(MotorError::LimitExceeded { position: 55, limit: 50 }));
+        assert_eq!(motor.position_steps(), Steps(25));
+        assert!(log.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_planner_matches_move_to() {
+        use crate::config::LimitPolicy;
+        use crate::trajectory::TrajectoryBuilder;
+
+        let mut clamped = limited_config();
+        clamped.limits.as_mut().unwrap().policy = LimitPolicy::Clamp;
+        // (config, backlash steps, approach direction)
+        let setups = [
+            (limited_config(), 0, None),
+            (clamped.clone(), 0, None),
+            (clamped, 4, Some(Direction::Clockwise)),
+        ];
+        let positions = [Degrees(9.0), Degrees(18.0), Degrees(45.0), Degrees(90.0)];
+        let targets = [-18.0, 7.2, 9.0, 14.4, 45.0, 88.2, 90.0, 92.0, 180.0];
+
+        for (config, backlash, approach) in &setups {
+            for &start in &positions {
+                for &target in &targets {
+                    let mut builder = StepperMotorBuilder::new()
+                        .from_motor_config(config)
+                        .step_pin(NoopPin)
+                        .dir_pin(NoopPin)
+                        .delay(NoopDelay)
+                        .backlash_steps(*backlash);
+                    if let Some(direction) = *approach {
+                        builder = builder.approach_direction(direction);
+                    }
+                    let mut motor = builder.build().unwrap();
+                    motor.set_position(start);
+                    let plan = motor.planner().plan_absolute(
+                        start,
+                        Degrees(target),
+                        MoveParams::default(),
+                    );
+                    match (plan, motor.move_to(Degrees(target))) {
+                        (Ok(plan), Ok(mut moving)) => {
+                            let mut steps = 1;
+                            while !moving.step().unwrap() {
+                                steps += 1;
+                            }
+                            assert_eq!(steps, plan.total_steps(), "{start:?} -> {target}");
+                            let motor = moving.finish();
+                            assert_eq!(motor.position_steps().0, plan.target_steps);
+                        }
+                        (Err(expected), Err((_, err))) => assert_eq!(err, expected),
+                        (plan, moved) => panic!(
+                            "{start:?} -> {target}: {plan:?} vs {:?}",
+                            moved.err().map(|(_, err)| err)
+                        ),
+                    }
+                }
+            }
+        }
+
+        // Trajectories run at their own rates, with the same overshoot
+        let trajectory = TrajectoryBuilder::new()
+            .motor("x_axis")
+            .target(Degrees(45.0))
+            .velocity_percent(25)
+            .approach_from(Direction::CounterClockwise, Degrees(9.0))
+            .build()
+            .unwrap();
+        let log = EventLog::default();
+        let mut motor = event_builder(&log)
+            .from_motor_config(&limited_config())
+            .build()
+            .unwrap();
+        motor.set_position(Degrees(18.0));
+        let plan = motor.planner().plan_trajectory(Degrees(18.0), &trajectory).unwrap();
+        assert_eq!(plan.legs.len(), 2);
+        // 25% of 2000 steps/s
+        assert!(plan.legs[0].cruise_interval_ns.abs_diff(2_000_000) < 10);
+        let motor = motor.execute_trajectory(&trajectory).ok().unwrap();
+        assert_eq!(motor.position_steps().0, plan.target_steps);
+        assert_eq!(
+            summarize(&log.borrow()),
+            [("dir_high", 0), ("pulses", 20), ("dir_low", 0), ("pulses", 5)]
+        );
+    }
+
+    #[test]
+    fn test_constrain_to_limits() {
+        let log = EventLog::default();
+        let mut motor = event_builder(&log)
+            .from_motor_config(&limited_config())
+            .build()
+            .unwrap();
+        motor.set_position(Degrees(180.0));
+
+        let Ok(motor) = motor.constrain_to_limits() else {
+            panic!("constrain should succeed");
+        };
+        assert_eq!(motor.position_steps(), Steps(50));
+
+        let steps = log.borrow().iter().filter(|e| **e == Event::StepHigh).count();
+        assert_eq!(steps, 50);
+        // Quarter speed: 500 steps/s, so no interval shorter than 2 ms
+        let shortest_gap = log
+            .borrow()
+            .iter()
+            .filter_map(|e| match e {
+                Event::DelayNs(ns) if *ns > crate::config::DEFAULT_STEP_PULSE_NS => Some(*ns),
+                _ => None,
+            })
+            .min()
+            .unwrap();
+        assert!(shortest_gap + crate::config::DEFAULT_STEP_PULSE_NS >= 1_999_000, "{}", shortest_gap);
+
+        // Already inside: nothing happens
+        log.borrow_mut().clear();
+        let Ok(motor) = motor.constrain_to_limits() else {
+            panic!("constrain should succeed");
+        };
+        assert_eq!(motor.position_steps(), Steps(50));
+        assert!(log.borrow().is_empty());
+
+        // No limits: position is left alone
+        let mut motor = test_motor();
+        motor.set_position(Degrees(720.0));
+        let Ok(motor) = motor.constrain_to_limits() else {
+            panic!("constrain without limits should succeed");
+        };
+        assert_eq!(motor.position_steps(), Steps(400));
+    }
+
+    #[test]
+    fn test_rewind_to_pre_move() {
+        use crate::trajectory::TrajectoryBuilder;
+
+        let log = EventLog::default();
+        let mut motor = event_builder(&log).build().unwrap();
+        motor.set_position(Degrees(18.0));
+
+        // Nothing to rewind to yet
+        let (motor, err) = motor.rewind_to_pre_move().err().unwrap();
+        assert_eq!(err, Error::Motor(MotorError::NotInitialized));
+
+        let motor = motor.move_to_blocking(Degrees(90.0)).ok().unwrap();
+        assert_eq!(motor.pre_move_position(), Some(Steps(10)));
+        log.borrow_mut().clear();
+        let Ok(motor) = motor.rewind_to_pre_move() else {
+            panic!("rewind should succeed");
+        };
+        assert_eq!(motor.position_steps(), Steps(10));
+        assert_eq!(summarize(&log.borrow()), [("dir_low", 0), ("pulses", 40)]);
+
+        // Used up by the rewind
+        assert_eq!(motor.pre_move_position(), None);
+        let (motor, _) = motor.rewind_to_pre_move().err().unwrap();
+
+        // Relative moves too
+        let motor = motor.move_by(Degrees(-9.0)).ok().unwrap().run_to_completion().unwrap();
+        assert_eq!(motor.position_steps(), Steps(5));
+        let Ok(motor) = motor.rewind_to_pre_move() else {
+            panic!("rewind should succeed");
+        };
+        assert_eq!(motor.position_steps(), Steps(10));
+
+        // Trajectories record their start like any other move
+        let motor = motor.move_to_blocking(Degrees(90.0)).ok().unwrap();
+        let trajectory = TrajectoryBuilder::new().motor("x_axis").target(Degrees(180.0));
+        let Ok(motor) = motor.execute_trajectory(&trajectory.build().unwrap()) else {
+            panic!("trajectory should execute");
+        };
+        assert_eq!(motor.pre_move_position(), Some(Steps(50)));
+        let Ok(motor) = motor.rewind_to_pre_move() else {
+            panic!("rewind should succeed");
+        };
+        assert_eq!(motor.position_steps(), Steps(50)); // 90°
+
+        // Setting the position forgets the old frame
+        let mut motor = motor.move_to_blocking(Degrees(36.0)).ok().unwrap();
+        motor.set_origin();
+        assert!(motor.rewind_to_pre_move().is_err());
+    }
+
+    #[test]
+    fn test_limit_margin_slows_approach() {
+        let mut config = limited_config();
+        config.limits.as_mut().unwrap().margin = Some(Degrees(9.0));
+        let motor_with = |config: &crate::config::MotorConfig| {
+            let mut motor = StepperMotorBuilder::new()
+                .step_pin(NoopPin)
+                .dir_pin(NoopPin)
+                .delay(NoopDelay)
+                .from_motor_config(config)
+                .build()
+                .unwrap();
+            motor.set_position(Degrees(18.0));
+            motor
+        };
+        let cruise_interval = |motor: StepperMotor<NoopPin, NoopPin, NoopDelay>, target| {
+            let Ok(moving) = motor.move_to(Degrees(target)) else {
+                panic!("move should start");
+            };
+            moving.profile().unwrap().cruise_interval_ns
+        };
+
+        // Ending 1° from the max limit: capped at sqrt(2 * 20000 * 5) ≈ 447 steps/s
+        let near = cruise_interval(motor_with(&config), 89.0);
+        assert!(near >= 2_230_000, "{}", near);
+        let unlimited = cruise_interval(motor_with(&limited_config()), 89.0);
+        assert!(unlimited < near);
+
+        // Mid-range moves are unaffected
+        assert_eq!(
+            cruise_interval(motor_with(&config), 50.0),
+            cruise_interval(motor_with(&limited_config()), 50.0)
+        );
+    }
+
+    #[test]
+    fn test_move_to_nearest_limit() {
+        let log = EventLog::default();
+        let mut motor = event_builder(&log)
+            .from_motor_config(&limited_config())
+            .build()
+            .unwrap();
+        motor.set_position(Degrees(45.0));
+
+        // Soft limits don't stop the search for the hard stop below 9°
+        let Ok(mut moving) = motor.move_to_nearest_limit(Direction::CounterClockwise, 10) else {
+            panic!("search should start");
+        };
+        assert_eq!(moving.profile().unwrap().total_steps, u32::MAX);
+        for _ in 0..40 {
+            assert!(!moving.step().unwrap());
+        }
+        let motor = moving.abort();
+        assert_eq!(motor.position_steps(), Steps(-15));
+
+        // 10% of 2000 steps/s: every interval is 5 ms (± float rounding), no ramp
+        let pulse_ns = crate::config::DEFAULT_STEP_PULSE_NS;
+        let delays: std::vec::Vec<u32> = log
+            .borrow()
+            .iter()
+            .filter_map(|e| match e {
+                Event::DelayNs(ns) if *ns != pulse_ns => Some(*ns),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(delays.len(), 40);
+        assert!(delays.iter().all(|&ns| ns.abs_diff(5_000_000 - pulse_ns) <= 1), "{:?}", delays);
+
+        let (_, err) = motor.move_to_nearest_limit(Direction::Clockwise, 0).err().unwrap();
+        assert_eq!(err, Error::Config(ConfigError::InvalidVelocityPercent(0)));
+    }
+
+    #[test]
+    fn test_move_to_step_limit() {
+        let log = EventLog::default();
+        let mut motor = event_builder(&log)
+            .from_motor_config(&limited_config())
+            .build()
+            .unwrap();
+        motor.set_position(Degrees(45.0));
+
+        let Ok(motor) = motor.move_to_step_limit(Direction::Clockwise, 50) else {
+            panic!("move to max limit should succeed");
+        };
+        assert_eq!(motor.position_steps(), Steps(50));
+        let Ok(motor) = motor.move_to_step_limit(Direction::Clockwise, 50) else {
+            panic!("already at the limit");
+        };
+        assert_eq!(motor.position_steps(), Steps(50));
+        let Ok(motor) = motor.move_to_step_limit(Direction::CounterClockwise, 100) else {
+            panic!("move to min limit should succeed");
+        };
+        assert_eq!(motor.position_steps(), Steps(5));
+
+        let (_, err) = test_motor().move_to_step_limit(Direction::Clockwise, 50).err().unwrap();
+        assert_eq!(err, Error::Motor(MotorError::NoSoftLimits));
+    }
+
+    #[test]
+    fn test_clamp_on_startup() {
+        let mut config = limited_config();
+        config.clamp_on_startup = true;
+
+        let motor = StepperMotorBuilder::new()
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .from_motor_config(&config)
+            .build()
+            .unwrap();
+        assert_eq!(motor.position_steps(), Steps(5));
+    }
+
+    #[test]
+    fn test_step_error_returns_motor() {
+        use crate::test_util::FailingPin;
+
+        // Clamping on startup reports the failure from build()
+        let mut config = limited_config();
+        config.clamp_on_startup = true;
+        let result = StepperMotorBuilder::new()
+            .step_pin(FailingPin::after(2))
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .from_motor_config(&config)
+            .build();
+        assert_eq!(result.err(), Some(Error::Motor(MotorError::PinError)));
+
+        let motor = StepperMotorBuilder::new()
+            .name("x_axis")
+            .step_pin(FailingPin::after(10))
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .steps_per_revolution(200)
+            .max_velocity(DegreesPerSec(3600.0))
+            .max_acceleration(DegreesPerSecSquared(36000.0))
+            .build()
+            .unwrap();
+        let (motor, err) = motor.move_to_blocking(Degrees(90.0)).err().unwrap();
+        assert_eq!(err, Error::Motor(MotorError::PinError));
+        assert_eq!(motor.position_steps(), Steps(10));
+    }
+
+    std::thread_local! {
+        static MOCK_NOW_NS: core::cell::Cell<u64> = const { core::cell::Cell::new(0) };
+    }
+
+    fn mock_clock() -> u64 {
+        MOCK_NOW_NS.with(|now| now.get())
+    }
+
+    /// Delay that advances the mock clock, returning late or early from some
+    /// of the inter-step delays.
+    struct JitterDelay {
+        gaps: u32,
+    }
+
+    impl DelayNs for JitterDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            let mut elapsed = ns as i64;
+            // The STEP pulse is 2 µs; longer delays are the gaps between steps
+            if ns > crate::config::DEFAULT_STEP_PULSE_NS {
+                elapsed += match self.gaps % 4 {
+                    0 => 2500,
+                    2 => -1500,
+                    _ => 0,
+                };
+                self.gaps += 1;
+            }
+            MOCK_NOW_NS.with(|now| now.set(now.get() + elapsed as u64));
+        }
+    }
+
+    fn jitter_builder() -> StepperMotorBuilder<NoopPin, NoopPin, JitterDelay> {
+        StepperMotorBuilder::new()
+            .name("x_axis")
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(JitterDelay { gaps: 0 })
+            .steps_per_revolution(200)
+            .microsteps(Microsteps::FULL)
+            .max_velocity(DegreesPerSec(3600.0))
+            .max_acceleration(DegreesPerSecSquared(36000.0))
+            .clock(mock_clock)
+    }
+
+    #[test]
+    fn test_timing_stats_histogram() {
+        let motor = jitter_builder().timing_bucket_ns(1000).build().unwrap();
+        let moving = motor.move_to(Degrees(90.0)).ok().unwrap();
+        let mut motor = moving.run_to_completion().unwrap();
+
+        // 50 steps give 49 intervals; gaps 0, 4, 8, ... are late
+        let stats = motor.take_timing_stats().unwrap();
+        assert_eq!(stats.samples, 49);
+        assert_eq!(stats.late_steps, 13);
+        assert_eq!(stats.max_late_ns, 2500);
+        assert_eq!(stats.max_early_ns, 1500);
+        assert_eq!(stats.histogram[0], 24);
+        assert_eq!(stats.histogram[1], 12);
+        assert_eq!(stats.histogram[2], 13);
+
+        // Taking resets; the next move starts without comparing to the last
+        assert_eq!(motor.timing_stats().unwrap().samples, 0);
+        let moving = motor.move_to(Degrees(0.0)).ok().unwrap();
+        let motor = moving.run_to_completion().unwrap();
+        assert_eq!(motor.timing_stats().unwrap().samples, 49);
+    }
+
+    #[test]
+    fn test_duty_tracks_moves_and_idle() {
+        use crate::config::ThermalConfig;
+
+        let thermal = ThermalConfig { time_constant_s: 0.02, max_duty: 0.5, enforce: true };
+        let motor = jitter_builder().thermal(thermal).build().unwrap();
+        assert_eq!(motor.duty_tracker().unwrap().current_duty(), 0.0);
+
+        let start = mock_clock();
+        let motor = motor.move_to(Degrees(90.0)).ok().unwrap().run_to_completion().unwrap();
+        let moving_s = (mock_clock() - start) as f32 * 1e-9;
+        let duty = motor.duty_tracker().unwrap().current_duty();
+        let expected = 1.0 - (-moving_s / 0.02).exp();
+        assert!((duty - expected).abs() < 1e-4, "{} vs {}", duty, expected);
+        assert!(duty > 0.5);
+
+        // Straight into another move: over budget
+        let (motor, err) = motor.move_to(Degrees(0.0)).err().unwrap();
+        assert!(matches!(err, Error::Motor(MotorError::ThermalBudgetExceeded { .. })));
+
+        // Resting for five time constants lets it cool down
+        MOCK_NOW_NS.with(|now| now.set(now.get() + 100_000_000));
+        let moving = motor.move_to(Degrees(0.0)).ok().unwrap();
+        let cooled = moving.duty_tracker().unwrap().current_duty();
+        assert!((cooled - duty * (-5.0f32).exp()).abs() < 1e-4);
+        let motor = moving.run_to_completion().unwrap();
+        assert!(motor.duty_tracker().unwrap().current_duty() > cooled);
+    }
+
+    #[test]
+    fn test_duty_without_clock_uses_estimates() {
+        use crate::config::ThermalConfig;
+
+        let mut motor = test_motor();
+        assert!(motor.duty_tracker().is_none());
+        motor.set_duty_tracker(Some(DutyTracker::new(&ThermalConfig::new(1.0, 0.5))));
+        let moving = motor.move_to(Degrees(90.0)).ok().unwrap();
+        let estimate = moving.profile().unwrap().estimated_duration_secs();
+        let mut motor = moving.run_to_completion().unwrap();
+        let duty = motor.duty_tracker().unwrap().current_duty();
+        assert!((duty - (1.0 - (-estimate).exp())).abs() < 1e-4);
+
+        // Not enforced: moves still start, and idle time is recorded by hand
+        motor.duty_tracker_mut().unwrap().record_idle(1.0);
+        let cooled = motor.duty_tracker().unwrap().current_duty();
+        assert!((cooled - duty / 1.0f32.exp()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_soft_start_after_enable() {
+        use crate::config::SoftStartConfig;
+
+        // Without a clock: only the first move after enabling is softened
+        let motor = StepperMotorBuilder::new()
+            .from_motor_config(&limited_config())
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .soft_start(SoftStartConfig::new(100, 50))
+            .build()
+            .unwrap();
+        let full = motor.constraints().max_acceleration_steps_per_sec2;
+        let accel_rates = |motor: StepperMotor<NoopPin, NoopPin, NoopDelay>, target: f32| {
+            let moving = motor.move_to(Degrees(target)).ok().unwrap();
+            let profile = moving.profile().unwrap().clone();
+            (moving.run_to_completion().unwrap(), profile.accel_rate, profile.decel_rate)
+        };
+        let (motor, accel, decel) = accel_rates(motor, 90.0);
+        assert_eq!((accel, decel), (full * 0.5, full));
+        let (mut motor, accel, _) = accel_rates(motor, 45.0);
+        assert_eq!(accel, full);
+
+        motor.notify_enabled();
+        let (motor, accel, _) = accel_rates(motor, 90.0);
+        assert_eq!(accel, full * 0.5);
+        assert_eq!(accel_rates(motor, 45.0).1, full);
+
+        // With a clock: every move inside the window
+        let mut motor = jitter_builder().soft_start(SoftStartConfig::new(1000, 25)).build().unwrap();
+        let full = motor.constraints().max_acceleration_steps_per_sec2;
+        // 20 steps each: long enough for acceleration ramps
+        for (target, expected) in [(36.0, full * 0.25), (72.0, full * 0.25)] {
+            let moving = motor.move_to(Degrees(target)).ok().unwrap();
+            assert_eq!(moving.profile().unwrap().accel_rate, expected);
+            motor = moving.run_to_completion().unwrap();
+        }
+        MOCK_NOW_NS.with(|now| now.set(now.get() + 1_000_000_000));
+        let moving = motor.move_to(Degrees(108.0)).ok().unwrap();
+        assert_eq!(moving.profile().unwrap().accel_rate, full);
+    }
+
+    #[test]
+    fn test_timing_stats_disabled() {
+        let mut motor = jitter_builder().timing_stats(false).build().unwrap();
+        assert!(motor.timing_stats().is_none());
+        assert!(motor.take_timing_stats().is_none());
+        assert!(test_motor().timing_stats().is_none());
+    }
+
+    /// Home switch reading high once its countdown of reads runs out.
+    ///
+    /// Clones share the countdown.
+    #[derive(Clone, Default)]
+    struct MockSwitch(std::rc::Rc<core::cell::Cell<u32>>);
+
+    impl MockSwitch {
+        fn trigger_after(&self, reads: u32) {
+            self.0.set(reads);
+        }
+    }
+
+    impl ErrorType for MockSwitch {
+        type Error = core::convert::Infallible;
+    }
+
+    impl InputPin for MockSwitch {
+        fn is_high(&mut self) -> core::result::Result<bool, Self::Error> {
+            let left = self.0.get();
+            self.0.set(left.saturating_sub(1));
+            Ok(left == 0)
+        }
+
+        fn is_low(&mut self) -> core::result::Result<bool, Self::Error> {
+            self.is_high().map(|high| !high)
+        }
+    }
+
+    fn homing_motor(
+        switch: &MockSwitch,
+    ) -> StepperMotor<NoopPin, NoopPin, NoopDelay, Idle, MockSwitch> {
+        StepperMotorBuilder::new()
+            .name("x_axis")
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .steps_per_revolution(200)
+            .microsteps(Microsteps::FULL)
+            .max_velocity(DegreesPerSec(3600.0))
+            .max_acceleration(DegreesPerSecSquared(36000.0))
+            .home_switch(switch.clone())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_execute_homing_trajectory() {
+        use crate::trajectory::{TrajectoryBuilder, TrajectoryRegistry};
+
+        let mut registry = TrajectoryRegistry::new();
+        let home = TrajectoryBuilder::new()
+            .motor("x_axis")
+            .homing(Direction::CounterClockwise, Degrees(90.0));
+        registry.register("home", home.build().unwrap()).unwrap();
+
+        // Switch triggers 30 steps (54°) into the run
+        let switch = MockSwitch::default();
+        let mut motor = homing_motor(&switch);
+        motor.set_position(Degrees(45.0));
+        switch.trigger_after(30);
+        let Ok(motor) = motor.execute("home", &registry) else {
+            panic!("homing should find the switch");
+        };
+        assert_eq!(motor.position_steps(), Steps(0));
+        assert_eq!(motor.last_trajectory(), Some("home"));
+
+        // Switch never triggers within the 90° (50 step) travel
+        switch.trigger_after(u32::MAX);
+        let (motor, err) = motor.execute("home", &registry).err().unwrap();
+        assert_eq!(err, Error::Motor(MotorError::HomeSwitchNotFound { travel_steps: 50 }));
+        assert_eq!(motor.position_steps(), Steps(-50));
+        assert_eq!(motor.last_trajectory(), None);
+
+        // Without a switch, homing is refused before moving
+        let (motor, err) = test_motor().execute("home", &registry).err().unwrap();
+        assert_eq!(err, Error::Motor(MotorError::NoHomeSwitch));
+        assert_eq!(motor.position_steps(), Steps(0));
+    }
+
+    #[test]
+    fn test_start_homing_steps_until_switch() {
+        let switch = MockSwitch::default();
+        switch.trigger_after(3);
+        let motor = homing_motor(&switch);
+        let Ok(mut homing) = motor.start_homing(Direction::Clockwise, Degrees(90.0)) else {
+            panic!("homing should start");
+        };
+        let mut triggered = false;
+        while !triggered {
+            triggered = homing.step().unwrap();
+        }
+        assert_eq!(homing.travelled_steps(), 3);
+        assert_eq!(homing.finish().position_steps(), Steps(0));
+    }
+
+    #[test]
+    fn test_homing_trajectory_matches_start_homing_velocity() {
+        use crate::trajectory::TrajectoryBuilder;
+
+        let switch = MockSwitch::default();
+        switch.trigger_after(u32::MAX);
+        let motor = homing_motor(&switch);
+        let constraints = motor.constraints().clone();
+        let Ok(homing) = motor.start_homing(Direction::Clockwise, Degrees(360.0)) else {
+            panic!("homing should start");
+        };
+        let direct = homing.executor.as_ref().unwrap().profile().cruise_interval_ns;
+
+        // At the default 100 %, a homing trajectory cruises as fast as
+        // start_homing, a quarter of the max velocity
+        let home = TrajectoryBuilder::new()
+            .motor("x_axis")
+            .homing(Direction::Clockwise, Degrees(360.0))
+            .build()
+            .unwrap();
+        let planned = home.plan_profile(&constraints, 0).unwrap().cruise_interval_ns;
+        assert_eq!(planned, direct);
+        // 3600°/s * 0.25 = 500 steps/s
+        assert!(planned.abs_diff(2_000_000) <= 1, "{planned}");
+    }
 }
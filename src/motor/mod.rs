@@ -4,12 +4,38 @@
 
 mod builder;
 mod driver;
+mod planner;
 mod position;
+#[cfg(any(feature = "alloc", feature = "std"))]
+mod preflight;
+mod result;
+#[cfg(feature = "shared-delay")]
+mod shared_delay;
+#[cfg(feature = "shared-pin")]
+mod shared_pin;
+mod snapshot;
 pub mod state;
 mod system;
+mod telemetry;
+mod thermal;
+mod timing;
 
-pub use builder::StepperMotorBuilder;
-pub use driver::StepperMotor;
+pub use builder::{StepperMotorBuilder, DEFAULT_TIMING_BUDGET_NS};
+pub use driver::{NoHomeSwitch, ParkOutcome, StepperMotor};
+pub use planner::{
+    MotionPlanner, MoveParams, MovePlan, MoveReport, MAX_PLAN_LEGS, MAX_SEQUENCE_PLANS,
+};
 pub use position::Position;
-pub use state::{Fault, Homing, Idle, MotorState, Moving, StateName};
-pub use system::MotorSystem;
+#[cfg(any(feature = "alloc", feature = "std"))]
+pub use preflight::{DryRunResult, PreflightReport};
+pub use result::MotorResultExt;
+#[cfg(feature = "shared-delay")]
+pub use shared_delay::{SharedDelay, SharedDelayCell};
+#[cfg(feature = "shared-pin")]
+pub use shared_pin::{SharedPin, SharedPinCell};
+pub use snapshot::{SystemSnapshot, SNAPSHOT_BYTES};
+pub use state::{Armed, Fault, Homing, Idle, MotorState, Moving, Paused, StateName};
+pub use system::{MotorSystem, PlannedStep, ReloadReport};
+pub use telemetry::MotorTelemetry;
+pub use thermal::DutyTracker;
+pub use timing::{ClockFn, TimingStats, DEFAULT_TIMING_BUCKET_NS, TIMING_HISTOGRAM_BUCKETS};
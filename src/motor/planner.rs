@@ -0,0 +1,759 @@
+//! Move planning without hardware.
+//!
+//! [`MotionPlanner`] turns targets into the exact profiles a
+//! [`StepperMotor`](super::StepperMotor) would run: soft limit checks and
+//! clamping, the slowdown near limits, gravity compensation and the backlash
+//! approach overshoot. The motor delegates its own planning to it, so a plan
+//! computed here matches what the hardware does.
+
+use heapless::Vec;
+
+use crate::config::{
+    DegreesPerSec, DegreesPerSecSquared, FinalApproach, LimitPolicy, MechanicalConstraints,
+    MotorConfig, SoftLimits, StepLimits, TrajectoryConfig, WaypointTrajectory,
+};
+use crate::config::units::Degrees;
+use crate::error::{Error, MotionError, MotorError, Result};
+use crate::motion::{Direction, MotionProfile, MIN_TRAPEZOIDAL_STEPS};
+
+/// Maximum number of profiles in one [`MovePlan`].
+///
+/// A trajectory overshoot, the backlash approach and a slow final stretch (a
+/// sequence's final approach or the clamp slow zone) can each add a leg.
+pub const MAX_PLAN_LEGS: usize = 4;
+
+/// Maximum number of plans returned by [`MotionPlanner::plan_sequence`].
+pub const MAX_SEQUENCE_PLANS: usize = 32;
+
+/// Rates for a [`MotionPlanner::plan_absolute`] move.
+///
+/// Unset rates default to the motor maximum; an unset deceleration follows
+/// the acceleration.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MoveParams {
+    /// Cruise velocity.
+    pub velocity: Option<DegreesPerSec>,
+    /// Acceleration rate.
+    pub acceleration: Option<DegreesPerSecSquared>,
+    /// Deceleration rate.
+    pub deceleration: Option<DegreesPerSecSquared>,
+}
+
+impl MoveParams {
+    /// Rates in steps: (velocity, acceleration, deceleration).
+    fn to_steps(self, constraints: &MechanicalConstraints) -> (f32, f32, f32) {
+        let steps_per_degree = constraints.steps_per_degree;
+        let accel = self
+            .acceleration
+            .map_or(constraints.max_acceleration_steps_per_sec2, |a| a.0 * steps_per_degree);
+        let decel = self.deceleration.map_or(accel, |d| d.0 * steps_per_degree);
+        let velocity = self
+            .velocity
+            .map_or(constraints.max_velocity_steps_per_sec, |v| v.0 * steps_per_degree);
+        (velocity, accel, decel)
+    }
+}
+
+/// Profiles for one move, in execution order.
+///
+/// Legs run back to back as a single move. A plan always has at least one
+/// leg, and each leg starts where the previous one ended.
+#[derive(Debug, Clone)]
+pub struct MovePlan {
+    /// Position the plan starts from, in steps.
+    pub start_steps: i64,
+    /// Final position, in steps (after clamping).
+    pub target_steps: i64,
+    /// Whether the requested target was clamped to a soft limit.
+    pub clamped: bool,
+    /// Spans the requested target was reduced by under the wrap policy
+    /// (negative when it was below the window, 0 when not wrapped).
+    pub wrapped_spans: i64,
+    /// Dwell after arriving (waypoint sequences only).
+    pub dwell_ms: Option<u32>,
+    /// Profiles to run, in order.
+    pub legs: Vec<MotionProfile, MAX_PLAN_LEGS>,
+}
+
+impl MovePlan {
+    /// Total number of steps over all legs.
+    pub fn total_steps(&self) -> u64 {
+        self.legs.iter().map(|leg| leg.total_steps as u64).sum()
+    }
+
+    /// Estimated duration of all legs in seconds, excluding the dwell.
+    pub fn estimated_duration_secs(&self) -> f32 {
+        self.legs.iter().map(|leg| leg.estimated_duration_secs()).sum()
+    }
+}
+
+/// What a trajectory run did, or would do: see
+/// [`StepperMotor::execute_reported`](super::StepperMotor::execute_reported)
+/// and [`StepperMotor::rehearse`](super::StepperMotor::rehearse).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveReport {
+    /// Name of the trajectory.
+    pub trajectory: heapless::String<32>,
+    /// Position before the move, in steps.
+    pub start_steps: i64,
+    /// Position after the move, in steps.
+    pub end_steps: i64,
+    /// Steps issued over all legs (the full search travel for homing).
+    pub total_steps: u64,
+    /// Number of legs, including approach overshoots and slow stretches.
+    pub legs: usize,
+    /// Whether the target was clamped to a soft limit.
+    pub clamped: bool,
+    /// Spans the target was reduced by under the wrap policy.
+    pub wrapped_spans: i64,
+    /// Estimated duration of the legs in seconds, excluding the dwell.
+    pub estimated_duration_secs: f32,
+    /// Dwell after arriving.
+    pub dwell_ms: Option<u32>,
+}
+
+impl MoveReport {
+    /// Report a plan for the trajectory `name`.
+    pub(crate) fn from_plan(name: &str, plan: &MovePlan) -> Self {
+        Self {
+            trajectory: heapless::String::try_from(name).unwrap_or_default(),
+            start_steps: plan.start_steps,
+            end_steps: plan.target_steps,
+            total_steps: plan.total_steps(),
+            legs: plan.legs.len(),
+            clamped: plan.clamped,
+            wrapped_spans: plan.wrapped_spans,
+            estimated_duration_secs: plan.estimated_duration_secs(),
+            dwell_ms: plan.dwell_ms,
+        }
+    }
+}
+
+/// Distance before the target covered at a creep velocity, in steps and
+/// steps/sec.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FinalStretch {
+    velocity: f32,
+    steps: i64,
+}
+
+/// Plans moves for one motor from its constraints alone.
+#[derive(Debug, Clone)]
+pub struct MotionPlanner {
+    constraints: MechanicalConstraints,
+    backlash_steps: i64,
+    approach_direction: Option<Direction>,
+}
+
+impl MotionPlanner {
+    /// Create a planner without backlash compensation.
+    pub fn new(constraints: MechanicalConstraints) -> Self {
+        Self { constraints, backlash_steps: 0, approach_direction: None }
+    }
+
+    /// Create a planner with the constraints, backlash compensation and
+    /// approach direction of a motor configuration.
+    pub fn from_motor_config(config: &MotorConfig) -> Self {
+        Self::new(MechanicalConstraints::from_config(config))
+            .with_backlash(config.backlash_steps(), config.backlash_approach_direction)
+    }
+
+    /// Set the backlash compensation in steps and the direction every move
+    /// must finish in (see [`StepperMotorBuilder::approach_direction`]).
+    ///
+    /// [`StepperMotorBuilder::approach_direction`]: super::StepperMotorBuilder::approach_direction
+    pub fn with_backlash(mut self, steps: i64, approach_direction: Option<Direction>) -> Self {
+        self.backlash_steps = steps;
+        self.approach_direction = approach_direction;
+        self
+    }
+
+    /// Plan against `limits` instead of the constraints' soft limits (`None`
+    /// plans without limits).
+    pub fn with_limits(mut self, limits: Option<&SoftLimits>) -> Self {
+        self.constraints = self.constraints.with_limits(limits);
+        self
+    }
+
+    /// The constraints moves are planned against.
+    #[inline]
+    pub fn constraints(&self) -> &MechanicalConstraints {
+        &self.constraints
+    }
+
+    /// Plan a move between two absolute positions, as
+    /// [`StepperMotor::move_to`](super::StepperMotor::move_to) does.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotorError::LimitExceeded` if the target or the approach
+    /// overshoot is outside limits that reject, `MotionError::MoveTooShort`
+    /// if there is nothing to move, or a planning error for invalid rates.
+    pub fn plan_absolute(
+        &self,
+        from: Degrees,
+        to: Degrees,
+        params: MoveParams,
+    ) -> Result<MovePlan> {
+        let from_steps = self.constraints.degrees_to_steps(from.0);
+        let to_steps = self.constraints.degrees_to_steps(to.0);
+        self.plan_steps(from_steps, to_steps, params)
+    }
+
+    /// Plan a trajectory from `from`, as
+    /// [`StepperMotor::execute_trajectory`](super::StepperMotor::execute_trajectory)
+    /// runs it.
+    ///
+    /// A homing trajectory plans its search move over the full travel; the
+    /// motor stops early when the switch triggers. A `limit_override`
+    /// replaces the soft limits. Preconditions are not checked.
+    ///
+    /// # Errors
+    ///
+    /// As [`plan_absolute`](Self::plan_absolute).
+    pub fn plan_trajectory(
+        &self,
+        from: Degrees,
+        trajectory: &TrajectoryConfig,
+    ) -> Result<MovePlan> {
+        self.plan_trajectory_steps(self.constraints.degrees_to_steps(from.0), trajectory)
+    }
+
+    /// Plan every segment of a waypoint sequence from `from`, as
+    /// [`StepperMotor::execute_waypoints`](super::StepperMotor::execute_waypoints)
+    /// runs them.
+    ///
+    /// Waypoints equal to the position before them are left out. With a
+    /// [final approach](crate::config::FinalApproach), the last leg of the
+    /// final segment is split into a leg ending at the creep velocity and a
+    /// creep leg continuing from it. A segment clamped to a limit with a
+    /// clamp slow zone is split the same way; when both apply, the slower
+    /// velocity and the longer distance win.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first segment's planning error.
+    pub fn plan_sequence(
+        &self,
+        from: Degrees,
+        sequence: &WaypointTrajectory,
+    ) -> Result<Vec<MovePlan, MAX_SEQUENCE_PLANS>> {
+        let mut plans = Vec::new();
+        let mut position = self.constraints.degrees_to_steps(from.0);
+        for index in 0..sequence.waypoints.len() {
+            if let Some(plan) = self.plan_waypoint(position, sequence, index)? {
+                position = plan.target_steps;
+                // A sequence has at most 32 waypoints
+                let _ = plans.push(plan);
+            }
+        }
+        Ok(plans)
+    }
+
+    /// Plan the segment into waypoint `index` of `sequence`, or `None` when
+    /// it would not move.
+    pub(crate) fn plan_waypoint(
+        &self,
+        from_steps: i64,
+        sequence: &WaypointTrajectory,
+        index: usize,
+    ) -> Result<Option<MovePlan>> {
+        let segment = sequence.segment(sequence.waypoints[index]);
+        let stretch = sequence
+            .final_approach
+            .filter(|_| index + 1 == sequence.waypoints.len())
+            .map(|approach| self.final_approach_stretch(&approach));
+        self.plan_segment(from_steps, &segment, stretch)
+    }
+
+    /// Plan a waypoint segment, or `None` when it would not move, slowing
+    /// down over the final `stretch` if given.
+    pub(crate) fn plan_segment(
+        &self,
+        from_steps: i64,
+        segment: &TrajectoryConfig,
+        stretch: Option<FinalStretch>,
+    ) -> Result<Option<MovePlan>> {
+        match self.plan_trajectory_slowing(from_steps, segment, stretch) {
+            Ok(plan) => Ok(Some(plan)),
+            Err(Error::Motion(MotionError::MoveTooShort { steps: 0, .. })) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Plan a move between two positions in steps.
+    pub(crate) fn plan_steps(
+        &self,
+        from_steps: i64,
+        to_steps: i64,
+        params: MoveParams,
+    ) -> Result<MovePlan> {
+        let mut plan = self.limited_plan(from_steps, to_steps)?;
+        let target_steps = plan.target_steps;
+        let rates = params.to_steps(&self.constraints);
+        self.push_approach(&mut plan.legs, from_steps, target_steps, rates)?;
+        self.check_arrival(&plan)?;
+        self.slow_final_stretch(&mut plan, rates, None)?;
+        Ok(plan)
+    }
+
+    /// Plan a trajectory from a position in steps, within its
+    /// `limit_override` if it has one.
+    pub(crate) fn plan_trajectory_steps(
+        &self,
+        from_steps: i64,
+        trajectory: &TrajectoryConfig,
+    ) -> Result<MovePlan> {
+        self.plan_trajectory_slowing(from_steps, trajectory, None)
+    }
+
+    /// [`plan_trajectory_steps`](Self::plan_trajectory_steps), slowing down
+    /// over the final `stretch` if given.
+    fn plan_trajectory_slowing(
+        &self,
+        from_steps: i64,
+        trajectory: &TrajectoryConfig,
+        stretch: Option<FinalStretch>,
+    ) -> Result<MovePlan> {
+        match &trajectory.limit_override {
+            Some(limits) => self
+                .clone()
+                .with_limits(Some(limits))
+                .plan_trajectory_within(from_steps, trajectory, stretch),
+            None => self.plan_trajectory_within(from_steps, trajectory, stretch),
+        }
+    }
+
+    /// Plan a trajectory from a position in steps within this planner's
+    /// limits, slowing down over the final `stretch` if given.
+    pub(crate) fn plan_trajectory_within(
+        &self,
+        from_steps: i64,
+        trajectory: &TrajectoryConfig,
+        stretch: Option<FinalStretch>,
+    ) -> Result<MovePlan> {
+        if trajectory.is_homing_trajectory() {
+            let profile = trajectory.plan_profile(&self.constraints, from_steps)?;
+            let delta = profile.direction.sign() * profile.total_steps as i64;
+            let mut plan = self.empty_plan(from_steps, from_steps + delta, false)?;
+            let _ = plan.legs.push(profile);
+            return Ok(plan);
+        }
+
+        let rates = self.trajectory_rates(trajectory);
+        let target = trajectory.target_steps(&self.constraints);
+        let mut plan = self.limited_plan(from_steps, target)?;
+        let target_steps = plan.target_steps;
+        plan.dwell_ms = trajectory.dwell_ms;
+
+        let from = Degrees(self.constraints.steps_to_degrees(from_steps));
+        let mut position = from_steps;
+        if let Some(via) = trajectory.approach_via(from) {
+            let via_steps = self.constraints.degrees_to_steps(via.0);
+            let toward = Direction::from_steps(via_steps - position);
+            let via_steps = self.check_via(via_steps, target_steps, toward)?;
+            if let Some(via_steps) = via_steps.filter(|&via| via != position) {
+                self.push_approach(&mut plan.legs, position, via_steps, rates)?;
+                position = via_steps;
+            }
+        }
+        self.push_approach(&mut plan.legs, position, target_steps, rates)?;
+        self.check_arrival(&plan)?;
+        self.slow_final_stretch(&mut plan, rates, stretch)?;
+        Ok(plan)
+    }
+
+    /// A trajectory's rates in steps: (velocity, acceleration, deceleration).
+    fn trajectory_rates(&self, trajectory: &TrajectoryConfig) -> (f32, f32, f32) {
+        let steps_per_degree = self.constraints.steps_per_degree;
+        (
+            trajectory.effective_velocity(&self.constraints) * steps_per_degree,
+            trajectory.effective_acceleration(&self.constraints) * steps_per_degree,
+            trajectory.effective_deceleration(&self.constraints) * steps_per_degree,
+        )
+    }
+
+    /// A sequence's final approach as a final stretch.
+    fn final_approach_stretch(&self, approach: &FinalApproach) -> FinalStretch {
+        FinalStretch {
+            velocity: approach.effective_velocity(&self.constraints)
+                * self.constraints.steps_per_degree,
+            steps: self.constraints.degrees_to_steps(approach.distance.0).abs(),
+        }
+    }
+
+    /// The clamp slow zone as a final stretch, if the soft limits have one.
+    fn clamp_stretch(&self) -> Option<FinalStretch> {
+        let limits = self.constraints.limits.as_ref()?;
+        let percent = limits.clamp_approach_velocity_percent?;
+        (limits.clamp_slow_zone_steps > 0).then(|| FinalStretch {
+            velocity: self.constraints.max_velocity_steps_per_sec * percent as f32 / 100.0,
+            steps: limits.clamp_slow_zone_steps,
+        })
+    }
+
+    /// Replace the last leg of `plan` with one slowing to the creep velocity
+    /// and a creep leg over the final stretch. A last leg no longer than the
+    /// stretch runs at the creep velocity throughout.
+    ///
+    /// `stretch` is combined with the clamp slow zone when the target was
+    /// clamped: the slower velocity and the longer distance apply. Nothing
+    /// changes when neither applies.
+    fn slow_final_stretch(
+        &self,
+        plan: &mut MovePlan,
+        rates: (f32, f32, f32),
+        stretch: Option<FinalStretch>,
+    ) -> Result<()> {
+        let clamp = self.clamp_stretch().filter(|_| plan.clamped);
+        let stretch = match (stretch, clamp) {
+            (Some(a), Some(b)) => FinalStretch {
+                velocity: a.velocity.min(b.velocity),
+                steps: a.steps.max(b.steps),
+            },
+            (Some(stretch), None) | (None, Some(stretch)) => stretch,
+            (None, None) => return Ok(()),
+        };
+        let Some(last) = plan.legs.pop() else {
+            return Ok(());
+        };
+        let (velocity, accel, decel) = rates;
+        let creep = stretch.velocity.min(velocity);
+        let creep_rates = (creep, accel, decel);
+
+        let target = plan.target_steps;
+        let sign = last.direction.sign();
+        let start = target - sign * last.total_steps as i64;
+        let creep_steps = stretch.steps;
+        if creep_steps >= last.total_steps as i64 {
+            let _ = plan.legs.push(self.plan_leg(start, target, creep_rates)?);
+            return Ok(());
+        }
+
+        // Reach the junction at the creep velocity and carry on from there
+        let junction = target - sign * creep_steps;
+        let initial = self.constraints.initial_velocity_steps_per_sec;
+        let rates = (velocity, accel, decel);
+        let _ = plan.legs.push(self.plan_leg_between(start, junction, rates, initial, creep)?);
+        let _ = plan.legs.push(self.plan_leg_between(junction, target, creep_rates, creep, 0.0)?);
+        Ok(())
+    }
+
+    /// A plan without legs, failing if it would not move.
+    fn empty_plan(&self, from_steps: i64, target_steps: i64, clamped: bool) -> Result<MovePlan> {
+        self.constraints.check_move_steps(target_steps - from_steps)?;
+        Ok(MovePlan {
+            start_steps: from_steps,
+            target_steps,
+            clamped,
+            wrapped_spans: 0,
+            dwell_ms: None,
+            legs: Vec::new(),
+        })
+    }
+
+    /// Start a plan to `target_steps` with the soft limits applied.
+    fn limited_plan(&self, from_steps: i64, target_steps: i64) -> Result<MovePlan> {
+        let wrapping = self.wrapping_limits();
+        let (target_steps, wrapped_spans) =
+            wrapping.map_or((target_steps, 0), |limits| limits.wrap(target_steps));
+        let direction = Direction::from_steps(target_steps - from_steps);
+        let (target_steps, clamped) = self.check_target(target_steps, direction)?;
+        let mut plan = self.empty_plan(from_steps, target_steps, clamped)?;
+        plan.wrapped_spans = wrapped_spans;
+        Ok(plan)
+    }
+
+    /// The soft limits, if their policy is to wrap.
+    fn wrapping_limits(&self) -> Option<&StepLimits> {
+        self.constraints.limits.as_ref().filter(|l| l.policy == LimitPolicy::Wrap)
+    }
+
+    /// Apply the soft limits for a move in `direction` to a target,
+    /// returning it (clamped under the clamp policy) and whether it was
+    /// clamped.
+    fn check_target(&self, target_steps: i64, direction: Direction) -> Result<(i64, bool)> {
+        match self.constraints.check_limits_moving(target_steps, direction) {
+            Some(steps) => Ok((steps, steps != target_steps)),
+            None => Err(self.limit_exceeded(target_steps, direction)),
+        }
+    }
+
+    /// Check the target against the bounds for the direction of the final
+    /// leg, which differs from that of the move when it overshoots to
+    /// approach from the other side.
+    fn check_arrival(&self, plan: &MovePlan) -> Result<()> {
+        let Some(last) = plan.legs.last() else {
+            return Ok(());
+        };
+        if self.wrapping_limits().is_some()
+            || self.constraints.check_limits_moving(plan.target_steps, last.direction)
+                == Some(plan.target_steps)
+        {
+            return Ok(());
+        }
+        Err(self.limit_exceeded(plan.target_steps, last.direction))
+    }
+
+    /// Check an overshoot point beyond `target_steps` against the soft limits.
+    ///
+    /// Returns the point to overshoot to (clamped under the clamp policy), or
+    /// `None` when the target sits on the limit and there is no room to
+    /// overshoot. Under the wrap policy the axis is continuous, so the
+    /// overshoot may leave the window.
+    fn check_via(
+        &self,
+        via_steps: i64,
+        target_steps: i64,
+        direction: Direction,
+    ) -> Result<Option<i64>> {
+        if self.wrapping_limits().is_some() {
+            return Ok(Some(via_steps));
+        }
+        match self.constraints.check_limits_moving(via_steps, direction) {
+            Some(steps) if steps == target_steps => Ok(None),
+            Some(steps) => Ok(Some(steps)),
+            None => Err(self.limit_exceeded(via_steps, direction)),
+        }
+    }
+
+    fn limit_exceeded(&self, position: i64, direction: Direction) -> Error {
+        let limit = match &self.constraints.limits {
+            Some(limits) => {
+                let (min, max) = limits.bounds(direction);
+                if position > max {
+                    max
+                } else {
+                    min
+                }
+            }
+            None => position,
+        };
+        Error::Motor(MotorError::LimitExceeded { position, limit })
+    }
+
+    /// Append the legs to `target_steps`, overshooting first when the move
+    /// would arrive against the approach direction.
+    fn push_approach(
+        &self,
+        legs: &mut Vec<MotionProfile, MAX_PLAN_LEGS>,
+        from_steps: i64,
+        target_steps: i64,
+        rates: (f32, f32, f32),
+    ) -> Result<()> {
+        let overshoot = self
+            .approach_direction
+            .filter(|&d| d != Direction::from_steps(target_steps - from_steps))
+            .filter(|_| self.backlash_steps > 0)
+            .map(|d| target_steps - d.sign() * self.backlash_steps);
+        let mut position = from_steps;
+        if let Some(via) = overshoot {
+            // Already at the overshoot point: only the approach leg is left
+            let toward = Direction::from_steps(via - position);
+            let via = self.check_via(via, target_steps, toward)?;
+            if let Some(via) = via.filter(|&via| via != position) {
+                let _ = legs.push(self.plan_leg(position, via, rates)?);
+                position = via;
+            }
+        }
+        let _ = legs.push(self.plan_leg(position, target_steps, rates)?);
+        Ok(())
+    }
+
+    /// `velocity` limited for a leg ending at `to_steps` near a limit.
+    fn capped_velocity(&self, to_steps: i64, velocity: f32) -> f32 {
+        match self.constraints.margin_velocity_limit(to_steps) {
+            Some(cap) => velocity.min(cap),
+            None => velocity,
+        }
+    }
+
+    /// Plan one leg, slowing down for targets near a limit.
+    ///
+    /// Legs from rest use [`MotionProfile::optimal`], so very short ones run
+    /// at constant velocity.
+    fn plan_leg(
+        &self,
+        from_steps: i64,
+        to_steps: i64,
+        rates: (f32, f32, f32),
+    ) -> Result<MotionProfile> {
+        let initial = self.constraints.initial_velocity_steps_per_sec;
+        let steps = to_steps - from_steps;
+        if initial > 0.0 || steps.unsigned_abs() > MIN_TRAPEZOIDAL_STEPS as u64 {
+            return self.plan_leg_between(from_steps, to_steps, rates, initial, 0.0);
+        }
+        let (velocity, accel, decel) = rates;
+        let velocity = self.capped_velocity(to_steps, velocity);
+        let profile = MotionProfile::optimal(steps, velocity, accel, decel);
+        Ok(self.constraints.apply_gravity(profile))
+    }
+
+    /// Plan one leg between two velocities in steps/sec, slowing down for
+    /// targets near a limit.
+    fn plan_leg_between(
+        &self,
+        from_steps: i64,
+        to_steps: i64,
+        rates: (f32, f32, f32),
+        initial_velocity: f32,
+        final_velocity: f32,
+    ) -> Result<MotionProfile> {
+        let (velocity, accel, decel) = rates;
+        let velocity = self.capped_velocity(to_steps, velocity);
+        let profile = MotionProfile::try_between_velocities(
+            to_steps - from_steps,
+            velocity,
+            accel,
+            decel,
+            initial_velocity,
+            final_velocity,
+        )?;
+        Ok(self.constraints.apply_gravity(profile))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trajectory::WaypointTrajectoryBuilder;
+
+    fn config() -> MotorConfig {
+        toml::from_str(
+            r#"
+name = "x_axis"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 3600.0
+max_acceleration_deg_per_sec2 = 36000.0
+backlash_compensation_deg = 3.6
+approach_direction = "clockwise"
+
+[limits]
+min_degrees = 9.0
+max_degrees = 90.0
+policy = "clamp"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_plan_absolute() {
+        let planner = MotionPlanner::from_motor_config(&config());
+
+        // Clamped to the 50-step maximum, arriving clockwise
+        let plan = planner.plan_absolute(Degrees(18.0), Degrees(180.0), MoveParams::default());
+        let plan = plan.unwrap();
+        assert!(plan.clamped);
+        assert_eq!((plan.start_steps, plan.target_steps), (10, 50));
+        assert_eq!(plan.legs.len(), 1);
+
+        // Counter-clockwise: overshoot 2 steps, then approach
+        let params = MoveParams {
+            velocity: Some(DegreesPerSec(360.0)),
+            ..MoveParams::default()
+        };
+        let plan = planner.plan_absolute(Degrees(90.0), Degrees(45.0), params).unwrap();
+        assert!(!plan.clamped);
+        let legs: std::vec::Vec<_> =
+            plan.legs.iter().map(|l| (l.direction, l.total_steps)).collect();
+        assert_eq!(legs, [(Direction::CounterClockwise, 27), (Direction::Clockwise, 2)]);
+        assert_eq!(plan.total_steps(), 29);
+        assert!(plan.estimated_duration_secs() > 27.0 / 200.0);
+
+        assert_eq!(
+            planner.plan_absolute(Degrees(45.0), Degrees(45.0), params).unwrap_err(),
+            Error::Motion(MotionError::MoveTooShort { steps: 0, minimum: 1 })
+        );
+
+        let mut rejecting = config();
+        rejecting.limits.as_mut().unwrap().policy = LimitPolicy::Reject;
+        let planner = MotionPlanner::from_motor_config(&rejecting);
+        assert_eq!(
+            planner.plan_absolute(Degrees(18.0), Degrees(180.0), params).unwrap_err(),
+            Error::Motor(MotorError::LimitExceeded { position: 100, limit: 50 })
+        );
+    }
+
+    #[test]
+    fn test_plan_wraps_target() {
+        // 0-360° window, 200 steps; overshooting CCW arrivals by 2 steps
+        let mut wrapping = config();
+        wrapping.limits = Some(SoftLimits::new(Degrees(0.0), Degrees(360.0), LimitPolicy::Wrap));
+        let planner = MotionPlanner::from_motor_config(&wrapping);
+        let params = MoveParams::default();
+
+        let plan = planner.plan_absolute(Degrees(18.0), Degrees(450.0), params).unwrap();
+        assert_eq!((plan.target_steps, plan.wrapped_spans, plan.clamped), (50, 1, false));
+
+        let plan = planner.plan_absolute(Degrees(18.0), Degrees(90.0), params).unwrap();
+        assert_eq!((plan.target_steps, plan.wrapped_spans), (50, 0));
+
+        let plan = planner.plan_absolute(Degrees(180.0), Degrees(-90.0), params).unwrap();
+        assert_eq!((plan.target_steps, plan.wrapped_spans), (150, -1));
+
+        // Exactly on max wraps to min; the overshoot may leave the window
+        let plan = planner.plan_absolute(Degrees(18.0), Degrees(360.0), params).unwrap();
+        assert_eq!((plan.target_steps, plan.wrapped_spans), (0, 1));
+        let legs: std::vec::Vec<_> =
+            plan.legs.iter().map(|l| (l.direction, l.total_steps)).collect();
+        assert_eq!(legs, [(Direction::CounterClockwise, 12), (Direction::Clockwise, 2)]);
+
+        // Wrapping onto the current position leaves nothing to move
+        assert_eq!(
+            planner.plan_absolute(Degrees(90.0), Degrees(810.0), params).unwrap_err(),
+            Error::Motion(MotionError::MoveTooShort { steps: 0, minimum: 1 })
+        );
+    }
+
+    #[test]
+    fn test_plan_clamp_slow_zone() {
+        // Last 18° (10 steps) of clamped moves at 10%: 200 steps/sec
+        let mut slowed = config();
+        let limits = slowed.limits.take().unwrap();
+        slowed.limits = Some(limits.with_clamp_approach(Degrees(18.0), 10));
+        let planner = MotionPlanner::from_motor_config(&slowed);
+        let params = MoveParams::default();
+        let creeps = |leg: &MotionProfile| leg.cruise_interval_ns.abs_diff(5_000_000) < 10;
+
+        let plan = planner.plan_absolute(Degrees(18.0), Degrees(180.0), params).unwrap();
+        assert!(plan.clamped);
+        let legs: std::vec::Vec<_> = plan.legs.iter().map(|l| l.total_steps).collect();
+        assert_eq!(legs, [30, 10]);
+        assert!(creeps(&plan.legs[1]) && !creeps(&plan.legs[0]));
+
+        // Not clamped: full speed to the limit
+        let plan = planner.plan_absolute(Degrees(18.0), Degrees(90.0), params).unwrap();
+        assert!(!plan.clamped);
+        assert_eq!(plan.legs.len(), 1);
+
+        // With a final approach the longer stretch and slower velocity win
+        let sequence = WaypointTrajectoryBuilder::new()
+            .motor("x_axis")
+            .waypoints(&[Degrees(180.0)])
+            .final_approach(Degrees(9.0), 20)
+            .build()
+            .unwrap();
+        let plans = planner.plan_sequence(Degrees(18.0), &sequence).unwrap();
+        let legs: std::vec::Vec<_> = plans[0].legs.iter().map(|l| l.total_steps).collect();
+        assert_eq!(legs, [30, 10]);
+        assert!(creeps(&plans[0].legs[1]));
+    }
+
+    #[test]
+    fn test_plan_sequence_skips_repeated_waypoints() {
+        let sequence = WaypointTrajectoryBuilder::new()
+            .motor("x_axis")
+            .waypoints(&[Degrees(45.0), Degrees(45.0), Degrees(18.0)])
+            .dwell(50)
+            .build()
+            .unwrap();
+        let planner = MotionPlanner::new(MechanicalConstraints::from_config(&config()));
+        let plans = planner.plan_sequence(Degrees(9.0), &sequence).unwrap();
+        let targets: std::vec::Vec<_> = plans.iter().map(|p| p.target_steps).collect();
+        assert_eq!(targets, [25, 10]);
+        assert_eq!(plans[1].start_steps, 25);
+        assert_eq!(plans[1].dwell_ms, Some(50));
+    }
+}
@@ -2,27 +2,39 @@
 //!
 //! Provides absolute position tracking in steps with unit conversions.
 
-use crate::config::units::{Degrees, Steps};
+use crate::config::units::{round_to_places, Degrees, Steps};
+use crate::config::MechanicalConstraints;
+use crate::math::roundf;
 
 /// Motor position tracker.
 ///
 /// Maintains absolute position in steps and provides unit conversions.
+///
+/// # Turn counting
+///
+/// For spooling and other continuous axes, the position also splits into
+/// whole output revolutions and an angle within the current one. Turns are
+/// floored, so the angle is always in `[0°, 360°)`: −90° is turn −1 at 270°,
+/// and −360° is turn −1 at 0°.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Position {
     /// Current position in steps (from origin)
     steps: Steps,
-    /// Steps per degree for conversions
+    /// Steps per degree for conversions (negative for an inverted output)
     steps_per_degree: f32,
+    /// Steps per output revolution (magnitude), for turn counting
+    steps_per_revolution: f64,
 }
 
 impl Position {
     /// Create a new position tracker.
+    ///
+    /// Steps per revolution are derived from `steps_per_degree`, rounded to
+    /// whole steps; use [`from_constraints`](Self::from_constraints) for
+    /// exact turn counting with fractional gear ratios.
     #[inline]
     pub fn new(steps_per_degree: f32) -> Self {
-        Self {
-            steps: Steps::default(),
-            steps_per_degree,
-        }
+        Self::at(Steps::default(), steps_per_degree)
     }
 
     /// Create a position tracker at a specific position.
@@ -31,6 +43,18 @@ impl Position {
         Self {
             steps,
             steps_per_degree,
+            steps_per_revolution: roundf(steps_per_degree.abs() * 360.0) as f64,
+        }
+    }
+
+    /// Create a position tracker at the origin for a motor's constraints,
+    /// with their exact steps per output revolution.
+    #[inline]
+    pub fn from_constraints(constraints: &MechanicalConstraints) -> Self {
+        Self {
+            steps: Steps::default(),
+            steps_per_degree: constraints.signed_steps_per_degree(),
+            steps_per_revolution: constraints.steps_per_revolution_exact,
         }
     }
 
@@ -46,6 +70,13 @@ impl Position {
         self.steps.to_degrees(self.steps_per_degree)
     }
 
+    /// Get current position in degrees rounded to `decimal_places` (at most
+    /// 9), for display.
+    #[inline]
+    pub fn display_degrees(&self, decimal_places: u8) -> f32 {
+        round_to_places(self.degrees().0, decimal_places)
+    }
+
     /// Set position in steps.
     #[inline]
     pub fn set_steps(&mut self, steps: Steps) {
@@ -89,6 +120,48 @@ impl Position {
         self.steps_per_degree
     }
 
+    /// Get steps per output revolution.
+    #[inline]
+    pub fn steps_per_revolution(&self) -> f64 {
+        self.steps_per_revolution
+    }
+
+    /// Get the position in output revolutions (negative below the origin).
+    #[inline]
+    pub fn total_revolutions(&self) -> f32 {
+        (self.output_steps() as f64 / self.steps_per_revolution) as f32
+    }
+
+    /// Get the number of whole output revolutions, rounded towards negative
+    /// infinity (see [Turn counting](Self#turn-counting)).
+    pub fn whole_turns(&self) -> i64 {
+        let turns = self.output_steps() as f64 / self.steps_per_revolution;
+        let whole = turns as i64;
+        if (whole as f64) > turns {
+            whole - 1
+        } else {
+            whole
+        }
+    }
+
+    /// Get the angle within the current output revolution, in `[0°, 360°)`
+    /// (see [Turn counting](Self#turn-counting)).
+    pub fn angle_in_turn(&self) -> Degrees {
+        let into_turn =
+            self.output_steps() as f64 - self.whole_turns() as f64 * self.steps_per_revolution;
+        Degrees((into_turn * 360.0 / self.steps_per_revolution) as f32)
+    }
+
+    /// Position in steps towards positive output degrees.
+    #[inline]
+    fn output_steps(&self) -> i64 {
+        if self.steps_per_degree < 0.0 {
+            -self.steps.0
+        } else {
+            self.steps.0
+        }
+    }
+
     /// Calculate steps needed to reach a target position in degrees.
     #[inline]
     pub fn steps_to(&self, target: Degrees) -> i64 {
@@ -128,4 +201,73 @@ mod tests {
         let steps = pos.steps_to(Degrees(180.0));
         assert_eq!(steps, 900); // 1800 - 900 = 900
     }
+
+    #[test]
+    fn test_turn_counting() {
+        // 3200 steps/rev
+        let turns = |steps: i64| {
+            let pos = Position::at(Steps(steps), 3200.0 / 360.0);
+            (pos.whole_turns(), pos.angle_in_turn().0)
+        };
+        let near = |(turns, angle): (i64, f32), expected: (i64, f32)| {
+            turns == expected.0 && (angle - expected.1).abs() < 0.01
+        };
+
+        assert!(near(turns(0), (0, 0.0)));
+        assert!(near(turns(800), (0, 90.0)));
+        // Crossing 360° forwards and back
+        assert!(near(turns(3199), (0, 359.8875)));
+        assert!(near(turns(3200), (1, 0.0)));
+        assert!(near(turns(3201), (1, 0.1125)));
+        // Crossing 0° backwards and forwards: −90° is turn −1 at 270°
+        assert!(near(turns(1), (0, 0.1125)));
+        assert!(near(turns(-1), (-1, 359.8875)));
+        assert!(near(turns(-800), (-1, 270.0)));
+        assert!(near(turns(-3200), (-1, 0.0)));
+        assert!(near(turns(-3201), (-2, 359.8875)));
+
+        let pos = Position::at(Steps(-4000), 3200.0 / 360.0);
+        assert_eq!(pos.total_revolutions(), -1.25);
+        assert_eq!(pos.steps_per_revolution(), 3200.0);
+    }
+
+    #[test]
+    fn test_turns_from_constraints() {
+        use crate::config::MotorConfig;
+
+        // 200 × 7/3 = 466⅔ steps per output revolution, inverted output
+        let config: MotorConfig = toml::from_str(
+            r#"
+name = "spool"
+steps_per_revolution = 200
+microsteps = 1
+gear_ratio_numerator = 7
+gear_ratio_denominator = 3
+output_inverted = true
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+"#,
+        )
+        .unwrap();
+        let mut pos = Position::from_constraints(&MechanicalConstraints::from_config(&config));
+
+        // Three output turns are exactly 1400 steps, towards negative steps
+        pos.set_steps(Steps(-1400));
+        assert_eq!(pos.whole_turns(), 3);
+        assert!(pos.angle_in_turn().0.abs() < 1e-3);
+        pos.set_steps(Steps(-1399));
+        assert_eq!(pos.whole_turns(), 2);
+        assert!(pos.angle_in_turn().0 > 359.0);
+        pos.set_steps(Steps(1));
+        assert_eq!(pos.whole_turns(), -1);
+        assert!((pos.total_revolutions() + 3.0 / 1400.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_display_degrees() {
+        let pos = Position::at(Steps(801), 3200.0 / 360.0);
+        assert_eq!(pos.display_degrees(2), 90.11);
+        assert_eq!(pos.display_degrees(0), 90.0);
+        assert_eq!(Position::at(Steps(-801), 3200.0 / 360.0).display_degrees(1), -90.1);
+    }
 }
@@ -0,0 +1,425 @@
+//! Pre-flight check of every trajectory on the registered motors (alloc).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::config::{MechanicalConstraints, TrajectoryConfig};
+use crate::error::{ConfigError, Error, MotorError, TrajectoryError, ValidationReport};
+
+use super::MotorSystem;
+
+/// Dry-run outcome of one trajectory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DryRunResult {
+    /// Trajectory name.
+    pub trajectory: String,
+    /// Steps the move takes from the motor's recorded position.
+    pub total_steps: u32,
+    /// Estimated duration in seconds, including the dwell.
+    pub duration_secs: f32,
+    /// Why the trajectory can't run (`None` if it is feasible).
+    pub error: Option<Error>,
+}
+
+impl DryRunResult {
+    /// Check if the trajectory can run.
+    pub fn is_feasible(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Result of [`MotorSystem::preflight_check`] and [`MotorSystem::preflight`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PreflightReport {
+    /// Dry-run results per registered motor, in registration order.
+    pub motor_results: Vec<(String, Vec<DryRunResult>)>,
+    /// Whether any trajectory is infeasible.
+    pub any_infeasible: bool,
+    /// System-wide problems found by [`MotorSystem::preflight`] (always
+    /// empty from `preflight_check`).
+    pub problems: ValidationReport,
+}
+
+impl PreflightReport {
+    /// Check that every trajectory is feasible and no problem was found.
+    pub fn passed(&self) -> bool {
+        !self.any_infeasible && self.problems.is_empty()
+    }
+
+    /// Get the results for one motor.
+    pub fn results(&self, motor: &str) -> Option<&[DryRunResult]> {
+        self.motor_results
+            .iter()
+            .find(|(name, _)| name == motor)
+            .map(|(_, results)| results.as_slice())
+    }
+
+    /// Iterate over the infeasible trajectories with their motor names.
+    pub fn infeasible(&self) -> impl Iterator<Item = (&str, &DryRunResult)> {
+        self.motor_results.iter().flat_map(|(motor, results)| {
+            results.iter().filter(|r| !r.is_feasible()).map(move |r| (motor.as_str(), r))
+        })
+    }
+
+    /// Print the report as a table (see the `Display` impl).
+    #[cfg(feature = "std")]
+    pub fn print_summary(&self) {
+        std::print!("{}", self);
+    }
+}
+
+/// One row per trajectory: motor, trajectory, steps, duration and status,
+/// then one line per system problem, e.g. for a boot log.
+impl fmt::Display for PreflightReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<16} {:<24} {:>10} {:>9}  status",
+            "motor", "trajectory", "steps", "time (s)"
+        )?;
+        for (motor, results) in &self.motor_results {
+            for result in results {
+                write!(
+                    f,
+                    "{:<16} {:<24} {:>10} {:>9.3}  ",
+                    motor, result.trajectory, result.total_steps, result.duration_secs
+                )?;
+                match &result.error {
+                    None => writeln!(f, "ok")?,
+                    Some(e) => writeln!(f, "FAIL ({}): {}", e.code(), e)?,
+                }
+            }
+        }
+        for e in self.problems.errors() {
+            writeln!(f, "system: FAIL ({}): {}", e.code(), e)?;
+        }
+        if self.problems.omitted() > 0 {
+            writeln!(f, "system: {} more problems omitted", self.problems.omitted())?;
+        }
+        match (self.infeasible().count(), self.problems.len()) {
+            (0, 0) => writeln!(f, "all trajectories feasible"),
+            (count, 0) => writeln!(f, "{} infeasible trajectories", count),
+            (count, problems) => writeln!(
+                f,
+                "{} infeasible trajectories, {} system problems",
+                count, problems
+            ),
+        }
+    }
+}
+
+impl MotorSystem {
+    /// Dry-run every trajectory of every registered motor.
+    ///
+    /// Each trajectory is checked against the motor's registered constraints
+    /// and planned from the motor's recorded position, without touching
+    /// hardware, e.g. to audit a job before a long production run. Problems
+    /// are reported per trajectory rather than stopping the check.
+    pub fn preflight_check(&self) -> PreflightReport {
+        let mut report = PreflightReport::default();
+        for motor in self.registered_motor_names() {
+            let Some(constraints) = self.registered_constraints(motor) else {
+                continue;
+            };
+            let from = self.recorded_position(motor).map_or(0, |steps| steps.0);
+            let results: Vec<DryRunResult> = self
+                .trajectories()
+                .trajectories_for_motor(motor)
+                .map(|(name, trajectory)| dry_run(name, trajectory, constraints, from))
+                .collect();
+            report.any_infeasible |= results.iter().any(|r| !r.is_feasible());
+            report.motor_results.push((String::from(motor), results));
+        }
+        report
+    }
+
+    /// Cross-check the whole system against the registered hardware, e.g.
+    /// once at startup before the machine may run.
+    ///
+    /// On top of [`preflight_check`](Self::preflight_check), reports in
+    /// [`PreflightReport::problems`]:
+    ///
+    /// - trajectories and waypoint sequences whose motor is not registered
+    /// - registered motors whose constraints no longer match the
+    ///   configuration, e.g. after a reload changed their step resolution
+    /// - waypoint sequences infeasible for their motor
+    /// - park and required previous trajectories that don't resolve to a
+    ///   trajectory of the same motor
+    ///
+    /// # Errors
+    ///
+    /// Returns the full report if anything was found.
+    pub fn preflight(&self) -> core::result::Result<(), PreflightReport> {
+        let mut report = self.preflight_check();
+        let problems = &mut report.problems;
+        let name = |s: &str| heapless::String::try_from(s).unwrap_or_default();
+
+        for motor in self.registered_motor_names() {
+            if self.registered_constraints(motor) != self.constraints(motor).as_ref() {
+                problems.push(Error::Motor(MotorError::StaleConstraints(name(motor))));
+            }
+        }
+
+        let unregistered = |user: &str, motor: &heapless::String<32>| {
+            if self.is_registered(motor) {
+                None
+            } else if self.has_motor(motor) {
+                Some(Error::Motor(MotorError::NotRegistered {
+                    trajectory: name(user),
+                    motor: motor.clone(),
+                }))
+            } else {
+                Some(Error::Trajectory(TrajectoryError::MotorNotFound {
+                    trajectory: name(user),
+                    motor: motor.clone(),
+                }))
+            }
+        };
+        for (trajectory_name, trajectory) in self.trajectories().iter() {
+            if let Some(e) = unregistered(trajectory_name, &trajectory.motor) {
+                problems.push(e);
+            }
+            if let Some(previous) = &trajectory.requires_previous {
+                let found = self.trajectories().get(previous);
+                if !found.is_some_and(|p| p.motor == trajectory.motor) {
+                    problems.push(Error::Config(ConfigError::TrajectoryNotFound(
+                        previous.clone(),
+                    )));
+                }
+            }
+        }
+        for (sequence_name, sequence) in self.config().sequences.iter() {
+            if let Some(e) = unregistered(sequence_name, &sequence.motor) {
+                problems.push(e);
+            } else if let Some(constraints) = self.registered_constraints(&sequence.motor) {
+                if let Err(e) = sequence.check_feasibility(constraints) {
+                    problems.push(e);
+                }
+            }
+        }
+
+        for (motor, park) in self.park_plan() {
+            let found = self.trajectories().get(park);
+            if !found.is_some_and(|t| t.motor.as_str() == motor) {
+                problems.push(Error::Config(ConfigError::TrajectoryNotFound(name(park))));
+            }
+        }
+
+        if report.passed() {
+            Ok(())
+        } else {
+            Err(report)
+        }
+    }
+}
+
+/// Check and plan one trajectory from `from_steps`.
+fn dry_run(
+    name: &str,
+    trajectory: &TrajectoryConfig,
+    constraints: &MechanicalConstraints,
+    from_steps: i64,
+) -> DryRunResult {
+    let mut result = DryRunResult {
+        trajectory: String::from(name),
+        total_steps: 0,
+        duration_secs: 0.0,
+        error: trajectory.check_feasibility(constraints).err(),
+    };
+    match trajectory.plan_profile(constraints, from_steps) {
+        Ok(profile) => {
+            result.total_steps = profile.total_steps;
+            result.duration_secs = profile.estimated_duration_secs();
+        }
+        Err(e) => {
+            result.error.get_or_insert(e);
+        }
+    }
+    if let Some(dwell_ms) = trajectory.dwell_ms {
+        result.duration_secs += dwell_ms as f32 / 1000.0;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SystemConfig;
+    use crate::error::TrajectoryError;
+    use crate::test_util::{NoopDelay, NoopPin};
+
+    const CONFIG: &str = r#"
+[motors.x]
+name = "X"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[motors.x.limits]
+min_degrees = -90.0
+max_degrees = 180.0
+
+[motors.y]
+name = "Y"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[motors.z]
+name = "Z"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+
+[trajectories.x_far]
+motor = "x"
+target_degrees = 360.0
+
+[trajectories.x_short]
+motor = "x"
+target_degrees = 9.0
+dwell_ms = 500
+
+[trajectories.y_home]
+motor = "y"
+target_degrees = 0.0
+
+[trajectories.z_unregistered]
+motor = "z"
+target_degrees = 90.0
+"#;
+
+    #[test]
+    fn test_preflight_check() {
+        let config: SystemConfig = toml::from_str(CONFIG).unwrap();
+        let mut system = MotorSystem::from_config(config);
+        system.register_motor("x", NoopPin, NoopPin, NoopDelay).unwrap();
+        let y = system.register_motor("y", NoopPin, NoopPin, NoopDelay).unwrap();
+        let y = y.move_to_blocking(crate::Degrees(36.0)).ok().unwrap();
+        system.record_position(&y).unwrap();
+
+        let report = system.preflight_check();
+        assert!(report.any_infeasible);
+        assert_eq!(report.motor_results.len(), 2);
+        assert!(report.results("z").is_none());
+
+        let x = report.results("x").unwrap();
+        assert_eq!(x.len(), 2);
+        assert_eq!(x[0].trajectory, "x_far");
+        assert_eq!(
+            x[0].error,
+            Some(Error::Trajectory(TrajectoryError::TargetExceedsLimits {
+                target: 360.0,
+                min: -90.0,
+                max: 180.0,
+            }))
+        );
+        assert!(x[1].is_feasible());
+        assert_eq!(x[1].total_steps, 5);
+        assert!(x[1].duration_secs > 0.5);
+
+        // Planned from the recorded position
+        let y = report.results("y").unwrap();
+        assert!(y[0].is_feasible());
+        assert_eq!(y[0].total_steps, 20);
+
+        let infeasible: std::vec::Vec<_> =
+            report.infeasible().map(|(motor, r)| (motor, r.trajectory.as_str())).collect();
+        assert_eq!(infeasible, [("x", "x_far")]);
+
+        let table = std::format!("{}", report);
+        assert_eq!(table.lines().count(), 1 + 3 + 1);
+        assert!(table.lines().nth(1).unwrap().contains("FAIL (401)"), "{}", table);
+        assert!(table.ends_with("1 infeasible trajectories\n"));
+    }
+
+    #[test]
+    fn test_preflight() {
+        let config: SystemConfig = toml::from_str(CONFIG).unwrap();
+        let mut system = MotorSystem::from_config(config);
+        system.register_motor("x", NoopPin, NoopPin, NoopDelay).unwrap();
+        system.register_motor("y", NoopPin, NoopPin, NoopDelay).unwrap();
+
+        let report = system.preflight().unwrap_err();
+        assert!(!report.passed());
+        let infeasible: std::vec::Vec<_> =
+            report.infeasible().map(|(motor, r)| (motor, r.trajectory.as_str())).collect();
+        assert_eq!(infeasible, [("x", "x_far")]);
+        assert_eq!(
+            report.problems.errors(),
+            [Error::Motor(MotorError::NotRegistered {
+                trajectory: heapless::String::try_from("z_unregistered").unwrap(),
+                motor: heapless::String::try_from("z").unwrap(),
+            })]
+        );
+
+        let log = std::format!("{}", report);
+        assert!(log.contains("system: FAIL (211): Motor error: 'z_unregistered'"), "{}", log);
+        assert!(log.ends_with("1 infeasible trajectories, 1 system problems\n"));
+    }
+
+    #[test]
+    fn test_preflight_detects_drift_and_dangling_park() {
+        let motor = |microsteps: u16, park: &str| {
+            std::format!(
+                r#"
+[motors.x]
+name = "X"
+steps_per_revolution = 200
+microsteps = {microsteps}
+max_velocity_deg_per_sec = 360.0
+max_acceleration_deg_per_sec2 = 720.0
+park_trajectory = "{park}"
+
+[trajectories.x_rest]
+motor = "x"
+target_degrees = 0.0
+"#
+            )
+        };
+        let config = |text: std::string::String| toml::from_str::<SystemConfig>(&text).unwrap();
+
+        let mut system = MotorSystem::from_config(config(motor(1, "x_rest")));
+        system.register_motor("x", NoopPin, NoopPin, NoopDelay).unwrap();
+        assert_eq!(system.preflight(), Ok(()));
+
+        // The registered motor still steps at the old resolution
+        let reload = system.reload_config(config(motor(2, "x_rest"))).unwrap();
+        assert!(reload.needs_rebuild());
+        let report = system.preflight().unwrap_err();
+        assert!(!report.any_infeasible);
+        assert_eq!(
+            report.problems.errors(),
+            [Error::Motor(MotorError::StaleConstraints(heapless::String::try_from("x").unwrap()))]
+        );
+
+        // Not validated on the way in
+        let mut system = MotorSystem::from_config(config(motor(1, "x_park")));
+        system.register_motor("x", NoopPin, NoopPin, NoopDelay).unwrap();
+        let report = system.preflight().unwrap_err();
+        assert_eq!(
+            report.problems.errors(),
+            [Error::Config(ConfigError::TrajectoryNotFound(
+                heapless::String::try_from("x_park").unwrap()
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_preflight_check_all_feasible() {
+        let config: SystemConfig = toml::from_str(CONFIG).unwrap();
+        let mut system = MotorSystem::from_config(config);
+        assert_eq!(system.preflight_check(), PreflightReport::default());
+
+        system.register_motor("y", NoopPin, NoopPin, NoopDelay).unwrap();
+        let report = system.preflight_check();
+        assert!(!report.any_infeasible);
+        // Already at the target: nothing to do, which is not an error
+        assert_eq!(report.results("y").unwrap()[0].total_steps, 0);
+        assert!(std::format!("{}", report).ends_with("all trajectories feasible\n"));
+    }
+}
@@ -0,0 +1,180 @@
+//! Helpers for results that hand the motor back on error.
+//!
+//! State transitions consume the motor, so a failed transition returns it
+//! with the error as `Err((motor, error))`. [`MotorResultExt`] covers the
+//! common ways of handling that tuple.
+
+use crate::error::Error;
+
+/// Combinators for `Result<T, (M, Error)>`, the return type of fallible
+/// [`StepperMotor`](super::StepperMotor) transitions.
+///
+/// ```rust,ignore
+/// use stepper_motion::MotorResultExt;
+///
+/// // Give up on the motor when the move fails
+/// let moving = motor.move_to(Degrees(90.0)).discarding_motor()?;
+///
+/// // Or fall back to a safe position with the motor handed back
+/// let moving = motor
+///     .move_to(target)
+///     .retry_with(|motor, _| motor.move_to(Degrees(0.0)))
+///     .discarding_motor()?;
+/// ```
+pub trait MotorResultExt<T, M>: Sized {
+    /// The error, if the transition failed.
+    fn error(&self) -> Option<&Error>;
+
+    /// Split into the new-state motor on success, or the old-state motor and
+    /// the error on failure.
+    fn into_parts(self) -> (Option<T>, Option<(M, Error)>);
+
+    /// Map the motor handed back on failure, keeping the error.
+    fn map_motor<N>(self, f: impl FnOnce(M) -> N) -> Result<T, (N, Error)>;
+
+    /// On failure, call `f` once with the handed-back motor and the error,
+    /// e.g. to try a fallback move.
+    fn retry_with(
+        self,
+        f: impl FnOnce(M, Error) -> Result<T, (M, Error)>,
+    ) -> Result<T, (M, Error)>;
+
+    /// Drop the handed-back motor, keeping only the error, for use with `?`.
+    ///
+    /// The motor's pins and delay are dropped with it.
+    fn discarding_motor(self) -> Result<T, Error>;
+}
+
+impl<T, M> MotorResultExt<T, M> for Result<T, (M, Error)> {
+    fn error(&self) -> Option<&Error> {
+        self.as_ref().err().map(|(_, e)| e)
+    }
+
+    fn into_parts(self) -> (Option<T>, Option<(M, Error)>) {
+        match self {
+            Ok(value) => (Some(value), None),
+            Err(failed) => (None, Some(failed)),
+        }
+    }
+
+    fn map_motor<N>(self, f: impl FnOnce(M) -> N) -> Result<T, (N, Error)> {
+        self.map_err(|(motor, e)| (f(motor), e))
+    }
+
+    fn retry_with(
+        self,
+        f: impl FnOnce(M, Error) -> Result<T, (M, Error)>,
+    ) -> Result<T, (M, Error)> {
+        self.or_else(|(motor, e)| f(motor, e))
+    }
+
+    fn discarding_motor(self) -> Result<T, Error> {
+        self.map_err(|(_, e)| e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::units::Degrees;
+    use crate::error::{MotionError, MotorError};
+    use crate::motor::StepperMotorBuilder;
+    use crate::test_util::{test_motor, NoopDelay, NoopPin};
+
+    fn limited_motor() -> crate::test_util::TestMotor {
+        // 200 steps/rev, limits at 5 and 50 steps, rejecting moves beyond them
+        let config: crate::config::MotorConfig = toml::from_str(
+            r#"
+name = "x_axis"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 3600.0
+max_acceleration_deg_per_sec2 = 36000.0
+
+[limits]
+min_degrees = 9.0
+max_degrees = 90.0
+"#,
+        )
+        .unwrap();
+        StepperMotorBuilder::new()
+            .from_motor_config(&config)
+            .step_pin(NoopPin)
+            .dir_pin(NoopPin)
+            .delay(NoopDelay)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_error_and_into_parts() {
+        let result = test_motor().move_to(Degrees(18.0));
+        assert!(result.error().is_none());
+        let (moving, failed) = result.into_parts();
+        assert!(failed.is_none());
+        let motor = moving.unwrap().run_to_completion().unwrap();
+
+        let result = motor.move_to(Degrees(18.0));
+        let too_short = Error::Motion(MotionError::MoveTooShort { steps: 0, minimum: 1 });
+        assert_eq!(result.error(), Some(&too_short));
+        let (moving, failed) = result.into_parts();
+        assert!(moving.is_none());
+        let (motor, e) = failed.unwrap();
+        assert_eq!(e, too_short);
+        assert_eq!(motor.position_steps().0, 10);
+    }
+
+    #[test]
+    fn test_map_motor() {
+        let position = |motor: crate::test_util::TestMotor| motor.position_steps().0;
+        assert!(test_motor().move_to(Degrees(18.0)).map_motor(position).is_ok());
+
+        let err = limited_motor().move_to(Degrees(180.0)).map_motor(position).err();
+        let limit = Error::Motor(MotorError::LimitExceeded { position: 100, limit: 50 });
+        assert_eq!(err, Some((0, limit)));
+    }
+
+    #[test]
+    fn test_retry_with() {
+        // Succeeds first time: the closure is not called
+        let moving = test_motor()
+            .move_to(Degrees(18.0))
+            .retry_with(|_, _| unreachable!())
+            .ok()
+            .unwrap();
+        assert_eq!(moving.run_to_completion().unwrap().position_steps().0, 10);
+
+        // Falls back to the limit
+        let moving = limited_motor()
+            .move_to(Degrees(180.0))
+            .retry_with(|motor, e| {
+                assert!(matches!(e, Error::Motor(MotorError::LimitExceeded { .. })));
+                motor.move_to(Degrees(90.0))
+            })
+            .ok()
+            .unwrap();
+        assert_eq!(moving.run_to_completion().unwrap().position_steps().0, 50);
+
+        // A failing retry hands the motor back again
+        let (motor, e) = limited_motor()
+            .move_to(Degrees(180.0))
+            .retry_with(|motor, _| motor.move_to(Degrees(-90.0)))
+            .err()
+            .unwrap();
+        assert_eq!(e, Error::Motor(MotorError::LimitExceeded { position: -50, limit: 5 }));
+        assert_eq!(motor.position_steps().0, 0);
+    }
+
+    #[test]
+    fn test_discarding_motor() {
+        fn run(motor: crate::test_util::TestMotor, target: f32) -> crate::error::Result<i64> {
+            let moving = motor.move_to(Degrees(target)).discarding_motor()?;
+            Ok(moving.run_to_completion()?.position_steps().0)
+        }
+        assert_eq!(run(test_motor(), 18.0), Ok(10));
+        assert_eq!(
+            run(limited_motor(), 180.0),
+            Err(Error::Motor(MotorError::LimitExceeded { position: 100, limit: 50 }))
+        );
+    }
+}
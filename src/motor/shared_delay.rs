@@ -0,0 +1,221 @@
+//! Delay providers shared between motors (`shared-delay` feature).
+//!
+//! A motor owns its delay, but many HALs have a single delay (e.g. one built
+//! on SysTick) that can't be cloned. [`SharedDelayCell`] owns such a delay and
+//! hands out [`SharedDelay`] handles, one per motor. Each delay call runs in a
+//! critical section, so the handles may live in different contexts; note that
+//! interrupts are held off for the length of the delay.
+//!
+//! A motor keeps its timing state (intervals, overhead compensation, timing
+//! statistics) itself, so motors sharing a delay may step in any interleaving.
+//! Each call simply waits its own time. For a motor used for a bounded stretch
+//! of code, a plain `&mut delay` also works, as embedded-hal implements
+//! `DelayNs` for `&mut D`.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use embedded_hal::delay::DelayNs;
+
+/// Owner of a delay shared by several [`SharedDelay`] handles.
+///
+/// `new` is `const`, so the cell can be placed in a `static`.
+///
+/// Three motors from one delay:
+///
+/// ```rust
+/// # use core::convert::Infallible;
+/// # use embedded_hal::delay::DelayNs;
+/// # use embedded_hal::digital::{ErrorType, OutputPin};
+/// # struct Pin;
+/// # impl ErrorType for Pin { type Error = Infallible; }
+/// # impl OutputPin for Pin {
+/// #     fn set_low(&mut self) -> Result<(), Infallible> { Ok(()) }
+/// #     fn set_high(&mut self) -> Result<(), Infallible> { Ok(()) }
+/// # }
+/// # struct SysTickDelay;
+/// # impl DelayNs for SysTickDelay { fn delay_ns(&mut self, _ns: u32) {} }
+/// use stepper_motion::motor::{MotorSystem, SharedDelayCell};
+/// use stepper_motion::{Degrees, SystemConfig};
+///
+/// const CONFIG: &str = r#"
+/// [motors.x]
+/// name = "X"
+/// steps_per_revolution = 200
+/// microsteps = 16
+/// max_velocity_deg_per_sec = 360.0
+/// max_acceleration_deg_per_sec2 = 720.0
+///
+/// [motors.y]
+/// name = "Y"
+/// steps_per_revolution = 200
+/// microsteps = 16
+/// max_velocity_deg_per_sec = 360.0
+/// max_acceleration_deg_per_sec2 = 720.0
+///
+/// [motors.z]
+/// name = "Z"
+/// steps_per_revolution = 200
+/// microsteps = 16
+/// max_velocity_deg_per_sec = 360.0
+/// max_acceleration_deg_per_sec2 = 720.0
+/// "#;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let config: SystemConfig = toml::from_str(CONFIG)?;
+/// let mut system = MotorSystem::from_config(config);
+///
+/// let delay = SharedDelayCell::new(SysTickDelay);
+/// let x = system.register_motor("x", Pin, Pin, delay.delay())?;
+/// let y = system.register_motor("y", Pin, Pin, delay.delay())?;
+/// let z = system.register_motor("z", Pin, Pin, delay.delay())?;
+///
+/// let x = x.move_to_blocking(Degrees(90.0)).map_err(|(_, e)| e)?;
+/// # let _ = (x, y, z);
+/// # Ok(())
+/// # }
+/// ```
+pub struct SharedDelayCell<D> {
+    inner: Mutex<RefCell<D>>,
+}
+
+impl<D> SharedDelayCell<D> {
+    /// Wrap `delay` for sharing.
+    pub const fn new(delay: D) -> Self {
+        Self {
+            inner: Mutex::new(RefCell::new(delay)),
+        }
+    }
+
+    /// Get a handle that delays through the shared provider.
+    pub fn delay(&self) -> SharedDelay<'_, D> {
+        SharedDelay { cell: self }
+    }
+
+    /// Release the delay.
+    pub fn into_inner(self) -> D {
+        self.inner.into_inner().into_inner()
+    }
+}
+
+/// Handle to a [`SharedDelayCell`], usable as a `DelayNs`.
+///
+/// Handles are `Copy`; give one to each motor.
+pub struct SharedDelay<'a, D> {
+    cell: &'a SharedDelayCell<D>,
+}
+
+impl<D> Clone for SharedDelay<'_, D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D> Copy for SharedDelay<'_, D> {}
+
+impl<D: DelayNs> SharedDelay<'_, D> {
+    fn with<R>(&self, f: impl FnOnce(&mut D) -> R) -> R {
+        critical_section::with(|cs| f(&mut self.cell.inner.borrow_ref_mut(cs)))
+    }
+}
+
+impl<D: DelayNs> DelayNs for SharedDelay<'_, D> {
+    fn delay_ns(&mut self, ns: u32) {
+        self.with(|delay| delay.delay_ns(ns));
+    }
+
+    // Forwarded so the provider's own (possibly more precise) versions are used
+    fn delay_us(&mut self, us: u32) {
+        self.with(|delay| delay.delay_us(us));
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.with(|delay| delay.delay_ms(ms));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::units::{Degrees, DegreesPerSec, DegreesPerSecSquared, Microsteps};
+    use crate::motor::StepperMotorBuilder;
+    use crate::test_util::{event_builder, Event, EventLog, EventDelay, EventPin};
+
+    /// Delay events in the log from index `from` on.
+    fn delays_since(log: &EventLog, from: usize) -> std::vec::Vec<Event> {
+        log.borrow()[from..]
+            .iter()
+            .filter(|e| matches!(e, Event::DelayNs(_) | Event::DelayUs(_) | Event::DelayMs(_)))
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_interleaved_motors_keep_their_timing() {
+        // Delays of each move on its own
+        let solo = |target: f32| {
+            let log = EventLog::default();
+            let motor = event_builder(&log).build().unwrap();
+            motor.move_to_blocking(Degrees(target)).ok().unwrap();
+            delays_since(&log, 0)
+        };
+        let (solo_a, solo_b) = (solo(36.0), solo(-54.0));
+
+        let log = EventLog::default();
+        let cell = SharedDelayCell::new(EventDelay::new(&log));
+        let shared = || {
+            StepperMotorBuilder::new()
+                .name("x_axis")
+                .step_pin(EventPin::step(&log))
+                .dir_pin(EventPin::dir(&log))
+                .delay(cell.delay())
+                .steps_per_revolution(200)
+                .microsteps(Microsteps::FULL)
+                .max_velocity(DegreesPerSec(3600.0))
+                .max_acceleration(DegreesPerSecSquared(36000.0))
+                .build()
+                .unwrap()
+        };
+        let mut a = shared().move_to(Degrees(36.0)).ok().unwrap();
+        let mut b = shared().move_to(Degrees(-54.0)).ok().unwrap();
+
+        // Alternate steps, attributing each call's delays to its motor
+        let (mut delays_a, mut delays_b) = (std::vec::Vec::new(), std::vec::Vec::new());
+        let (mut done_a, mut done_b) = (false, false);
+        while !(done_a && done_b) {
+            if !done_a {
+                let from = log.borrow().len();
+                done_a = a.step().unwrap();
+                delays_a.extend(delays_since(&log, from));
+            }
+            if !done_b {
+                let from = log.borrow().len();
+                done_b = b.step().unwrap();
+                delays_b.extend(delays_since(&log, from));
+            }
+        }
+        let (a, b) = (a.finish(), b.finish());
+
+        assert_eq!(delays_a, solo_a);
+        assert_eq!(delays_b, solo_b);
+        assert_eq!(a.position_steps().0, 20);
+        assert_eq!(b.position_steps().0, -30);
+
+        let _delay: EventDelay = cell.into_inner();
+    }
+
+    #[test]
+    fn test_forwards_each_unit() {
+        let log = EventLog::default();
+        let cell = SharedDelayCell::new(EventDelay::new(&log));
+        let (mut first, mut second) = (cell.delay(), cell.delay());
+
+        first.delay_ns(500);
+        second.delay_us(20);
+        first.delay_ms(3);
+        assert_eq!(
+            *log.borrow(),
+            [Event::DelayNs(500), Event::DelayUs(20), Event::DelayMs(3)]
+        );
+    }
+}
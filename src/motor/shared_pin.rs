@@ -0,0 +1,208 @@
+//! Output pins shared between motors (`shared-pin` feature).
+//!
+//! Some driver boards route one DIR line to several drivers and select the
+//! active one with an enable mux. [`SharedPinCell`] owns such a pin and hands
+//! out [`SharedPin`] handles, one per motor. Every write goes through a
+//! critical section, so the handles may live in different contexts.
+//!
+//! A motor normally skips the DIR write when its direction is unchanged. With
+//! a shared line another motor may have changed the level in between, so the
+//! cell counts level changes and a motor built with
+//! [`StepperMotorBuilder::shared_dir_pin`](super::StepperMotorBuilder::shared_dir_pin)
+//! rewrites the pin whenever the count has moved since its own last write.
+//! Motors sharing a DIR line must not move at the same time.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use embedded_hal::digital::{ErrorType, OutputPin};
+
+/// Owner of a pin shared by several [`SharedPin`] handles.
+///
+/// `new` is `const`, so the cell can be placed in a `static`.
+pub struct SharedPinCell<P> {
+    inner: Mutex<RefCell<Shared<P>>>,
+}
+
+struct Shared<P> {
+    pin: P,
+    /// Last level written (`None` until the first write).
+    level: Option<bool>,
+    /// Number of level changes so far (wrapping).
+    generation: u32,
+}
+
+impl<P> SharedPinCell<P> {
+    /// Wrap `pin` for sharing.
+    pub const fn new(pin: P) -> Self {
+        Self {
+            inner: Mutex::new(RefCell::new(Shared {
+                pin,
+                level: None,
+                generation: 0,
+            })),
+        }
+    }
+
+    /// Get a handle that drives the shared pin.
+    pub fn pin(&self) -> SharedPin<'_, P> {
+        SharedPin { cell: self }
+    }
+
+    /// Number of level changes so far (wrapping).
+    pub fn generation(&self) -> u32 {
+        critical_section::with(|cs| self.inner.borrow_ref(cs).generation)
+    }
+
+    /// Release the pin.
+    pub fn into_inner(self) -> P {
+        self.inner.into_inner().into_inner().pin
+    }
+}
+
+/// Handle to a [`SharedPinCell`], usable as an `OutputPin`.
+///
+/// Handles are `Copy`; give one to each motor's builder.
+pub struct SharedPin<'a, P> {
+    cell: &'a SharedPinCell<P>,
+}
+
+impl<P> Clone for SharedPin<'_, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for SharedPin<'_, P> {}
+
+impl<P> SharedPin<'_, P> {
+    /// Number of level changes of the underlying pin so far (wrapping).
+    pub fn generation(&self) -> u32 {
+        self.cell.generation()
+    }
+}
+
+impl<P: OutputPin> SharedPin<'_, P> {
+    fn write(&mut self, high: bool) -> Result<(), P::Error> {
+        critical_section::with(|cs| {
+            let mut shared = self.cell.inner.borrow_ref_mut(cs);
+            if high {
+                shared.pin.set_high()?;
+            } else {
+                shared.pin.set_low()?;
+            }
+            if shared.level != Some(high) {
+                shared.level = Some(high);
+                shared.generation = shared.generation.wrapping_add(1);
+            }
+            Ok(())
+        })
+    }
+}
+
+impl<P: OutputPin> ErrorType for SharedPin<'_, P> {
+    type Error = P::Error;
+}
+
+impl<P: OutputPin> OutputPin for SharedPin<'_, P> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.write(false)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.write(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::units::Degrees;
+    use crate::config::SystemConfig;
+    use crate::motor::MotorSystem;
+    use crate::test_util::{Event, EventLog, EventDelay, EventPin};
+
+    const CONFIG: &str = r#"
+[motors.a]
+name = "A"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 3600.0
+max_acceleration_deg_per_sec2 = 36000.0
+
+[motors.b]
+name = "B"
+steps_per_revolution = 200
+microsteps = 1
+max_velocity_deg_per_sec = 3600.0
+max_acceleration_deg_per_sec2 = 36000.0
+"#;
+
+    /// DIR writes in the log, as `true` for high.
+    fn dir_writes(log: &EventLog) -> std::vec::Vec<bool> {
+        log.borrow()
+            .iter()
+            .filter_map(|e| match e {
+                Event::DirHigh => Some(true),
+                Event::DirLow => Some(false),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_shared_dir_rewritten_after_other_motor() {
+        let config: SystemConfig = toml::from_str(CONFIG).unwrap();
+        let mut system = MotorSystem::from_config(config);
+        let log = EventLog::default();
+        let dir = SharedPinCell::new(EventPin::dir(&log));
+        let delay = || EventDelay::new(&log);
+
+        let a = system
+            .register_motor_shared_dir("a", EventPin::step(&log), &dir, delay())
+            .unwrap();
+        let b = system
+            .register_motor_shared_dir("b", EventPin::step(&log), &dir, delay())
+            .unwrap();
+
+        // A forward, A forward again: the second move keeps DIR
+        let a = a.move_to_blocking(Degrees(18.0)).ok().unwrap();
+        let a = a.move_to_blocking(Degrees(36.0)).ok().unwrap();
+        assert_eq!(dir_writes(&log), [true]);
+        assert_eq!(dir.generation(), 1);
+
+        // B backward flips the shared line
+        let b = b.move_to_blocking(Degrees(-18.0)).ok().unwrap();
+        assert_eq!(dir_writes(&log), [true, false]);
+
+        // A forward again: its own direction is unchanged, but the line isn't
+        let a = a.move_to_blocking(Degrees(54.0)).ok().unwrap();
+        assert_eq!(dir_writes(&log), [true, false, true]);
+
+        // B backward again must restore its level too
+        let b = b.move_to_blocking(Degrees(-36.0)).ok().unwrap();
+        assert_eq!(dir_writes(&log), [true, false, true, false]);
+        assert_eq!(dir.generation(), 4);
+
+        assert_eq!(a.position_steps().0, 30);
+        assert_eq!(b.position_steps().0, -20);
+        let steps = log.borrow().iter().filter(|e| **e == Event::StepHigh).count();
+        assert_eq!(steps, 30 + 20);
+    }
+
+    #[test]
+    fn test_generation_counts_level_changes() {
+        let log = EventLog::default();
+        let cell = SharedPinCell::new(EventPin::dir(&log));
+        let (mut first, mut second) = (cell.pin(), cell.pin());
+
+        first.set_high().unwrap();
+        second.set_high().unwrap();
+        assert_eq!(first.generation(), 1);
+        second.set_low().unwrap();
+        assert_eq!(first.generation(), 2);
+
+        let _pin: EventPin = cell.into_inner();
+        assert_eq!(log.borrow().len(), 3);
+    }
+}
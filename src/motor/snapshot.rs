@@ -0,0 +1,215 @@
+//! Saved motor positions of a [`MotorSystem`](super::MotorSystem).
+//!
+//! Under `std` a [`SystemSnapshot`] is serde-serializable, e.g. to a JSON
+//! file. For flash or EEPROM it encodes to a fixed 256-byte record:
+//!
+//! | Bytes | Field |
+//! |-------|-------|
+//! | 0 | format version (1) |
+//! | 1 | number of motors |
+//! | 2.. | per motor: name length, name, position in steps (`i64`, little-endian) |
+//! | 252..256 | CRC-32 of bytes 0..252 (little-endian) |
+//!
+//! Unused bytes are zero.
+
+use heapless::{String, Vec};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MotorError, Result};
+
+/// Size of an encoded [`SystemSnapshot`] in bytes.
+pub const SNAPSHOT_BYTES: usize = 256;
+
+const SNAPSHOT_VERSION: u8 = 1;
+
+/// Bytes before the CRC.
+const PAYLOAD_BYTES: usize = SNAPSHOT_BYTES - 4;
+
+/// Motor positions in steps, by motor name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SystemSnapshot {
+    motors: Vec<(String<32>, i64), 8>,
+}
+
+impl SystemSnapshot {
+    /// Create an empty snapshot.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the position of `name`, replacing any earlier one.
+    ///
+    /// Returns `false` if the snapshot already holds 8 other motors or the
+    /// name is longer than 32 bytes.
+    pub fn set(&mut self, name: &str, steps: i64) -> bool {
+        if let Some(entry) = self.motors.iter_mut().find(|(n, _)| n.as_str() == name) {
+            entry.1 = steps;
+            return true;
+        }
+        match String::try_from(name) {
+            Ok(name) => self.motors.push((name, steps)).is_ok(),
+            Err(()) => false,
+        }
+    }
+
+    /// Saved position of `name` in steps.
+    pub fn position(&self, name: &str) -> Option<i64> {
+        self.motors
+            .iter()
+            .find(|(n, _)| n.as_str() == name)
+            .map(|&(_, steps)| steps)
+    }
+
+    /// Iterate over `(name, steps)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, i64)> {
+        self.motors.iter().map(|(name, steps)| (name.as_str(), *steps))
+    }
+
+    /// Number of motors in the snapshot.
+    pub fn len(&self) -> usize {
+        self.motors.len()
+    }
+
+    /// Check if the snapshot holds no motors.
+    pub fn is_empty(&self) -> bool {
+        self.motors.is_empty()
+    }
+
+    /// Encode as a fixed-size record with a trailing CRC-32.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotorError::SnapshotTooLarge` if the names and positions need
+    /// more than the 250 bytes available (only possible with long names).
+    pub fn to_bytes(&self) -> Result<[u8; SNAPSHOT_BYTES]> {
+        let needed = 2 + self.motors.iter().map(|(name, _)| 1 + name.len() + 8).sum::<usize>();
+        if needed > PAYLOAD_BYTES {
+            return Err(MotorError::SnapshotTooLarge { bytes: needed as u32 }.into());
+        }
+
+        let mut bytes = [0u8; SNAPSHOT_BYTES];
+        bytes[0] = SNAPSHOT_VERSION;
+        bytes[1] = self.motors.len() as u8;
+        let mut at = 2;
+        for (name, steps) in &self.motors {
+            bytes[at] = name.len() as u8;
+            at += 1;
+            bytes[at..at + name.len()].copy_from_slice(name.as_bytes());
+            at += name.len();
+            bytes[at..at + 8].copy_from_slice(&steps.to_le_bytes());
+            at += 8;
+        }
+        let crc = crc32(&bytes[..PAYLOAD_BYTES]);
+        bytes[PAYLOAD_BYTES..].copy_from_slice(&crc.to_le_bytes());
+        Ok(bytes)
+    }
+
+    /// Decode a record produced by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns `MotorError::InvalidSnapshot` if the CRC does not match, or
+    /// the version or layout is not one `to_bytes` produces.
+    pub fn from_bytes(data: &[u8; SNAPSHOT_BYTES]) -> Result<Self> {
+        let mut crc = [0u8; 4];
+        crc.copy_from_slice(&data[PAYLOAD_BYTES..]);
+        if crc32(&data[..PAYLOAD_BYTES]) != u32::from_le_bytes(crc) {
+            return Err(MotorError::InvalidSnapshot.into());
+        }
+        Self::decode_payload(&data[..PAYLOAD_BYTES]).ok_or(MotorError::InvalidSnapshot.into())
+    }
+
+    fn decode_payload(payload: &[u8]) -> Option<Self> {
+        if payload[0] != SNAPSHOT_VERSION {
+            return None;
+        }
+        let mut snapshot = Self::new();
+        let mut at = 2;
+        for _ in 0..payload[1] {
+            let len = *payload.get(at)? as usize;
+            let name = payload.get(at + 1..at + 1 + len)?;
+            let name = core::str::from_utf8(name).ok()?;
+            at += 1 + len;
+            let mut steps = [0u8; 8];
+            steps.copy_from_slice(payload.get(at..at + 8)?);
+            at += 8;
+            if snapshot.position(name).is_some() || !snapshot.set(name, i64::from_le_bytes(steps)) {
+                return None;
+            }
+        }
+        Some(snapshot)
+    }
+}
+
+/// CRC-32 (IEEE 802.3, reflected), bitwise to avoid a lookup table.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_round_trip() {
+        // Standard check value
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let mut snapshot = SystemSnapshot::new();
+        assert!(snapshot.set("x_axis", -1_234_567));
+        assert!(snapshot.set("y_axis", i64::MAX));
+        assert!(snapshot.set("x_axis", 42));
+        assert_eq!(snapshot.len(), 2);
+
+        let bytes = snapshot.to_bytes().unwrap();
+        assert_eq!(&bytes[..3], &[1, 2, 6]);
+        assert_eq!(SystemSnapshot::from_bytes(&bytes).unwrap(), snapshot);
+
+        let mut corrupted = bytes;
+        corrupted[10] ^= 0x01;
+        assert_eq!(
+            SystemSnapshot::from_bytes(&corrupted),
+            Err(MotorError::InvalidSnapshot.into())
+        );
+        assert_eq!(
+            SystemSnapshot::from_bytes(&[0; SNAPSHOT_BYTES]),
+            Err(MotorError::InvalidSnapshot.into())
+        );
+    }
+
+    #[test]
+    fn test_long_names_do_not_fit() {
+        let mut snapshot = SystemSnapshot::new();
+        for i in 0..8u8 {
+            let mut name: String<32> = String::new();
+            for _ in 0..31 {
+                let _ = name.push((b'a' + i) as char);
+            }
+            assert!(snapshot.set(&name, 0));
+        }
+        assert!(!snapshot.set("ninth", 0));
+        assert_eq!(
+            snapshot.to_bytes(),
+            Err(MotorError::SnapshotTooLarge { bytes: 2 + 8 * 40 }.into())
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_round_trip() {
+        let mut snapshot = SystemSnapshot::new();
+        snapshot.set("x_axis", 3200);
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert_eq!(serde_json::from_str::<SystemSnapshot>(&json).unwrap(), snapshot);
+    }
+}
@@ -6,10 +6,18 @@
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Idle;
 
+/// Motor has a move prepared and waits for a trigger to start it.
+#[derive(Debug, Clone, Copy)]
+pub struct Armed;
+
 /// Motor is currently executing a move.
 #[derive(Debug, Clone, Copy)]
 pub struct Moving;
 
+/// Motor has stopped part-way through a move and can resume it.
+#[derive(Debug, Clone, Copy)]
+pub struct Paused;
+
 /// Motor is executing a homing sequence.
 #[derive(Debug, Clone, Copy)]
 pub struct Homing;
@@ -22,14 +30,18 @@ pub struct Fault;
 pub trait MotorState: private::Sealed {}
 
 impl MotorState for Idle {}
+impl MotorState for Armed {}
 impl MotorState for Moving {}
+impl MotorState for Paused {}
 impl MotorState for Homing {}
 impl MotorState for Fault {}
 
 mod private {
     pub trait Sealed {}
     impl Sealed for super::Idle {}
+    impl Sealed for super::Armed {}
     impl Sealed for super::Moving {}
+    impl Sealed for super::Paused {}
     impl Sealed for super::Homing {}
     impl Sealed for super::Fault {}
 }
@@ -46,12 +58,24 @@ impl StateName for Idle {
     }
 }
 
+impl StateName for Armed {
+    fn name() -> &'static str {
+        "Armed"
+    }
+}
+
 impl StateName for Moving {
     fn name() -> &'static str {
         "Moving"
     }
 }
 
+impl StateName for Paused {
+    fn name() -> &'static str {
+        "Paused"
+    }
+}
+
 impl StateName for Homing {
     fn name() -> &'static str {
         "Homing"
@@ -3,14 +3,98 @@
 //! Provides a high-level API for managing multiple motors from a single configuration.
 
 use embedded_hal::delay::DelayNs;
-use embedded_hal::digital::OutputPin;
-use heapless::{FnvIndexMap, String};
+use embedded_hal::digital::{InputPin, OutputPin};
+use heapless::{FnvIndexMap, String, Vec};
 
-use crate::config::{MechanicalConstraints, MotorConfig, SystemConfig};
-use crate::error::{ConfigError, Error, Result};
-use crate::motor::state::Idle;
-use crate::motor::{StepperMotor, StepperMotorBuilder};
-use crate::trajectory::TrajectoryRegistry;
+use crate::config::units::Steps;
+use crate::config::{
+    validate_config, MechanicalConstraints, MotorConfig, SystemConfig, TrajectoryConfig,
+};
+use crate::error::{ConfigError, Error, MotionError, Result, TrajectoryError};
+use crate::motor::state::{Idle, MotorState, StateName};
+use crate::motor::{StepperMotor, StepperMotorBuilder, SystemSnapshot};
+use crate::trajectory::{TrajectoryRegistry, MAX_SEQUENCE_LENGTH};
+
+/// Idle motor handed through [`MotorSystem::execute`].
+type IdleMotor<STEP, DIR, DELAY, HOME> = StepperMotor<STEP, DIR, DELAY, Idle, HOME>;
+
+/// Result of [`MotorSystem::execute`]: the motor is returned on both paths.
+type ExecuteResult<STEP, DIR, DELAY, HOME> = core::result::Result<
+    IdleMotor<STEP, DIR, DELAY, HOME>,
+    (IdleMotor<STEP, DIR, DELAY, HOME>, Error),
+>;
+
+/// Result of the repeated execution methods: on failure, also the zero-based
+/// cycle that failed.
+type RepeatResult<STEP, DIR, DELAY, HOME> = core::result::Result<
+    IdleMotor<STEP, DIR, DELAY, HOME>,
+    (IdleMotor<STEP, DIR, DELAY, HOME>, Error, u32),
+>;
+
+/// One resolved step of a multi-motor sequence.
+///
+/// Returned by [`MotorSystem::plan_msequence`]; the application runs
+/// `trajectory` on the motor it owns under the name `motor`.
+#[derive(Debug, Clone)]
+pub struct PlannedStep<'a> {
+    /// Motor that executes this step.
+    pub motor: &'a str,
+    /// Trajectory to execute (pass to `StepperMotor::execute_trajectory`).
+    pub trajectory: &'a TrajectoryConfig,
+    /// Constraints of the motor, for feasibility checks or conversions.
+    pub constraints: MechanicalConstraints,
+    /// Target position in steps.
+    pub target_steps: i64,
+}
+
+/// Maximum number of trajectory names in each list of a [`ReloadReport`].
+const MAX_RELOADED_TRAJECTORIES: usize = 64;
+
+/// Outcome of [`MotorSystem::reload_config`].
+///
+/// Motors listed in `changed_motors`, `retuned_motors` or `removed_motors`
+/// need attention from the caller; trajectory changes were applied in place.
+#[derive(Debug, Clone, Default)]
+pub struct ReloadReport {
+    /// Registered motors whose step resolution changed.
+    ///
+    /// Their tracked position no longer matches the new configuration, so the
+    /// caller must rebuild the motor instances (e.g. with
+    /// [`MotorSystem::register_motor`]) and re-home them.
+    pub changed_motors: Vec<String<32>, 8>,
+    /// Registered motors with other changed settings, such as velocity,
+    /// acceleration or soft limits, at the same step resolution.
+    ///
+    /// Motor instances keep the constraints they were built with, so rebuild
+    /// these too; their positions remain valid and can be carried over with
+    /// [`StepperMotor::set_position_steps`].
+    pub retuned_motors: Vec<String<32>, 8>,
+    /// Registered motors missing from the new configuration (now unregistered).
+    pub removed_motors: Vec<String<32>, 8>,
+    /// Trajectories that only exist in the new configuration.
+    pub added_trajectories: Vec<String<32>, MAX_RELOADED_TRAJECTORIES>,
+    /// Trajectories that only existed in the old configuration.
+    pub removed_trajectories: Vec<String<32>, MAX_RELOADED_TRAJECTORIES>,
+    /// Trajectories present in both configurations with different settings.
+    pub modified_trajectories: Vec<String<32>, MAX_RELOADED_TRAJECTORIES>,
+}
+
+impl ReloadReport {
+    /// Check whether any motor instance has to be rebuilt.
+    pub fn needs_rebuild(&self) -> bool {
+        !self.changed_motors.is_empty() || !self.retuned_motors.is_empty()
+    }
+
+    /// Check whether the reload changed nothing.
+    pub fn is_empty(&self) -> bool {
+        self.changed_motors.is_empty()
+            && self.retuned_motors.is_empty()
+            && self.removed_motors.is_empty()
+            && self.added_trajectories.is_empty()
+            && self.removed_trajectories.is_empty()
+            && self.modified_trajectories.is_empty()
+    }
+}
 
 /// A facade for managing multiple stepper motors from configuration.
 ///
@@ -43,6 +127,8 @@ pub struct MotorSystem {
     registry: TrajectoryRegistry,
     /// Registered motor names (actual motors are owned by user due to generic types).
     registered_motors: FnvIndexMap<String<32>, MechanicalConstraints, 8>,
+    /// Last recorded position of each registered motor (steps).
+    positions: FnvIndexMap<String<32>, i64, 8>,
 }
 
 impl MotorSystem {
@@ -57,6 +143,7 @@ impl MotorSystem {
             config,
             registry,
             registered_motors: FnvIndexMap::new(),
+            positions: FnvIndexMap::new(),
         }
     }
 
@@ -86,6 +173,42 @@ impl MotorSystem {
             .map(MechanicalConstraints::from_config)
     }
 
+    /// Get the computed constraints of every configured motor, in
+    /// configuration order.
+    pub fn all_constraints(&self) -> Vec<(&str, MechanicalConstraints), 8> {
+        self.config
+            .motors
+            .iter()
+            .map(|(name, motor)| (name.as_str(), MechanicalConstraints::from_config(motor)))
+            .collect()
+    }
+
+    /// Smallest `max_velocity_deg_per_sec` among all motors.
+    ///
+    /// This is the fastest speed a synchronized move can command on every
+    /// axis. Returns 0.0 if no motors are configured.
+    pub fn max_velocity_deg_per_sec_across_all(&self) -> f32 {
+        self.fold_motors(|motor| motor.max_velocity.0, f32::min)
+    }
+
+    /// Coarsest resolution among all motors, in steps per degree.
+    ///
+    /// Returns 0.0 if no motors are configured.
+    pub fn min_steps_per_degree(&self) -> f32 {
+        self.fold_motors(MotorConfig::steps_per_degree, f32::min)
+    }
+
+    /// Finest resolution among all motors, in steps per degree.
+    ///
+    /// Returns 0.0 if no motors are configured.
+    pub fn max_steps_per_degree(&self) -> f32 {
+        self.fold_motors(MotorConfig::steps_per_degree, f32::max)
+    }
+
+    fn fold_motors(&self, value: impl Fn(&MotorConfig) -> f32, pick: fn(f32, f32) -> f32) -> f32 {
+        self.config.motors.values().map(value).reduce(pick).unwrap_or(0.0)
+    }
+
     /// Check if a motor name exists in the configuration.
     pub fn has_motor(&self, name: &str) -> bool {
         self.config.motor(name).is_some()
@@ -111,6 +234,53 @@ impl MotorSystem {
         dir_pin: DIR,
         delay: DELAY,
     ) -> Result<StepperMotor<STEP, DIR, DELAY, Idle>>
+    where
+        STEP: OutputPin,
+        DIR: OutputPin,
+        DELAY: DelayNs,
+    {
+        self.registered_builder(name)?
+            .step_pin(step_pin)
+            .dir_pin(dir_pin)
+            .delay(delay)
+            .build()
+    }
+
+    /// Register a motor whose DIR line is shared with other motors.
+    ///
+    /// Call once per motor with the same `dir` cell; each motor gets its own
+    /// handle built with
+    /// [`StepperMotorBuilder::shared_dir_pin`], so a motor rewrites DIR when
+    /// another one has changed it. The motors must not move at the same time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the motor name doesn't exist in the configuration.
+    #[cfg(feature = "shared-pin")]
+    pub fn register_motor_shared_dir<'a, STEP, P, DELAY>(
+        &mut self,
+        name: &str,
+        step_pin: STEP,
+        dir: &'a super::SharedPinCell<P>,
+        delay: DELAY,
+    ) -> Result<StepperMotor<STEP, super::SharedPin<'a, P>, DELAY, Idle>>
+    where
+        STEP: OutputPin,
+        P: OutputPin,
+        DELAY: DelayNs,
+    {
+        self.registered_builder(name)?
+            .step_pin(step_pin)
+            .shared_dir_pin(dir.pin())
+            .delay(delay)
+            .build()
+    }
+
+    /// Register `name` and get a builder configured from its motor config.
+    fn registered_builder<STEP, DIR, DELAY>(
+        &mut self,
+        name: &str,
+    ) -> Result<StepperMotorBuilder<STEP, DIR, DELAY>>
     where
         STEP: OutputPin,
         DIR: OutputPin,
@@ -126,15 +296,14 @@ impl MotorSystem {
 
         // Store the constraints for this motor
         let motor_name: String<32> = String::try_from(name).unwrap_or_default();
-        let _ = self.registered_motors.insert(motor_name, constraints);
+        let _ = self.registered_motors.insert(motor_name.clone(), constraints);
+        // Freshly built motors start at zero
+        let _ = self.positions.insert(motor_name, 0);
 
-        // Build and return the motor
-        StepperMotorBuilder::new()
-            .step_pin(step_pin)
-            .dir_pin(dir_pin)
-            .delay(delay)
+        Ok(StepperMotorBuilder::new()
             .from_motor_config(motor_config)
-            .build()
+            // Trajectories reference motors by config key, not display name
+            .name(name))
     }
 
     /// Build a motor from configuration without registering it.
@@ -167,9 +336,106 @@ impl MotorSystem {
             .dir_pin(dir_pin)
             .delay(delay)
             .from_motor_config(motor_config)
+            .name(name)
             .build()
     }
 
+    /// Replace the configuration while keeping registered motors.
+    ///
+    /// The new configuration is validated first; on error nothing changes.
+    /// Otherwise the trajectory registry is rebuilt. Motor instances are
+    /// owned by the caller and keep the constraints they were built with, so
+    /// registered motors whose constraints change are listed in
+    /// [`ReloadReport::changed_motors`] (step resolution changed) or
+    /// [`ReloadReport::retuned_motors`] (anything else) and keep their
+    /// registered constraints until re-registered, which
+    /// [`preflight`](Self::preflight) reports meanwhile. Registered motors
+    /// missing from the new configuration are unregistered.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from [`validate_config`].
+    pub fn reload_config(&mut self, new: SystemConfig) -> Result<ReloadReport> {
+        validate_config(&new)?;
+
+        let mut report = ReloadReport::default();
+        for (name, constraints) in self.registered_motors.iter() {
+            let list = match (self.config.motor(name), new.motor(name)) {
+                (Some(old), Some(motor)) if !old.same_resolution(motor) => {
+                    &mut report.changed_motors
+                }
+                (_, Some(motor)) if MechanicalConstraints::from_config(motor) != *constraints => {
+                    &mut report.retuned_motors
+                }
+                (_, Some(_)) => continue,
+                (_, None) => &mut report.removed_motors,
+            };
+            let _ = list.push(name.clone());
+        }
+        for name in &report.removed_motors {
+            self.registered_motors.remove(name);
+            self.positions.remove(name);
+        }
+
+        for (name, trajectory) in new.trajectories.iter() {
+            let list = match self.config.trajectory(name) {
+                None => &mut report.added_trajectories,
+                Some(old) if old != trajectory => &mut report.modified_trajectories,
+                Some(_) => continue,
+            };
+            let _ = list.push(name.clone());
+        }
+        for name in self.config.trajectories.keys() {
+            if new.trajectory(name).is_none() {
+                let _ = report.removed_trajectories.push(name.clone());
+            }
+        }
+
+        self.registry = TrajectoryRegistry::from_config(&new);
+        self.config = new;
+        Ok(report)
+    }
+
+    /// Replace one motor's configuration while it keeps running.
+    ///
+    /// For live tuning of velocity, acceleration, soft limits or direction
+    /// inversion on an idle motor. The configuration with the new motor is
+    /// validated first; on error nothing changes. Otherwise the stored
+    /// constraints are updated if the motor is registered, and its recorded
+    /// position is kept. The motor instance keeps the constraints it was
+    /// built with; rebuild it to apply them there too.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::MotorNotFound` if the motor isn't configured,
+    /// `ConfigError::UnsafeReload` if `steps_per_revolution`, `microsteps`
+    /// or the gear ratio changes (use [`reload_config`](Self::reload_config)
+    /// and rebuild the motor instead), or any error from [`validate_config`].
+    pub fn reload_motor_config(&mut self, name: &str, new_config: MotorConfig) -> Result<()> {
+        let key: String<32> = String::try_from(name).unwrap_or_default();
+        let old = self
+            .config
+            .motor(name)
+            .ok_or_else(|| Error::Config(ConfigError::MotorNotFound(key.clone())))?;
+        if let Some(field) = old.resolution_change(&new_config) {
+            return Err(Error::Config(ConfigError::UnsafeReload { motor: key, field }));
+        }
+
+        let mut config = self.config.clone();
+        if let Some(motor) = config.motors.get_mut(&key) {
+            *motor = new_config;
+        }
+        validate_config(&config)?;
+
+        if let (Some(constraints), Some(motor)) =
+            (self.registered_motors.get_mut(&key), config.motor(name))
+        {
+            *constraints = MechanicalConstraints::from_config(motor);
+        }
+        self.config = config;
+        Ok(())
+    }
+
     /// Check if a motor has been registered.
     pub fn is_registered(&self, name: &str) -> bool {
         self.registered_motors
@@ -177,6 +443,120 @@ impl MotorSystem {
             .any(|(k, _)| k.as_str() == name)
     }
 
+    /// Unregister a motor, e.g. after it has been physically disconnected.
+    ///
+    /// Returns the constraints stored at registration, or `None` if the motor
+    /// was not registered. The motor stays in the configuration and can be
+    /// registered again later.
+    pub fn unregister_motor(&mut self, name: &str) -> Option<MechanicalConstraints> {
+        let key: String<32> = String::try_from(name).ok()?;
+        self.positions.remove(&key);
+        self.registered_motors.remove(&key)
+    }
+
+    /// Record a registered motor's current position for [`snapshot`](Self::snapshot).
+    ///
+    /// Motors are owned by the application, so the system only knows the
+    /// positions recorded here; call it after moves that should be saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::MotorNotFound` if the motor is not registered.
+    pub fn record_position<STEP, DIR, DELAY, STATE, HOME>(
+        &mut self,
+        motor: &StepperMotor<STEP, DIR, DELAY, STATE, HOME>,
+    ) -> Result<()>
+    where
+        STEP: OutputPin,
+        DIR: OutputPin,
+        DELAY: DelayNs,
+        HOME: InputPin,
+        STATE: MotorState + StateName,
+    {
+        let position = self.recorded_position_mut(motor.name())?;
+        *position = motor.position_steps().0;
+        Ok(())
+    }
+
+    fn recorded_position_mut(&mut self, name: &str) -> Result<&mut i64> {
+        let not_found = || {
+            Error::Config(ConfigError::MotorNotFound(
+                String::try_from(name).unwrap_or_default(),
+            ))
+        };
+        let key: String<32> = String::try_from(name).map_err(|_| not_found())?;
+        self.positions.get_mut(&key).ok_or_else(not_found)
+    }
+
+    /// Capture the recorded positions of all registered motors.
+    pub fn snapshot(&self) -> SystemSnapshot {
+        let mut snapshot = SystemSnapshot::new();
+        for (name, &steps) in &self.positions {
+            // At most 8 registered motors with names of at most 32 bytes
+            snapshot.set(name, steps);
+        }
+        snapshot
+    }
+
+    /// Replace the recorded positions with those of a saved snapshot.
+    ///
+    /// Registered motors missing from the snapshot keep their recorded
+    /// position. Apply the restored positions to the motors with
+    /// [`restore_position`](Self::restore_position).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::MotorNotFound` for the first motor in the
+    /// snapshot that is not registered; nothing is restored then.
+    pub fn restore_from_snapshot(&mut self, snapshot: &SystemSnapshot) -> Result<()> {
+        if let Some((name, _)) = snapshot.iter().find(|(name, _)| !self.is_registered(name)) {
+            return Err(Error::Config(ConfigError::MotorNotFound(
+                String::try_from(name).unwrap_or_default(),
+            )));
+        }
+        for (name, steps) in snapshot.iter() {
+            *self.recorded_position_mut(name)? = steps;
+        }
+        Ok(())
+    }
+
+    /// Set a registered motor's position to its recorded one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::MotorNotFound` if the motor is not registered.
+    pub fn restore_position<STEP, DIR, DELAY, HOME>(
+        &self,
+        motor: &mut IdleMotor<STEP, DIR, DELAY, HOME>,
+    ) -> Result<()>
+    where
+        STEP: OutputPin,
+        DIR: OutputPin,
+        DELAY: DelayNs,
+        HOME: InputPin,
+    {
+        let steps = self.recorded_position(motor.name()).ok_or_else(|| {
+            Error::Config(ConfigError::MotorNotFound(
+                String::try_from(motor.name()).unwrap_or_default(),
+            ))
+        })?;
+        motor.set_position_steps(steps);
+        Ok(())
+    }
+
+    /// Last recorded position of a registered motor.
+    pub fn recorded_position(&self, name: &str) -> Option<Steps> {
+        self.positions
+            .iter()
+            .find(|(k, _)| k.as_str() == name)
+            .map(|(_, &steps)| Steps(steps))
+    }
+
+    /// List the names of registered motors (a subset of `motor_names()`).
+    pub fn registered_motor_names(&self) -> impl Iterator<Item = &str> {
+        self.registered_motors.keys().map(|k| k.as_str())
+    }
+
     /// Get the number of registered motors.
     pub fn registered_count(&self) -> usize {
         self.registered_motors.len()
@@ -199,21 +579,199 @@ impl MotorSystem {
         self.registry.get_or_error(name)
     }
 
+    /// Execute a named trajectory on a registered motor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::MotorNotFound` if the trajectory's motor is not
+    /// currently registered, plus any error from
+    /// [`StepperMotor::execute`]. The motor is returned on all paths.
+    pub fn execute<STEP, DIR, DELAY, HOME>(
+        &self,
+        motor: IdleMotor<STEP, DIR, DELAY, HOME>,
+        trajectory_name: &str,
+    ) -> ExecuteResult<STEP, DIR, DELAY, HOME>
+    where
+        STEP: OutputPin,
+        DIR: OutputPin,
+        DELAY: DelayNs,
+        HOME: InputPin,
+    {
+        let trajectory = match self.trajectory(trajectory_name) {
+            Ok(t) => t,
+            Err(e) => return Err((motor, e)),
+        };
+
+        if !self.is_registered(trajectory.motor.as_str()) {
+            return Err((
+                motor,
+                Error::Config(ConfigError::MotorNotFound(trajectory.motor.clone())),
+            ));
+        }
+
+        motor.execute(trajectory_name, &self.registry)
+    }
+
+    /// Execute a named trajectory `count` times in a row, e.g. for stress tests.
+    ///
+    /// Trajectories are absolute moves, so repeats that find the motor already
+    /// at the target complete immediately.
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first failure and returns the motor, the error from
+    /// [`execute`](Self::execute) and the zero-based cycle that failed.
+    pub fn execute_repeated<STEP, DIR, DELAY, HOME>(
+        &self,
+        mut motor: IdleMotor<STEP, DIR, DELAY, HOME>,
+        trajectory_name: &str,
+        count: u32,
+    ) -> RepeatResult<STEP, DIR, DELAY, HOME>
+    where
+        STEP: OutputPin,
+        DIR: OutputPin,
+        DELAY: DelayNs,
+        HOME: InputPin,
+    {
+        for cycle in 0..count {
+            #[cfg(feature = "defmt")]
+            defmt::info!("{=str}: cycle {=u32}/{=u32}", trajectory_name, cycle + 1, count);
+
+            motor = match self.execute_or_stay(motor, trajectory_name) {
+                Ok(motor) => motor,
+                Err((motor, e)) => return Err((motor, e, cycle)),
+            };
+        }
+        Ok(motor)
+    }
+
+    /// Alternate between two named trajectories for `cycles` round trips.
+    ///
+    /// Each cycle runs `outward` then `return_traj`, e.g. "go to 90°" then
+    /// "go to 0°" for a repeating scan. A leg that finds the motor already at
+    /// its target completes immediately.
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first failure and returns the motor, the error from
+    /// [`execute`](Self::execute) and the zero-based cycle that failed.
+    pub fn execute_cycle<STEP, DIR, DELAY, HOME>(
+        &self,
+        mut motor: IdleMotor<STEP, DIR, DELAY, HOME>,
+        outward: &str,
+        return_traj: &str,
+        cycles: u32,
+    ) -> RepeatResult<STEP, DIR, DELAY, HOME>
+    where
+        STEP: OutputPin,
+        DIR: OutputPin,
+        DELAY: DelayNs,
+        HOME: InputPin,
+    {
+        for cycle in 0..cycles {
+            #[cfg(feature = "defmt")]
+            defmt::info!(
+                "{=str}/{=str}: cycle {=u32}/{=u32}",
+                outward,
+                return_traj,
+                cycle + 1,
+                cycles
+            );
+
+            for trajectory_name in [outward, return_traj] {
+                motor = match self.execute_or_stay(motor, trajectory_name) {
+                    Ok(motor) => motor,
+                    Err((motor, e)) => return Err((motor, e, cycle)),
+                };
+            }
+        }
+        Ok(motor)
+    }
+
+    /// Like [`execute`](Self::execute), but a motor already at the target is
+    /// not an error.
+    fn execute_or_stay<STEP, DIR, DELAY, HOME>(
+        &self,
+        motor: IdleMotor<STEP, DIR, DELAY, HOME>,
+        trajectory_name: &str,
+    ) -> ExecuteResult<STEP, DIR, DELAY, HOME>
+    where
+        STEP: OutputPin,
+        DIR: OutputPin,
+        DELAY: DelayNs,
+        HOME: InputPin,
+    {
+        match self.execute(motor, trajectory_name) {
+            Err((motor, Error::Motion(MotionError::MoveTooShort { steps: 0, .. }))) => Ok(motor),
+            result => result,
+        }
+    }
+
+    /// List the park trajectory of every configured motor that has one.
+    ///
+    /// Yields `(motor, trajectory)` name pairs, e.g. to park each motor with
+    /// [`StepperMotor::park`] on shutdown.
+    pub fn park_plan(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.config
+            .motors
+            .iter()
+            .filter_map(|(name, motor)| Some((name.as_str(), motor.park()?)))
+    }
+
     /// Get all trajectory names for a specific motor.
     pub fn trajectories_for_motor<'a>(
         &'a self,
         motor_name: &'a str,
     ) -> impl Iterator<Item = &'a str> + 'a {
-        self.registry
-            .iter()
-            .filter(move |(_, traj)| traj.motor.as_str() == motor_name)
-            .map(|(name, _)| name)
+        self.registry.trajectories_for_motor(motor_name).map(|(name, _)| name)
+    }
+
+    /// Resolve a multi-motor sequence into its ordered steps.
+    ///
+    /// Motors are owned by the application, so the system only plans: each
+    /// step carries the motor name and trajectory to execute, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::TrajectoryNotFound` if no `[msequences]` entry has
+    /// that name, or `TrajectoryError::MotorNotFound` if a step references a
+    /// motor missing from the configuration.
+    pub fn plan_msequence(
+        &self,
+        name: &str,
+    ) -> Result<heapless::Vec<PlannedStep<'_>, MAX_SEQUENCE_LENGTH>> {
+        let mseq = self.config.msequence(name).ok_or_else(|| {
+            Error::Config(ConfigError::TrajectoryNotFound(
+                String::try_from(name).unwrap_or_default(),
+            ))
+        })?;
+
+        let mut plan = heapless::Vec::new();
+        for step in mseq.steps.iter() {
+            let constraints = self.constraints(step.motor.as_str()).ok_or_else(|| {
+                Error::Trajectory(TrajectoryError::MotorNotFound {
+                    trajectory: String::try_from(name).unwrap_or_default(),
+                    motor: step.motor.clone(),
+                })
+            })?;
+            let target_steps = step.target_steps(&constraints);
+            // Capacity matches MultiMotorSequence::steps, so this cannot fail
+            let _ = plan.push(PlannedStep {
+                motor: step.motor.as_str(),
+                trajectory: step,
+                constraints,
+                target_steps,
+            });
+        }
+        Ok(plan)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::units::Degrees;
+    use std::vec::Vec;
 
     fn test_config() -> SystemConfig {
         let toml = r#"
@@ -236,10 +794,21 @@ motor = "x_axis"
 target_degrees = 0.0
 velocity_percent = 50
 
+[trajectories.scan_x]
+motor = "x_axis"
+target_degrees = 90.0
+
 [trajectories.home_y]
 motor = "y_axis"
 target_degrees = 0.0
 velocity_percent = 50
+
+[msequences.load_part]
+steps = [
+    { motor = "x_axis", target_degrees = 30.0 },
+    { motor = "y_axis", target_degrees = 90.0, dwell_ms = 200 },
+    { motor = "x_axis", target_degrees = 0.0 },
+]
 "#;
         toml::from_str(toml).unwrap()
     }
@@ -278,6 +847,37 @@ velocity_percent = 50
         assert_eq!(constraints.steps_per_revolution, 3200);
     }
 
+    #[test]
+    fn test_all_constraints_and_extremes() {
+        use crate::config::units::Microsteps;
+
+        let system = MotorSystem::from_config(test_config());
+
+        let all = system.all_constraints();
+        assert_eq!(all.len(), 2);
+        let names: Vec<&str> = all.iter().map(|(name, _)| *name).collect();
+        assert!(names.contains(&"x_axis") && names.contains(&"y_axis"));
+        let (_, y) = all.iter().find(|(name, _)| *name == "y_axis").unwrap();
+        assert_eq!(y.max_velocity.0, 180.0);
+
+        assert_eq!(system.max_velocity_deg_per_sec_across_all(), 180.0);
+        // Both motors have 3200 steps/rev
+        let expected = 3200.0 / 360.0;
+        assert!((system.min_steps_per_degree() - expected).abs() < 1e-4);
+        assert!((system.max_steps_per_degree() - expected).abs() < 1e-4);
+
+        let mut config = test_config();
+        config.motors.get_mut(&key("y_axis")).unwrap().microsteps = Microsteps::new(32).unwrap();
+        let system = MotorSystem::from_config(config);
+        assert!((system.min_steps_per_degree() - expected).abs() < 1e-4);
+        assert!((system.max_steps_per_degree() - 4.0 * expected).abs() < 1e-3);
+
+        let empty = MotorSystem::from_config(SystemConfig::default());
+        assert!(empty.all_constraints().is_empty());
+        assert_eq!(empty.max_velocity_deg_per_sec_across_all(), 0.0);
+        assert_eq!(empty.min_steps_per_degree(), 0.0);
+    }
+
     #[test]
     fn test_trajectories_for_motor() {
         let config = test_config();
@@ -304,4 +904,287 @@ velocity_percent = 50
         let traj = system.trajectory("nonexistent");
         assert!(traj.is_err());
     }
+
+    #[test]
+    fn test_plan_msequence() {
+        let system = MotorSystem::from_config(test_config());
+
+        let plan = system.plan_msequence("load_part").unwrap();
+        let motors: Vec<&str> = plan.iter().map(|s| s.motor).collect();
+        assert_eq!(motors.as_slice(), ["x_axis", "y_axis", "x_axis"]);
+        assert_eq!(plan[1].trajectory.dwell_ms, Some(200));
+        // 3200 steps/rev on both axes: 30° = 266.67 -> 266 (truncated), 90° = 800
+        assert_eq!(plan[0].target_steps, 266);
+        assert_eq!(plan[1].target_steps, 800);
+        assert_eq!(plan[2].target_steps, 0);
+
+        assert!(matches!(
+            system.plan_msequence("nonexistent"),
+            Err(Error::Config(ConfigError::TrajectoryNotFound(_)))
+        ));
+    }
+
+    #[test]
+    fn test_plan_msequence_bad_motor() {
+        let mut config = test_config();
+        let mseq = config.msequences.values_mut().next().unwrap();
+        mseq.steps[1].motor = String::try_from("z_axis").unwrap();
+        let system = MotorSystem::from_config(config);
+
+        assert!(matches!(
+            system.plan_msequence("load_part"),
+            Err(Error::Trajectory(TrajectoryError::MotorNotFound { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_unregister_motor() {
+        use crate::test_util::{NoopDelay, NoopPin};
+
+        let mut system = MotorSystem::from_config(test_config());
+        let Ok(motor) = system.register_motor("x_axis", NoopPin, NoopPin, NoopDelay) else {
+            panic!("x_axis should register");
+        };
+        assert!(system.register_motor("y_axis", NoopPin, NoopPin, NoopDelay).is_ok());
+
+        let mut names: Vec<&str> = system.registered_motor_names().collect();
+        names.sort();
+        assert_eq!(names, ["x_axis", "y_axis"]);
+
+        let constraints = system.unregister_motor("x_axis").unwrap();
+        assert_eq!(constraints.steps_per_revolution, 3200);
+        assert!(system.unregister_motor("x_axis").is_none());
+        assert!(!system.is_registered("x_axis"));
+        assert!(system.has_motor("x_axis"));
+        assert_eq!(system.registered_motor_names().collect::<Vec<_>>(), ["y_axis"]);
+
+        let (_motor, err) = system.execute(motor, "home_x").err().unwrap();
+        assert!(matches!(err, Error::Config(ConfigError::MotorNotFound(ref m)) if m == "x_axis"));
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        use crate::test_util::{NoopDelay, NoopPin};
+
+        let mut system = MotorSystem::from_config(test_config());
+        let x = system.register_motor("x_axis", NoopPin, NoopPin, NoopDelay).unwrap();
+        let y = system.register_motor("y_axis", NoopPin, NoopPin, NoopDelay).unwrap();
+        let x = system.execute(x, "scan_x").ok().unwrap();
+        system.record_position(&x).unwrap();
+        system.record_position(&y).unwrap();
+
+        let snapshot = system.snapshot();
+        assert_eq!(snapshot.position("x_axis"), Some(800));
+        assert_eq!(snapshot.position("y_axis"), Some(0));
+        let bytes = snapshot.to_bytes().unwrap();
+
+        // After a restart, both motors are rebuilt at zero
+        let mut system = MotorSystem::from_config(test_config());
+        let mut x = system.register_motor("x_axis", NoopPin, NoopPin, NoopDelay).unwrap();
+        let saved = SystemSnapshot::from_bytes(&bytes).unwrap();
+        let err = system.restore_from_snapshot(&saved).unwrap_err();
+        assert!(matches!(err, Error::Config(ConfigError::MotorNotFound(ref m)) if m == "y_axis"));
+        assert_eq!(system.recorded_position("x_axis"), Some(Steps(0)));
+
+        let mut y = system.register_motor("y_axis", NoopPin, NoopPin, NoopDelay).unwrap();
+        system.restore_from_snapshot(&saved).unwrap();
+        system.restore_position(&mut x).unwrap();
+        system.restore_position(&mut y).unwrap();
+        assert_eq!(x.position_steps(), Steps(800));
+        assert_eq!(y.position_steps(), Steps(0));
+
+        system.unregister_motor("y_axis");
+        assert!(system.record_position(&y).is_err());
+        assert_eq!(system.snapshot().len(), 1);
+    }
+
+    #[test]
+    fn test_execute_cycle() {
+        use crate::test_util::{NoopDelay, NoopPin};
+
+        let mut system = MotorSystem::from_config(test_config());
+        let Ok(motor) = system.register_motor("x_axis", NoopPin, NoopPin, NoopDelay) else {
+            panic!("x_axis should register");
+        };
+
+        let Ok(motor) = system.execute_cycle(motor, "scan_x", "home_x", 3) else {
+            panic!("cycle should run");
+        };
+        assert_eq!(motor.position_steps().0, 0);
+
+        let Ok(motor) = system.execute_repeated(motor, "scan_x", 3) else {
+            panic!("repeat should run");
+        };
+        assert!((motor.position_degrees().0 - 90.0).abs() < 0.01);
+
+        // home_y belongs to another motor: the return leg of the first cycle fails
+        let (motor, err, cycle) = system.execute_cycle(motor, "home_x", "home_y", 5).err().unwrap();
+        assert!(matches!(err, Error::Config(ConfigError::MotorNotFound(_))));
+        assert_eq!(cycle, 0);
+        assert_eq!(motor.position_steps().0, 0);
+
+        let (_motor, _err, cycle) = system.execute_repeated(motor, "missing", 1).err().unwrap();
+        assert_eq!(cycle, 0);
+    }
+
+    fn key(name: &str) -> String<32> {
+        String::try_from(name).unwrap()
+    }
+
+    #[test]
+    fn test_reload_trajectory_only() {
+        use crate::test_util::{NoopDelay, NoopPin};
+
+        let mut system = MotorSystem::from_config(test_config());
+        assert!(system.register_motor("x_axis", NoopPin, NoopPin, NoopDelay).is_ok());
+
+        let mut config = test_config();
+        config.trajectories.get_mut(&key("scan_x")).unwrap().target_degrees = Degrees(45.0);
+        config.trajectories.remove(&key("home_y"));
+        let mut trajectory = config.trajectory("home_x").unwrap().clone();
+        trajectory.target_degrees = Degrees(180.0);
+        config
+            .trajectories
+            .insert(key("park_x"), trajectory)
+            .unwrap();
+
+        let report = system.reload_config(config).unwrap();
+        assert!(!report.needs_rebuild());
+        assert!(report.removed_motors.is_empty());
+        assert_eq!(report.added_trajectories, ["park_x"]);
+        assert_eq!(report.removed_trajectories, ["home_y"]);
+        assert_eq!(report.modified_trajectories, ["scan_x"]);
+
+        assert!(system.is_registered("x_axis"));
+        assert_eq!(system.trajectory("scan_x").unwrap().target_degrees, Degrees(45.0));
+        assert!(system.trajectory("park_x").is_ok());
+        assert!(system.trajectory("home_y").is_err());
+
+        assert!(system.reload_config(system.config().clone()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_reload_flags_resolution_change() {
+        use crate::config::units::{DegreesPerSec, Microsteps};
+        use crate::test_util::{NoopDelay, NoopPin};
+
+        let mut system = MotorSystem::from_config(test_config());
+        assert!(system.register_motor("x_axis", NoopPin, NoopPin, NoopDelay).is_ok());
+        assert!(system.register_motor("y_axis", NoopPin, NoopPin, NoopDelay).is_ok());
+
+        let mut config = test_config();
+        config.motors.get_mut(&key("x_axis")).unwrap().microsteps = Microsteps::new(32).unwrap();
+        config.motors.get_mut(&key("y_axis")).unwrap().max_velocity = DegreesPerSec(90.0);
+
+        let report = system.reload_config(config).unwrap();
+        assert!(report.needs_rebuild());
+        assert_eq!(report.changed_motors, ["x_axis"]);
+        assert_eq!(report.retuned_motors, ["y_axis"]);
+
+        // Both keep the constraints of their motor instances until re-registered
+        let x = system.registered_constraints("x_axis").unwrap();
+        assert_eq!(x.steps_per_revolution, 3200);
+        let y = system.registered_constraints("y_axis").unwrap();
+        assert_ne!(y.max_velocity, DegreesPerSec(90.0));
+        assert_eq!(system.preflight().unwrap_err().problems.len(), 2);
+
+        assert!(system.register_motor("y_axis", NoopPin, NoopPin, NoopDelay).is_ok());
+        let y = system.registered_constraints("y_axis").unwrap();
+        assert_eq!(y.max_velocity, DegreesPerSec(90.0));
+
+        assert!(system.register_motor("x_axis", NoopPin, NoopPin, NoopDelay).is_ok());
+        let x = system.registered_constraints("x_axis").unwrap();
+        assert_eq!(x.steps_per_revolution, 6400);
+    }
+
+    #[test]
+    fn test_reload_rejects_invalid_config() {
+        let mut system = MotorSystem::from_config(test_config());
+        let mut config = test_config();
+        config.motors.get_mut(&key("x_axis")).unwrap().gear_ratio = -1.0;
+
+        assert!(system.reload_config(config).is_err());
+        assert_eq!(system.motor_config("x_axis").unwrap().gear_ratio, 1.0);
+    }
+
+    #[test]
+    fn test_reload_motor_config() {
+        use crate::config::units::{DegreesPerSec, Microsteps};
+        use crate::config::{LimitPolicy, SoftLimits};
+        use crate::test_util::{NoopDelay, NoopPin};
+
+        let mut system = MotorSystem::from_config(test_config());
+        let motor = system.register_motor("x_axis", NoopPin, NoopPin, NoopDelay).unwrap();
+        let motor = motor.move_to_blocking(Degrees(45.0)).ok().unwrap();
+        system.record_position(&motor).unwrap();
+
+        let mut config = system.motor_config("x_axis").unwrap().clone();
+        config.max_velocity = DegreesPerSec(90.0);
+        config.invert_direction = true;
+        config.limits = Some(SoftLimits::new(Degrees(-90.0), Degrees(90.0), LimitPolicy::Reject));
+        system.reload_motor_config("x_axis", config.clone()).unwrap();
+
+        assert!(system.motor_config("x_axis").unwrap().invert_direction);
+        let x = system.registered_constraints("x_axis").unwrap();
+        assert_eq!(x.max_velocity, DegreesPerSec(90.0));
+        assert!(x.limits.is_some());
+        assert_eq!(system.snapshot().position("x_axis"), Some(400));
+
+        // Resolution changes would invalidate the position
+        let mut finer = config.clone();
+        finer.microsteps = Microsteps::THIRTY_SECOND;
+        let err = system.reload_motor_config("x_axis", finer).unwrap_err();
+        assert_eq!(
+            err,
+            Error::Config(ConfigError::UnsafeReload { motor: key("x_axis"), field: "microsteps" })
+        );
+        let mut geared = config.clone();
+        geared.gear_ratio = 2.0;
+        assert!(matches!(
+            system.reload_motor_config("x_axis", geared),
+            Err(Error::Config(ConfigError::UnsafeReload { field: "gear_ratio", .. }))
+        ));
+
+        // Invalid or unknown: nothing changes
+        config.max_acceleration = crate::config::units::DegreesPerSecSquared(-1.0);
+        assert!(system.reload_motor_config("x_axis", config.clone()).is_err());
+        assert!(system.reload_motor_config("z_axis", config).is_err());
+        let x = system.motor_config("x_axis").unwrap();
+        assert_eq!((x.max_velocity, x.microsteps), (DegreesPerSec(90.0), Microsteps::SIXTEENTH));
+    }
+
+    #[test]
+    fn test_park() {
+        use crate::config::units::Steps;
+        use crate::motor::ParkOutcome;
+        use crate::test_util::{NoopDelay, NoopPin};
+
+        let mut config = test_config();
+        config.motors.get_mut(&key("x_axis")).unwrap().park_trajectory = Some(key("scan_x"));
+        let mut system = MotorSystem::from_config(config);
+        let plan: Vec<_> = system.park_plan().collect();
+        assert_eq!(plan, [("x_axis", "scan_x")]);
+
+        let x = system.register_motor("x_axis", NoopPin, NoopPin, NoopDelay).unwrap();
+        assert_eq!(x.park_trajectory(), Some("scan_x"));
+        let Ok((x, outcome)) = x.park(system.trajectories()) else {
+            panic!("park should succeed");
+        };
+        assert_eq!(outcome, ParkOutcome::Parked);
+        // 3200 steps/rev: 90° = 800 steps
+        assert_eq!(x.position_steps(), Steps(800));
+
+        // Already parked
+        let Ok((_, outcome)) = x.park(system.trajectories()) else {
+            panic!("parking twice should succeed");
+        };
+        assert_eq!(outcome, ParkOutcome::Parked);
+
+        let y = system.register_motor("y_axis", NoopPin, NoopPin, NoopDelay).unwrap();
+        let Ok((y, outcome)) = y.park(system.trajectories()) else {
+            panic!("park without a trajectory should succeed");
+        };
+        assert_eq!(outcome, ParkOutcome::NotConfigured);
+        assert_eq!(y.position_steps(), Steps(0));
+    }
 }
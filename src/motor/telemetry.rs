@@ -0,0 +1,188 @@
+//! Compact motor telemetry for serial or CAN links.
+//!
+//! A [`MotorTelemetry`] frame encodes to 16 bytes, little-endian:
+//!
+//! | Bytes | Field |
+//! |-------|-------|
+//! | 0..8 | name, zero-padded |
+//! | 8..12 | position in steps (`i32`) |
+//! | 12..14 | velocity in steps/sec (`i16`) |
+//! | 14 | progress in percent |
+//! | 15 | phase (low nibble), fault flags (high nibble) |
+
+use crate::error::{MotorError, Result};
+use crate::motion::MotionPhase;
+
+/// Fixed-size snapshot of a motor's state for transmission.
+///
+/// Fields are read by value (`{ telemetry.position_steps }`): the packed
+/// layout does not allow references to multi-byte fields.
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MotorTelemetry {
+    /// First bytes of the motor name, zero-padded.
+    pub name: [u8; 8],
+    /// Position in steps, saturated to the `i32` range.
+    pub position_steps: i32,
+    /// Signed commanded velocity in steps/sec, saturated to the `i16` range.
+    pub velocity_steps_per_sec: i16,
+    /// Motion phase code (see [`MotorTelemetry::motion_phase`]).
+    pub phase: u8,
+    /// Move progress (0 to 100).
+    pub progress_pct: u8,
+    /// Fault flags ([`MotorTelemetry::FAULT_THERMAL`], [`MotorTelemetry::FAULT_UNDERRUN`]).
+    pub fault: u8,
+}
+
+impl MotorTelemetry {
+    /// Fault flag: duty is above the thermal budget.
+    pub const FAULT_THERMAL: u8 = 0x01;
+
+    /// Fault flag: steps ran late since the underrun count was last reset.
+    pub const FAULT_UNDERRUN: u8 = 0x02;
+
+    pub(crate) fn new(
+        name: &str,
+        position_steps: i64,
+        velocity_steps_per_sec: f32,
+        phase: MotionPhase,
+        progress: f32,
+        fault: u8,
+    ) -> Self {
+        // Truncate on a character boundary so the name stays valid UTF-8
+        let mut len = name.len().min(8);
+        while !name.is_char_boundary(len) {
+            len -= 1;
+        }
+        let mut name_bytes = [0u8; 8];
+        name_bytes[..len].copy_from_slice(&name.as_bytes()[..len]);
+
+        Self {
+            name: name_bytes,
+            position_steps: position_steps.clamp(i32::MIN as i64, i32::MAX as i64) as i32,
+            // Float to int casts saturate
+            velocity_steps_per_sec: velocity_steps_per_sec as i16,
+            phase: phase_code(phase),
+            progress_pct: (progress.clamp(0.0, 1.0) * 100.0) as u8,
+            fault,
+        }
+    }
+
+    /// The motor name, without the zero padding.
+    pub fn name_str(&self) -> &str {
+        let len = self.name.iter().position(|&b| b == 0).unwrap_or(8);
+        core::str::from_utf8(&self.name[..len]).unwrap_or_default()
+    }
+
+    /// The motion phase: 0 is `Complete` (idle), 1 `Accelerating`,
+    /// 2 `Cruising` and 3 `Decelerating`.
+    pub fn motion_phase(&self) -> Option<MotionPhase> {
+        match self.phase {
+            0 => Some(MotionPhase::Complete),
+            1 => Some(MotionPhase::Accelerating),
+            2 => Some(MotionPhase::Cruising),
+            3 => Some(MotionPhase::Decelerating),
+            _ => None,
+        }
+    }
+
+    /// Encode as a little-endian frame.
+    ///
+    /// Phase and fault share the last byte, so only their low nibbles are
+    /// transmitted.
+    pub fn encode(&self) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.name);
+        bytes[8..12].copy_from_slice(&{ self.position_steps }.to_le_bytes());
+        bytes[12..14].copy_from_slice(&{ self.velocity_steps_per_sec }.to_le_bytes());
+        bytes[14] = self.progress_pct;
+        bytes[15] = (self.phase & 0x0F) | (self.fault << 4);
+        bytes
+    }
+
+    /// Decode a frame produced by [`encode`](Self::encode).
+    ///
+    /// Fails with [`MotorError::InvalidTelemetry`] when the progress is above
+    /// 100 or the phase code is unknown.
+    pub fn decode(bytes: &[u8; 16]) -> Result<Self> {
+        let invalid = |offset: u8| MotorError::InvalidTelemetry {
+            offset,
+            value: bytes[offset as usize],
+        };
+        if bytes[14] > 100 {
+            return Err(invalid(14).into());
+        }
+        if bytes[15] & 0x0F > 3 {
+            return Err(invalid(15).into());
+        }
+
+        let mut name = [0u8; 8];
+        name.copy_from_slice(&bytes[..8]);
+        Ok(Self {
+            name,
+            position_steps: i32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]),
+            velocity_steps_per_sec: i16::from_le_bytes([bytes[12], bytes[13]]),
+            phase: bytes[15] & 0x0F,
+            progress_pct: bytes[14],
+            fault: bytes[15] >> 4,
+        })
+    }
+}
+
+fn phase_code(phase: MotionPhase) -> u8 {
+    match phase {
+        MotionPhase::Complete => 0,
+        MotionPhase::Accelerating => 1,
+        MotionPhase::Cruising => 2,
+        MotionPhase::Decelerating => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_round_trip() {
+        let telemetry = MotorTelemetry::new(
+            "shoulder_pan",
+            -70_000,
+            -1234.7,
+            MotionPhase::Cruising,
+            0.456,
+            MotorTelemetry::FAULT_UNDERRUN,
+        );
+        assert_eq!(core::mem::size_of::<MotorTelemetry>(), 17);
+        assert_eq!(telemetry.name_str(), "shoulder");
+        assert_eq!({ telemetry.velocity_steps_per_sec }, -1234);
+        assert_eq!(telemetry.progress_pct, 45);
+
+        let bytes = telemetry.encode();
+        assert_eq!(&bytes[8..12], &(-70_000i32).to_le_bytes());
+        assert_eq!(bytes[15], 0x22);
+        assert_eq!(MotorTelemetry::decode(&bytes).unwrap(), telemetry);
+        assert_eq!(telemetry.motion_phase(), Some(MotionPhase::Cruising));
+
+        // Out of range values saturate
+        let far = MotorTelemetry::new("x", i64::MAX, 1e9, MotionPhase::Complete, 2.0, 0);
+        assert_eq!({ far.position_steps }, i32::MAX);
+        assert_eq!({ far.velocity_steps_per_sec }, i16::MAX);
+        assert_eq!(far.progress_pct, 100);
+    }
+
+    #[test]
+    fn test_decode_rejects_invalid_fields() {
+        let mut bytes = MotorTelemetry::new("x", 0, 0.0, MotionPhase::Complete, 1.0, 0).encode();
+        bytes[15] = 0x04;
+        assert_eq!(
+            MotorTelemetry::decode(&bytes),
+            Err(MotorError::InvalidTelemetry { offset: 15, value: 4 }.into())
+        );
+        bytes[15] = 0;
+        bytes[14] = 101;
+        assert_eq!(
+            MotorTelemetry::decode(&bytes),
+            Err(MotorError::InvalidTelemetry { offset: 14, value: 101 }.into())
+        );
+    }
+}
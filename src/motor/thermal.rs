@@ -0,0 +1,159 @@
+//! Thermal duty bookkeeping.
+//!
+//! The duty follows moving time (1.0) and idle time (0.0) through a
+//! first-order exponential average, so it rises towards 1.0 during long moves
+//! and decays towards 0.0 at rest. The motor updates it when a move starts and
+//! ends, from the configured clock or, without one, from the profile's
+//! estimated duration.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ThermalConfig;
+use crate::math::expf;
+
+/// Exponentially averaged moving-time fraction of one motor.
+///
+/// Serializable so the duty can be persisted across restarts; the clock
+/// reading of the last update is not saved, since clock epochs differ
+/// between runs.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DutyTracker {
+    time_constant_s: f32,
+    max_duty: f32,
+    enforce: bool,
+    duty: f32,
+    /// Clock reading at the last move start or end.
+    #[serde(skip)]
+    mark_ns: Option<u64>,
+}
+
+impl DutyTracker {
+    /// Create a tracker at zero duty.
+    pub fn new(config: &ThermalConfig) -> Self {
+        Self {
+            time_constant_s: config.time_constant_s,
+            max_duty: config.max_duty,
+            enforce: config.enforce,
+            duty: 0.0,
+            mark_ns: None,
+        }
+    }
+
+    /// Current duty (0.0 to 1.0) as of the last update.
+    #[inline]
+    pub fn current_duty(&self) -> f32 {
+        self.duty
+    }
+
+    /// Configured duty limit.
+    #[inline]
+    pub fn max_duty(&self) -> f32 {
+        self.max_duty
+    }
+
+    /// Whether moves are refused while over budget.
+    #[inline]
+    pub fn is_enforced(&self) -> bool {
+        self.enforce
+    }
+
+    /// Check whether the duty is above the limit.
+    #[inline]
+    pub fn is_over_budget(&self) -> bool {
+        self.duty > self.max_duty
+    }
+
+    /// Account for `secs` seconds of motion.
+    pub fn record_moving(&mut self, secs: f32) {
+        self.integrate(1.0, secs);
+    }
+
+    /// Account for `secs` seconds at rest.
+    ///
+    /// Without a clock the motor cannot measure idle time itself; call this
+    /// to let the duty decay between moves.
+    pub fn record_idle(&mut self, secs: f32) {
+        self.integrate(0.0, secs);
+    }
+
+    fn integrate(&mut self, level: f32, secs: f32) {
+        if secs.is_nan() || secs <= 0.0 {
+            return;
+        }
+        let decay = expf(-secs / self.time_constant_s);
+        self.duty = level + (self.duty - level) * decay;
+    }
+
+    /// Seconds since the last mark, moving the mark to `now_ns`.
+    fn lap(&mut self, now_ns: Option<u64>) -> Option<f32> {
+        let now = now_ns?;
+        let elapsed = self.mark_ns.map(|mark| now.wrapping_sub(mark) as f32 * 1e-9);
+        self.mark_ns = Some(now);
+        elapsed
+    }
+
+    /// A move is starting: account for the idle time since the last one.
+    pub(crate) fn move_started(&mut self, now_ns: Option<u64>) {
+        if let Some(idle) = self.lap(now_ns) {
+            self.record_idle(idle);
+        }
+    }
+
+    /// A move has ended: account for its measured or estimated duration.
+    pub(crate) fn move_ended(&mut self, now_ns: Option<u64>, estimated_secs: f32) {
+        let moving = self.lap(now_ns).unwrap_or(estimated_secs);
+        self.record_moving(moving);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duty_rises_and_decays() {
+        let mut tracker = DutyTracker::new(&ThermalConfig::new(10.0, 0.5));
+        tracker.record_moving(10.0);
+        // One time constant: 1 - 1/e
+        assert!((tracker.current_duty() - 0.632).abs() < 1e-3);
+        assert!(tracker.is_over_budget());
+
+        tracker.record_idle(10.0);
+        assert!((tracker.current_duty() - 0.232).abs() < 1e-3);
+        assert!(!tracker.is_over_budget());
+
+        tracker.record_idle(-1.0);
+        tracker.record_moving(f32::NAN);
+        assert!((tracker.current_duty() - 0.232).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_clock_marks() {
+        let mut tracker = DutyTracker::new(&ThermalConfig::new(1.0, 1.0));
+        // The first start has no previous mark to measure idle time from
+        tracker.move_started(Some(5_000_000_000));
+        tracker.move_ended(Some(6_000_000_000), 100.0);
+        assert!((tracker.current_duty() - 0.632).abs() < 1e-3);
+
+        // Without a clock, the estimate is used
+        let mut tracker = DutyTracker::new(&ThermalConfig::new(1.0, 1.0));
+        tracker.move_started(None);
+        tracker.move_ended(None, 1.0);
+        assert!((tracker.current_duty() - 0.632).abs() < 1e-3);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_persist_round_trip() {
+        let mut tracker = DutyTracker::new(&ThermalConfig::new(2.0, 0.8));
+        tracker.move_started(Some(0));
+        tracker.move_ended(Some(1_000_000_000), 0.0);
+
+        let saved = serde_json::to_string(&tracker).unwrap();
+        let restored: DutyTracker = serde_json::from_str(&saved).unwrap();
+        assert_eq!(restored.current_duty(), tracker.current_duty());
+        assert_eq!(restored.max_duty(), 0.8);
+        // Clock readings are not carried over
+        assert_eq!(restored.mark_ns, None);
+    }
+}
@@ -0,0 +1,171 @@
+//! Step timing statistics.
+//!
+//! With a clock source configured, the motor timestamps every rising STEP edge
+//! and compares the time since the previous edge against the planned
+//! interval. Recording is allocation-free: one clock read and a few integer
+//! operations per step.
+
+/// Monotonic clock in nanoseconds, e.g. a wrapper around a hardware timer.
+///
+/// Only differences between readings are used, so the epoch is arbitrary.
+pub type ClockFn = fn() -> u64;
+
+/// Number of buckets in [`TimingStats::histogram`].
+pub const TIMING_HISTOGRAM_BUCKETS: usize = 16;
+
+/// Default width of one histogram bucket in nanoseconds.
+pub const DEFAULT_TIMING_BUCKET_NS: u32 = 1000;
+
+/// Actual vs planned step intervals over one or more moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimingStats {
+    /// Width of one histogram bucket in nanoseconds.
+    pub bucket_width_ns: u32,
+    /// Number of intervals measured.
+    pub samples: u32,
+    /// Intervals that took longer than planned.
+    pub late_steps: u32,
+    /// Largest amount by which an interval exceeded the plan (nanoseconds).
+    pub max_late_ns: u32,
+    /// Largest amount by which an interval fell short of the plan (nanoseconds).
+    pub max_early_ns: u32,
+    /// Count of intervals by absolute deviation from the plan.
+    ///
+    /// Bucket `i` holds deviations in `[i * width, (i + 1) * width)`; the last
+    /// bucket also collects everything beyond.
+    pub histogram: [u32; TIMING_HISTOGRAM_BUCKETS],
+}
+
+impl TimingStats {
+    /// Create empty statistics with the given bucket width (at least 1 ns).
+    pub fn new(bucket_width_ns: u32) -> Self {
+        Self {
+            bucket_width_ns: bucket_width_ns.max(1),
+            samples: 0,
+            late_steps: 0,
+            max_late_ns: 0,
+            max_early_ns: 0,
+            histogram: [0; TIMING_HISTOGRAM_BUCKETS],
+        }
+    }
+
+    /// Record one interval.
+    pub fn record(&mut self, planned_ns: u32, actual_ns: u64) {
+        let planned_ns = planned_ns as u64;
+        let deviation = if actual_ns > planned_ns {
+            let late = clamp_u32(actual_ns - planned_ns);
+            self.late_steps = self.late_steps.saturating_add(1);
+            self.max_late_ns = self.max_late_ns.max(late);
+            late
+        } else {
+            let early = clamp_u32(planned_ns - actual_ns);
+            self.max_early_ns = self.max_early_ns.max(early);
+            early
+        };
+
+        let bucket =
+            ((deviation / self.bucket_width_ns) as usize).min(TIMING_HISTOGRAM_BUCKETS - 1);
+        self.histogram[bucket] = self.histogram[bucket].saturating_add(1);
+        self.samples = self.samples.saturating_add(1);
+    }
+}
+
+impl Default for TimingStats {
+    fn default() -> Self {
+        Self::new(DEFAULT_TIMING_BUCKET_NS)
+    }
+}
+
+fn clamp_u32(value: u64) -> u32 {
+    value.min(u32::MAX as u64) as u32
+}
+
+/// Per-motor recording state.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TimingRecorder {
+    clock: Option<ClockFn>,
+    enabled: bool,
+    stats: TimingStats,
+    /// Time of the previous rising STEP edge in the current move.
+    last_edge_ns: Option<u64>,
+    /// Interval planned after the previous edge.
+    planned_ns: u32,
+}
+
+impl TimingRecorder {
+    pub(crate) fn new(clock: Option<ClockFn>, enabled: bool, bucket_width_ns: u32) -> Self {
+        Self {
+            clock,
+            enabled,
+            stats: TimingStats::new(bucket_width_ns),
+            last_edge_ns: None,
+            planned_ns: 0,
+        }
+    }
+
+    /// Whether statistics are being collected.
+    #[inline]
+    pub(crate) fn is_active(&self) -> bool {
+        self.enabled && self.clock.is_some()
+    }
+
+    /// Timestamp a rising STEP edge. `first` starts a new move, whose first
+    /// edge has no previous one to compare against.
+    #[inline]
+    pub(crate) fn edge(&mut self, first: bool) {
+        let Some(clock) = self.clock.filter(|_| self.enabled) else {
+            return;
+        };
+        let now = clock();
+        if let Some(last) = self.last_edge_ns.filter(|_| !first) {
+            self.stats.record(self.planned_ns, now.wrapping_sub(last));
+        }
+        self.last_edge_ns = Some(now);
+    }
+
+    /// Set the interval planned until the next edge.
+    #[inline]
+    pub(crate) fn plan(&mut self, interval_ns: u32) {
+        self.planned_ns = interval_ns;
+    }
+
+    /// Read the clock, whether or not statistics are enabled.
+    #[inline]
+    pub(crate) fn now(&self) -> Option<u64> {
+        self.clock.map(|clock| clock())
+    }
+
+    pub(crate) fn stats(&self) -> Option<&TimingStats> {
+        self.is_active().then_some(&self.stats)
+    }
+
+    pub(crate) fn take(&mut self) -> Option<TimingStats> {
+        if !self.is_active() {
+            return None;
+        }
+        let fresh = TimingStats::new(self.stats.bucket_width_ns);
+        Some(core::mem::replace(&mut self.stats, fresh))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets() {
+        let mut stats = TimingStats::new(100);
+        stats.record(1000, 1000);
+        stats.record(1000, 1150);
+        stats.record(1000, 950);
+        stats.record(1000, 1_000_000);
+
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.late_steps, 2);
+        assert_eq!(stats.max_late_ns, 999_000);
+        assert_eq!(stats.max_early_ns, 50);
+        assert_eq!(stats.histogram[0], 2);
+        assert_eq!(stats.histogram[1], 1);
+        assert_eq!(stats.histogram[TIMING_HISTOGRAM_BUCKETS - 1], 1);
+    }
+}
@@ -0,0 +1,188 @@
+//! Shared helpers for unit tests.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::vec::Vec;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{ErrorType, OutputPin};
+
+use crate::config::units::{DegreesPerSec, DegreesPerSecSquared, Microsteps};
+use crate::motor::state::Idle;
+use crate::motor::{StepperMotor, StepperMotorBuilder};
+
+/// Output pin that accepts every write.
+pub struct NoopPin;
+
+impl ErrorType for NoopPin {
+    type Error = core::convert::Infallible;
+}
+
+impl OutputPin for NoopPin {
+    fn set_low(&mut self) -> core::result::Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> core::result::Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Output pin whose writes start failing after a number of high writes.
+pub struct FailingPin {
+    highs_left: u32,
+}
+
+impl FailingPin {
+    /// Accept `highs` high writes, then fail every write.
+    pub fn after(highs: u32) -> Self {
+        Self { highs_left: highs }
+    }
+}
+
+impl ErrorType for FailingPin {
+    type Error = embedded_hal::digital::ErrorKind;
+}
+
+impl OutputPin for FailingPin {
+    fn set_low(&mut self) -> core::result::Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> core::result::Result<(), Self::Error> {
+        self.highs_left = self.highs_left.checked_sub(1).ok_or(Self::Error::Other)?;
+        Ok(())
+    }
+}
+
+/// Delay provider that returns immediately.
+pub struct NoopDelay;
+
+impl DelayNs for NoopDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// Motor type used throughout the unit tests.
+pub type TestMotor = StepperMotor<NoopPin, NoopPin, NoopDelay, Idle>;
+
+/// Build a full-step motor named `x_axis` (200 steps/rev, fast enough to
+/// keep simulated moves short).
+pub fn test_motor() -> TestMotor {
+    StepperMotorBuilder::new()
+        .name("x_axis")
+        .step_pin(NoopPin)
+        .dir_pin(NoopPin)
+        .delay(NoopDelay)
+        .steps_per_revolution(200)
+        .microsteps(Microsteps::FULL)
+        .max_velocity(DegreesPerSec(3600.0))
+        .max_acceleration(DegreesPerSecSquared(36000.0))
+        .build()
+        .unwrap()
+}
+
+/// Hardware event captured by the event-log mocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// STEP pin driven high.
+    StepHigh,
+    /// STEP pin driven low.
+    StepLow,
+    /// DIR pin driven high.
+    DirHigh,
+    /// DIR pin driven low.
+    DirLow,
+    /// `delay_ns` call.
+    DelayNs(u32),
+    /// `delay_us` call.
+    DelayUs(u32),
+    /// `delay_ms` call.
+    DelayMs(u32),
+}
+
+/// Shared, ordered log of hardware events.
+pub type EventLog = Rc<RefCell<Vec<Event>>>;
+
+/// Output pin that records its transitions into an [`EventLog`].
+pub struct EventPin {
+    log: EventLog,
+    high: Event,
+    low: Event,
+}
+
+impl EventPin {
+    /// STEP pin logging into `log`.
+    pub fn step(log: &EventLog) -> Self {
+        Self {
+            log: log.clone(),
+            high: Event::StepHigh,
+            low: Event::StepLow,
+        }
+    }
+
+    /// DIR pin logging into `log`.
+    pub fn dir(log: &EventLog) -> Self {
+        Self {
+            log: log.clone(),
+            high: Event::DirHigh,
+            low: Event::DirLow,
+        }
+    }
+}
+
+impl ErrorType for EventPin {
+    type Error = core::convert::Infallible;
+}
+
+impl OutputPin for EventPin {
+    fn set_low(&mut self) -> core::result::Result<(), Self::Error> {
+        self.log.borrow_mut().push(self.low);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> core::result::Result<(), Self::Error> {
+        self.log.borrow_mut().push(self.high);
+        Ok(())
+    }
+}
+
+/// Delay provider that records every call into an [`EventLog`].
+pub struct EventDelay {
+    log: EventLog,
+}
+
+impl EventDelay {
+    /// Create a delay logging into `log`.
+    pub fn new(log: &EventLog) -> Self {
+        Self { log: log.clone() }
+    }
+}
+
+impl DelayNs for EventDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.log.borrow_mut().push(Event::DelayNs(ns));
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.log.borrow_mut().push(Event::DelayUs(us));
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.log.borrow_mut().push(Event::DelayMs(ms));
+    }
+}
+
+/// Builder for a motor like [`test_motor`] wired to the event-log mocks.
+pub fn event_builder(
+    log: &EventLog,
+) -> StepperMotorBuilder<EventPin, EventPin, EventDelay> {
+    StepperMotorBuilder::new()
+        .name("x_axis")
+        .step_pin(EventPin::step(log))
+        .dir_pin(EventPin::dir(log))
+        .delay(EventDelay::new(log))
+        .steps_per_revolution(200)
+        .microsteps(Microsteps::FULL)
+        .max_velocity(DegreesPerSec(3600.0))
+        .max_acceleration(DegreesPerSecSquared(36000.0))
+}
@@ -0,0 +1,265 @@
+//! Hardware-free test doubles (`test-utils` feature).
+//!
+//! Drop-in `OutputPin` and `DelayNs` implementations for running motors on
+//! the host, in unit tests or examples:
+//!
+//! - [`RecordingPin`] records every level change with a timestamp,
+//! - [`StepCounter`] only counts rising edges, for STEP pins on long moves,
+//! - [`InstantDelay`] returns immediately and advances a [`VirtualClock`].
+//!
+//! The doubles are cheap handles onto shared state: keep a clone and pass
+//! the other to the motor builder, then inspect the clone after the move.
+//! Timestamps come from the clock shared with the delay, so they measure
+//! the time the motor asked to wait, not wall-clock time.
+//!
+//! ```
+//! use stepper_motion::config::units::{Degrees, DegreesPerSec, DegreesPerSecSquared};
+//! use stepper_motion::motor::StepperMotorBuilder;
+//! use stepper_motion::testing::{InstantDelay, RecordingPin, StepCounter, VirtualClock};
+//!
+//! let clock = VirtualClock::new();
+//! let step = StepCounter::new();
+//! let dir = RecordingPin::new(&clock);
+//! let motor = StepperMotorBuilder::new()
+//!     .step_pin(step.clone())
+//!     .dir_pin(dir.clone())
+//!     .delay(InstantDelay::new(&clock))
+//!     .steps_per_revolution(200)
+//!     .max_velocity(DegreesPerSec(360.0))
+//!     .max_acceleration(DegreesPerSecSquared(720.0))
+//!     .build()
+//!     .unwrap();
+//!
+//! let motor = motor.move_to_blocking(Degrees(90.0)).ok().unwrap();
+//! assert_eq!(step.pulses(), 50);
+//! assert_eq!(dir.level(), Some(true));
+//! assert_eq!(motor.position_steps().0, 50);
+//! assert!(clock.now_ns() > 0);
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::vec::Vec;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{ErrorType, OutputPin};
+
+/// Simulated time in nanoseconds, advanced only by [`InstantDelay`].
+///
+/// Clones share the same time.
+#[derive(Debug, Clone, Default)]
+pub struct VirtualClock {
+    now_ns: Rc<Cell<u64>>,
+}
+
+impl VirtualClock {
+    /// Create a clock at time 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current simulated time in nanoseconds.
+    pub fn now_ns(&self) -> u64 {
+        self.now_ns.get()
+    }
+
+    /// Move the clock forward by `ns`.
+    pub fn advance(&self, ns: u64) {
+        self.now_ns.set(self.now_ns.get().saturating_add(ns));
+    }
+}
+
+/// Level change captured by a [`RecordingPin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    /// [`VirtualClock`] time of the write in nanoseconds.
+    pub at_ns: u64,
+    /// Level written (`true` = high).
+    pub high: bool,
+}
+
+/// Output pin that records every write as a timestamped [`Transition`].
+///
+/// Writes of the level the pin already has are recorded too, so the log
+/// shows exactly what the driver did. Clones share the log.
+#[derive(Debug, Clone)]
+pub struct RecordingPin {
+    clock: VirtualClock,
+    transitions: Rc<RefCell<Vec<Transition>>>,
+}
+
+impl RecordingPin {
+    /// Create a pin stamping its writes with `clock`.
+    pub fn new(clock: &VirtualClock) -> Self {
+        Self {
+            clock: clock.clone(),
+            transitions: Rc::default(),
+        }
+    }
+
+    /// All writes so far, oldest first.
+    pub fn transitions(&self) -> Vec<Transition> {
+        self.transitions.borrow().clone()
+    }
+
+    /// Last level written, or `None` if the pin was never written.
+    pub fn level(&self) -> Option<bool> {
+        self.transitions.borrow().last().map(|t| t.high)
+    }
+
+    /// Number of low-to-high changes. The first write counts if it is high.
+    pub fn rising_edges(&self) -> usize {
+        let mut previous = false;
+        self.transitions
+            .borrow()
+            .iter()
+            .filter(|t| {
+                let rising = t.high && !previous;
+                previous = t.high;
+                rising
+            })
+            .count()
+    }
+
+    /// Forget the writes recorded so far.
+    pub fn clear(&self) {
+        self.transitions.borrow_mut().clear();
+    }
+
+    fn record(&self, high: bool) {
+        let at_ns = self.clock.now_ns();
+        self.transitions.borrow_mut().push(Transition { at_ns, high });
+    }
+}
+
+impl ErrorType for RecordingPin {
+    type Error = core::convert::Infallible;
+}
+
+impl OutputPin for RecordingPin {
+    fn set_low(&mut self) -> core::result::Result<(), Self::Error> {
+        self.record(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> core::result::Result<(), Self::Error> {
+        self.record(true);
+        Ok(())
+    }
+}
+
+/// Output pin that counts step pulses (rising edges) without storing them.
+///
+/// Clones share the count.
+#[derive(Debug, Clone, Default)]
+pub struct StepCounter {
+    pulses: Rc<Cell<u64>>,
+    high: Rc<Cell<bool>>,
+}
+
+impl StepCounter {
+    /// Create a counter at 0 with the pin low.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of rising edges so far.
+    pub fn pulses(&self) -> u64 {
+        self.pulses.get()
+    }
+
+    /// Reset the count to 0.
+    pub fn reset(&self) {
+        self.pulses.set(0);
+    }
+}
+
+impl ErrorType for StepCounter {
+    type Error = core::convert::Infallible;
+}
+
+impl OutputPin for StepCounter {
+    fn set_low(&mut self) -> core::result::Result<(), Self::Error> {
+        self.high.set(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> core::result::Result<(), Self::Error> {
+        if !self.high.replace(true) {
+            self.pulses.set(self.pulses.get() + 1);
+        }
+        Ok(())
+    }
+}
+
+/// Delay provider that returns immediately and advances a [`VirtualClock`].
+#[derive(Debug, Clone, Default)]
+pub struct InstantDelay {
+    clock: VirtualClock,
+}
+
+impl InstantDelay {
+    /// Create a delay advancing `clock`.
+    pub fn new(clock: &VirtualClock) -> Self {
+        Self {
+            clock: clock.clone(),
+        }
+    }
+
+    /// The clock this delay advances.
+    pub fn clock(&self) -> &VirtualClock {
+        &self.clock
+    }
+}
+
+impl DelayNs for InstantDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.clock.advance(ns as u64);
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.clock.advance(us as u64 * 1_000);
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.clock.advance(ms as u64 * 1_000_000);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doubles_share_state() {
+        let clock = VirtualClock::new();
+        let mut delay = InstantDelay::new(&clock);
+        let pin = RecordingPin::new(&clock);
+        let mut handle = pin.clone();
+
+        handle.set_high().unwrap();
+        delay.delay_us(5);
+        handle.set_high().unwrap();
+        delay.delay_ms(1);
+        handle.set_low().unwrap();
+        handle.set_high().unwrap();
+
+        assert_eq!(clock.now_ns(), 1_005_000);
+        assert_eq!(pin.rising_edges(), 2);
+        assert_eq!(pin.level(), Some(true));
+        assert_eq!(pin.transitions()[1], Transition { at_ns: 5_000, high: true });
+        pin.clear();
+        assert_eq!(pin.level(), None);
+
+        let counter = StepCounter::new();
+        let mut step = counter.clone();
+        for _ in 0..3 {
+            step.set_high().unwrap();
+            step.set_high().unwrap();
+            step.set_low().unwrap();
+        }
+        assert_eq!(counter.pulses(), 3);
+        counter.reset();
+        assert_eq!(counter.pulses(), 0);
+    }
+}
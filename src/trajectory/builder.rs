@@ -2,20 +2,35 @@
 
 use heapless::String;
 
-use crate::config::{TrajectoryConfig, WaypointTrajectory};
+use crate::config::{
+    check_turn_remainder, Approach, FinalApproach, PresetConfig, SoftLimits, TrajectoryConfig,
+    TrajectoryKind, WaypointTrajectory,
+};
 use crate::config::units::{Degrees, DegreesPerSecSquared};
-use crate::error::{Error, Result, TrajectoryError};
+use crate::error::{ConfigError, Error, Result, TrajectoryError};
+use crate::motion::Direction;
 
 /// Builder for creating single-target trajectories.
 #[derive(Debug, Clone)]
 pub struct TrajectoryBuilder {
     motor: Option<String<32>>,
     target_degrees: Option<Degrees>,
-    velocity_percent: u8,
-    acceleration_percent: u8,
+    target_turns: Option<i32>,
+    velocity_percent: Option<u8>,
+    acceleration_percent: Option<u8>,
     acceleration: Option<DegreesPerSecSquared>,
     deceleration: Option<DegreesPerSecSquared>,
     dwell_ms: Option<u32>,
+    requires_position: Option<Degrees>,
+    tolerance: Option<Degrees>,
+    /// `Err` holds the length of a name too long to store.
+    requires_previous: Option<core::result::Result<String<32>, usize>>,
+    kind: TrajectoryKind,
+    approach: Approach,
+    overshoot: Degrees,
+    preset: Option<PresetConfig>,
+    limit_override: Option<SoftLimits>,
+    load_torque_nm: Option<f32>,
 }
 
 impl Default for TrajectoryBuilder {
@@ -30,11 +45,21 @@ impl TrajectoryBuilder {
         Self {
             motor: None,
             target_degrees: None,
-            velocity_percent: 100,
-            acceleration_percent: 100,
+            target_turns: None,
+            velocity_percent: None,
+            acceleration_percent: None,
             acceleration: None,
             deceleration: None,
             dwell_ms: None,
+            requires_position: None,
+            tolerance: None,
+            requires_previous: None,
+            kind: TrajectoryKind::Standard,
+            approach: Approach::Either,
+            overshoot: Degrees(0.0),
+            preset: None,
+            limit_override: None,
+            load_torque_nm: None,
         }
     }
 
@@ -50,15 +75,46 @@ impl TrajectoryBuilder {
         self
     }
 
+    /// Set a multi-turn target: `turns` whole output turns plus `remainder`.
+    ///
+    /// The remainder must be under one turn and share the sign of `turns`.
+    pub fn target_turns(mut self, turns: i32, remainder: Degrees) -> Self {
+        self.target_turns = Some(turns);
+        self.target_degrees = Some(remainder);
+        self
+    }
+
+    /// Make this a homing trajectory: travel in `direction` until the home
+    /// switch triggers, giving up after `max_travel`. No target is needed.
+    pub fn homing(mut self, direction: Direction, max_travel: Degrees) -> Self {
+        self.kind = TrajectoryKind::Homing { direction, max_travel_degrees: max_travel };
+        self
+    }
+
+    /// Always arrive at the target moving in `direction`, overshooting by
+    /// `overshoot` first when the motor starts on the wrong side.
+    pub fn approach_from(mut self, direction: Direction, overshoot: Degrees) -> Self {
+        self.approach = direction.into();
+        self.overshoot = overshoot;
+        self
+    }
+
     /// Set velocity as percentage of motor's max (1-200).
     pub fn velocity_percent(mut self, percent: u8) -> Self {
-        self.velocity_percent = percent.clamp(1, 200);
+        self.velocity_percent = Some(percent.clamp(1, 200));
         self
     }
 
     /// Set acceleration as percentage of motor's max (1-200).
     pub fn acceleration_percent(mut self, percent: u8) -> Self {
-        self.acceleration_percent = percent.clamp(1, 200);
+        self.acceleration_percent = Some(percent.clamp(1, 200));
+        self
+    }
+
+    /// Take motion parameters from `preset` for every field not set
+    /// explicitly on this builder, before or after this call.
+    pub fn preset(mut self, preset: &PresetConfig) -> Self {
+        self.preset = Some(*preset);
         self
     }
 
@@ -87,11 +143,42 @@ impl TrajectoryBuilder {
         self
     }
 
+    /// Require the motor to start within `tolerance` of `position`.
+    pub fn requires_position(mut self, position: Degrees, tolerance: Degrees) -> Self {
+        self.requires_position = Some(position);
+        self.tolerance = Some(tolerance);
+        self
+    }
+
+    /// Require `name` to be the last trajectory executed on the motor.
+    ///
+    /// A name longer than 32 bytes makes [`build`](Self::build) fail.
+    pub fn requires_previous(mut self, name: &str) -> Self {
+        self.requires_previous = Some(String::try_from(name).map_err(|_| name.len()));
+        self
+    }
+
+    /// Use `limits` instead of the motor's soft limits for this trajectory.
+    pub fn limit_override(mut self, limits: SoftLimits) -> Self {
+        self.limit_override = Some(limits);
+        self
+    }
+
+    /// Set the load torque in newton-metres, checked against the motor's
+    /// torque at cruise velocity.
+    pub fn load_torque(mut self, torque_nm: f32) -> Self {
+        self.load_torque_nm = Some(torque_nm);
+        self
+    }
+
     /// Build the trajectory configuration.
     ///
     /// # Errors
     ///
-    /// Returns an error if required fields are missing.
+    /// Returns an error if required fields are missing,
+    /// `TrajectoryError::NameTooLong` if the `requires_previous` name does
+    /// not fit in 32 bytes, or `ConfigError::InvalidSoftLimits` if the limit
+    /// override's minimum is not below its maximum.
     pub fn build(self) -> Result<TrajectoryConfig> {
         let motor = self.motor.ok_or_else(|| {
             Error::Trajectory(TrajectoryError::InvalidName(
@@ -99,20 +186,58 @@ impl TrajectoryBuilder {
             ))
         })?;
 
-        let target_degrees = self.target_degrees.ok_or_else(|| {
-            Error::Trajectory(TrajectoryError::InvalidName(
-                String::try_from("target not specified").unwrap(),
-            ))
-        })?;
+        let requires_previous = self
+            .requires_previous
+            .transpose()
+            .map_err(|len| Error::Trajectory(TrajectoryError::NameTooLong(len)))?;
+
+        if let Some(limits) = self.limit_override.as_ref().filter(|l| !l.is_valid()) {
+            return Err(Error::Config(ConfigError::InvalidSoftLimits {
+                min: limits.min.0,
+                max: limits.lowest_max().0,
+            }));
+        }
+
+        let (target_degrees, target_turns) = match self.kind {
+            TrajectoryKind::Homing { .. } => (Degrees(0.0), None),
+            TrajectoryKind::Standard => {
+                let target = self.target_degrees.ok_or_else(|| {
+                    Error::Trajectory(TrajectoryError::InvalidName(
+                        String::try_from("target not specified").unwrap(),
+                    ))
+                })?;
+                if let Some(turns) = self.target_turns {
+                    check_turn_remainder(turns, target).map_err(|_| {
+                        Error::Trajectory(TrajectoryError::InvalidName(
+                            String::try_from("target remainder exceeds a turn").unwrap(),
+                        ))
+                    })?;
+                }
+                (target, self.target_turns)
+            }
+        };
 
+        let preset = self.preset.unwrap_or_default();
         Ok(TrajectoryConfig {
             motor,
             target_degrees,
-            velocity_percent: self.velocity_percent,
-            acceleration_percent: self.acceleration_percent,
-            acceleration: self.acceleration,
-            deceleration: self.deceleration,
+            target_turns,
+            velocity_percent: self.velocity_percent.or(preset.velocity_percent).unwrap_or(100),
+            acceleration_percent: self
+                .acceleration_percent
+                .or(preset.acceleration_percent)
+                .unwrap_or(100),
+            acceleration: self.acceleration.or(preset.acceleration),
+            deceleration: self.deceleration.or(preset.deceleration),
             dwell_ms: self.dwell_ms,
+            requires_position: self.requires_position,
+            tolerance: self.tolerance,
+            requires_previous,
+            trajectory_kind: self.kind,
+            approach: self.approach,
+            overshoot: self.overshoot,
+            limit_override: self.limit_override,
+            load_torque_nm: self.load_torque_nm,
         })
     }
 }
@@ -126,7 +251,11 @@ pub struct WaypointTrajectoryBuilder {
     motor: Option<String<32>>,
     waypoints: heapless::Vec<Degrees, MAX_WAYPOINTS>,
     velocity_percent: u8,
+    acceleration_percent: u8,
+    acceleration: Option<DegreesPerSecSquared>,
+    deceleration: Option<DegreesPerSecSquared>,
     dwell_ms: u32,
+    final_approach: Option<FinalApproach>,
 }
 
 impl Default for WaypointTrajectoryBuilder {
@@ -142,7 +271,11 @@ impl WaypointTrajectoryBuilder {
             motor: None,
             waypoints: heapless::Vec::new(),
             velocity_percent: 100,
+            acceleration_percent: 100,
+            acceleration: None,
+            deceleration: None,
             dwell_ms: 0,
+            final_approach: None,
         }
     }
 
@@ -158,6 +291,18 @@ impl WaypointTrajectoryBuilder {
         self
     }
 
+    /// Insert a waypoint at `index`, shifting later waypoints right.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TrajectoryError::TooManyWaypoints` if the builder already
+    /// holds [`MAX_WAYPOINTS`], or `TrajectoryError::WaypointIndexOutOfRange`
+    /// if `index` is past the end of the list.
+    pub fn insert_waypoint_at(mut self, index: usize, position: Degrees) -> Result<Self> {
+        crate::config::insert_waypoint(&mut self.waypoints, index, position)?;
+        Ok(self)
+    }
+
     /// Add multiple waypoints.
     pub fn waypoints(mut self, positions: &[Degrees]) -> Self {
         for pos in positions {
@@ -172,17 +317,38 @@ impl WaypointTrajectoryBuilder {
         self
     }
 
+    /// Set acceleration as percentage of motor's max (1-200).
+    pub fn acceleration_percent(mut self, percent: u8) -> Self {
+        self.acceleration_percent = percent.clamp(1, 200);
+        self
+    }
+
+    /// Set asymmetric acceleration/deceleration rates for every segment.
+    pub fn asymmetric(mut self, accel: DegreesPerSecSquared, decel: DegreesPerSecSquared) -> Self {
+        self.acceleration = Some(accel);
+        self.deceleration = Some(decel);
+        self
+    }
+
     /// Set dwell time at each waypoint in milliseconds.
     pub fn dwell(mut self, dwell_ms: u32) -> Self {
         self.dwell_ms = dwell_ms;
         self
     }
 
+    /// Cover the last `distance` before the final waypoint at
+    /// `velocity_percent` of the motor's max velocity.
+    pub fn final_approach(mut self, distance: Degrees, velocity_percent: u8) -> Self {
+        self.final_approach = Some(FinalApproach { distance, velocity_percent });
+        self
+    }
+
     /// Build the waypoint trajectory configuration.
     ///
     /// # Errors
     ///
-    /// Returns an error if required fields are missing or waypoints are empty.
+    /// Returns an error if required fields are missing, waypoints are empty
+    /// or the final approach does not fit.
     pub fn build(self) -> Result<WaypointTrajectory> {
         let motor = self.motor.ok_or_else(|| {
             Error::Trajectory(TrajectoryError::InvalidName(
@@ -194,11 +360,17 @@ impl WaypointTrajectoryBuilder {
             return Err(Error::Trajectory(TrajectoryError::Empty));
         }
 
-        Ok(WaypointTrajectory {
+        let trajectory = WaypointTrajectory {
             motor,
             waypoints: self.waypoints,
             velocity_percent: self.velocity_percent,
+            acceleration_percent: self.acceleration_percent,
+            acceleration: self.acceleration,
+            deceleration: self.deceleration,
             dwell_ms: self.dwell_ms,
-        })
+            final_approach: self.final_approach,
+        };
+        trajectory.check_final_approach()?;
+        Ok(trajectory)
     }
 }
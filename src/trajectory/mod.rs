@@ -4,6 +4,8 @@
 
 mod builder;
 mod registry;
+mod sequence;
 
 pub use builder::{TrajectoryBuilder, WaypointTrajectoryBuilder, MAX_WAYPOINTS};
 pub use registry::{TrajectoryRegistry, MAX_TRAJECTORIES};
+pub use sequence::{TrajectorySequence, MAX_SEQUENCE_LENGTH};
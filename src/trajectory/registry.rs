@@ -1,8 +1,11 @@
 //! Trajectory registry for named trajectory lookup.
 
+#[cfg(any(feature = "alloc", feature = "std"))]
+use alloc::{string::String as OwnedString, vec::Vec};
+
 use heapless::{FnvIndexMap, String};
 
-use crate::config::TrajectoryConfig;
+use crate::config::{check_config_name, TrajectoryConfig};
 use crate::error::{Error, Result, TrajectoryError};
 
 /// Maximum number of trajectories in the registry.
@@ -12,6 +15,7 @@ pub const MAX_TRAJECTORIES: usize = 32;
 #[derive(Debug)]
 pub struct TrajectoryRegistry {
     trajectories: FnvIndexMap<String<32>, TrajectoryConfig, MAX_TRAJECTORIES>,
+    permissive_names: bool,
 }
 
 impl Default for TrajectoryRegistry {
@@ -25,32 +29,112 @@ impl TrajectoryRegistry {
     pub fn new() -> Self {
         Self {
             trajectories: FnvIndexMap::new(),
+            permissive_names: false,
         }
     }
 
+    /// Allow any non-control characters in names registered from now on,
+    /// instead of only letters, digits, `_` and `-`.
+    pub fn set_permissive_names(&mut self, permissive: bool) {
+        self.permissive_names = permissive;
+    }
+
     /// Register a trajectory with a name.
     ///
     /// # Errors
     ///
-    /// Returns an error if the registry is full.
+    /// Returns `TrajectoryError::NameTooLong` if the name exceeds 32 bytes,
+    /// `ConfigError::InvalidName` if it is empty or has characters outside
+    /// the [naming rules](crate::config::is_valid_name),
+    /// `TrajectoryError::Duplicate` if the name is already registered (use
+    /// [`replace`](Self::replace) to overwrite), or
+    /// `TrajectoryError::RegistryFull` if all `MAX_TRAJECTORIES` slots are used.
     pub fn register(&mut self, name: &str, trajectory: TrajectoryConfig) -> Result<()> {
-        let name_str = String::try_from(name).map_err(|_| {
-            Error::Trajectory(TrajectoryError::InvalidName(
-                String::try_from("name too long").unwrap(),
-            ))
-        })?;
+        self.register_owned(self.new_key(name)?, trajectory)
+    }
+
+    /// Register a trajectory under an already-owned name.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`register`](Self::register), minus the name length check.
+    pub fn register_owned(&mut self, name: String<32>, trajectory: TrajectoryConfig) -> Result<()> {
+        self.check_name(&name)?;
+        if self.trajectories.contains_key(&name) {
+            return Err(Error::Trajectory(TrajectoryError::Duplicate(name)));
+        }
 
         self.trajectories
-            .insert(name_str, trajectory)
-            .map_err(|_| {
-                Error::Trajectory(TrajectoryError::InvalidName(
-                    String::try_from("registry full").unwrap(),
-                ))
-            })?;
+            .insert(name, trajectory)
+            .map_err(|_| Error::Trajectory(TrajectoryError::RegistryFull))?;
+
+        Ok(())
+    }
+
+    /// Register a trajectory, overwriting any existing entry with the same name.
+    ///
+    /// Returns the previous configuration, if there was one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TrajectoryError::NameTooLong`, `ConfigError::InvalidName`
+    /// or `TrajectoryError::RegistryFull` (the latter only when adding a new
+    /// name).
+    pub fn replace(
+        &mut self,
+        name: &str,
+        trajectory: TrajectoryConfig,
+    ) -> Result<Option<TrajectoryConfig>> {
+        self.trajectories
+            .insert(self.new_key(name)?, trajectory)
+            .map_err(|_| Error::Trajectory(TrajectoryError::RegistryFull))
+    }
 
+    /// Move the entry registered as `old` to `new`.
+    ///
+    /// The renamed entry may change position in iteration order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TrajectoryError::NotFound` if `old` isn't registered,
+    /// `TrajectoryError::Duplicate` if `new` already is, or
+    /// `TrajectoryError::NameTooLong` if either name exceeds 32 bytes, or
+    /// `ConfigError::InvalidName` if `new` breaks the naming rules.
+    pub fn rename(&mut self, old: &str, new: &str) -> Result<()> {
+        let old = Self::key(old)?;
+        let new = self.new_key(new)?;
+        if !self.trajectories.contains_key(&old) {
+            return Err(Error::Trajectory(TrajectoryError::NotFound(old)));
+        }
+        if self.trajectories.contains_key(&new) {
+            return Err(Error::Trajectory(TrajectoryError::Duplicate(new)));
+        }
+
+        if let Some(trajectory) = self.trajectories.remove(&old) {
+            // Cannot fail: a slot was just freed
+            let _ = self.trajectories.insert(new, trajectory);
+        }
         Ok(())
     }
 
+    /// Convert a name into a registry key.
+    fn key(name: &str) -> Result<String<32>> {
+        String::try_from(name)
+            .map_err(|_| Error::Trajectory(TrajectoryError::NameTooLong(name.len())))
+    }
+
+    /// Convert a name to register into a registry key, checking the naming
+    /// rules.
+    fn new_key(&self, name: &str) -> Result<String<32>> {
+        let key = Self::key(name)?;
+        self.check_name(&key)?;
+        Ok(key)
+    }
+
+    fn check_name(&self, name: &str) -> Result<()> {
+        check_config_name(name, self.permissive_names)
+    }
+
     /// Get a trajectory by name.
     pub fn get(&self, name: &str) -> Option<&TrajectoryConfig> {
         let name_str = String::try_from(name).ok()?;
@@ -123,6 +207,38 @@ impl TrajectoryRegistry {
             .map(|(k, v)| (k.as_str(), v))
     }
 
+    /// Get an iterator over the trajectories targeting `motor_name`.
+    pub fn trajectories_for_motor<'a>(
+        &'a self,
+        motor_name: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a TrajectoryConfig)> + 'a {
+        self.iter().filter(move |(_, traj)| traj.motor.as_str() == motor_name)
+    }
+
+    /// Count the trajectories targeting `motor_name`.
+    pub fn count_for_motor(&self, motor_name: &str) -> usize {
+        self.trajectories_for_motor(motor_name).count()
+    }
+
+    /// Copy all entries into a `Vec` in registration order.
+    #[cfg(any(feature = "alloc", feature = "std"))]
+    pub fn to_unsorted_vec(&self) -> Vec<(OwnedString, TrajectoryConfig)> {
+        self.trajectories
+            .iter()
+            .map(|(k, v)| (OwnedString::from(k.as_str()), v.clone()))
+            .collect()
+    }
+
+    /// Copy all entries into a `Vec` sorted alphabetically by trajectory name.
+    ///
+    /// Useful for display tables or serialized exports.
+    #[cfg(any(feature = "alloc", feature = "std"))]
+    pub fn to_sorted_vec(&self) -> Vec<(OwnedString, TrajectoryConfig)> {
+        let mut entries = self.to_unsorted_vec();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+
     /// Clear all trajectories.
     pub fn clear(&mut self) {
         self.trajectories.clear();
@@ -131,9 +247,140 @@ impl TrajectoryRegistry {
     /// Load trajectories from a SystemConfig.
     pub fn from_config(config: &crate::config::SystemConfig) -> Self {
         let mut registry = Self::new();
+        registry.set_permissive_names(config.permissive_names);
         for (name, trajectory) in &config.trajectories {
-            let _ = registry.register(name.as_str(), trajectory.clone());
+            let _ = registry.register_owned(name.clone(), trajectory.clone());
         }
         registry
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::units::Degrees;
+    use crate::trajectory::TrajectoryBuilder;
+
+    fn move_to(degrees: f32) -> TrajectoryConfig {
+        TrajectoryBuilder::new()
+            .motor("x_axis")
+            .target(Degrees(degrees))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_register_rejects_duplicate() {
+        let mut registry = TrajectoryRegistry::new();
+        registry.register("home", move_to(0.0)).unwrap();
+
+        let err = registry.register("home", move_to(90.0)).unwrap_err();
+        assert!(matches!(err, Error::Trajectory(TrajectoryError::Duplicate(ref n)) if n == "home"));
+        assert_eq!(registry.get("home").unwrap().target_degrees, Degrees(0.0));
+    }
+
+    #[test]
+    fn test_register_name_too_long() {
+        let mut registry = TrajectoryRegistry::new();
+        let name = "a_trajectory_name_well_over_32_bytes";
+
+        let err = registry.register(name, move_to(0.0)).unwrap_err();
+        assert_eq!(err, Error::Trajectory(TrajectoryError::NameTooLong(name.len())));
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    fn test_register_name_rules() {
+        use crate::error::{ConfigError, NameError};
+
+        let mut registry = TrajectoryRegistry::new();
+        let err = registry.register("pick place", move_to(0.0)).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Config(ConfigError::InvalidName {
+                error: NameError::InvalidCharacter { character: ' ', index: 4 },
+                ..
+            })
+        ));
+        assert!(registry.register("", move_to(0.0)).is_err());
+        assert!(registry.register_owned(String::try_from("a/b").unwrap(), move_to(0.0)).is_err());
+        assert!(registry.is_empty());
+
+        registry.register("pick", move_to(0.0)).unwrap();
+        assert!(registry.rename("pick", "pick up").is_err());
+
+        registry.set_permissive_names(true);
+        registry.rename("pick", "pick up").unwrap();
+        assert!(registry.get("pick up").is_some());
+    }
+
+    #[test]
+    fn test_register_full_registry() {
+        let mut registry = TrajectoryRegistry::new();
+        for i in 0..MAX_TRAJECTORIES {
+            let mut name: String<32> = String::new();
+            core::fmt::write(&mut name, format_args!("t{}", i)).unwrap();
+            registry.register_owned(name, move_to(i as f32)).unwrap();
+        }
+        assert_eq!(registry.len(), MAX_TRAJECTORIES);
+
+        let err = registry.register("one_more", move_to(0.0)).unwrap_err();
+        assert_eq!(err, Error::Trajectory(TrajectoryError::RegistryFull));
+        assert_eq!(err.code(), 408);
+
+        // Overwriting and renaming existing entries still work when full
+        assert!(registry.replace("t0", move_to(1.0)).unwrap().is_some());
+        assert!(registry.replace("one_more", move_to(0.0)).is_err());
+        registry.rename("t1", "renamed").unwrap();
+        assert_eq!(registry.len(), MAX_TRAJECTORIES);
+    }
+
+    #[test]
+    fn test_replace_returns_previous() {
+        let mut registry = TrajectoryRegistry::new();
+        assert!(registry.replace("home", move_to(0.0)).unwrap().is_none());
+
+        let old = registry.replace("home", move_to(90.0)).unwrap().unwrap();
+        assert_eq!(old.target_degrees, Degrees(0.0));
+        assert_eq!(registry.get("home").unwrap().target_degrees, Degrees(90.0));
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_rename() {
+        let mut registry = TrajectoryRegistry::new();
+        registry.register("home", move_to(0.0)).unwrap();
+        registry.register("park", move_to(90.0)).unwrap();
+
+        registry.rename("home", "origin").unwrap();
+        assert!(!registry.contains("home"));
+        assert_eq!(registry.get("origin").unwrap().target_degrees, Degrees(0.0));
+
+        let err = registry.rename("missing", "other").unwrap_err();
+        assert!(matches!(err, Error::Trajectory(TrajectoryError::NotFound(_))));
+
+        let err = registry.rename("origin", "park").unwrap_err();
+        assert!(matches!(err, Error::Trajectory(TrajectoryError::Duplicate(ref n)) if n == "park"));
+        assert_eq!(registry.get("park").unwrap().target_degrees, Degrees(90.0));
+        assert_eq!(registry.len(), 2);
+    }
+
+    #[test]
+    fn test_trajectories_for_motor() {
+        let mut registry = TrajectoryRegistry::new();
+        registry.register("home", move_to(0.0)).unwrap();
+        let mut tilt = move_to(45.0);
+        tilt.motor = String::try_from("y_axis").unwrap();
+        registry.register("tilt", tilt).unwrap();
+        registry.register("park", move_to(90.0)).unwrap();
+
+        let x: heapless::Vec<(&str, f32), 4> = registry
+            .trajectories_for_motor("x_axis")
+            .map(|(name, traj)| (name, traj.target_degrees.0))
+            .collect();
+        assert_eq!(x.as_slice(), &[("home", 0.0), ("park", 90.0)]);
+        assert_eq!(registry.count_for_motor("x_axis"), 2);
+        assert_eq!(registry.count_for_motor("y_axis"), 1);
+        assert_eq!(registry.count_for_motor("z_axis"), 0);
+    }
+}
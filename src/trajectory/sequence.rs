@@ -0,0 +1,250 @@
+//! Sequential composition of trajectories.
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{InputPin, OutputPin};
+use heapless::Vec;
+
+use crate::config::TrajectoryConfig;
+use crate::error::{ConfigError, Error, Result, TrajectoryError};
+use crate::motor::state::Idle;
+use crate::motor::StepperMotor;
+
+use super::TrajectoryRegistry;
+
+/// Idle motor handed through a sequence.
+type IdleMotor<STEP, DIR, DELAY, HOME> = StepperMotor<STEP, DIR, DELAY, Idle, HOME>;
+
+/// Result of executing a sequence: the motor is returned on both paths.
+type SequenceResult<STEP, DIR, DELAY, HOME> = core::result::Result<
+    IdleMotor<STEP, DIR, DELAY, HOME>,
+    (IdleMotor<STEP, DIR, DELAY, HOME>, Error),
+>;
+
+/// Maximum number of trajectories in a sequence.
+pub const MAX_SEQUENCE_LENGTH: usize = 16;
+
+/// An ordered list of trajectories executed one after another.
+///
+/// Usually created by chaining [`TrajectoryConfig::then`]:
+///
+/// ```rust,ignore
+/// let motor = home.then(scan).then(park).execute(motor, &registry)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TrajectorySequence {
+    steps: Vec<TrajectoryConfig, MAX_SEQUENCE_LENGTH>,
+    /// A trajectory was chained past [`MAX_SEQUENCE_LENGTH`].
+    overflowed: bool,
+}
+
+impl TrajectorySequence {
+    /// Create a new empty sequence.
+    pub fn new() -> Self {
+        Self { steps: Vec::new(), overflowed: false }
+    }
+
+    /// Append a trajectory to the end of the sequence.
+    ///
+    /// Chaining more than [`MAX_SEQUENCE_LENGTH`] trajectories makes
+    /// [`execute`](Self::execute) fail before moving; use
+    /// [`try_then`](Self::try_then) to find out at once.
+    pub fn then(mut self, next: TrajectoryConfig) -> Self {
+        self.overflowed |= self.steps.push(next).is_err();
+        self
+    }
+
+    /// Append a trajectory to the end of the sequence, failing if it is full.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TrajectoryError::SequenceTooLong` if the sequence already
+    /// holds [`MAX_SEQUENCE_LENGTH`] trajectories.
+    pub fn try_then(mut self, next: TrajectoryConfig) -> Result<Self> {
+        self.steps
+            .push(next)
+            .map_err(|_| Error::Trajectory(TrajectoryError::SequenceTooLong(MAX_SEQUENCE_LENGTH)))?;
+        Ok(self)
+    }
+
+    /// Get the number of trajectories in the sequence.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Check if the sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Get an iterator over the trajectories in execution order.
+    pub fn iter(&self) -> impl Iterator<Item = &TrajectoryConfig> {
+        self.steps.iter()
+    }
+
+    /// Execute every trajectory in order, blocking until the last one completes.
+    ///
+    /// Each stored configuration runs as given, through
+    /// [`StepperMotor::execute_trajectory`]; steps are never matched against
+    /// `registry` entries, so they leave the motor's
+    /// [`last_trajectory`](StepperMotor::last_trajectory) unchanged. The
+    /// registry resolves `requires_previous` references: every one must name
+    /// a trajectory registered for the same motor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TrajectoryError::SequenceTooLong` if more than
+    /// [`MAX_SEQUENCE_LENGTH`] trajectories were chained, or
+    /// `ConfigError::TrajectoryNotFound` for an unresolved
+    /// `requires_previous`, both without moving. Otherwise stops at the
+    /// first trajectory that fails and returns the motor together with the
+    /// error. Trajectories before the failing one have already run.
+    pub fn execute<STEP, DIR, DELAY, HOME>(
+        &self,
+        mut motor: IdleMotor<STEP, DIR, DELAY, HOME>,
+        registry: &TrajectoryRegistry,
+    ) -> SequenceResult<STEP, DIR, DELAY, HOME>
+    where
+        STEP: OutputPin,
+        DIR: OutputPin,
+        DELAY: DelayNs,
+        HOME: InputPin,
+    {
+        if self.overflowed {
+            let error = TrajectoryError::SequenceTooLong(MAX_SEQUENCE_LENGTH);
+            return Err((motor, Error::Trajectory(error)));
+        }
+        for step in &self.steps {
+            if let Some(previous) = &step.requires_previous {
+                let found = registry.get(previous);
+                if !found.is_some_and(|p| p.motor == step.motor) {
+                    let error = ConfigError::TrajectoryNotFound(previous.clone());
+                    return Err((motor, Error::Config(error)));
+                }
+            }
+        }
+        for step in &self.steps {
+            motor = motor.execute_trajectory(step)?;
+        }
+        Ok(motor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::units::Degrees;
+    use crate::test_util::test_motor;
+    use crate::trajectory::TrajectoryBuilder;
+
+    fn move_to(motor: &str, degrees: f32) -> TrajectoryConfig {
+        TrajectoryBuilder::new()
+            .motor(motor)
+            .target(Degrees(degrees))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_then_chaining() {
+        let sequence = move_to("x_axis", 90.0)
+            .then(move_to("x_axis", 180.0))
+            .then(move_to("x_axis", 0.0));
+
+        assert_eq!(sequence.len(), 3);
+        let targets: std::vec::Vec<f32> = sequence.iter().map(|t| t.target_degrees.0).collect();
+        assert_eq!(targets, [90.0, 180.0, 0.0]);
+    }
+
+    #[test]
+    fn test_execute_sequence() {
+        let Ok(motor) = move_to("x_axis", 90.0)
+            .then(move_to("x_axis", -45.0))
+            .execute(test_motor(), &TrajectoryRegistry::new())
+        else {
+            panic!("sequence should execute");
+        };
+
+        assert!((motor.position_degrees().0 - -45.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_execute_stops_on_wrong_motor() {
+        let result = move_to("x_axis", 90.0)
+            .then(move_to("y_axis", 45.0))
+            .then(move_to("x_axis", 0.0))
+            .execute(test_motor(), &TrajectoryRegistry::new());
+
+        let (motor, err) = result.err().unwrap();
+        assert!(matches!(err, Error::Trajectory(_)));
+        // First move ran, the rest did not
+        assert!((motor.position_degrees().0 - 90.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_sequence_capacity() {
+        let full = (0..MAX_SEQUENCE_LENGTH)
+            .fold(TrajectorySequence::new(), |seq, i| seq.then(move_to("x_axis", i as f32)));
+        assert_eq!(full.len(), MAX_SEQUENCE_LENGTH);
+
+        let err = full.clone().try_then(move_to("x_axis", 90.0)).err().unwrap();
+        assert_eq!(err, Error::Trajectory(TrajectoryError::SequenceTooLong(MAX_SEQUENCE_LENGTH)));
+
+        // An overflowing then() is reported before anything moves
+        let overflowed = full.then(move_to("x_axis", 90.0));
+        let registry = TrajectoryRegistry::new();
+        let (motor, err) = overflowed.execute(test_motor(), &registry).err().unwrap();
+        assert!(matches!(err, Error::Trajectory(TrajectoryError::SequenceTooLong(_))));
+        assert_eq!(motor.position_steps().0, 0);
+    }
+
+    #[test]
+    fn test_execute_runs_configs_directly() {
+        let approach = move_to("x_axis", 90.0);
+        let mut registry = TrajectoryRegistry::new();
+        registry.register("approach", approach.clone()).unwrap();
+        registry.register("approach_copy", approach.clone()).unwrap();
+
+        // Steps equal to registered entries still run as plain configs
+        let sequence = approach.clone().then(move_to("x_axis", 45.0));
+        let Ok(motor) = sequence.execute(test_motor(), &registry) else {
+            panic!("sequence should execute");
+        };
+        assert_eq!(motor.position_steps().0, 25);
+        assert_eq!(motor.last_trajectory(), None);
+    }
+
+    #[test]
+    fn test_execute_resolves_requires_previous() {
+        let insert = TrajectoryBuilder::new()
+            .motor("x_axis")
+            .target(Degrees(135.0))
+            .requires_previous("approach")
+            .build()
+            .unwrap();
+        let mut registry = TrajectoryRegistry::new();
+        registry.register("approach", move_to("x_axis", 90.0)).unwrap();
+
+        // An unresolved reference fails before anything moves
+        let sequence = move_to("x_axis", 45.0).then(insert.clone());
+        let empty = TrajectoryRegistry::new();
+        let (motor, err) = sequence.execute(test_motor(), &empty).err().unwrap();
+        assert!(matches!(err, Error::Config(ConfigError::TrajectoryNotFound(_))));
+        assert_eq!(motor.position_steps().0, 0);
+
+        // The precondition is checked against the motor's last named run
+        let Ok(motor) = test_motor().execute("approach", &registry) else {
+            panic!("approach should execute");
+        };
+        let (motor, err) = sequence.execute(motor, &registry).err().unwrap();
+        assert!(matches!(err, Error::Trajectory(TrajectoryError::PreconditionFailed { .. })));
+        assert_eq!(motor.position_steps().0, 25);
+
+        let Ok(motor) = test_motor().execute("approach", &registry) else {
+            panic!("approach should execute");
+        };
+        let Ok(motor) = TrajectorySequence::new().then(insert).execute(motor, &registry) else {
+            panic!("the named approach should satisfy the precondition");
+        };
+        assert_eq!(motor.position_steps().0, 75);
+    }
+}
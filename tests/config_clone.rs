@@ -0,0 +1,86 @@
+//! Regression guard: cloned configurations are fully independent of the original.
+//!
+//! heapless collections are stored by value, so this should always hold; the
+//! test catches any future field type that shares data between clones.
+
+use stepper_motion::config::units::{Degrees, DegreesPerSec};
+use stepper_motion::config::{LimitPolicy, SystemConfig};
+
+const CONFIG: &str = r#"
+[motors.pan]
+name = "Pan Axis"
+steps_per_revolution = 200
+microsteps = 16
+gear_ratio = 4.0
+max_velocity_deg_per_sec = 180.0
+max_acceleration_deg_per_sec2 = 360.0
+backlash_compensation_deg = 0.5
+
+[motors.pan.limits]
+min_degrees = -90.0
+max_degrees = 90.0
+policy = "reject"
+
+[trajectories.home]
+motor = "pan"
+target_degrees = 0.0
+velocity_percent = 50
+
+[sequences.sweep]
+motor = "pan"
+waypoints = [-45.0, 45.0]
+"#;
+
+#[test]
+fn cloned_system_config_is_independent() {
+    let mut original: SystemConfig = toml::from_str(CONFIG).unwrap();
+    let clone = original.clone();
+
+    // Modify every kind of field on the original
+    let motor = original.motors.values_mut().next().unwrap();
+    motor.name.clear();
+    motor.name.push_str("Renamed").unwrap();
+    motor.max_velocity = DegreesPerSec(1.0);
+    motor.backlash_compensation = None;
+    let limits = motor.limits.as_mut().unwrap();
+    limits.min = Degrees(-1.0);
+    limits.policy = LimitPolicy::Clamp;
+
+    let trajectory = original.trajectories.values_mut().next().unwrap();
+    trajectory.motor.clear();
+    trajectory.target_degrees = Degrees(12.0);
+
+    let sequence = original.sequences.values_mut().next().unwrap();
+    sequence.waypoints.clear();
+
+    original.motors.clear();
+
+    // The clone still holds the parsed values
+    let motor = clone.motor("pan").unwrap();
+    assert_eq!(motor.name.as_str(), "Pan Axis");
+    assert_eq!(motor.max_velocity, DegreesPerSec(180.0));
+    assert_eq!(motor.backlash_compensation, Some(Degrees(0.5)));
+    let limits = motor.limits.as_ref().unwrap();
+    assert_eq!(limits.min, Degrees(-90.0));
+    assert_eq!(limits.policy, LimitPolicy::Reject);
+
+    let trajectory = clone.trajectory("home").unwrap();
+    assert_eq!(trajectory.motor.as_str(), "pan");
+    assert_eq!(trajectory.target_degrees, Degrees(0.0));
+
+    assert_eq!(clone.sequence("sweep").unwrap().waypoints.len(), 2);
+}
+
+#[test]
+fn cloned_motor_config_is_independent() {
+    let config: SystemConfig = toml::from_str(CONFIG).unwrap();
+    let mut original = config.motor("pan").unwrap().clone();
+    let clone = original.clone();
+
+    original.name.push_str(" (left)").unwrap();
+    original.limits = None;
+
+    assert_eq!(clone.name.as_str(), "Pan Axis");
+    assert!(clone.limits.is_some());
+    assert_eq!(clone.total_steps_per_revolution_f64(), 12_800.0);
+}
@@ -121,7 +121,7 @@ max_acceleration_deg_per_sec2 = 200.0
 "#
         );
         
-        let config = parse_config(&toml).expect(&format!("Microsteps {} should parse", ms_value));
+        let config = parse_config(&toml).unwrap_or_else(|_| panic!("Microsteps {} should parse", ms_value));
         let motor = config.motor("m1").unwrap();
         assert_eq!(motor.microsteps, expected);
     }
@@ -148,7 +148,7 @@ policy = "{policy_str}"
 "#
         );
         
-        let config = parse_config(&toml).expect(&format!("Policy '{}' should parse", policy_str));
+        let config = parse_config(&toml).unwrap_or_else(|_| panic!("Policy '{}' should parse", policy_str));
         let motor = config.motor("m1").unwrap();
         let limits = motor.limits.as_ref().unwrap();
         assert_eq!(limits.policy, expected);
@@ -385,6 +385,23 @@ fn t049_registry_get_by_name() {
     assert!(missing.is_none());
 }
 
+#[test]
+fn t049_registry_export_to_vec() {
+    let config = parse_config(FULL_CONFIG).unwrap();
+    let mut registry = TrajectoryRegistry::from_config(&config);
+    let park = registry.get("home").unwrap().clone();
+    registry.register("park", park).unwrap();
+
+    let unsorted = registry.to_unsorted_vec();
+    let names: Vec<&str> = unsorted.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["home", "asymmetric", "park"]);
+
+    let sorted = registry.to_sorted_vec();
+    let names: Vec<&str> = sorted.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["asymmetric", "home", "park"]);
+    assert!((sorted[0].1.target_degrees.0 - 45.0).abs() < 0.01);
+}
+
 // =============================================================================
 // T050: Unit test for trajectory lookup by name
 // =============================================================================
@@ -499,3 +516,156 @@ fn t051_complete_execution_workflow() {
         );
     }
 }
+
+// =============================================================================
+// Dry-run simulation report
+// =============================================================================
+
+#[test]
+fn simulation_report_for_full_config() {
+    use stepper_motion::config::{simulate, SimulationKind, StartingPoses};
+    use stepper_motion::Direction;
+
+    let config = parse_config(FULL_CONFIG).unwrap();
+    let report = simulate(&config, &StartingPoses::new()).unwrap();
+    assert_eq!(report.entries.len(), 2);
+    assert_eq!(report.with_warnings().count(), 0);
+
+    let entry = report.entry("asymmetric").expect("asymmetric should be simulated");
+    assert_eq!(entry.kind, SimulationKind::Trajectory);
+    assert_eq!(entry.motor.as_str(), "pan");
+    assert_eq!(entry.direction, Some(Direction::Clockwise));
+    assert!((entry.acceleration_deg_per_sec2 - 288.0).abs() < 0.01);
+    assert!((entry.deceleration_deg_per_sec2 - 180.0).abs() < 0.01);
+    assert!((entry.end_degrees - 45.0).abs() < 0.01);
+
+    // 45° never reaches 180°/s: the ramps meet at sqrt(2 * 45 * a * d / (a + d))
+    let (a, d) = (288.0f32, 180.0f32);
+    let peak = (2.0 * 45.0 * a * d / (a + d)).sqrt();
+    assert!((entry.peak_velocity_deg_per_sec - peak).abs() / peak < 0.05);
+    let duration = peak / a + peak / d;
+    assert!(
+        (entry.duration_secs - duration).abs() / duration < 0.05,
+        "{} vs {}",
+        entry.duration_secs,
+        duration
+    );
+
+    // Already at home
+    let home = report.entry("home").unwrap();
+    assert_eq!(home.total_steps, 0);
+    assert_eq!(home.direction, None);
+    assert_eq!(home.duration_secs, 0.0);
+}
+
+// =============================================================================
+// Configuration documentation
+// =============================================================================
+
+#[test]
+fn document_full_config() {
+    let config = parse_config(FULL_CONFIG).unwrap();
+    let doc = config.document();
+    let lines: Vec<&str> = doc.lines().collect();
+
+    assert_eq!(lines.len(), 5, "{}", doc);
+    assert_eq!(lines[0], "Motors (1):");
+    assert_eq!(
+        lines[1],
+        "  Pan Axis (pan): 200 step × 32 μstep × 4.0 gear = 25600 steps/rev (71.11 steps/°), \
+         max 180°/s, max 360°/s², limits: -90° to 90° (reject)"
+    );
+    assert_eq!(lines[2], "Trajectories (2):");
+    assert_eq!(lines[3], "  home: move pan to 0° at 50% velocity, 100% acceleration");
+    assert!(lines[4].starts_with("  asymmetric: move pan to 45° at 100% velocity"));
+    assert!(lines[4].contains("accel 288°/s², decel 180°/s²"));
+}
+
+// =============================================================================
+// Configuration diff
+// =============================================================================
+
+#[test]
+fn diff_full_config() {
+    use stepper_motion::config::diff;
+
+    let old = parse_config(FULL_CONFIG).unwrap();
+    let mut new = old.clone();
+    new.motors.clear();
+    for (name, trajectory) in new.trajectories.iter_mut() {
+        match name.as_str() {
+            "asymmetric" => trajectory.velocity_percent = 75,
+            // Rounding noise is not a change
+            _ => trajectory.target_degrees = Degrees(1e-6),
+        }
+    }
+
+    let report = diff(&old, &new);
+    assert!(report.added_motors.is_empty());
+    assert_eq!(report.removed_motors, ["pan"]);
+    assert!(report.changed_motors.is_empty());
+    assert_eq!(report.changed_trajectories.len(), 1);
+    let changed = &report.changed_trajectories[0];
+    assert_eq!(changed.name, "asymmetric");
+    assert_eq!(changed.fields.len(), 1);
+    assert!(changed.changed("velocity_percent"));
+    assert_eq!((changed.fields[0].old.as_str(), changed.fields[0].new.as_str()), ("100", "75"));
+    assert!(report.added_trajectories.is_empty() && report.removed_trajectories.is_empty());
+    assert_eq!(
+        report.to_string(),
+        "- motor pan\n~ trajectory asymmetric: velocity_percent (100 -> 75)\n"
+    );
+
+    assert!(diff(&old, &old).is_empty());
+    assert!(diff(&new, &old).added_motors.contains(&"pan".try_into().unwrap()));
+}
+
+// =============================================================================
+// Hardware-free execution with the crate's test doubles
+// =============================================================================
+
+#[test]
+fn execute_full_config_on_mock_motor() {
+    use stepper_motion::motor::StepperMotorBuilder;
+    use stepper_motion::testing::{InstantDelay, RecordingPin, StepCounter, VirtualClock};
+
+    let config = parse_config(FULL_CONFIG).unwrap();
+    let registry = TrajectoryRegistry::from_config(&config);
+    let clock = VirtualClock::new();
+    let step = StepCounter::new();
+    let dir = RecordingPin::new(&clock);
+    let mut motor = StepperMotorBuilder::new()
+        .from_config(&config, "pan")
+        .unwrap()
+        // Trajectories name the motor by its key, not its display name
+        .name("pan")
+        .step_pin(step.clone())
+        .dir_pin(dir.clone())
+        .delay(InstantDelay::new(&clock))
+        .build()
+        .unwrap();
+    // 200 steps x 32 microsteps x 4:1 gearing: 45 degrees is 3200 steps
+    let steps_per_degree = 25_600.0 / 360.0;
+
+    // `pan` is inverted, so clockwise moves drive DIR low
+    for (name, degrees, dir_high) in [("asymmetric", 45.0, false), ("home", 0.0, true)] {
+        let start = motor.position_steps().0;
+        let pulses_before = step.pulses();
+        let time_before = clock.now_ns();
+
+        motor = motor.execute(name, &registry).map_err(|(_, e)| e).unwrap();
+
+        let target = (degrees * steps_per_degree) as i64;
+        assert_eq!(motor.position_steps().0, target, "{name}");
+        assert_eq!(step.pulses() - pulses_before, (target - start).unsigned_abs(), "{name}");
+        assert_eq!(dir.level(), Some(dir_high), "{name}");
+        assert!(clock.now_ns() > time_before, "{name}");
+    }
+
+    // One DIR write per move
+    let dir_writes = dir.transitions();
+    assert_eq!(dir_writes.len(), 2);
+    assert!(dir_writes[0].at_ns < dir_writes[1].at_ns);
+    assert_eq!(dir.rising_edges(), 1);
+    assert_eq!(step.pulses(), 6400);
+}
@@ -0,0 +1,109 @@
+//! no_std smoke test.
+//!
+//! Builds a motion profile and drives an executor through every step without
+//! std or an allocator. On bare-metal targets this is a `#![no_main]` binary:
+//!
+//! ```text
+//! cargo test --no-default-features --features libm --target thumbv6m-none-eabi --test nostd_smoke
+//! ```
+//!
+//! The linker script comes from `build.rs` and the runner (QEMU with
+//! semihosting) from `.cargo/config.toml`. Success is signalled by
+//! `panic!("passed")` after the smoke run has recorded completion; the panic
+//! handler turns that into a zero exit status.
+//!
+//! On hosted targets the same checks run from a plain `main`, so the file is
+//! also exercised by `cargo test`.
+
+#![cfg_attr(target_os = "none", no_std, no_main)]
+
+use stepper_motion::motion::{MotionExecutor, MotionProfile};
+
+/// Run the smoke checks, returning `false` on the first failure.
+fn smoke() -> bool {
+    let profile = MotionProfile::symmetric_trapezoidal(1000, 2000.0, 8000.0);
+    if profile.total_steps != 1000 {
+        return false;
+    }
+
+    let mut executor = MotionExecutor::new(profile);
+    let mut executed = 1u32;
+    let mut saw_cruise = false;
+    while executor.advance() {
+        executed += 1;
+        saw_cruise |= executor.phase().is_constant_velocity();
+        if executed > 1000 {
+            return false;
+        }
+    }
+
+    executed == 1000
+        && saw_cruise
+        && executor.is_complete()
+        && executor.steps_remaining() == 0
+        && executor.phase().is_terminal()
+}
+
+#[cfg(not(target_os = "none"))]
+fn main() {
+    assert!(smoke(), "no_std smoke test failed");
+}
+
+#[cfg(target_os = "none")]
+mod bare_metal {
+    use core::panic::PanicInfo;
+    use core::sync::atomic::{AtomicBool, Ordering};
+
+    /// Set once the smoke run has completed successfully.
+    static PASSED: AtomicBool = AtomicBool::new(false);
+
+    /// Initial stack pointer and reset vector, placed by the generated linker script.
+    #[link_section = ".vector_table.reset"]
+    #[no_mangle]
+    static RESET_VECTOR: unsafe extern "C" fn() -> ! = reset;
+
+    extern "C" {
+        static mut __sbss: u32;
+        static mut __ebss: u32;
+        static mut __sdata: u32;
+        static mut __edata: u32;
+        static __sidata: u32;
+    }
+
+    #[no_mangle]
+    unsafe extern "C" fn reset() -> ! {
+        let mut bss = core::ptr::addr_of_mut!(__sbss);
+        while bss < core::ptr::addr_of_mut!(__ebss) {
+            bss.write_volatile(0);
+            bss = bss.add(1);
+        }
+        let mut data = core::ptr::addr_of_mut!(__sdata);
+        let mut init = core::ptr::addr_of!(__sidata);
+        while data < core::ptr::addr_of_mut!(__edata) {
+            data.write_volatile(init.read());
+            data = data.add(1);
+            init = init.add(1);
+        }
+
+        if super::smoke() {
+            PASSED.store(true, Ordering::SeqCst);
+        }
+        panic!("passed");
+    }
+
+    #[panic_handler]
+    fn panic(_info: &PanicInfo) -> ! {
+        exit(PASSED.load(Ordering::SeqCst))
+    }
+
+    /// Semihosting `SYS_EXIT` with `ADP_Stopped_ApplicationExit` or `ADP_Stopped_RunTimeErrorUnknown`.
+    fn exit(success: bool) -> ! {
+        let reason: u32 = if success { 0x20026 } else { 0x20023 };
+        loop {
+            // SAFETY: semihosting call; the debugger or QEMU terminates the program.
+            unsafe {
+                core::arch::asm!("bkpt 0xAB", in("r0") 0x18u32, in("r1") reason);
+            }
+        }
+    }
+}